@@ -17,6 +17,7 @@ use deep_research::agent::{
     SandboxLanguage, TokenUsage, UserResponse,
 };
 use deep_research::types::{KnowledgeItem, KnowledgeType, Reference, SerpQuery};
+use deep_research::utils::CostBreakdown;
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 // BENCHMARK: Estados do Agente
@@ -482,11 +483,21 @@ fn bench_results(c: &mut Criterion) {
                     completion_tokens: 3000,
                     total_tokens: 18000,
                 },
+                cost: CostBreakdown::default(),
+                structured: None,
+                report: None,
+                beast_mode_vote: None,
+                claim_checks: vec![],
+                disputed_facts: vec![],
+                source_credibility: vec![],
+                knowledge: vec![],
                 visited_urls: vec![
                     "https://example.com/1".to_string(),
                     "https://example.com/2".to_string(),
                 ],
                 error: None,
+                cancelled: false,
+                partial_knowledge: vec![],
                 total_time_ms: 5000,
                 search_time_ms: 1500,
                 read_time_ms: 800,
@@ -507,8 +518,18 @@ fn bench_results(c: &mut Criterion) {
                     completion_tokens: 10000,
                     total_tokens: 60000,
                 },
+                cost: CostBreakdown::default(),
+                structured: None,
+                report: None,
+                beast_mode_vote: None,
+                claim_checks: vec![],
+                disputed_facts: vec![],
+                source_credibility: vec![],
+                knowledge: vec![],
                 visited_urls: vec![],
                 error: Some("Budget exhausted".to_string()),
+                cancelled: false,
+                partial_knowledge: vec![],
                 total_time_ms: 30000,
                 search_time_ms: 10000,
                 read_time_ms: 5000,
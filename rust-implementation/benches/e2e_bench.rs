@@ -21,6 +21,7 @@ use deep_research::types::{
     BoostedSearchSnippet, KnowledgeItem, KnowledgeType, Language, Reference, SerpQuery,
     TopicCategory,
 };
+use deep_research::utils::CostBreakdown;
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -342,8 +343,18 @@ fn bench_final_result(c: &mut Criterion) {
                             completion_tokens: 5000,
                             total_tokens: 30000,
                         },
+                        cost: CostBreakdown::default(),
+                        structured: None,
+                        report: None,
+                        beast_mode_vote: None,
+                        claim_checks: vec![],
+                        disputed_facts: vec![],
+                        source_credibility: vec![],
+                        knowledge: vec![],
                         visited_urls: visited_urls.clone(),
                         error: None,
+                        cancelled: false,
+                        partial_knowledge: vec![],
                         total_time_ms: 8000,
                         search_time_ms: 2500,
                         read_time_ms: 1200,
@@ -366,8 +377,18 @@ fn bench_final_result(c: &mut Criterion) {
                     completion_tokens: 10000,
                     total_tokens: 60000,
                 },
+                cost: CostBreakdown::default(),
+                structured: None,
+                report: None,
+                beast_mode_vote: None,
+                claim_checks: vec![],
+                disputed_facts: vec![],
+                source_credibility: vec![],
+                knowledge: vec![],
                 visited_urls: vec![],
                 error: Some("Budget exhausted without satisfactory answer".to_string()),
+                cancelled: false,
+                partial_knowledge: vec![],
                 total_time_ms: 45000,
                 search_time_ms: 15000,
                 read_time_ms: 8000,
@@ -4,6 +4,7 @@
 
 use crate::agent::interaction::QuestionType;
 use crate::types::{Reference, SerpQuery, Url};
+use serde::{Deserialize, Serialize};
 use serde_json;
 
 /// Cada ação carrega seus próprios dados - impossível ter ação "Search" sem queries
@@ -26,7 +27,7 @@ use serde_json;
 ///     _ => {}
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AgentAction {
     /// Buscar informações na web
     ///
@@ -149,92 +150,30 @@ pub enum AgentAction {
         think: String,
     },
 
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-    // AÇÕES DE INTEGRAÇÃO PAYTOUR
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-
-    /// Listar passeios turísticos via Paytour
-    ///
-    /// Esta ação consulta a API Paytour para listar
-    /// passeios disponíveis com filtros opcionais.
-    PaytourListarPasseios {
-        /// Filtros opcionais para a busca (JSON)
-        filtros: Option<serde_json::Value>,
-        /// Raciocínio do agente para esta ação
-        think: String,
-    },
-
-    /// Detalhar um passeio específico via Paytour
-    ///
-    /// Esta ação obtém informações detalhadas de um passeio.
-    PaytourDetalharPasseio {
-        /// ID do passeio
-        id: u64,
-        /// Raciocínio do agente para esta ação
-        think: String,
-    },
-
-    /// Verificar disponibilidade de um passeio
-    ///
-    /// Esta ação verifica datas disponíveis para um passeio.
-    PaytourVerificarDisponibilidade {
-        /// ID do passeio
-        id: u64,
-        /// Mês (1-12)
-        mes: u8,
-        /// Ano
-        ano: u32,
-        /// Raciocínio do agente para esta ação
-        think: String,
-    },
-
-    /// Obter horários de um passeio
-    ///
-    /// Esta ação obtém horários disponíveis para uma data específica.
-    PaytourObterHorarios {
-        /// ID do passeio
-        id: u64,
-        /// Data no formato YYYY-MM-DD
-        dia: String,
-        /// Raciocínio do agente para esta ação
-        think: String,
-    },
-
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-    // AÇÕES DE INTEGRAÇÃO DIGISAC
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-
-    /// Enviar mensagem via Digisac (WhatsApp)
+    /// Chamar uma ferramenta externa registrada (`ToolRegistry`)
     ///
-    /// Esta ação envia uma mensagem de texto para um contato.
-    DigisacEnviarMensagem {
-        /// ID do serviço (canal)
-        service_id: String,
-        /// ID do contato
-        contact_id: String,
-        /// Texto da mensagem
-        texto: String,
+    /// Integrações (Paytour, Digisac, ...) são expostas como ferramentas:
+    /// os argumentos são validados contra o JSON Schema da ferramenta e o
+    /// resultado entra no knowledge.
+    ToolCall {
+        /// Nome da ferramenta
+        tool: String,
+        /// Argumentos (JSON) conforme o schema da ferramenta
+        arguments: serde_json::Value,
         /// Raciocínio do agente para esta ação
         think: String,
     },
 
-    /// Listar webhooks configurados no Digisac
+    /// Executar várias ações independentes no mesmo step
     ///
-    /// Esta ação lista todos os webhooks configurados.
-    DigisacListarWebhooks {
-        /// Raciocínio do agente para esta ação
-        think: String,
-    },
-
-    /// Criar webhook no Digisac
-    ///
-    /// Esta ação cria um novo webhook para receber eventos.
-    DigisacCriarWebhook {
-        /// URL de destino do webhook
-        url: String,
-        /// Eventos a assinar
-        eventos: Vec<String>,
-        /// Raciocínio do agente para esta ação
+    /// O LLM pode planejar, por exemplo, duas buscas e a leitura de três
+    /// URLs de uma vez. As sub-ações (apenas `Search`, `Read` e `ToolCall`)
+    /// rodam em paralelo e seus resultados entram no knowledge e no diário
+    /// na ordem do plano.
+    Parallel {
+        /// Sub-ações do plano (limitado a MAX_PARALLEL_ACTIONS)
+        actions: Vec<AgentAction>,
+        /// Raciocínio do agente para este plano
         think: String,
     },
 }
@@ -250,15 +189,8 @@ impl AgentAction {
             AgentAction::Coding { .. } => "coding",
             AgentAction::History { .. } => "history",
             AgentAction::AskUser { .. } => "ask_user",
-            // Paytour actions
-            AgentAction::PaytourListarPasseios { .. } => "paytour_listar_passeios",
-            AgentAction::PaytourDetalharPasseio { .. } => "paytour_detalhar_passeio",
-            AgentAction::PaytourVerificarDisponibilidade { .. } => "paytour_verificar_disponibilidade",
-            AgentAction::PaytourObterHorarios { .. } => "paytour_obter_horarios",
-            // Digisac actions
-            AgentAction::DigisacEnviarMensagem { .. } => "digisac_enviar_mensagem",
-            AgentAction::DigisacListarWebhooks { .. } => "digisac_listar_webhooks",
-            AgentAction::DigisacCriarWebhook { .. } => "digisac_criar_webhook",
+            AgentAction::ToolCall { .. } => "tool",
+            AgentAction::Parallel { .. } => "parallel",
         }
     }
 
@@ -272,15 +204,8 @@ impl AgentAction {
             AgentAction::Coding { think, .. } => think,
             AgentAction::History { think, .. } => think,
             AgentAction::AskUser { think, .. } => think,
-            // Paytour actions
-            AgentAction::PaytourListarPasseios { think, .. } => think,
-            AgentAction::PaytourDetalharPasseio { think, .. } => think,
-            AgentAction::PaytourVerificarDisponibilidade { think, .. } => think,
-            AgentAction::PaytourObterHorarios { think, .. } => think,
-            // Digisac actions
-            AgentAction::DigisacEnviarMensagem { think, .. } => think,
-            AgentAction::DigisacListarWebhooks { think, .. } => think,
-            AgentAction::DigisacCriarWebhook { think, .. } => think,
+            AgentAction::ToolCall { think, .. } => think,
+            AgentAction::Parallel { think, .. } => think,
         }
     }
 
@@ -289,6 +214,16 @@ impl AgentAction {
         matches!(self, AgentAction::Search { .. })
     }
 
+    /// Verifica se a ação pode fazer parte de um plano paralelo
+    ///
+    /// Só ações que apenas coletam informação são independentes entre si.
+    pub fn is_parallelizable(&self) -> bool {
+        matches!(
+            self,
+            AgentAction::Search { .. } | AgentAction::Read { .. } | AgentAction::ToolCall { .. }
+        )
+    }
+
     /// Verifica se é uma ação de resposta
     pub fn is_answer(&self) -> bool {
         matches!(self, AgentAction::Answer { .. })
@@ -315,30 +250,9 @@ impl AgentAction {
         )
     }
 
-    /// Verifica se é uma ação de integração Paytour
-    pub fn is_paytour(&self) -> bool {
-        matches!(
-            self,
-            AgentAction::PaytourListarPasseios { .. }
-                | AgentAction::PaytourDetalharPasseio { .. }
-                | AgentAction::PaytourVerificarDisponibilidade { .. }
-                | AgentAction::PaytourObterHorarios { .. }
-        )
-    }
-
-    /// Verifica se é uma ação de integração Digisac
-    pub fn is_digisac(&self) -> bool {
-        matches!(
-            self,
-            AgentAction::DigisacEnviarMensagem { .. }
-                | AgentAction::DigisacListarWebhooks { .. }
-                | AgentAction::DigisacCriarWebhook { .. }
-        )
-    }
-
-    /// Verifica se é uma ação de integração externa
-    pub fn is_integration(&self) -> bool {
-        self.is_paytour() || self.is_digisac()
+    /// Verifica se é uma chamada de ferramenta externa
+    pub fn is_tool_call(&self) -> bool {
+        matches!(self, AgentAction::ToolCall { .. })
     }
 }
 
@@ -351,7 +265,7 @@ impl AgentAction {
 /// - **Auditoria**: Rastrear o processo de pesquisa
 ///
 /// Cada variante corresponde a um tipo de ação executada.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DiaryEntry {
     /// Registro de uma busca executada.
     ///
@@ -454,32 +368,16 @@ pub enum DiaryEntry {
         was_spontaneous: bool,
     },
 
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-    // ENTRADAS DE INTEGRAÇÃO PAYTOUR
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-
-    /// Registro de consulta Paytour executada.
-    PaytourQuery {
-        /// Tipo de consulta (listar, detalhar, disponibilidade, horarios).
-        query_type: String,
-        /// Raciocínio do agente.
-        think: String,
-        /// Quantidade de resultados obtidos.
-        results_count: usize,
-    },
-
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-    // ENTRADAS DE INTEGRAÇÃO DIGISAC
-    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-
-    /// Registro de ação Digisac executada.
-    DigisacAction {
-        /// Tipo de ação (enviar_mensagem, listar_webhooks, criar_webhook).
-        action_type: String,
+    /// Registro de chamada de ferramenta externa.
+    ToolCall {
+        /// Nome da ferramenta.
+        tool: String,
         /// Raciocínio do agente.
         think: String,
-        /// Se a ação foi bem-sucedida.
+        /// Se a chamada foi bem-sucedida.
         success: bool,
+        /// Itens de conhecimento produzidos (ou o erro, em falhas).
+        summary: String,
     },
 }
 
@@ -549,19 +447,17 @@ impl DiaryEntry {
                 };
                 format!("{} {}", prefix, response)
             }
-            DiaryEntry::PaytourQuery { query_type, think, results_count } => {
-                format!(
-                    "[PAYTOUR] {} -> {} results\nThink: {}",
-                    query_type,
-                    results_count,
-                    think
-                )
-            }
-            DiaryEntry::DigisacAction { action_type, think, success } => {
+            DiaryEntry::ToolCall {
+                tool,
+                think,
+                success,
+                summary,
+            } => {
                 format!(
-                    "[DIGISAC] {} -> {}\nThink: {}",
-                    action_type,
+                    "[TOOL:{}] {} -> {}\nThink: {}",
+                    tool,
                     if *success { "success" } else { "failed" },
+                    summary,
                     think
                 )
             }
@@ -570,7 +466,7 @@ impl DiaryEntry {
 }
 
 /// Prompt para o agente decidir a próxima ação
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentPrompt {
     /// Prompt do sistema
     pub system: String,
@@ -181,30 +181,19 @@ fn format_diary_for_analysis(diary: &[DiaryEntry], original_question: &str) -> S
                     step_num, action_type, response
                 ));
             }
-            DiaryEntry::PaytourQuery {
-                query_type,
-                think,
-                results_count,
-            } => {
-                output.push_str(&format!(
-                    "At step {}, you took the **paytour_query** action:\n\
-                    Query type: {}\n\
-                    Results found: {}\n\
-                    Think: {}\n\n",
-                    step_num, query_type, results_count, think
-                ));
-            }
-            DiaryEntry::DigisacAction {
-                action_type,
+            DiaryEntry::ToolCall {
+                tool,
                 think,
                 success,
+                summary,
             } => {
                 output.push_str(&format!(
-                    "At step {}, you took the **digisac_action** action:\n\
-                    Action type: {}\n\
+                    "At step {}, you took the **tool** action:\n\
+                    Tool: {}\n\
                     Success: {}\n\
+                    Result: {}\n\
                     Think: {}\n\n",
-                    step_num, action_type, success, think
+                    step_num, tool, success, summary, think
                 ));
             }
         }
@@ -0,0 +1,584 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CHECKPOINTS - Persistência do estado do agente entre steps
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Após cada step o agente serializa todo o seu working set (contexto,
+// estado, tracker de tokens e contadores) em um arquivo JSON. Se o processo
+// morrer no meio da pesquisa (crash, deploy, OOM), `DeepResearchAgent::resume`
+// reconstrói o agente a partir do último step concluído, sem repetir buscas,
+// leituras ou tokens já pagos. Execuções concluídas ou falhas removem o
+// checkpoint; só as interrompidas ficam no diretório.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{AgentAction, AgentContext, AgentState};
+use crate::types::Language;
+use crate::utils::TokenTracker;
+
+/// Versão do formato do checkpoint (incrementar ao mudar o schema)
+pub const CHECKPOINT_VERSION: u32 = 2;
+
+/// Diretório padrão dos checkpoints (relativo ao diretório atual)
+pub const DEFAULT_CHECKPOINT_DIR: &str = "checkpoints";
+
+/// Erros de leitura/escrita de checkpoints
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    /// Falha de I/O ao ler ou gravar o arquivo
+    #[error("Checkpoint I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON inválido ou incompatível
+    #[error("Checkpoint serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Checkpoint gravado por uma versão incompatível do formato
+    #[error("Checkpoint version mismatch: found {found}, expected {expected}")]
+    VersionMismatch {
+        /// Versão encontrada no arquivo
+        found: u32,
+        /// Versão suportada por este binário
+        expected: u32,
+    },
+
+    /// Nenhum checkpoint encontrado para o id/caminho informado
+    #[error("Checkpoint not found: {0}")]
+    NotFound(String),
+
+    /// run_id com caracteres fora de `[A-Za-z0-9_-]` (ex.: `../`, caminho absoluto)
+    #[error("Invalid run id: {0}")]
+    InvalidRunId(String),
+}
+
+/// Contadores internos do agente preservados entre execuções
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentCounters {
+    /// Buscas realizadas
+    pub search_count: usize,
+    /// Leituras realizadas
+    pub read_count: usize,
+    /// Respostas geradas
+    pub answer_count: usize,
+    /// Vitórias Jina no modo comparativo
+    pub jina_wins: usize,
+    /// Vitórias Rust no modo comparativo
+    pub rust_wins: usize,
+    /// Empates no modo comparativo
+    pub ties: usize,
+    /// Falhas consecutivas de validação
+    pub consecutive_failures: usize,
+    /// Análises do AgentAnalyzer já disparadas
+    pub analysis_count: usize,
+}
+
+/// Snapshot completo do agente após um step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCheckpoint {
+    /// Versão do formato
+    pub version: u32,
+    /// ID único da execução
+    pub run_id: String,
+    /// Data/hora de criação da execução (ISO 8601)
+    pub created_at: String,
+    /// Data/hora da última gravação (ISO 8601)
+    pub updated_at: String,
+    /// Estado da máquina de estados
+    pub state: AgentState,
+    /// Contexto acumulado (conhecimento, diário, URLs, gap questions)
+    pub context: AgentContext,
+    /// Tracker de tokens (budget + histórico por step)
+    pub token_tracker: TokenTracker,
+    /// Contadores internos
+    pub counters: AgentCounters,
+    /// Idioma das respostas
+    pub response_language: Language,
+    /// Se o modo de leitura comparativa estava ativo
+    pub enable_comparative_read: bool,
+    /// Se o polimento pós-aprovação estava ativo
+    #[serde(default)]
+    pub finalize_answers: bool,
+    /// Se a verificação por afirmação estava ativa
+    #[serde(default)]
+    pub verify_claims: bool,
+    /// Se a detecção de contradições estava ativa
+    #[serde(default)]
+    pub detect_contradictions: bool,
+    /// JSON Schema exigido da resposta, se configurado
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
+    /// Budget de tempo de parede (ms), se configurado
+    #[serde(default)]
+    pub time_budget_ms: Option<u64>,
+    /// Fração do prazo que dispara o Beast Mode
+    #[serde(default = "default_time_beast_mode_fraction")]
+    pub time_beast_mode_fraction: f64,
+    /// Tempo de parede já consumido pela execução (ms)
+    pub elapsed_ms: u64,
+    /// Ação que aguardava aprovação humana (perguntada de novo ao retomar)
+    #[serde(default)]
+    pub pending_approval: Option<AgentAction>,
+}
+
+fn default_time_beast_mode_fraction() -> f64 {
+    super::DEFAULT_TIME_BEAST_MODE_FRACTION
+}
+
+impl AgentCheckpoint {
+    /// Grava o checkpoint de forma atômica (arquivo temporário + rename)
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let json = serde_json::to_string(self)?;
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Carrega um checkpoint do disco validando a versão
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        if !path.exists() {
+            return Err(CheckpointError::NotFound(path.display().to_string()));
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let checkpoint: Self = serde_json::from_str(&content)?;
+
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::VersionMismatch {
+                found: checkpoint.version,
+                expected: CHECKPOINT_VERSION,
+            });
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Se a execução foi interrompida antes de chegar a um estado terminal
+    pub fn is_interrupted(&self) -> bool {
+        !self.state.is_terminal()
+    }
+
+    /// Resumo leve para listagem (CLI / servidor)
+    pub fn summary(&self, path: &Path) -> CheckpointSummary {
+        let state = match &self.state {
+            AgentState::Processing { .. } => "processing",
+            AgentState::BeastMode { .. } => "beast_mode",
+            AgentState::InputRequired { .. } => "input_required",
+            AgentState::Completed { .. } => "completed",
+            AgentState::Failed { .. } => "failed",
+            AgentState::Cancelled { .. } => "cancelled",
+        };
+
+        CheckpointSummary {
+            run_id: self.run_id.clone(),
+            question: self.context.original_question.clone(),
+            path: path.to_path_buf(),
+            state: state.to_string(),
+            total_step: self.context.total_step,
+            knowledge_items: self.context.knowledge.len(),
+            visited_urls: self.context.visited_urls.len(),
+            tokens_used: self.token_tracker.total_tokens(),
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+        }
+    }
+}
+
+/// Resumo de um checkpoint para listagem de execuções
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointSummary {
+    /// ID único da execução
+    pub run_id: String,
+    /// Pergunta original
+    pub question: String,
+    /// Caminho do arquivo de checkpoint
+    pub path: PathBuf,
+    /// Estado no momento da gravação
+    pub state: String,
+    /// Último step concluído
+    pub total_step: usize,
+    /// Itens de conhecimento acumulados
+    pub knowledge_items: usize,
+    /// URLs visitadas
+    pub visited_urls: usize,
+    /// Tokens consumidos
+    pub tokens_used: u64,
+    /// Criação da execução (ISO 8601)
+    pub created_at: String,
+    /// Última gravação (ISO 8601)
+    pub updated_at: String,
+}
+
+/// Caminho do checkpoint de uma execução dentro de um diretório
+pub fn checkpoint_path(dir: &Path, run_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", run_id))
+}
+
+/// Lista todos os checkpoints válidos de um diretório (mais recentes primeiro)
+pub fn list_checkpoints(dir: &Path) -> Vec<CheckpointSummary> {
+    let mut summaries = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                match AgentCheckpoint::load(&path) {
+                    Ok(checkpoint) => summaries.push(checkpoint.summary(&path)),
+                    Err(e) => log::debug!("Ignorando checkpoint {}: {}", path.display(), e),
+                }
+            }
+        }
+    }
+
+    summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    summaries
+}
+
+/// Lista apenas execuções interrompidas (estado não terminal)
+pub fn list_interrupted_runs(dir: &Path) -> Vec<CheckpointSummary> {
+    list_checkpoints(dir)
+        .into_iter()
+        .filter(|s| !matches!(s.state.as_str(), "completed" | "failed"))
+        .collect()
+}
+
+/// Verifica se um run_id é seguro para virar nome de arquivo (`[A-Za-z0-9_-]`)
+pub fn is_valid_run_id(run_id: &str) -> bool {
+    !run_id.is_empty()
+        && run_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Resolve um run_id (ou prefixo) para um checkpoint dentro de `dir`
+///
+/// Não aceita caminhos: ids com separadores ou `..` são recusados e o
+/// arquivo resolvido precisa continuar dentro do diretório. Usado pelo
+/// servidor, onde o id vem da URL.
+pub fn find_run_checkpoint(dir: &Path, run_id: &str) -> Result<PathBuf, CheckpointError> {
+    if !is_valid_run_id(run_id) {
+        return Err(CheckpointError::InvalidRunId(run_id.to_string()));
+    }
+
+    let exact = checkpoint_path(dir, run_id);
+    let path = if exact.is_file() {
+        exact
+    } else {
+        let mut matches: Vec<PathBuf> = list_checkpoints(dir)
+            .into_iter()
+            .filter(|s| s.run_id.starts_with(run_id))
+            .map(|s| s.path)
+            .collect();
+        if matches.len() != 1 {
+            return Err(CheckpointError::NotFound(run_id.to_string()));
+        }
+        matches.remove(0)
+    };
+
+    // Links simbólicos não podem levar o checkpoint para fora do diretório
+    let inside = match (dir.canonicalize(), path.canonicalize()) {
+        (Ok(dir), Ok(resolved)) => resolved.starts_with(dir),
+        _ => false,
+    };
+    if !inside {
+        return Err(CheckpointError::NotFound(run_id.to_string()));
+    }
+    Ok(path)
+}
+
+/// Resolve um run_id (ou prefixo) ou caminho explícito para o arquivo de checkpoint
+///
+/// Caminhos explícitos só fazem sentido na CLI (`--resume arquivo.json`);
+/// ids vindos de fora devem passar por [`find_run_checkpoint`].
+pub fn find_checkpoint(dir: &Path, id_or_path: &str) -> Result<PathBuf, CheckpointError> {
+    let explicit = PathBuf::from(id_or_path);
+    if explicit.is_file() {
+        return Ok(explicit);
+    }
+    find_run_checkpoint(dir, id_or_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::GapQueue;
+    use crate::types::{KnowledgeItem, KnowledgeType};
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("deep-research-ckpt-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn sample_checkpoint(run_id: &str, state: AgentState) -> AgentCheckpoint {
+        let mut context = AgentContext::new();
+        context.original_question = "Qual a capital da França?".into();
+        context.gap_queue = GapQueue::new("Qual a capital da França?");
+        context.total_step = 3;
+        context.visited_urls.push("https://example.com".into());
+        context.add_knowledge(KnowledgeItem {
+            question: "Capital?".into(),
+            answer: "Paris".into(),
+            item_type: KnowledgeType::Url,
+            references: vec![],
+        });
+
+        let mut token_tracker = TokenTracker::new(Some(10_000));
+        token_tracker.track(1, "decide_action:search", 100, 50);
+
+        AgentCheckpoint {
+            version: CHECKPOINT_VERSION,
+            run_id: run_id.into(),
+            created_at: "2024-01-01T00:00:00Z".into(),
+            updated_at: "2024-01-01T00:01:00Z".into(),
+            state,
+            context,
+            token_tracker,
+            counters: AgentCounters {
+                search_count: 2,
+                ..Default::default()
+            },
+            response_language: Language::Portuguese,
+            enable_comparative_read: false,
+            finalize_answers: false,
+            verify_claims: false,
+            detect_contradictions: false,
+            response_schema: None,
+            time_budget_ms: None,
+            time_beast_mode_fraction: 0.85,
+            elapsed_ms: 1234,
+            pending_approval: None,
+        }
+    }
+
+    fn processing() -> AgentState {
+        AgentState::Processing {
+            step: 3,
+            total_step: 3,
+            current_question: "Qual a capital da França?".into(),
+            budget_used: 0.015,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "run-1");
+        let checkpoint = sample_checkpoint("run-1", processing());
+
+        checkpoint.save(&path).unwrap();
+        let loaded = AgentCheckpoint::load(&path).unwrap();
+
+        assert_eq!(loaded.run_id, "run-1");
+        assert_eq!(loaded.context.total_step, 3);
+        assert_eq!(loaded.context.knowledge.len(), 1);
+        assert_eq!(loaded.context.visited_urls, vec!["https://example.com"]);
+        assert_eq!(loaded.token_tracker.total_tokens(), 150);
+        assert_eq!(loaded.counters.search_count, 2);
+        assert!(loaded.is_interrupted());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_list_interrupted_runs() {
+        let dir = temp_dir();
+        sample_checkpoint("run-open", processing())
+            .save(&checkpoint_path(&dir, "run-open"))
+            .unwrap();
+        sample_checkpoint(
+            "run-done",
+            AgentState::Completed {
+                answer: "Paris".into(),
+                references: vec![],
+                trivial: false,
+            },
+        )
+        .save(&checkpoint_path(&dir, "run-done"))
+        .unwrap();
+
+        assert_eq!(list_checkpoints(&dir).len(), 2);
+        let interrupted = list_interrupted_runs(&dir);
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].run_id, "run-open");
+        assert_eq!(interrupted[0].state, "processing");
+
+        let found = find_checkpoint(&dir, "run-o").unwrap();
+        assert_eq!(found, checkpoint_path(&dir, "run-open"));
+        assert!(find_checkpoint(&dir, "run-").is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "old");
+        let mut checkpoint = sample_checkpoint("old", processing());
+        checkpoint.version = CHECKPOINT_VERSION + 1;
+        checkpoint.save(&path).unwrap();
+
+        assert!(matches!(
+            AgentCheckpoint::load(&path),
+            Err(CheckpointError::VersionMismatch { .. })
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_resume_completed_run() {
+        use crate::agent::DeepResearchAgent;
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "run-done");
+        sample_checkpoint(
+            "run-done",
+            AgentState::Completed {
+                answer: "Paris".into(),
+                references: vec![],
+                trivial: false,
+            },
+        )
+        .save(&path)
+        .unwrap();
+
+        let agent = DeepResearchAgent::resume(
+            &path,
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+        )
+        .unwrap();
+        assert_eq!(agent.run_id(), "run-done");
+        assert_eq!(agent.original_question(), "Qual a capital da França?");
+
+        let question = agent.original_question().to_string();
+        let result = agent.run(question).await;
+        assert!(result.success);
+        assert_eq!(result.answer.as_deref(), Some("Paris"));
+        assert_eq!(result.token_usage.total_tokens, 150);
+        assert_eq!(result.visited_urls, vec!["https://example.com"]);
+
+        // Execução encerrada não deixa checkpoint para trás
+        assert!(!path.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_stays_resumable() {
+        use crate::agent::{CancellationToken, DeepResearchAgent};
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "run-cancel");
+        sample_checkpoint("run-cancel", processing()).save(&path).unwrap();
+
+        let token = CancellationToken::new();
+        let agent = DeepResearchAgent::resume(
+            &path,
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+        )
+        .unwrap()
+        .with_cancellation_token(token.clone());
+        token.cancel();
+
+        let question = agent.original_question().to_string();
+        let result = agent.run(question).await;
+        assert!(!result.success);
+        assert!(result.cancelled);
+        assert_eq!(result.partial_knowledge.len(), 1);
+        assert_eq!(result.token_usage.total_tokens, 150);
+
+        // O checkpoint continua no último step e pode ser retomado
+        assert!(AgentCheckpoint::load(&path).unwrap().is_interrupted());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_resume_with_expired_time_budget() {
+        use crate::agent::DeepResearchAgent;
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "run-deadline");
+        let mut checkpoint = sample_checkpoint("run-deadline", processing());
+        checkpoint.time_budget_ms = Some(0);
+        checkpoint.save(&path).unwrap();
+
+        let agent = DeepResearchAgent::resume(
+            &path,
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+        )
+        .unwrap();
+
+        let question = agent.original_question().to_string();
+        let result = agent.run(question).await;
+        assert!(!result.success);
+        assert!(!result.cancelled);
+        assert_eq!(result.error.as_deref(), Some("Time budget exhausted"));
+        assert_eq!(result.partial_knowledge.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_pending_approval_refused_after_resume() {
+        use crate::agent::{ApprovalPolicy, DeepResearchAgent, QuestionType, StepResult};
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let path = checkpoint_path(&dir, "run-approval");
+        let mut checkpoint = sample_checkpoint(
+            "run-approval",
+            AgentState::InputRequired {
+                question_id: "q-1".into(),
+                question: "Aprovar a ação coding?".into(),
+                question_type: QuestionType::Confirmation,
+                options: None,
+            },
+        );
+        checkpoint.pending_approval = Some(AgentAction::Coding {
+            problem: "somar valores".into(),
+            context_vars: None,
+            language: None,
+            think: "calcular".into(),
+        });
+        checkpoint.save(&path).unwrap();
+
+        let mut agent = DeepResearchAgent::resume(
+            &path,
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+        )
+        .unwrap()
+        .with_approval_policy(ApprovalPolicy::new().require("coding"));
+        assert!(agent.checkpoint().pending_approval.is_some());
+
+        // Sem canal de interação ninguém aprova: a ação é recusada, não executada
+        assert!(matches!(agent.execute_step().await, StepResult::Continue));
+        assert!(agent.checkpoint().pending_approval.is_none());
+        let refusal = agent.context.knowledge.last().unwrap();
+        assert_eq!(refusal.item_type, KnowledgeType::Error);
+        assert!(refusal.question.contains("coding"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -3,8 +3,16 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use super::agent_analyzer::AgentAnalysis;
+use super::contradictions::FactLedger;
+use super::gap_queue::GapQueue;
 use super::DiaryEntry;
 use crate::types::{BoostedSearchSnippet, KnowledgeItem, KnowledgeType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Conjunto de URLs compartilhado entre os agentes de uma equipe
+pub type SharedUrlSet = Arc<Mutex<HashSet<String>>>;
 
 /// Contexto acumulado durante a execução do agente
 ///
@@ -13,13 +21,13 @@ use crate::types::{BoostedSearchSnippet, KnowledgeItem, KnowledgeType};
 /// - Conhecimento acumulado
 /// - Perguntas de gap
 /// - Histórico de ações (diário)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentContext {
     /// Pergunta original do usuário
     pub original_question: String,
 
-    /// Perguntas de gap a serem respondidas
-    pub gap_questions: Vec<String>,
+    /// Árvore priorizada de perguntas (raiz = pergunta original)
+    pub gap_queue: GapQueue,
 
     /// Conhecimento acumulado durante a pesquisa
     pub knowledge: Vec<KnowledgeItem>,
@@ -62,6 +70,16 @@ pub struct AgentContext {
 
     /// Última análise de erro realizada (para display na TUI)
     pub last_agent_analysis: Option<AgentAnalysis>,
+
+    /// Fatos extraídos das fontes e conflitos entre elas
+    #[serde(default)]
+    pub fact_ledger: FactLedger,
+
+    /// URLs já lidas (ou em leitura) por qualquer agente da equipe
+    ///
+    /// `None` fora do modo equipe. Não é persistido em checkpoints.
+    #[serde(skip)]
+    pub shared_visited_urls: Option<SharedUrlSet>,
 }
 
 impl AgentContext {
@@ -69,7 +87,7 @@ impl AgentContext {
     pub fn new() -> Self {
         Self {
             original_question: String::new(),
-            gap_questions: Vec::new(),
+            gap_queue: GapQueue::default(),
             knowledge: Vec::new(),
             collected_urls: Vec::new(),
             weighted_urls: Vec::new(),
@@ -84,6 +102,8 @@ impl AgentContext {
             executed_queries: Vec::new(),
             improvement_hints: Vec::new(),
             last_agent_analysis: None,
+            fact_ledger: FactLedger::default(),
+            shared_visited_urls: None,
         }
     }
 
@@ -101,12 +121,10 @@ impl AgentContext {
 
     /// Retorna a pergunta atual sendo processada
     pub fn current_question(&self) -> &str {
-        if self.gap_questions.is_empty() {
-            &self.original_question
-        } else {
-            let idx = self.total_step % self.gap_questions.len();
-            &self.gap_questions[idx]
-        }
+        self.gap_queue
+            .current()
+            .map(|q| q.question.as_str())
+            .unwrap_or(&self.original_question)
     }
 
     /// Adiciona URLs ao contexto
@@ -167,17 +185,47 @@ impl AgentContext {
     /// Verifica se uma URL já foi visitada
     pub fn is_url_visited(&self, url: &str) -> bool {
         self.visited_urls.contains(&url.to_string())
+            || self
+                .shared_visited_urls
+                .as_ref()
+                .map(|shared| shared.lock().map(|set| set.contains(url)).unwrap_or(false))
+                .unwrap_or(false)
+    }
+
+    /// Reserva uma URL para leitura no conjunto compartilhado da equipe
+    ///
+    /// Retorna `false` se outro agente já reservou a URL.
+    /// Sem conjunto compartilhado, sempre retorna `true`.
+    pub fn claim_url(&self, url: &str) -> bool {
+        match &self.shared_visited_urls {
+            Some(shared) => shared
+                .lock()
+                .map(|mut set| set.insert(url.to_string()))
+                .unwrap_or(true),
+            None => true,
+        }
     }
 
     /// Verifica se uma URL é ruim (falhou anteriormente)
     ///
     /// NOTA: URLs de redes sociais, paywalls e JS-heavy agora são
     /// automaticamente roteadas para Jina Reader em vez de bloqueadas.
-    /// Veja `search::url_requires_jina()` para a lista de domínios.
+    /// Veja `CredibilityConfig::reader_required` para a lista de domínios.
     pub fn is_url_bad(&self, url: &str) -> bool {
         self.bad_urls.contains(&url.to_string())
     }
 
+    /// URLs ainda não lidas, da maior para a menor pontuação final
+    pub fn ranked_available_urls(&self) -> Vec<&BoostedSearchSnippet> {
+        let mut urls: Vec<_> = self
+            .collected_urls
+            .iter()
+            .filter(|u| !self.is_url_visited(&u.url) && !self.is_url_bad(&u.url))
+            .collect();
+        urls.sort_by(|a, b| b.final_score.total_cmp(&a.final_score));
+        urls
+    }
+
     /// Retorna o número total de URLs coletadas
     pub fn total_urls(&self) -> usize {
         self.collected_urls.len()
@@ -222,7 +270,7 @@ impl AgentContext {
     /// Limpa o contexto para reutilização
     pub fn reset(&mut self) {
         self.original_question.clear();
-        self.gap_questions.clear();
+        self.gap_queue = GapQueue::default();
         self.knowledge.clear();
         self.collected_urls.clear();
         self.weighted_urls.clear();
@@ -237,6 +285,7 @@ impl AgentContext {
         self.executed_queries.clear();
         self.improvement_hints.clear();
         self.last_agent_analysis = None;
+        self.fact_ledger = FactLedger::default();
     }
 
     /// Adiciona um hint de melhoria do AgentAnalyzer
@@ -267,12 +316,13 @@ impl Default for AgentContext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::gap_queue::ROOT_GAP_ID;
 
     #[test]
     fn test_context_creation() {
         let ctx = AgentContext::new();
         assert!(ctx.original_question.is_empty());
-        assert!(ctx.gap_questions.is_empty());
+        assert!(ctx.gap_queue.is_empty());
         assert!(ctx.knowledge.is_empty());
         assert_eq!(ctx.total_step, 0);
     }
@@ -296,22 +346,19 @@ mod tests {
     }
 
     #[test]
-    fn test_current_question_rotation() {
+    fn test_current_question_follows_gap_queue() {
         let mut ctx = AgentContext::new();
         ctx.original_question = "Original".into();
-        ctx.gap_questions = vec!["Q1".into(), "Q2".into(), "Q3".into()];
+        assert_eq!(ctx.current_question(), "Original");
 
-        ctx.total_step = 0;
-        assert_eq!(ctx.current_question(), "Q1");
-
-        ctx.total_step = 1;
-        assert_eq!(ctx.current_question(), "Q2");
+        ctx.gap_queue = GapQueue::new("Original");
+        ctx.gap_queue.select_next();
+        ctx.gap_queue
+            .add("Q1".into(), ROOT_GAP_ID, 0, 1, None)
+            .unwrap();
 
-        ctx.total_step = 2;
-        assert_eq!(ctx.current_question(), "Q3");
-
-        ctx.total_step = 3;
-        assert_eq!(ctx.current_question(), "Q1"); // Volta ao início
+        ctx.gap_queue.select_next();
+        assert_eq!(ctx.current_question(), "Q1");
     }
 
     #[test]
@@ -0,0 +1,374 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// DETECÇÃO DE CONTRADIÇÕES - Fatos em que as fontes discordam
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Quando duas fontes discordam (datas de lançamento, preços, estatísticas),
+// o agente escolhia uma delas sem perceber. Agora:
+// - Após cada ação, o LLM extrai fatos (entidade, atributo, valor) do
+//   conhecimento novo, reaproveitando os nomes de entidade/atributo já vistos
+// - O `FactLedger` compara cada fato com os anteriores da mesma entidade e
+//   atributo; valores diferentes de fontes diferentes viram um conflito
+// - Os conflitos entram no prompt como "fatos disputados" e no
+//   `ResearchResult`, para a resposta dizer que as fontes discordam
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+
+use super::AgentPrompt;
+use crate::llm::{LlmClient, LlmError, LlmResponse};
+use crate::types::KnowledgeItem;
+use crate::utils::extract_json;
+
+/// Máximo de fatos extraídos por fonte
+pub const MAX_FACTS_PER_SOURCE: usize = 10;
+
+/// Máximo de chaves conhecidas enviadas ao LLM para reaproveitar nomes
+const MAX_KNOWN_KEYS: usize = 50;
+
+/// Tamanho máximo do texto de cada fonte enviado na extração (em caracteres)
+const MAX_SOURCE_CHARS: usize = 4_000;
+
+/// Instruções da extração de fatos
+const EXTRACT_PROMPT: &str = "You extract checkable facts from research sources.
+For each numbered source, list up to {max} concrete facts as (entity, attribute, value): dates, prices, quantities, statistics, names of people in a role.
+Use short canonical entity and attribute names. When a fact is about an entity/attribute in the known list, reuse that exact name.
+Normalize values: ISO dates (YYYY-MM-DD, YYYY-MM or YYYY), numbers with '.' as decimal separator and no thousands separators, and keep units and currencies.
+Skip opinions and facts without a concrete value.
+Reply with JSON only: {\"facts\": [{\"source\": 1, \"entity\": \"...\", \"attribute\": \"...\", \"value\": \"...\"}]}";
+
+/// Fato extraído de uma fonte
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fact {
+    /// Entidade (ex.: "iPhone 15")
+    pub entity: String,
+    /// Atributo (ex.: "release date")
+    pub attribute: String,
+    /// Valor afirmado pela fonte
+    pub value: String,
+    /// URL da fonte
+    pub source: String,
+}
+
+impl Fact {
+    /// Chave normalizada `entidade | atributo`
+    fn key(&self) -> (String, String) {
+        (normalize_name(&self.entity), normalize_name(&self.attribute))
+    }
+}
+
+/// Valor afirmado por uma fonte em um conflito
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourcedValue {
+    /// Valor afirmado
+    pub value: String,
+    /// URL da fonte
+    pub source: String,
+}
+
+/// Duas fontes que discordam sobre o mesmo atributo de uma entidade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactConflict {
+    /// Entidade
+    pub entity: String,
+    /// Atributo
+    pub attribute: String,
+    /// Valor registrado primeiro
+    pub first: SourcedValue,
+    /// Valor divergente
+    pub second: SourcedValue,
+}
+
+impl FactConflict {
+    /// Linha legível do conflito
+    pub fn describe(&self) -> String {
+        format!(
+            "{} / {}: \"{}\" ({}) vs \"{}\" ({})",
+            self.entity,
+            self.attribute,
+            self.first.value,
+            self.first.source,
+            self.second.value,
+            self.second.source
+        )
+    }
+}
+
+/// Fatos já vistos e conflitos encontrados
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FactLedger {
+    /// Fatos registrados, na ordem de chegada
+    pub facts: Vec<Fact>,
+    /// Conflitos detectados
+    pub conflicts: Vec<FactConflict>,
+}
+
+impl FactLedger {
+    /// Registra fatos e retorna quantos conflitos novos surgiram
+    ///
+    /// Um conflito exige fontes diferentes e valores que não contêm um ao
+    /// outro ("2023" e "2023-09" são compatíveis). Cada par de valores de
+    /// uma chave é registrado uma vez só.
+    pub fn record(&mut self, facts: Vec<Fact>) -> usize {
+        let before = self.conflicts.len();
+        for fact in facts {
+            let key = fact.key();
+            if key.0.is_empty() || key.1.is_empty() || fact.value.trim().is_empty() {
+                continue;
+            }
+            let value = normalize_value(&fact.value);
+
+            let conflicting = self.facts.iter().find(|known| {
+                known.key() == key
+                    && known.source != fact.source
+                    && !values_agree(&normalize_value(&known.value), &value)
+            });
+            if let Some(known) = conflicting {
+                let already = self.conflicts.iter().any(|c| {
+                    (normalize_name(&c.entity), normalize_name(&c.attribute)) == key
+                        && [&c.first.value, &c.second.value]
+                            .iter()
+                            .any(|v| values_agree(&normalize_value(v), &value))
+                });
+                if !already {
+                    log::info!(
+                        "⚖️ Fontes discordam: {} / {}: {} vs {}",
+                        fact.entity,
+                        fact.attribute,
+                        known.value,
+                        fact.value
+                    );
+                    self.conflicts.push(FactConflict {
+                        entity: known.entity.clone(),
+                        attribute: known.attribute.clone(),
+                        first: SourcedValue {
+                            value: known.value.clone(),
+                            source: known.source.clone(),
+                        },
+                        second: SourcedValue {
+                            value: fact.value.clone(),
+                            source: fact.source.clone(),
+                        },
+                    });
+                }
+            }
+
+            let duplicate = self
+                .facts
+                .iter()
+                .any(|known| known.key() == key && known.source == fact.source);
+            if !duplicate {
+                self.facts.push(fact);
+            }
+        }
+        self.conflicts.len() - before
+    }
+
+    /// Nomes `entidade | atributo` já usados, para o LLM reaproveitar
+    pub fn known_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for fact in &self.facts {
+            let key = format!("{} | {}", fact.entity, fact.attribute);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        // Os mais recentes primeiro
+        keys.into_iter().rev().take(MAX_KNOWN_KEYS).collect()
+    }
+
+    /// Seção de fatos disputados para o prompt, se houver conflitos
+    pub fn prompt_section(&self) -> Option<String> {
+        if self.conflicts.is_empty() {
+            return None;
+        }
+        let lines = self
+            .conflicts
+            .iter()
+            .map(|c| format!("- {}", c.describe()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(format!(
+            "\n## ⚖️ DISPUTED FACTS (sources disagree):\n{}\n\
+             Do not silently pick one value: prefer the most authoritative or recent source, \
+             or state in the answer that sources disagree and cite both.\n",
+            lines
+        ))
+    }
+}
+
+/// Extrai fatos dos itens de conhecimento com fonte
+///
+/// `known_keys` são os nomes já usados no ledger, para que o mesmo fato de
+/// fontes diferentes caia na mesma chave.
+pub async fn extract_facts(
+    items: &[KnowledgeItem],
+    known_keys: &[String],
+    llm: &dyn LlmClient,
+) -> Result<(Vec<Fact>, LlmResponse), LlmError> {
+    let sources: Vec<(&str, &KnowledgeItem)> = items
+        .iter()
+        .filter_map(|item| item.references.first().map(|r| (r.url.as_str(), item)))
+        .collect();
+
+    let body = sources
+        .iter()
+        .enumerate()
+        .map(|(i, (url, item))| {
+            let text: String = item.answer.chars().take(MAX_SOURCE_CHARS).collect();
+            format!("<source id=\"{}\" url=\"{}\">\n{}\n</source>", i + 1, url, text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let known = if known_keys.is_empty() {
+        "(none)".to_string()
+    } else {
+        known_keys.join("\n")
+    };
+    let prompt = AgentPrompt {
+        system: EXTRACT_PROMPT.replace("{max}", &MAX_FACTS_PER_SOURCE.to_string()),
+        user: format!("Known entity | attribute names:\n{}\n\n{}", known, body),
+        diary: vec![],
+    };
+
+    let response = llm.generate_answer(&prompt, 0.0).await?;
+    let urls: Vec<&str> = sources.iter().map(|(url, _)| *url).collect();
+    let facts = parse_facts(&response.answer, &urls);
+    Ok((facts, response))
+}
+
+/// Interpreta os fatos do LLM, resolvendo o número da fonte para a URL
+fn parse_facts(raw: &str, urls: &[&str]) -> Vec<Fact> {
+    #[derive(Deserialize)]
+    struct RawFact {
+        source: usize,
+        entity: String,
+        attribute: String,
+        value: serde_json::Value,
+    }
+
+    #[derive(Deserialize)]
+    struct RawFacts {
+        facts: Vec<RawFact>,
+    }
+
+    let Some(parsed) = extract_json(raw)
+        .ok()
+        .and_then(|value| serde_json::from_value::<RawFacts>(value).ok())
+    else {
+        log::warn!("⚖️ Contradições: lista de fatos inválida");
+        return vec![];
+    };
+
+    let mut per_source = vec![0usize; urls.len()];
+    parsed
+        .facts
+        .into_iter()
+        .filter_map(|f| {
+            let index = f.source.checked_sub(1).filter(|&i| i < urls.len())?;
+            if per_source[index] >= MAX_FACTS_PER_SOURCE {
+                return None;
+            }
+            per_source[index] += 1;
+            let value = match f.value {
+                serde_json::Value::String(text) => text,
+                other => other.to_string(),
+            };
+            Some(Fact {
+                entity: f.entity.trim().to_string(),
+                attribute: f.attribute.trim().to_string(),
+                value: value.trim().to_string(),
+                source: urls[index].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Nome normalizado: minúsculas e espaços simples
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Valor normalizado: minúsculas, sem espaços e pontuação final
+fn normalize_value(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .trim_end_matches(['.', ','])
+        .to_string()
+}
+
+/// Valores compatíveis: iguais ou um contido no outro
+fn values_agree(a: &str, b: &str) -> bool {
+    a == b || (!a.is_empty() && !b.is_empty() && (a.contains(b) || b.contains(a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact(entity: &str, attribute: &str, value: &str, source: &str) -> Fact {
+        Fact {
+            entity: entity.into(),
+            attribute: attribute.into(),
+            value: value.into(),
+            source: source.into(),
+        }
+    }
+
+    #[test]
+    fn test_ledger_records_conflicts_once() {
+        let mut ledger = FactLedger::default();
+        assert_eq!(
+            ledger.record(vec![fact("iPhone 15", "release date", "2023-09-22", "https://a.com")]),
+            0
+        );
+
+        // Mesma fonte, valor compatível ou mais vago: sem conflito
+        assert_eq!(
+            ledger.record(vec![
+                fact("iPhone 15", "release date", "2023-09-12", "https://a.com"),
+                fact("iphone  15", "Release Date", "2023-09", "https://b.com"),
+            ]),
+            0
+        );
+
+        // Fonte diferente com outro valor
+        assert_eq!(
+            ledger.record(vec![fact("iPhone 15", "release date", "2023-09-15", "https://c.com")]),
+            1
+        );
+        assert_eq!(ledger.conflicts[0].first.source, "https://a.com");
+        assert_eq!(ledger.conflicts[0].second.value, "2023-09-15");
+
+        // O mesmo valor divergente de outra fonte não duplica o conflito
+        assert_eq!(
+            ledger.record(vec![fact("iPhone 15", "release date", "2023-09-15", "https://d.com")]),
+            0
+        );
+
+        let section = ledger.prompt_section().unwrap();
+        assert!(section.contains("DISPUTED FACTS"));
+        assert!(section.contains("\"2023-09-22\" (https://a.com) vs \"2023-09-15\" (https://c.com)"));
+        assert!(FactLedger::default().prompt_section().is_none());
+    }
+
+    #[test]
+    fn test_parse_facts() {
+        let raw = r#"{"facts": [
+            {"source": 1, "entity": " Widget ", "attribute": "price", "value": "US$ 10"},
+            {"source": 2, "entity": "Widget", "attribute": "units sold", "value": 5000},
+            {"source": 3, "entity": "Widget", "attribute": "price", "value": "US$ 12"}
+        ]}"#;
+        let facts = parse_facts(raw, &["https://a.com", "https://b.com"]);
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts[0].entity, "Widget");
+        assert_eq!(facts[1].value, "5000");
+        assert_eq!(facts[1].source, "https://b.com");
+        assert!(parse_facts("not json", &["https://a.com"]).is_empty());
+    }
+}
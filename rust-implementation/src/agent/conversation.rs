@@ -0,0 +1,339 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CONVERSAS - Perguntas de acompanhamento com contexto
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Uma conversa guarda, entre chamadas, o que a próxima pergunta precisa:
+// - Turnos anteriores (pergunta do usuário + resposta do assistente), que
+//   entram no contexto como `KnowledgeType::ChatHistory`
+// - Conhecimento acumulado pelas pesquisas anteriores
+// - URLs já visitadas (não são lidas de novo)
+//
+// `ConversationStore` mantém as conversas em memória, indexadas por id.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::ResearchResult;
+use crate::types::{KnowledgeItem, KnowledgeType};
+
+/// Máximo de turnos anteriores incluídos no contexto
+pub const MAX_HISTORY_TURNS: usize = 10;
+
+/// Máximo de itens de conhecimento mantidos por conversa (os mais recentes)
+pub const MAX_CONVERSATION_KNOWLEDGE: usize = 50;
+
+/// Máximo de URLs visitadas lembradas por conversa (as mais recentes)
+pub const MAX_CONVERSATION_URLS: usize = 200;
+
+/// Máximo de conversas em memória (as menos recentes são descartadas)
+pub const DEFAULT_MAX_CONVERSATIONS: usize = 1000;
+
+/// Um turno da conversa: pergunta do usuário e resposta do assistente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatTurn {
+    /// Mensagem do usuário
+    pub question: String,
+    /// Resposta do assistente (vazia se o turno ficou sem resposta)
+    pub answer: String,
+}
+
+impl ChatTurn {
+    /// Cria um turno
+    pub fn new(question: impl Into<String>, answer: impl Into<String>) -> Self {
+        Self {
+            question: question.into(),
+            answer: answer.into(),
+        }
+    }
+}
+
+/// Converte os últimos `MAX_HISTORY_TURNS` turnos em itens de conhecimento
+pub fn history_knowledge(turns: &[ChatTurn]) -> Vec<KnowledgeItem> {
+    turns[turns.len().saturating_sub(MAX_HISTORY_TURNS)..]
+        .iter()
+        .map(|turn| KnowledgeItem {
+            question: turn.question.clone(),
+            answer: turn.answer.clone(),
+            item_type: KnowledgeType::ChatHistory,
+            references: Vec::new(),
+        })
+        .collect()
+}
+
+/// Estado de uma conversa entre chamadas
+#[derive(Debug, Clone, Default)]
+pub struct ConversationState {
+    /// Turnos concluídos, em ordem
+    pub turns: Vec<ChatTurn>,
+    /// Conhecimento das pesquisas anteriores (sem histórico de chat)
+    pub knowledge: Vec<KnowledgeItem>,
+    /// URLs já visitadas nas pesquisas anteriores
+    pub visited_urls: Vec<String>,
+}
+
+impl ConversationState {
+    /// Registra o resultado de uma pergunta da conversa
+    ///
+    /// A resposta só vira turno quando a pesquisa teve sucesso; o
+    /// conhecimento e as URLs visitadas são aproveitados mesmo em falhas.
+    /// URLs cujo conhecimento foi descartado deixam de contar como visitadas,
+    /// para que turnos seguintes possam lê-las de novo.
+    pub fn record(&mut self, question: &str, result: &ResearchResult) {
+        if let Some(answer) = result.answer.as_ref().filter(|_| result.success) {
+            self.turns.push(ChatTurn::new(question, answer.as_str()));
+        }
+
+        for item in &result.knowledge {
+            let duplicate = self
+                .knowledge
+                .iter()
+                .any(|k| k.question == item.question && k.answer == item.answer);
+            if item.item_type != KnowledgeType::ChatHistory && !duplicate {
+                self.knowledge.push(item.clone());
+            }
+        }
+        for url in &result.visited_urls {
+            if !self.visited_urls.contains(url) {
+                self.visited_urls.push(url.clone());
+            }
+        }
+
+        let excess = self
+            .knowledge
+            .len()
+            .saturating_sub(MAX_CONVERSATION_KNOWLEDGE);
+        let dropped: Vec<KnowledgeItem> = self.knowledge.drain(..excess).collect();
+        let forgotten: Vec<&str> = dropped
+            .iter()
+            .flat_map(|item| item.references.iter())
+            .map(|r| r.url.as_str())
+            .filter(|url| {
+                !self
+                    .knowledge
+                    .iter()
+                    .any(|k| k.references.iter().any(|r| r.url == *url))
+            })
+            .collect();
+        self.visited_urls.retain(|url| !forgotten.contains(&url.as_str()));
+
+        let excess = self
+            .visited_urls
+            .len()
+            .saturating_sub(MAX_CONVERSATION_URLS);
+        self.visited_urls.drain(..excess);
+    }
+}
+
+/// Conversas em memória, indexadas por id
+pub struct ConversationStore {
+    /// Estado e sequência da última atualização de cada conversa
+    conversations: Mutex<HashMap<String, (u64, ConversationState)>>,
+    max_conversations: usize,
+}
+
+impl ConversationStore {
+    /// Cria um store que mantém até `max_conversations` conversas
+    pub fn new(max_conversations: usize) -> Self {
+        Self {
+            conversations: Mutex::new(HashMap::new()),
+            max_conversations: max_conversations.max(1),
+        }
+    }
+
+    /// Estado atual de uma conversa
+    pub fn get(&self, id: &str) -> Option<ConversationState> {
+        let conversations = self.conversations.lock().ok()?;
+        conversations.get(id).map(|(_, state)| state.clone())
+    }
+
+    /// Registra o resultado de uma pergunta, criando a conversa se preciso
+    pub fn record(&self, id: &str, question: &str, result: &ResearchResult) {
+        let Ok(mut conversations) = self.conversations.lock() else {
+            return;
+        };
+
+        let sequence = conversations
+            .values()
+            .map(|(seq, _)| *seq)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let entry = conversations
+            .entry(id.to_string())
+            .or_insert_with(|| (sequence, ConversationState::default()));
+        entry.0 = sequence;
+        entry.1.record(question, result);
+
+        // Descarta a conversa atualizada há mais tempo
+        if conversations.len() > self.max_conversations {
+            if let Some(oldest) = conversations
+                .iter()
+                .min_by_key(|(_, (seq, _))| *seq)
+                .map(|(id, _)| id.clone())
+            {
+                conversations.remove(&oldest);
+            }
+        }
+    }
+
+    /// Número de conversas em memória
+    pub fn len(&self) -> usize {
+        self.conversations.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Verifica se não há conversas
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ConversationStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONVERSATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::TokenUsage;
+    use crate::utils::CostBreakdown;
+
+    fn result(answer: Option<&str>, knowledge: &[(&str, &str)], urls: &[&str]) -> ResearchResult {
+        ResearchResult {
+            success: answer.is_some(),
+            answer: answer.map(String::from),
+            references: vec![],
+            trivial: false,
+            token_usage: TokenUsage::default(),
+            cost: CostBreakdown::default(),
+            structured: None,
+            report: None,
+            beast_mode_vote: None,
+            claim_checks: vec![],
+            disputed_facts: vec![],
+            source_credibility: vec![],
+            knowledge: knowledge
+                .iter()
+                .map(|(q, a)| KnowledgeItem {
+                    question: q.to_string(),
+                    answer: a.to_string(),
+                    item_type: KnowledgeType::Qa,
+                    references: vec![],
+                })
+                .collect(),
+            visited_urls: urls.iter().map(|u| u.to_string()).collect(),
+            error: None,
+            cancelled: false,
+            partial_knowledge: vec![],
+            total_time_ms: 0,
+            search_time_ms: 0,
+            read_time_ms: 0,
+            llm_time_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_knowledge_keeps_recent_turns() {
+        let turns: Vec<ChatTurn> = (0..MAX_HISTORY_TURNS + 2)
+            .map(|i| ChatTurn::new(format!("Q{}", i), format!("A{}", i)))
+            .collect();
+
+        let items = history_knowledge(&turns);
+        assert_eq!(items.len(), MAX_HISTORY_TURNS);
+        assert_eq!(items[0].question, "Q2");
+        assert!(items
+            .iter()
+            .all(|k| k.item_type == KnowledgeType::ChatHistory));
+    }
+
+    #[test]
+    fn test_state_record_accumulates() {
+        let mut state = ConversationState::default();
+        state.record(
+            "Quem fundou a Jina?",
+            &result(
+                Some("Han Xiao"),
+                &[("fundador", "Han Xiao")],
+                &["https://a.com"],
+            ),
+        );
+        state.record(
+            "E quando?",
+            &result(
+                None,
+                &[("fundador", "Han Xiao"), ("ano", "2020")],
+                &["https://a.com", "https://b.com"],
+            ),
+        );
+
+        assert_eq!(
+            state.turns,
+            vec![ChatTurn::new("Quem fundou a Jina?", "Han Xiao")]
+        );
+        assert_eq!(state.knowledge.len(), 2);
+        assert_eq!(state.visited_urls, vec!["https://a.com", "https://b.com"]);
+    }
+
+    #[test]
+    fn test_state_record_forgets_urls_with_their_knowledge() {
+        use crate::types::Reference;
+
+        let page = |i: usize| KnowledgeItem {
+            question: format!("página {}", i),
+            answer: format!("conteúdo {}", i),
+            item_type: KnowledgeType::Url,
+            references: vec![Reference {
+                url: format!("https://site.com/{}", i),
+                title: String::new(),
+                ..Default::default()
+            }],
+        };
+
+        let mut state = ConversationState::default();
+        let mut first = result(Some("A"), &[], &["https://site.com/0"]);
+        first.knowledge = vec![page(0)];
+        state.record("Q1", &first);
+        assert_eq!(state.visited_urls, vec!["https://site.com/0"]);
+
+        // Conhecimento novo empurra a página 0 para fora do limite
+        let mut second = result(Some("B"), &[], &[]);
+        second.knowledge = (1..=MAX_CONVERSATION_KNOWLEDGE).map(page).collect();
+        second.visited_urls = (1..=MAX_CONVERSATION_KNOWLEDGE)
+            .map(|i| format!("https://site.com/{}", i))
+            .collect();
+        state.record("Q2", &second);
+
+        assert_eq!(state.knowledge.len(), MAX_CONVERSATION_KNOWLEDGE);
+        assert!(!state.visited_urls.contains(&"https://site.com/0".to_string()));
+        assert_eq!(state.visited_urls.len(), MAX_CONVERSATION_KNOWLEDGE);
+
+        // URLs sem conhecimento (ex.: leituras falhas) também têm limite
+        let urls: Vec<String> = (0..MAX_CONVERSATION_URLS + 10)
+            .map(|i| format!("https://other.com/{}", i))
+            .collect();
+        let urls: Vec<&str> = urls.iter().map(String::as_str).collect();
+        state.record("Q3", &result(Some("C"), &[], &urls));
+        assert_eq!(state.visited_urls.len(), MAX_CONVERSATION_URLS);
+        assert_eq!(
+            state.visited_urls.last().map(String::as_str),
+            urls.last().copied()
+        );
+    }
+
+    #[test]
+    fn test_store_evicts_least_recent() {
+        let store = ConversationStore::new(2);
+        store.record("a", "Q", &result(Some("A"), &[], &[]));
+        store.record("b", "Q", &result(Some("A"), &[], &[]));
+        store.record("a", "Q2", &result(Some("A2"), &[], &[]));
+        store.record("c", "Q", &result(Some("A"), &[], &[]));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("b").is_none());
+        assert_eq!(store.get("a").unwrap().turns.len(), 2);
+    }
+}
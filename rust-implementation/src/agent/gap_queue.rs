@@ -0,0 +1,404 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// FILA DE GAP QUESTIONS - Árvore priorizada sob a pergunta original
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Substitui a rotação round-robin de perguntas:
+// - A raiz é a pergunta original; cada reflexão pendura as novas perguntas
+//   sob a pergunta que estava sendo trabalhada
+// - Cada pergunta tem status (aberta, respondida, abandonada), um valor
+//   estimado e os índices do conhecimento que a respondeu
+// - A próxima pergunta é a aberta de maior prioridade: o valor dividido pelo
+//   número de vezes que ela já foi escolhida (+1). A raiz vale 1.0, então
+//   volta à frente sempre que as perguntas de gap perdem prioridade
+// - Perguntas escolhidas `MAX_GAP_VISITS` vezes sem resposta são abandonadas
+// - Novas perguntas semanticamente iguais (embedding) a uma já respondida
+//   são descartadas
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::performance::cosine_similarity;
+
+/// ID da pergunta original (raiz da árvore)
+pub const ROOT_GAP_ID: usize = 0;
+
+/// Similaridade mínima para considerar uma pergunta já respondida
+pub const GAP_DEDUP_THRESHOLD: f32 = 0.86;
+
+/// Vezes que uma pergunta de gap pode ser escolhida antes de ser abandonada
+const MAX_GAP_VISITS: usize = 3;
+
+/// Fator aplicado ao valor a cada nível abaixo do primeiro
+const DEPTH_DECAY: f32 = 0.8;
+
+/// Status de uma pergunta da fila
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapStatus {
+    /// Ainda não respondida
+    Open,
+    /// Respondida (ver `GapQuestion::answered_by`)
+    Answered,
+    /// Escolhida muitas vezes sem resposta
+    Abandoned,
+}
+
+impl GapStatus {
+    /// Ícone usado na árvore da TUI
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Open => "○",
+            Self::Answered => "✓",
+            Self::Abandoned => "✗",
+        }
+    }
+}
+
+/// Pergunta da árvore de gaps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapQuestion {
+    /// Posição na fila (`ROOT_GAP_ID` para a pergunta original)
+    pub id: usize,
+    /// Pergunta sob a qual esta surgiu (`None` na raiz)
+    pub parent: Option<usize>,
+    /// Texto da pergunta
+    pub question: String,
+    /// Status atual
+    pub status: GapStatus,
+    /// Valor estimado (0.0 - 1.0)
+    pub value: f32,
+    /// Quantas vezes foi escolhida como pergunta atual
+    pub visits: usize,
+    /// Índices em `AgentContext::knowledge` dos itens que a responderam
+    pub answered_by: Vec<usize>,
+    /// Step em que foi criada
+    pub created_step: usize,
+    /// Embedding da pergunta (para dedup semântico)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl GapQuestion {
+    /// Prioridade atual na fila
+    fn priority(&self) -> f32 {
+        self.value / (self.visits + 1) as f32
+    }
+}
+
+/// Linha da árvore de gaps para exibição
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GapTreeRow {
+    /// Profundidade (0 = pergunta original)
+    pub depth: usize,
+    /// Texto da pergunta
+    pub question: String,
+    /// Status atual
+    pub status: GapStatus,
+    /// Valor estimado
+    pub value: f32,
+    /// Se é a pergunta sendo trabalhada
+    pub current: bool,
+}
+
+/// Fila priorizada de perguntas, organizada em árvore
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GapQueue {
+    questions: Vec<GapQuestion>,
+    current: usize,
+}
+
+impl GapQueue {
+    /// Cria a fila com a pergunta original como raiz
+    pub fn new(original_question: &str) -> Self {
+        Self {
+            questions: vec![GapQuestion {
+                id: ROOT_GAP_ID,
+                parent: None,
+                question: original_question.to_string(),
+                status: GapStatus::Open,
+                value: 1.0,
+                visits: 0,
+                answered_by: Vec::new(),
+                created_step: 0,
+                embedding: None,
+            }],
+            current: ROOT_GAP_ID,
+        }
+    }
+
+    /// Número de perguntas (incluindo a raiz)
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    /// Verifica se a fila não foi inicializada
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    /// Busca uma pergunta pelo ID
+    pub fn get(&self, id: usize) -> Option<&GapQuestion> {
+        self.questions.get(id)
+    }
+
+    /// Todas as perguntas, na ordem de criação
+    pub fn iter(&self) -> impl Iterator<Item = &GapQuestion> {
+        self.questions.iter()
+    }
+
+    /// Pergunta sendo trabalhada
+    pub fn current(&self) -> Option<&GapQuestion> {
+        self.questions.get(self.current)
+    }
+
+    /// Número de perguntas abertas
+    pub fn open_count(&self) -> usize {
+        self.questions
+            .iter()
+            .filter(|q| q.status == GapStatus::Open)
+            .count()
+    }
+
+    /// Profundidade de uma pergunta na árvore (0 na raiz)
+    pub fn depth(&self, id: usize) -> usize {
+        let mut depth = 0;
+        let mut node = self.questions.get(id).and_then(|q| q.parent);
+        while let Some(parent) = node {
+            depth += 1;
+            node = self.questions.get(parent).and_then(|q| q.parent);
+        }
+        depth
+    }
+
+    /// Adiciona uma pergunta de gap sob `parent`
+    ///
+    /// `rank` é a posição da pergunta na reflexão (as primeiras valem mais).
+    /// Retorna `None` se uma pergunta equivalente (texto normalizado) já
+    /// existe na árvore.
+    pub fn add(
+        &mut self,
+        question: String,
+        parent: usize,
+        rank: usize,
+        step: usize,
+        embedding: Option<Vec<f32>>,
+    ) -> Option<usize> {
+        let normalized = normalize(&question);
+        if normalized.is_empty()
+            || parent >= self.questions.len()
+            || self
+                .questions
+                .iter()
+                .any(|q| normalize(&q.question) == normalized)
+        {
+            return None;
+        }
+
+        let id = self.questions.len();
+        let value = estimate_value(
+            &question,
+            &self.questions[ROOT_GAP_ID].question,
+            rank,
+            self.depth(parent) + 1,
+        );
+        self.questions.push(GapQuestion {
+            id,
+            parent: Some(parent),
+            question,
+            status: GapStatus::Open,
+            value,
+            visits: 0,
+            answered_by: Vec::new(),
+            created_step: step,
+            embedding,
+        });
+        Some(id)
+    }
+
+    /// Verifica se `embedding` é semanticamente igual a uma pergunta respondida
+    pub fn is_answered_like(&self, embedding: &[f32], threshold: f32) -> bool {
+        self.questions.iter().any(|q| {
+            q.status == GapStatus::Answered
+                && q.embedding
+                    .as_deref()
+                    .is_some_and(|e| cosine_similarity(e, embedding) >= threshold)
+        })
+    }
+
+    /// Escolhe a próxima pergunta pela prioridade e a marca como atual
+    ///
+    /// Abandona antes as perguntas de gap que já esgotaram as visitas. Sem
+    /// perguntas abertas, volta para a raiz.
+    pub fn select_next(&mut self) -> &GapQuestion {
+        for q in self.questions.iter_mut().skip(1) {
+            if q.status == GapStatus::Open && q.visits >= MAX_GAP_VISITS {
+                log::info!("🗑️ Pergunta de gap abandonada: {}", q.question);
+                q.status = GapStatus::Abandoned;
+            }
+        }
+
+        // Empate: a mais antiga vence
+        self.current = self
+            .questions
+            .iter()
+            .filter(|q| q.status == GapStatus::Open)
+            .fold(None::<&GapQuestion>, |best, q| match best {
+                Some(b) if b.priority() >= q.priority() => Some(b),
+                _ => Some(q),
+            })
+            .map(|q| q.id)
+            .unwrap_or(ROOT_GAP_ID);
+
+        let chosen = &mut self.questions[self.current];
+        chosen.visits += 1;
+        chosen
+    }
+
+    /// Marca uma pergunta como respondida pelo item de conhecimento indicado
+    pub fn mark_answered(&mut self, id: usize, knowledge_index: usize) {
+        if let Some(q) = self.questions.get_mut(id) {
+            q.status = GapStatus::Answered;
+            if !q.answered_by.contains(&knowledge_index) {
+                q.answered_by.push(knowledge_index);
+            }
+        }
+    }
+
+    /// Perguntas ainda abertas, na ordem de criação
+    pub fn open_questions(&self) -> Vec<String> {
+        self.questions
+            .iter()
+            .filter(|q| q.status == GapStatus::Open)
+            .map(|q| q.question.clone())
+            .collect()
+    }
+
+    /// Árvore em pré-ordem (filhos na ordem de criação)
+    pub fn tree_rows(&self) -> Vec<GapTreeRow> {
+        let mut rows = Vec::with_capacity(self.questions.len());
+        let mut visited = HashSet::new();
+        let mut stack = vec![(ROOT_GAP_ID, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let Some(q) = self.questions.get(id) else {
+                continue;
+            };
+            if !visited.insert(id) {
+                continue;
+            }
+            rows.push(GapTreeRow {
+                depth,
+                question: q.question.clone(),
+                status: q.status,
+                value: q.value,
+                current: id == self.current,
+            });
+            stack.extend(
+                self.questions
+                    .iter()
+                    .rev()
+                    .filter(|c| c.parent == Some(id))
+                    .map(|c| (c.id, depth + 1)),
+            );
+        }
+        rows
+    }
+}
+
+/// Valor estimado de uma pergunta de gap
+///
+/// Combina a posição na reflexão, a sobreposição de termos com a pergunta
+/// original e a profundidade na árvore.
+pub fn estimate_value(question: &str, original: &str, rank: usize, depth: usize) -> f32 {
+    let terms = |text: &str| -> HashSet<String> {
+        normalize(text)
+            .split_whitespace()
+            .filter(|t| t.chars().count() > 3)
+            .map(str::to_string)
+            .collect()
+    };
+    let original_terms = terms(original);
+    let overlap = if original_terms.is_empty() {
+        0.0
+    } else {
+        terms(question).intersection(&original_terms).count() as f32 / original_terms.len() as f32
+    };
+
+    let rank_factor = (1.0 - 0.15 * rank as f32).max(0.4);
+    let depth_factor = DEPTH_DECAY.powi(depth.saturating_sub(1) as i32);
+    (rank_factor * (0.7 + 0.3 * overlap) * depth_factor).clamp(0.0, 1.0)
+}
+
+/// Normaliza o texto para comparação (minúsculas, sem pontuação)
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_follows_priority_and_abandons_stale_questions() {
+        let mut queue = GapQueue::new("What is the market size of coffee in Brazil?");
+        assert_eq!(queue.select_next().id, ROOT_GAP_ID);
+
+        let a = queue
+            .add("Coffee market size Brazil 2024?".into(), ROOT_GAP_ID, 0, 1, None)
+            .unwrap();
+        let b = queue
+            .add("Who exports coffee?".into(), ROOT_GAP_ID, 3, 1, None)
+            .unwrap();
+        assert!(queue
+            .add("coffee market size brazil 2024".into(), a, 0, 2, None)
+            .is_none());
+
+        // Maior valor primeiro; depois do decaimento a raiz volta
+        assert_eq!(queue.select_next().id, a);
+        assert!(queue.get(a).unwrap().value > queue.get(b).unwrap().value);
+        assert_eq!(queue.select_next().id, ROOT_GAP_ID);
+
+        queue.mark_answered(a, 4);
+        assert_eq!(queue.get(a).unwrap().answered_by, vec![4]);
+
+        for _ in 0..12 {
+            queue.select_next();
+        }
+        assert_eq!(queue.get(b).unwrap().status, GapStatus::Abandoned);
+        assert_eq!(queue.open_count(), 1);
+    }
+
+    #[test]
+    fn test_tree_rows_and_semantic_dedup() {
+        let mut queue = GapQueue::new("Root");
+        let a = queue
+            .add("Child A".into(), ROOT_GAP_ID, 0, 1, Some(vec![1.0, 0.0]))
+            .unwrap();
+        queue
+            .add("Child B".into(), ROOT_GAP_ID, 1, 1, None)
+            .unwrap();
+        queue.add("Grandchild".into(), a, 0, 2, None).unwrap();
+        queue.mark_answered(a, 0);
+
+        let rows = queue.tree_rows();
+        let outline: Vec<(usize, &str)> = rows
+            .iter()
+            .map(|r| (r.depth, r.question.as_str()))
+            .collect();
+        assert_eq!(
+            outline,
+            vec![(0, "Root"), (1, "Child A"), (2, "Grandchild"), (1, "Child B")]
+        );
+        assert_eq!(rows[1].status, GapStatus::Answered);
+
+        assert!(queue.is_answered_like(&[0.99, 0.05], GAP_DEDUP_THRESHOLD));
+        assert!(!queue.is_answered_like(&[0.0, 1.0], GAP_DEDUP_THRESHOLD));
+    }
+}
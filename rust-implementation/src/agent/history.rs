@@ -212,7 +212,8 @@ impl LocalBackend {
         Self::new(sessions_dir)
     }
 
-    fn load_all_sessions(&self) -> Vec<ResearchSession> {
+    /// Carrega todas as sessões do diretório, mais recentes primeiro
+    pub fn load_all_sessions(&self) -> Vec<ResearchSession> {
         let mut sessions = Vec::new();
 
         if let Ok(entries) = std::fs::read_dir(&self.sessions_dir) {
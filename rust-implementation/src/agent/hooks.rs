@@ -0,0 +1,293 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// HOOKS DO CICLO DE VIDA - Middleware do agente
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Hooks permitem aplicar políticas e telemetria sem alterar `execute_step`:
+// - `before_decide`: pode reescrever o prompt (ex.: redação de dados)
+// - `after_decide`: pode vetar ou substituir a ação escolhida pelo LLM
+// - `before_action` / `after_action`: auditoria em volta de cada ação
+// - `on_knowledge_added`: cada item novo de conhecimento
+// - `on_answer_evaluated`: resultado do pipeline de avaliação
+//
+// Os hooks são registrados no agente com `with_hook` e executados na ordem
+// de registro. Na decisão, cada hook recebe a ação já reescrita pelos
+// anteriores; o primeiro veto interrompe a cadeia.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{AgentAction, AgentContext, AgentPrompt, StepResult};
+use crate::evaluation::EvaluationPipelineResult;
+use crate::types::KnowledgeItem;
+
+/// Decisão de um hook sobre a ação escolhida pelo LLM
+#[derive(Debug, Clone)]
+pub enum HookDecision {
+    /// Manter a ação
+    Proceed,
+    /// Substituir a ação
+    Replace(AgentAction),
+    /// Descartar a ação; o motivo vira conhecimento para o próximo step
+    Veto(String),
+}
+
+/// Veto de uma ação por um hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookVeto {
+    /// Nome do hook que vetou
+    pub hook: String,
+    /// Motivo informado
+    pub reason: String,
+}
+
+/// Hook do ciclo de vida do agente
+///
+/// Todos os métodos têm implementação vazia; implemente apenas os necessários.
+///
+/// # Exemplo
+/// ```rust,ignore
+/// struct NoCoding;
+///
+/// #[async_trait]
+/// impl AgentHook for NoCoding {
+///     fn name(&self) -> &str { "no_coding" }
+///     async fn after_decide(&self, _: &AgentContext, action: &AgentAction) -> HookDecision {
+///         match action {
+///             AgentAction::Coding { .. } => HookDecision::Veto("coding disabled".into()),
+///             _ => HookDecision::Proceed,
+///         }
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait AgentHook: Send + Sync {
+    /// Nome do hook (usado em logs e vetos)
+    fn name(&self) -> &str;
+
+    /// Antes de pedir a decisão ao LLM
+    async fn before_decide(&self, _context: &AgentContext, _prompt: &mut AgentPrompt) {}
+
+    /// Depois da decisão do LLM
+    async fn after_decide(&self, _context: &AgentContext, _action: &AgentAction) -> HookDecision {
+        HookDecision::Proceed
+    }
+
+    /// Antes de executar a ação
+    async fn before_action(&self, _context: &AgentContext, _action: &AgentAction) {}
+
+    /// Depois de executar a ação
+    async fn after_action(&self, _context: &AgentContext, _action: &str, _result: &StepResult) {}
+
+    /// Para cada item de conhecimento adicionado durante a ação
+    async fn on_knowledge_added(&self, _context: &AgentContext, _item: &KnowledgeItem) {}
+
+    /// Depois do pipeline de avaliação de uma resposta
+    async fn on_answer_evaluated(
+        &self,
+        _context: &AgentContext,
+        _answer: &str,
+        _result: &EvaluationPipelineResult,
+    ) {
+    }
+}
+
+/// Hooks registrados, executados em ordem
+#[derive(Clone, Default)]
+pub struct HookChain {
+    hooks: Vec<Arc<dyn AgentHook>>,
+}
+
+impl HookChain {
+    /// Cria uma cadeia vazia
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adiciona um hook ao fim da cadeia
+    pub fn push(&mut self, hook: Arc<dyn AgentHook>) {
+        self.hooks.push(hook);
+    }
+
+    /// Número de hooks
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Se não há hooks
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Nomes dos hooks, na ordem
+    pub fn names(&self) -> Vec<&str> {
+        self.hooks.iter().map(|h| h.name()).collect()
+    }
+
+    /// Executa `before_decide` em todos os hooks
+    pub async fn before_decide(&self, context: &AgentContext, prompt: &mut AgentPrompt) {
+        for hook in &self.hooks {
+            hook.before_decide(context, prompt).await;
+        }
+    }
+
+    /// Executa `after_decide`, aplicando substituições em ordem
+    ///
+    /// Retorna a ação final ou o primeiro veto.
+    pub async fn after_decide(
+        &self,
+        context: &AgentContext,
+        mut action: AgentAction,
+    ) -> Result<AgentAction, HookVeto> {
+        for hook in &self.hooks {
+            match hook.after_decide(context, &action).await {
+                HookDecision::Proceed => {}
+                HookDecision::Replace(replacement) => {
+                    log::info!(
+                        "🪝 Hook {}: {} → {}",
+                        hook.name(),
+                        action.name(),
+                        replacement.name()
+                    );
+                    action = replacement;
+                }
+                HookDecision::Veto(reason) => {
+                    return Err(HookVeto {
+                        hook: hook.name().to_string(),
+                        reason,
+                    });
+                }
+            }
+        }
+        Ok(action)
+    }
+
+    /// Executa `before_action` em todos os hooks
+    pub async fn before_action(&self, context: &AgentContext, action: &AgentAction) {
+        for hook in &self.hooks {
+            hook.before_action(context, action).await;
+        }
+    }
+
+    /// Executa `after_action` em todos os hooks
+    pub async fn after_action(&self, context: &AgentContext, action: &str, result: &StepResult) {
+        for hook in &self.hooks {
+            hook.after_action(context, action, result).await;
+        }
+    }
+
+    /// Executa `on_knowledge_added` para cada item, em todos os hooks
+    pub async fn on_knowledge_added(&self, context: &AgentContext, items: &[KnowledgeItem]) {
+        for item in items {
+            for hook in &self.hooks {
+                hook.on_knowledge_added(context, item).await;
+            }
+        }
+    }
+
+    /// Executa `on_answer_evaluated` em todos os hooks
+    pub async fn on_answer_evaluated(
+        &self,
+        context: &AgentContext,
+        answer: &str,
+        result: &EvaluationPipelineResult,
+    ) {
+        for hook in &self.hooks {
+            hook.on_answer_evaluated(context, answer, result).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for HookChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct SearchToReflect;
+
+    #[async_trait]
+    impl AgentHook for SearchToReflect {
+        fn name(&self) -> &str {
+            "search_to_reflect"
+        }
+
+        async fn after_decide(&self, _: &AgentContext, action: &AgentAction) -> HookDecision {
+            match action {
+                AgentAction::Search { think, .. } => HookDecision::Replace(AgentAction::Reflect {
+                    gap_questions: vec!["Q?".into()],
+                    think: think.clone(),
+                }),
+                _ => HookDecision::Proceed,
+            }
+        }
+    }
+
+    struct Audit {
+        seen: Mutex<Vec<String>>,
+        veto: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl AgentHook for Audit {
+        fn name(&self) -> &str {
+            "audit"
+        }
+
+        async fn before_decide(&self, _: &AgentContext, prompt: &mut AgentPrompt) {
+            prompt.user = prompt.user.replace("secret", "[REDACTED]");
+        }
+
+        async fn after_decide(&self, _: &AgentContext, action: &AgentAction) -> HookDecision {
+            self.seen.lock().unwrap().push(action.name().to_string());
+            match (action, self.veto) {
+                (AgentAction::Reflect { .. }, Some(reason)) => HookDecision::Veto(reason.into()),
+                _ => HookDecision::Proceed,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_run_in_order() {
+        let context = AgentContext::new();
+        let search = || AgentAction::Search {
+            queries: vec![],
+            think: "t".into(),
+        };
+
+        let audit = Arc::new(Audit {
+            seen: Mutex::new(vec![]),
+            veto: None,
+        });
+        let mut chain = HookChain::new();
+        chain.push(Arc::new(SearchToReflect));
+        chain.push(audit.clone());
+
+        let mut prompt = AgentPrompt {
+            system: String::new(),
+            user: "my secret key".into(),
+            diary: vec![],
+        };
+        chain.before_decide(&context, &mut prompt).await;
+        assert_eq!(prompt.user, "my [REDACTED] key");
+
+        // O segundo hook já vê a ação reescrita pelo primeiro
+        let action = chain.after_decide(&context, search()).await.unwrap();
+        assert_eq!(action.name(), "reflect");
+        assert_eq!(*audit.seen.lock().unwrap(), vec!["reflect"]);
+
+        chain.push(Arc::new(Audit {
+            seen: Mutex::new(vec![]),
+            veto: Some("no reflection"),
+        }));
+        let veto = chain.after_decide(&context, search()).await.unwrap_err();
+        assert_eq!(veto.hook, "audit");
+        assert_eq!(veto.reason, "no reflection");
+    }
+}
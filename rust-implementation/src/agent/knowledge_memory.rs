@@ -0,0 +1,543 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// MEMÓRIA DE CONHECIMENTO - Recuperação por relevância para prompts limitados
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Em pesquisas longas o conhecimento acumulado não cabe no prompt. A memória:
+// - Divide cada `KnowledgeItem` em chunks e gera embeddings ao indexar
+// - A cada prompt, recupera os top-k chunks mais relevantes para a pergunta
+//   atual e a original, dentro de uma franquia de tokens
+// - Compacta em resumos (LLM) os chunks que nunca são recuperados
+//
+// Itens fornecidos pelo usuário e histórico de chat são fixos: sempre entram
+// no prompt e nunca são compactados. Enquanto o conhecimento inteiro couber
+// na franquia, o agente continua incluindo tudo sem consultar a memória.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::HashSet;
+
+use super::AgentPrompt;
+use crate::llm::{LlmClient, LlmResponse};
+use crate::performance::simd::cosine_similarity;
+use crate::types::{KnowledgeItem, KnowledgeType};
+use crate::utils::estimate_tokens;
+
+/// Franquia padrão de tokens para o conhecimento no prompt
+pub const DEFAULT_KNOWLEDGE_TOKEN_ALLOWANCE: usize = 6_000;
+
+/// Máximo padrão de chunks recuperados por prompt
+pub const DEFAULT_RETRIEVAL_TOP_K: usize = 12;
+
+/// Tamanho padrão de um chunk (em caracteres)
+pub const DEFAULT_CHUNK_CHARS: usize = 1_500;
+
+/// Recuperações sem seleção após as quais um chunk é compactado
+pub const DEFAULT_COMPACT_AFTER: usize = 4;
+
+/// Mínimo de chunks para valer uma chamada de compactação
+const MIN_COMPACTION_CHUNKS: usize = 3;
+
+/// Instruções do resumo de compactação
+const COMPACTION_PROMPT: &str = "You compress research notes. Summarize the notes into a short \
+list of standalone facts. Keep names, numbers, dates and URLs exactly as written. \
+Drop repetition and boilerplate. Reply with the facts only.";
+
+/// Configuração da memória de conhecimento
+#[derive(Debug, Clone)]
+pub struct KnowledgeMemoryConfig {
+    /// Tokens disponíveis para o conhecimento em cada prompt
+    pub token_allowance: usize,
+    /// Máximo de chunks recuperados por prompt (além dos fixos)
+    pub top_k: usize,
+    /// Tamanho máximo de um chunk, em caracteres
+    pub chunk_chars: usize,
+    /// Recuperações sem seleção antes de compactar um chunk
+    pub compact_after: usize,
+}
+
+impl Default for KnowledgeMemoryConfig {
+    fn default() -> Self {
+        Self {
+            token_allowance: DEFAULT_KNOWLEDGE_TOKEN_ALLOWANCE,
+            top_k: DEFAULT_RETRIEVAL_TOP_K,
+            chunk_chars: DEFAULT_CHUNK_CHARS,
+            compact_after: DEFAULT_COMPACT_AFTER,
+        }
+    }
+}
+
+/// Trecho indexado de um item de conhecimento (ou resumo de compactação)
+#[derive(Debug, Clone)]
+struct MemoryChunk {
+    /// Cabeçalho exibido no prompt (tipo e pergunta do item)
+    label: String,
+    /// Conteúdo do trecho
+    text: String,
+    /// Embedding do trecho (`None` se a geração falhou)
+    embedding: Option<Vec<f32>>,
+    /// Tokens estimados de `label` + `text`
+    tokens: usize,
+    /// Sempre incluído e nunca compactado
+    pinned: bool,
+    /// Se já foi selecionado para algum prompt
+    retrieved: bool,
+    /// Recuperações em que não foi selecionado
+    misses: usize,
+}
+
+impl MemoryChunk {
+    fn new(label: String, text: String, pinned: bool) -> Self {
+        let tokens = estimate_tokens(&label) + estimate_tokens(&text);
+        Self {
+            label,
+            text,
+            embedding: None,
+            tokens,
+            pinned,
+            retrieved: false,
+            misses: 0,
+        }
+    }
+
+    fn embedding_text(&self) -> String {
+        format!("{}\n{}", self.label, self.text)
+    }
+}
+
+/// Memória de conhecimento com recuperação por relevância
+#[derive(Debug, Clone, Default)]
+pub struct KnowledgeMemory {
+    config: KnowledgeMemoryConfig,
+    chunks: Vec<MemoryChunk>,
+    /// Quantos itens de `AgentContext::knowledge` já foram indexados
+    indexed_items: usize,
+    /// Resumos de compactação gerados
+    summaries: usize,
+}
+
+impl KnowledgeMemory {
+    /// Cria uma memória vazia
+    pub fn new(config: KnowledgeMemoryConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Configuração em uso
+    pub fn config(&self) -> &KnowledgeMemoryConfig {
+        &self.config
+    }
+
+    /// Se o conhecimento inteiro cabe na franquia (sem recuperação)
+    pub fn fits(&self, knowledge: &[KnowledgeItem]) -> bool {
+        knowledge
+            .iter()
+            .map(|k| estimate_tokens(&k.question) + estimate_tokens(&k.answer))
+            .sum::<usize>()
+            <= self.config.token_allowance
+    }
+
+    /// Número de chunks indexados
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Verifica se não há chunks
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Número de resumos de compactação gerados
+    pub fn summaries(&self) -> usize {
+        self.summaries
+    }
+
+    /// Indexa os itens novos de `knowledge` (chunking + embeddings)
+    ///
+    /// Se os embeddings falharem os chunks continuam recuperáveis por
+    /// sobreposição de termos.
+    pub async fn index(&mut self, knowledge: &[KnowledgeItem], llm: &dyn LlmClient) {
+        if knowledge.len() < self.indexed_items {
+            // Conhecimento foi reiniciado: reindexar do zero
+            self.chunks.clear();
+            self.indexed_items = 0;
+        }
+
+        let first_new = self.chunks.len();
+        for item in &knowledge[self.indexed_items..] {
+            self.chunks
+                .extend(chunk_item(item, self.config.chunk_chars));
+        }
+        self.indexed_items = knowledge.len();
+
+        self.embed_chunks(first_new, llm).await;
+    }
+
+    /// Recupera o conhecimento relevante para `queries`, formatado para o prompt
+    pub async fn recall(&mut self, queries: &[&str], llm: &dyn LlmClient) -> String {
+        let mut texts: Vec<String> = Vec::new();
+        for query in queries.iter().map(|q| q.trim()) {
+            if !query.is_empty() && !texts.iter().any(|t| t == query) {
+                texts.push(query.to_string());
+            }
+        }
+
+        let query_embeddings = match llm.embed_batch(&texts).await {
+            Ok(results) => results.into_iter().map(|r| r.vector).collect(),
+            Err(e) => {
+                log::warn!(
+                    "🧠 Memória: embeddings da pergunta falharam ({}), usando termos",
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+        let selected = self.select(&query_embeddings, &texts);
+        self.format(&selected)
+    }
+
+    /// Compacta em um resumo os chunks que nunca foram recuperados
+    ///
+    /// Retorna a resposta do LLM (para contabilizar tokens) quando houve
+    /// compactação.
+    pub async fn compact(&mut self, llm: &dyn LlmClient) -> Option<LlmResponse> {
+        let candidates: Vec<usize> = (0..self.chunks.len())
+            .filter(|&i| {
+                let chunk = &self.chunks[i];
+                !chunk.pinned && !chunk.retrieved && chunk.misses >= self.config.compact_after
+            })
+            .collect();
+        if candidates.len() < MIN_COMPACTION_CHUNKS {
+            return None;
+        }
+
+        let notes = candidates
+            .iter()
+            .map(|&i| format!("{}\n{}", self.chunks[i].label, self.chunks[i].text))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let prompt = AgentPrompt {
+            system: COMPACTION_PROMPT.to_string(),
+            user: format!("<notes>\n{}\n</notes>", notes),
+            diary: vec![],
+        };
+
+        let response = match llm.generate_answer(&prompt, 0.0).await {
+            Ok(response) if !response.answer.trim().is_empty() => response,
+            Ok(_) | Err(_) => {
+                log::warn!("🧠 Memória: compactação falhou, mantendo os chunks");
+                // Evita tentar de novo a cada step
+                for &i in &candidates {
+                    self.chunks[i].misses = 0;
+                }
+                return None;
+            }
+        };
+
+        let compacted: HashSet<usize> = candidates.iter().copied().collect();
+        let mut index = 0;
+        self.chunks.retain(|_| {
+            let keep = !compacted.contains(&index);
+            index += 1;
+            keep
+        });
+
+        self.summaries += 1;
+        let mut summary = MemoryChunk::new(
+            format!("[summary] Compacted notes #{}", self.summaries),
+            response.answer.trim().to_string(),
+            false,
+        );
+        // Resumos contam como recuperados: não são compactados de novo
+        summary.retrieved = true;
+        self.chunks.push(summary);
+        let summary_index = self.chunks.len() - 1;
+        self.embed_chunks(summary_index, llm).await;
+
+        log::info!(
+            "🧠 Memória: {} chunks compactados em um resumo ({} chunks restantes)",
+            candidates.len(),
+            self.chunks.len()
+        );
+        Some(response)
+    }
+
+    /// Gera embeddings para os chunks a partir de `start`
+    async fn embed_chunks(&mut self, start: usize, llm: &dyn LlmClient) {
+        if start >= self.chunks.len() {
+            return;
+        }
+        let texts: Vec<String> = self.chunks[start..]
+            .iter()
+            .map(MemoryChunk::embedding_text)
+            .collect();
+
+        match llm.embed_batch(&texts).await {
+            Ok(results) if results.len() == texts.len() => {
+                for (chunk, result) in self.chunks[start..].iter_mut().zip(results) {
+                    chunk.embedding = Some(result.vector);
+                }
+            }
+            Ok(_) => log::warn!("🧠 Memória: número de embeddings divergente, usando termos"),
+            Err(e) => log::warn!("🧠 Memória: embeddings falharam ({}), usando termos", e),
+        }
+    }
+
+    /// Seleciona os chunks fixos e os top-k mais relevantes dentro da franquia
+    ///
+    /// Retorna os índices em ordem cronológica.
+    fn select(&mut self, query_embeddings: &[Vec<f32>], queries: &[String]) -> Vec<usize> {
+        let query_terms = terms(&queries.join(" "));
+        let mut budget = self.config.token_allowance;
+        let mut selected = Vec::new();
+
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if chunk.pinned && chunk.tokens <= budget {
+                budget -= chunk.tokens;
+                selected.push(i);
+            }
+        }
+
+        let mut ranked: Vec<(usize, f32)> = self
+            .chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| !chunk.pinned)
+            .map(|(i, chunk)| (i, relevance(chunk, query_embeddings, &query_terms)))
+            .collect();
+        // Empate: o mais recente primeiro
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+
+        let mut taken = 0;
+        for (i, _) in ranked {
+            if taken == self.config.top_k {
+                break;
+            }
+            if self.chunks[i].tokens <= budget {
+                budget -= self.chunks[i].tokens;
+                selected.push(i);
+                taken += 1;
+            }
+        }
+
+        let chosen: HashSet<usize> = selected.iter().copied().collect();
+        for (i, chunk) in self.chunks.iter_mut().enumerate() {
+            if chosen.contains(&i) {
+                chunk.retrieved = true;
+            } else {
+                chunk.misses += 1;
+            }
+        }
+
+        selected.sort_unstable();
+        selected
+    }
+
+    /// Formata os chunks selecionados no mesmo estilo do conhecimento completo
+    fn format(&self, selected: &[usize]) -> String {
+        let omitted = self.chunks.len() - selected.len();
+        let mut sections: Vec<String> = selected
+            .iter()
+            .map(|&i| format!("{}\nA: {}", self.chunks[i].label, self.chunks[i].text))
+            .collect();
+        if omitted > 0 {
+            sections.push(format!(
+                "({} less relevant knowledge chunks omitted)",
+                omitted
+            ));
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// Divide um item em chunks de até `chunk_chars` caracteres
+fn chunk_item(item: &KnowledgeItem, chunk_chars: usize) -> Vec<MemoryChunk> {
+    let pinned = matches!(
+        item.item_type,
+        KnowledgeType::UserProvided | KnowledgeType::ChatHistory
+    );
+    let parts = split_text(&item.answer, chunk_chars.max(1));
+    let total = parts.len();
+
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let label = if total > 1 {
+                format!(
+                    "[{}] Q: {} (part {}/{})",
+                    item.item_type.as_str(),
+                    item.question,
+                    i + 1,
+                    total
+                )
+            } else {
+                format!("[{}] Q: {}", item.item_type.as_str(), item.question)
+            };
+            MemoryChunk::new(label, text, pinned)
+        })
+        .collect()
+}
+
+/// Divide o texto em partes de até `max_chars`, preferindo quebras de linha
+fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let line_chars = line.chars().count();
+        if !current.is_empty() && current.chars().count() + 1 + line_chars > max_chars {
+            parts.push(std::mem::take(&mut current));
+        }
+        if line_chars > max_chars {
+            // Linha longa: corta por caracteres
+            let chars: Vec<char> = line.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                parts.push(piece.iter().collect());
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.trim().is_empty() || parts.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Relevância de um chunk: maior similaridade de cosseno com as perguntas,
+/// ou sobreposição de termos quando não há embeddings
+fn relevance(
+    chunk: &MemoryChunk,
+    query_embeddings: &[Vec<f32>],
+    query_terms: &HashSet<String>,
+) -> f32 {
+    if let Some(embedding) = &chunk.embedding {
+        let best = query_embeddings
+            .iter()
+            .filter(|q| q.len() == embedding.len())
+            .map(|q| cosine_similarity(q, embedding))
+            .filter(|score| score.is_finite())
+            .fold(None, |best: Option<f32>, score| {
+                Some(best.map_or(score, |b| b.max(score)))
+            });
+        if let Some(best) = best {
+            return best;
+        }
+    }
+
+    if query_terms.is_empty() {
+        return 0.0;
+    }
+    let chunk_terms = terms(&chunk.embedding_text());
+    query_terms.intersection(&chunk_terms).count() as f32 / query_terms.len() as f32
+}
+
+/// Termos em minúsculas com pelo menos 3 caracteres
+fn terms(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::MockLlmClient;
+
+    fn item(question: &str, answer: &str, item_type: KnowledgeType) -> KnowledgeItem {
+        KnowledgeItem {
+            question: question.into(),
+            answer: answer.into(),
+            item_type,
+            references: vec![],
+        }
+    }
+
+    fn memory(token_allowance: usize, top_k: usize) -> KnowledgeMemory {
+        KnowledgeMemory::new(KnowledgeMemoryConfig {
+            token_allowance,
+            top_k,
+            chunk_chars: 40,
+            compact_after: 2,
+        })
+    }
+
+    #[test]
+    fn test_split_text_respects_chunk_size() {
+        let text = "first line\nsecond line\n".to_string() + &"x".repeat(25);
+        let parts = split_text(&text, 22);
+
+        assert_eq!(
+            parts,
+            vec!["first line\nsecond line", "x".repeat(22).as_str(), "xxx"]
+        );
+        assert_eq!(split_text("", 10), vec![""]);
+    }
+
+    #[test]
+    fn test_select_prefers_relevant_chunks_and_keeps_pinned() {
+        let mut memory = memory(1_000, 1);
+        let knowledge = [
+            item(
+                "rust",
+                "Rust was created by Graydon Hoare",
+                KnowledgeType::Url,
+            ),
+            item(
+                "user",
+                "Answer in one sentence",
+                KnowledgeType::UserProvided,
+            ),
+            item("python", "Python was created by Guido", KnowledgeType::Url),
+        ];
+        memory.chunks = knowledge.iter().flat_map(|k| chunk_item(k, 40)).collect();
+
+        let selected = memory.select(&[], &["Who created Python?".to_string()]);
+
+        assert_eq!(selected, vec![1, 2]);
+        assert!(!memory.chunks[0].retrieved);
+        assert_eq!(memory.chunks[0].misses, 1);
+        assert!(memory
+            .format(&selected)
+            .contains("(1 less relevant knowledge chunks omitted)"));
+    }
+
+    #[test]
+    fn test_select_respects_token_allowance() {
+        let mut memory = memory(15, 10);
+        memory.chunks = vec![
+            MemoryChunk::new("[url] Q: a".into(), "a".repeat(40), false),
+            MemoryChunk::new("[url] Q: b".into(), "b".repeat(20), false),
+        ];
+
+        let selected = memory.select(&[vec![1.0, 0.0]], &[]);
+        assert_eq!(selected, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_compact_replaces_unretrieved_chunks() {
+        let llm = MockLlmClient::new();
+        let mut memory = memory(10_000, 1);
+        let knowledge: Vec<KnowledgeItem> = (0..4)
+            .map(|i| item(&format!("q{}", i), "noise", KnowledgeType::Url))
+            .collect();
+        memory.index(&knowledge, &llm).await;
+        assert_eq!(memory.len(), 4);
+
+        memory.recall(&["question"], &llm).await;
+        assert!(memory.compact(&llm).await.is_none());
+        memory.recall(&["question"], &llm).await;
+
+        let response = memory.compact(&llm).await;
+        assert!(response.is_some());
+        assert_eq!(memory.summaries(), 1);
+        assert_eq!(memory.len(), 2);
+        assert!(memory.chunks[1].label.starts_with("[summary]"));
+    }
+}
@@ -3,6 +3,9 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 mod actions;
+/// Módulo de checkpoints para retomar execuções interrompidas.
+/// Serializa o working set do agente após cada step.
+pub mod checkpoint;
 /// Módulo para análise e diagnóstico do agente durante a execução.
 /// Útil para debugging e geração de relatórios sobre decisões e erros do agente.
 pub mod agent_analyzer;
@@ -10,6 +13,9 @@ pub mod agent_analyzer;
 /// Define a trait ChatbotAdapter para DigiSac, Suri, Parrachos, etc.
 pub mod chatbot;
 mod context;
+/// Módulo de conversas multi-turno.
+/// Guarda turnos, conhecimento e URLs visitadas entre perguntas.
+pub mod conversation;
 /// Módulo para acesso ao histórico de sessões anteriores.
 /// Suporta múltiplos backends: local (JSON), PostgreSQL, Qdrant.
 pub mod history;
@@ -21,15 +27,52 @@ mod permissions;
 /// Permite ao agente gerar e executar código em sandbox isolado.
 pub mod sandbox;
 mod state;
+/// Módulo da memória de conhecimento.
+/// Recupera apenas o conhecimento relevante quando ele não cabe no prompt.
+pub mod knowledge_memory;
+/// Módulo de ferramentas externas plugáveis.
+/// Define a trait `Tool` e o `ToolRegistry` usado pela ação `tool`.
+pub mod tool_registry;
+/// Módulo de transcripts JSONL das execuções.
+pub mod transcript;
+/// Módulo de votação por autoconsistência no Beast Mode.
+/// Gera candidatas em paralelo, avalia e escolhe ou mescla as melhores.
+pub mod voting;
+/// Módulo de hooks do ciclo de vida.
+/// Middleware para políticas e telemetria em volta de cada step.
+pub mod hooks;
+/// Módulo da fila de perguntas de gap.
+/// Árvore priorizada de perguntas sob a pergunta original.
+pub mod gap_queue;
+/// Módulo do modo equipe (multi-agente).
+/// Planeja subproblemas, executa agentes em paralelo e mescla as respostas.
+pub mod team;
+/// Módulo do modo relatório.
+/// Gera um esboço, pesquisa cada seção e monta o documento final.
+pub mod report;
+/// Módulo de detecção de contradições.
+/// Compara fatos de fontes diferentes e registra onde elas discordam.
+pub mod contradictions;
 
 pub use actions::*;
 pub use agent_analyzer::AgentAnalysis;
+pub use checkpoint::{
+    AgentCheckpoint, AgentCounters, CheckpointError, CheckpointSummary, DEFAULT_CHECKPOINT_DIR,
+};
 pub use chatbot::{
     ButtonType, ChatbotAdapter, ChatbotError, ConnectionStatus, MessageButton, MockChatbotAdapter,
     RichMessage, UserMetadata,
 };
 pub use context::*;
+pub use contradictions::{Fact, FactConflict, FactLedger, SourcedValue};
+pub use conversation::{
+    history_knowledge, ChatTurn, ConversationState, ConversationStore, MAX_HISTORY_TURNS,
+};
+pub use gap_queue::{GapQuestion, GapQueue, GapStatus, GapTreeRow, ROOT_GAP_ID};
+pub use voting::{BeastModeVote, CandidateVote, VoteStrategy, DEFAULT_BEAST_MODE_CANDIDATES};
+pub use hooks::{AgentHook, HookChain, HookDecision, HookVeto};
 pub use history::{HistoryQuery, HistorySearchResult, HistoryService, SessionSummary};
+pub use knowledge_memory::{KnowledgeMemory, KnowledgeMemoryConfig};
 pub use interaction::{
     create_interaction_channels, InteractionError, InteractionHub, PendingQuestion, QuestionType,
     UserResponse,
@@ -40,12 +83,23 @@ pub use sandbox::{
     UnifiedSandbox,
 };
 pub use state::*;
+pub use report::{ReportResearch, ReportSection, ResearchReport};
+pub use team::{LlmClientFactory, ResearchRun, TeamResearch};
+pub use tool_registry::{tool_knowledge, Tool, ToolError, ToolRegistry};
+pub use transcript::{
+    find_transcript, format_timeline, read_transcript, transcript_path, TranscriptError,
+    TranscriptEvent, TranscriptLevel, TranscriptRecord, TranscriptWriter, DEFAULT_TRANSCRIPT_DIR,
+};
+pub use tokio_util::sync::CancellationToken;
 
+use crate::credibility::{CredibilityScore, SourceCredibility};
 use crate::llm::LlmClient;
-use crate::search::SearchClient;
+use crate::search::{SearchClient, SearchError, SearchResult, UrlContent};
+use crate::tools::{ClaimCheck, ClaimVerifier, QuestionRewriter, ResponseReducer};
 use crate::types::*;
 use crate::utils::{
-    ActionTimer, ReferenceBuilder, ReferenceBuilderConfig, TimingStats, TokenTracker, TrackerStats,
+    estimate_tokens, ActionTimer, JsonSchema, PricingTable, ReferenceBuilder, ReferenceBuilderConfig, TimingStats,
+    TokenTracker, TrackerStats,
 };
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -274,6 +328,45 @@ pub enum AgentProgress {
         /// Linguagem de programação usada
         language: String,
     },
+    /// Plano do modo equipe (um subproblema por sub-agente)
+    TeamPlan {
+        /// Subproblemas na ordem dos índices dos sub-agentes
+        subproblems: Vec<String>,
+    },
+    /// Árvore de perguntas de gap atualizada (pré-ordem, raiz primeiro)
+    GapTree(Vec<GapTreeRow>),
+    /// Esboço do modo relatório (seções pesquisadas em sequência)
+    ReportOutline {
+        /// Título do relatório
+        title: String,
+        /// Títulos das seções, na ordem dos índices de `SubAgent`
+        sections: Vec<String>,
+    },
+    /// Início do polimento pós-aprovação (ResponseFinalizer)
+    FinalizeStart {
+        /// Tamanho da resposta aprovada (chars)
+        chars: usize,
+    },
+    /// Fim do polimento pós-aprovação
+    FinalizeEnd {
+        /// Tamanho da resposta aprovada (chars)
+        original_chars: usize,
+        /// Tamanho da resposta final (chars)
+        final_chars: usize,
+        /// Se a resposta aprovada foi mantida (polimento descartado ou falhou)
+        preserved_original: bool,
+        /// Tokens consumidos pelo polimento
+        tokens: u64,
+        /// Tempo de execução em ms
+        duration_ms: u128,
+    },
+    /// Evento emitido por um sub-agente no modo equipe (ou por uma seção no modo relatório)
+    SubAgent {
+        /// Índice do sub-agente (0-based, mesmo índice de `TeamPlan::subproblems`)
+        agent_index: usize,
+        /// Evento original do sub-agente
+        event: Box<AgentProgress>,
+    },
 }
 
 /// Tipo do callback de progresso
@@ -287,6 +380,62 @@ const MAX_URLS_PER_STEP: usize = 5;
 /// Máximo de steps antes de forçar resposta (reservado para expansão futura)
 #[allow(dead_code)]
 const MAX_STEPS_BEFORE_ANSWER: usize = 15;
+/// Fração padrão do prazo que dispara o Beast Mode (mesmo limiar do budget de tokens)
+pub const DEFAULT_TIME_BEAST_MODE_FRACTION: f64 = 0.85;
+
+/// Sub-ação de um plano paralelo, já preparada para a coleta
+enum ParallelTask {
+    Search {
+        queries: Vec<SerpQuery>,
+        embeddings: Vec<Vec<f32>>,
+        think: String,
+    },
+    Read {
+        urls: Vec<Url>,
+        think: String,
+    },
+    Tool {
+        tool: String,
+        arguments: serde_json::Value,
+        think: String,
+        allowed: bool,
+    },
+}
+
+impl ParallelTask {
+    /// Tipo e descrição da tarefa nos eventos de batch
+    fn describe(&self) -> (&'static str, String) {
+        match self {
+            ParallelTask::Search { queries, .. } => (
+                "Search",
+                queries.iter().map(|q| q.q.as_str()).collect::<Vec<_>>().join(" | "),
+            ),
+            ParallelTask::Read { urls, .. } => ("WebRead", urls.join(" | ")),
+            ParallelTask::Tool { tool, .. } => ("Tool", tool.clone()),
+        }
+    }
+}
+
+/// Resultado da coleta de uma sub-ação, aplicado depois na ordem do plano
+enum ParallelResult {
+    Search {
+        queries: Vec<SerpQuery>,
+        embeddings: Vec<Vec<f32>>,
+        think: String,
+        results: Vec<Result<SearchResult, SearchError>>,
+    },
+    Read {
+        urls: Vec<Url>,
+        think: String,
+        reads: Vec<(&'static str, Result<UrlContent, String>)>,
+    },
+    Tool {
+        tool: String,
+        arguments: serde_json::Value,
+        think: String,
+        result: Result<Vec<KnowledgeItem>, ToolError>,
+    },
+}
 
 /// Agente principal de pesquisa profunda
 pub struct DeepResearchAgent {
@@ -328,6 +477,65 @@ pub struct DeepResearchAgent {
     interaction_hub: InteractionHub,
     /// Canal para enviar respostas do usuário para o hub
     user_response_tx: Option<mpsc::Sender<UserResponse>>,
+    /// ID único desta execução (nome do arquivo de checkpoint)
+    run_id: String,
+    /// Data/hora de criação da execução (ISO 8601)
+    created_at: String,
+    /// Arquivo de checkpoint gravado após cada step (None = desabilitado)
+    checkpoint_path: Option<std::path::PathBuf>,
+    /// Se o agente foi reconstruído a partir de um checkpoint
+    resumed: bool,
+    /// Polir a resposta aprovada com `ResponseFinalizer` antes de concluir
+    finalize_answers: bool,
+    /// Token de cancelamento cooperativo (verificado entre steps e nos batches)
+    cancel_token: CancellationToken,
+    /// Interrompe chamadas em andamento: filho de `cancel_token`, também
+    /// disparado pelo prazo rígido
+    interrupt_token: CancellationToken,
+    /// Budget de tempo de parede (None = sem prazo)
+    time_budget: Option<std::time::Duration>,
+    /// Fração do prazo a partir da qual entra em Beast Mode
+    time_beast_mode_fraction: f64,
+    /// Melhor resposta reprovada até agora (mais avaliações aprovadas)
+    best_candidate: Option<(usize, AnswerResult)>,
+    /// Último total de tokens de embedding lido do `llm_client`
+    embedding_tokens_seen: u64,
+    /// Último total de créditos lido do `search_client`
+    search_credits_seen: u64,
+    /// JSON Schema que a resposta final deve validar (None = texto livre)
+    response_schema: Option<JsonSchema>,
+    /// Turnos anteriores da conversa (reescrevem a pergunta de acompanhamento)
+    chat_history: Vec<ChatTurn>,
+    /// Ferramentas externas disponíveis para a ação `tool`
+    tools: ToolRegistry,
+    /// Memória que limita o conhecimento incluído nos prompts
+    memory: KnowledgeMemory,
+    /// Transcript JSONL da execução (se habilitado)
+    transcript: Option<TranscriptWriter>,
+    /// Entradas do diário já registradas no transcript
+    transcript_diary_len: usize,
+    /// Tokens consumidos no último registro de step do transcript
+    transcript_tokens: u64,
+    /// Hooks do ciclo de vida, em ordem de registro
+    hooks: HookChain,
+    /// Ações que exigem confirmação do usuário antes de executar
+    approval_policy: ApprovalPolicy,
+    /// Ação aguardando aprovação (ID da pergunta de confirmação, ação)
+    pending_approval: Option<(String, AgentAction)>,
+    /// Ação aprovada, executada no próximo step sem nova decisão do LLM
+    approved_action: Option<AgentAction>,
+    /// Candidatas geradas em paralelo no Beast Mode
+    beast_mode_candidates: usize,
+    /// Votação da resposta do Beast Mode (se houve)
+    beast_mode_vote: Option<BeastModeVote>,
+    /// Se respostas aprovadas passam pela verificação por afirmação
+    verify_claims: bool,
+    /// Vereditos das afirmações da resposta aceita
+    claim_checks: Vec<ClaimCheck>,
+    /// Se o conhecimento novo é comparado com o anterior em busca de conflitos
+    detect_contradictions: bool,
+    /// Credibilidade das fontes (pondera URLs e referências)
+    credibility: SourceCredibility,
 }
 
 impl DeepResearchAgent {
@@ -344,6 +552,15 @@ impl DeepResearchAgent {
 
         log::info!("🌐 Idioma de resposta: {}", response_language.display_name());
 
+        // Custo: preços embutidos para os modelos do cliente (ver `with_pricing`)
+        let mut token_tracker = TokenTracker::new(token_budget);
+        token_tracker.set_rates(
+            PricingTable::builtin()
+                .rates_for(llm_client.model_name(), llm_client.embedding_model_name()),
+        );
+        let embedding_tokens_seen = llm_client.get_embedding_tokens();
+        let search_credits_seen = search_client.get_credits();
+
         Self {
             state: AgentState::Processing {
                 step: 0,
@@ -354,7 +571,7 @@ impl DeepResearchAgent {
             context: AgentContext::new(),
             llm_client,
             search_client,
-            token_tracker: TokenTracker::new(token_budget),
+            token_tracker,
             timing_stats: TimingStats::new(),
             start_time: std::time::Instant::now(),
             progress_callback: None,
@@ -371,9 +588,223 @@ impl DeepResearchAgent {
             analysis_rx: None,
             interaction_hub: InteractionHub::new(),
             user_response_tx: None,
+            run_id: uuid::Uuid::new_v4().to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            checkpoint_path: None,
+            resumed: false,
+            finalize_answers: false,
+            cancel_token: CancellationToken::new(),
+            interrupt_token: CancellationToken::new(),
+            time_budget: None,
+            time_beast_mode_fraction: DEFAULT_TIME_BEAST_MODE_FRACTION,
+            best_candidate: None,
+            embedding_tokens_seen,
+            search_credits_seen,
+            response_schema: None,
+            chat_history: Vec::new(),
+            tools: ToolRegistry::new(),
+            memory: KnowledgeMemory::default(),
+            transcript: None,
+            transcript_diary_len: 0,
+            transcript_tokens: 0,
+            hooks: HookChain::new(),
+            approval_policy: ApprovalPolicy::new(),
+            pending_approval: None,
+            approved_action: None,
+            beast_mode_candidates: DEFAULT_BEAST_MODE_CANDIDATES,
+            beast_mode_vote: None,
+            verify_claims: false,
+            claim_checks: vec![],
+            detect_contradictions: false,
+            credibility: SourceCredibility::default(),
+        }
+    }
+
+    /// Reconstrói um agente a partir de um checkpoint gravado em disco
+    ///
+    /// O agente retomado continua do último step concluído: conhecimento,
+    /// diário, URLs visitadas, gap questions, tokens e contadores são
+    /// restaurados. Novos checkpoints continuam sendo gravados no mesmo arquivo.
+    ///
+    /// Use `original_question()` para obter a pergunta a passar para `run`.
+    pub fn resume(
+        path: impl AsRef<std::path::Path>,
+        llm_client: Arc<dyn LlmClient>,
+        search_client: Arc<dyn SearchClient>,
+    ) -> Result<Self, CheckpointError> {
+        let path = path.as_ref();
+        let checkpoint = AgentCheckpoint::load(path)?;
+
+        let mut agent = Self::new(llm_client, search_client, None);
+        agent.state = match checkpoint.state {
+            // A pergunta pendente morreu junto com o processo anterior
+            AgentState::InputRequired { .. } => AgentState::Processing {
+                step: checkpoint.context.total_step as u32,
+                total_step: checkpoint.context.total_step as u32,
+                current_question: checkpoint.context.current_question().to_string(),
+                budget_used: checkpoint.token_tracker.budget_used_percentage(),
+            },
+            state => state,
+        };
+        agent.context = checkpoint.context;
+        let rates = *agent.token_tracker.rates();
+        agent.token_tracker = checkpoint.token_tracker;
+        if agent.token_tracker.rates().is_zero() {
+            // Checkpoint anterior ao rastreamento de custo
+            agent.token_tracker.set_rates(rates);
+        }
+        agent.search_count = checkpoint.counters.search_count;
+        agent.read_count = checkpoint.counters.read_count;
+        agent.answer_count = checkpoint.counters.answer_count;
+        agent.jina_wins = checkpoint.counters.jina_wins;
+        agent.rust_wins = checkpoint.counters.rust_wins;
+        agent.ties = checkpoint.counters.ties;
+        agent.consecutive_failures = checkpoint.counters.consecutive_failures;
+        agent.analysis_count = checkpoint.counters.analysis_count;
+        agent.response_language = checkpoint.response_language;
+        agent.enable_comparative_read = checkpoint.enable_comparative_read;
+        agent.finalize_answers = checkpoint.finalize_answers;
+        agent.verify_claims = checkpoint.verify_claims;
+        agent.detect_contradictions = checkpoint.detect_contradictions;
+        agent.response_schema = checkpoint
+            .response_schema
+            .and_then(|schema| JsonSchema::new(schema).ok());
+        agent.time_budget = checkpoint.time_budget_ms.map(std::time::Duration::from_millis);
+        agent.time_beast_mode_fraction = checkpoint.time_beast_mode_fraction;
+        agent.start_time = std::time::Instant::now()
+            .checked_sub(std::time::Duration::from_millis(checkpoint.elapsed_ms))
+            .unwrap_or_else(std::time::Instant::now);
+        agent.run_id = checkpoint.run_id;
+        agent.created_at = checkpoint.created_at;
+        agent.checkpoint_path = Some(path.to_path_buf());
+        agent.resumed = true;
+        // A pergunta de aprovação morreu com o processo; o próximo step a refaz
+        agent.pending_approval = checkpoint.pending_approval.map(|action| (String::new(), action));
+
+        log::info!(
+            "♻️ Checkpoint carregado: {} (step {}, {} itens de conhecimento, {} tokens)",
+            agent.run_id,
+            agent.context.total_step,
+            agent.context.knowledge.len(),
+            agent.token_tracker.total_tokens()
+        );
+
+        Ok(agent)
+    }
+
+    /// Habilita checkpoints em `<dir>/<run_id>.json` após cada step
+    pub fn with_checkpoint_dir(mut self, dir: impl AsRef<std::path::Path>) -> Self {
+        self.checkpoint_path = Some(checkpoint::checkpoint_path(dir.as_ref(), &self.run_id));
+        self
+    }
+
+    /// ID único desta execução
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Pergunta original (útil para retomar com `run` após `resume`)
+    pub fn original_question(&self) -> &str {
+        &self.context.original_question
+    }
+
+    /// Monta o snapshot atual do agente
+    pub fn checkpoint(&self) -> AgentCheckpoint {
+        AgentCheckpoint {
+            version: checkpoint::CHECKPOINT_VERSION,
+            run_id: self.run_id.clone(),
+            created_at: self.created_at.clone(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            state: self.state.clone(),
+            context: self.context.clone(),
+            token_tracker: self.token_tracker.clone(),
+            counters: AgentCounters {
+                search_count: self.search_count,
+                read_count: self.read_count,
+                answer_count: self.answer_count,
+                jina_wins: self.jina_wins,
+                rust_wins: self.rust_wins,
+                ties: self.ties,
+                consecutive_failures: self.consecutive_failures,
+                analysis_count: self.analysis_count,
+            },
+            response_language: self.response_language,
+            enable_comparative_read: self.enable_comparative_read,
+            finalize_answers: self.finalize_answers,
+            verify_claims: self.verify_claims,
+            detect_contradictions: self.detect_contradictions,
+            response_schema: self.response_schema.as_ref().map(|s| s.as_value().clone()),
+            time_budget_ms: self.time_budget.map(|b| b.as_millis() as u64),
+            time_beast_mode_fraction: self.time_beast_mode_fraction,
+            elapsed_ms: self.start_time.elapsed().as_millis() as u64,
+            pending_approval: self.pending_approval.as_ref().map(|(_, action)| action.clone()),
+        }
+    }
+
+    /// Grava o checkpoint (se habilitado). Falhas apenas geram log.
+    fn save_checkpoint(&self) {
+        let Some(path) = &self.checkpoint_path else {
+            return;
+        };
+
+        if let Err(e) = self.checkpoint().save(path) {
+            log::warn!("⚠️ Falha ao gravar checkpoint {}: {}", path.display(), e);
+        }
+    }
+
+    /// Remove o checkpoint de uma execução encerrada (se habilitado)
+    fn remove_checkpoint(&self) {
+        let Some(path) = &self.checkpoint_path else {
+            return;
+        };
+
+        match std::fs::remove_file(path) {
+            Ok(()) => log::debug!("🗑️ Checkpoint removido: {}", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("⚠️ Falha ao remover checkpoint {}: {}", path.display(), e),
+        }
+    }
+
+    /// Habilita o transcript JSONL em `<dir>/<run_id>.jsonl`
+    ///
+    /// Execuções retomadas continuam no mesmo arquivo.
+    pub fn with_transcript_dir(mut self, dir: impl AsRef<std::path::Path>) -> Self {
+        let path = transcript::transcript_path(dir.as_ref(), &self.run_id);
+        match TranscriptWriter::open(&path) {
+            Ok(writer) => self.transcript = Some(writer),
+            Err(e) => log::warn!("⚠️ Falha ao abrir transcript {}: {}", path.display(), e),
+        }
+        self
+    }
+
+    /// Anexa um evento ao transcript (se habilitado)
+    fn record(&self, event: TranscriptEvent) {
+        if let Some(transcript) = &self.transcript {
+            transcript.append(self.start_time.elapsed().as_millis() as u64, event);
         }
     }
 
+    /// Registra no transcript as entradas do diário geradas desde o último registro
+    fn record_step(&mut self) {
+        if self.transcript.is_none() || self.context.diary.len() <= self.transcript_diary_len {
+            return;
+        }
+
+        let tokens_used = self.token_tracker.total_tokens();
+        let entries = self.context.diary[self.transcript_diary_len..].to_vec();
+        self.record(TranscriptEvent::StepCompleted {
+            step: self.context.total_step,
+            entries,
+            tokens_used,
+            tokens_delta: tokens_used.saturating_sub(self.transcript_tokens),
+            knowledge_items: self.context.knowledge.len(),
+            total_urls: self.context.collected_urls.len(),
+            visited_urls: self.context.visited_urls.len(),
+        });
+        self.transcript_diary_len = self.context.diary.len();
+        self.transcript_tokens = tokens_used;
+    }
+
     /// Define o idioma das respostas
     pub fn with_response_language(mut self, language: crate::types::Language) -> Self {
         self.response_language = language;
@@ -387,6 +818,12 @@ impl DeepResearchAgent {
         self
     }
 
+    /// Compartilha o conjunto de URLs visitadas com outros agentes (modo equipe)
+    pub fn with_shared_visited_urls(mut self, shared: SharedUrlSet) -> Self {
+        self.context.shared_visited_urls = Some(shared);
+        self
+    }
+
     /// Habilita modo de comparação Jina vs Rust local
     pub fn with_comparative_read(mut self, enable: bool) -> Self {
         self.enable_comparative_read = enable;
@@ -396,6 +833,206 @@ impl DeepResearchAgent {
         self
     }
 
+    /// Usa um token de cancelamento externo
+    ///
+    /// Ao cancelar, o agente interrompe chamadas HTTP em andamento, encerra
+    /// no próximo ponto de verificação e retorna um `ResearchResult` com
+    /// `cancelled = true`.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    /// Define um budget de tempo de parede
+    ///
+    /// Ao atingir `time_beast_mode_fraction` do prazo o agente entra em Beast
+    /// Mode; no prazo rígido interrompe o que estiver em andamento e retorna
+    /// a melhor resposta disponível.
+    pub fn with_time_budget(mut self, budget: std::time::Duration) -> Self {
+        self.time_budget = Some(budget);
+        log::info!("⏱️ Budget de tempo: {:.1}s", budget.as_secs_f64());
+        self
+    }
+
+    /// Fração do prazo (0.0 - 1.0) que dispara o Beast Mode (padrão: 0.85)
+    pub fn with_time_beast_mode_fraction(mut self, fraction: f64) -> Self {
+        self.time_beast_mode_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Usa uma tabela de preços própria (ex: carregada de AGENT_PRICING_FILE)
+    pub fn with_pricing(mut self, pricing: &PricingTable) -> Self {
+        self.token_tracker.set_rates(pricing.rates_for(
+            self.llm_client.model_name(),
+            self.llm_client.embedding_model_name(),
+        ));
+        self
+    }
+
+    /// Define um budget em USD; ao atingir 85% o agente entra em Beast Mode
+    pub fn with_cost_budget(mut self, budget_usd: f64) -> Self {
+        self.token_tracker.set_cost_budget(Some(budget_usd));
+        self
+    }
+
+    /// Token de cancelamento desta execução (clone para usar após `run`)
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+
+    /// Habilita o polimento da resposta aprovada (ResponseFinalizer)
+    ///
+    /// Os marcadores de citação são preservados e as posições das
+    /// referências recalculadas sobre o texto polido.
+    pub fn with_answer_finalization(mut self, enable: bool) -> Self {
+        self.finalize_answers = enable;
+        if enable {
+            log::info!("✨ Polimento pós-aprovação ATIVADO");
+        }
+        self
+    }
+
+    /// Habilita a verificação por afirmação das respostas aprovadas
+    ///
+    /// Cada afirmação é confrontada com o conteúdo lido (ou buscas
+    /// direcionadas); afirmações contraditas reprovam a resposta e voltam
+    /// como dica de melhoria. Os vereditos ficam em `ResearchResult::claim_checks`.
+    pub fn with_claim_verification(mut self, enable: bool) -> Self {
+        self.verify_claims = enable;
+        if enable {
+            log::info!("🔎 Verificação por afirmação ATIVADA");
+        }
+        self
+    }
+
+    /// Habilita a detecção de contradições entre fontes
+    ///
+    /// Após cada ação, fatos do conhecimento novo são comparados com os
+    /// anteriores; conflitos entram no prompt como fatos disputados e em
+    /// `ResearchResult::disputed_facts`.
+    pub fn with_contradiction_detection(mut self, enable: bool) -> Self {
+        self.detect_contradictions = enable;
+        if enable {
+            log::info!("⚖️ Detecção de contradições ATIVADA");
+        }
+        self
+    }
+
+    /// Usa uma pontuação de credibilidade própria (camadas do arquivo e histórico)
+    ///
+    /// O score de cada fonte vira o `hostname_boost` dos resultados de busca
+    /// e pondera a ordem das referências; domínios bloqueados são descartados.
+    pub fn with_credibility(mut self, credibility: SourceCredibility) -> Self {
+        self.credibility = credibility;
+        self
+    }
+
+    /// Exige uma resposta estruturada que valide contra `schema`
+    ///
+    /// A ação ANSWER passa a produzir JSON; a validação do schema vira um
+    /// gate obrigatório da avaliação (com tentativas de reparo) e o
+    /// `ResearchResult` traz o valor parseado em `structured`. O polimento
+    /// pós-aprovação é ignorado para não alterar o JSON.
+    pub fn with_response_schema(mut self, schema: JsonSchema) -> Self {
+        self.response_schema = Some(schema);
+        log::info!("🧩 Resposta estruturada (JSON Schema) ATIVADA");
+        self
+    }
+
+    /// Inclui os turnos anteriores de uma conversa
+    ///
+    /// Cada turno entra no contexto como `KnowledgeType::ChatHistory` e, antes
+    /// de pesquisar, a pergunta de acompanhamento é reescrita como uma
+    /// pergunta independente (`QuestionRewriter`).
+    pub fn with_chat_history(mut self, turns: Vec<ChatTurn>) -> Self {
+        for item in history_knowledge(&turns) {
+            self.context.add_knowledge(item);
+        }
+        self.chat_history = turns;
+        self
+    }
+
+    /// Reaproveita conhecimento e URLs visitadas de pesquisas anteriores
+    ///
+    /// As URLs não são lidas de novo; o conhecimento fica disponível desde
+    /// o primeiro step.
+    pub fn with_prior_research(
+        mut self,
+        knowledge: Vec<KnowledgeItem>,
+        visited_urls: Vec<String>,
+    ) -> Self {
+        for item in knowledge {
+            self.context.add_knowledge(item);
+        }
+        for url in visited_urls {
+            if !self.context.visited_urls.contains(&url) {
+                self.context.visited_urls.push(url);
+            }
+        }
+        self
+    }
+
+    /// Continua uma conversa: histórico, conhecimento e URLs visitadas
+    pub fn with_conversation(self, conversation: &ConversationState) -> Self {
+        self.with_chat_history(conversation.turns.clone()).with_prior_research(
+            conversation.knowledge.clone(),
+            conversation.visited_urls.clone(),
+        )
+    }
+
+    /// Configura a memória de conhecimento (franquia de tokens, top-k, ...)
+    ///
+    /// Quando o conhecimento acumulado excede a franquia, os prompts recebem
+    /// apenas os trechos mais relevantes para a pergunta atual e a original.
+    pub fn with_knowledge_memory(mut self, config: KnowledgeMemoryConfig) -> Self {
+        self.memory = KnowledgeMemory::new(config);
+        self
+    }
+
+    /// Disponibiliza ferramentas externas (Paytour, Digisac, ...) ao agente
+    ///
+    /// As ferramentas permitidas aparecem no system prompt e podem ser
+    /// chamadas pela ação `tool`.
+    pub fn with_tool_registry(mut self, tools: ToolRegistry) -> Self {
+        if !tools.is_empty() {
+            log::info!("🧰 Ferramentas disponíveis: {}", tools.names().join(", "));
+        }
+        self.tools = tools;
+        self
+    }
+
+    /// Registra um hook do ciclo de vida
+    ///
+    /// Hooks rodam na ordem de registro e podem reescrever o prompt, vetar ou
+    /// substituir a ação escolhida e observar ações, conhecimento e avaliações.
+    pub fn with_hook(mut self, hook: Arc<dyn AgentHook>) -> Self {
+        log::info!("🪝 Hook registrado: {}", hook.name());
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Define quantas respostas candidatas o Beast Mode gera (1 a 7, padrão 3)
+    ///
+    /// Com mais de uma, as candidatas são avaliadas e votadas; com 1, o Beast
+    /// Mode devolve a única resposta gerada.
+    pub fn with_beast_mode_candidates(mut self, candidates: usize) -> Self {
+        self.beast_mode_candidates = candidates.clamp(1, voting::MAX_BEAST_MODE_CANDIDATES);
+        self
+    }
+
+    /// Define quais ações exigem aprovação humana antes de executar
+    ///
+    /// A ação vira uma pergunta `Confirmation` no `InteractionHub` e só é
+    /// executada com resposta afirmativa. Sem canais de interação ninguém
+    /// pode aprovar, e a ação é recusada.
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        if !policy.is_empty() {
+            log::info!("🛂 {} regra(s) de aprovação humana", policy.rules.len());
+        }
+        self.approval_policy = policy;
+        self
+    }
+
     /// Configura canais de interação para comunicação com usuário
     ///
     /// Retorna um sender para enviar respostas do usuário e um receiver
@@ -434,11 +1071,44 @@ impl DeepResearchAgent {
 
     /// Envia evento de progresso se callback configurado
     fn emit(&self, event: AgentProgress) {
+        if self.transcript.is_some() {
+            self.record_progress(&event);
+        }
         if let Some(cb) = &self.progress_callback {
             cb(event);
         }
     }
 
+    /// Registra no transcript os eventos de progresso relevantes para replay
+    fn record_progress(&self, event: &AgentProgress) {
+        let log = |level, message: &String| TranscriptEvent::Log {
+            level,
+            message: message.clone(),
+        };
+        let event = match event {
+            AgentProgress::Info(msg) => log(TranscriptLevel::Info, msg),
+            AgentProgress::Success(msg) => log(TranscriptLevel::Success, msg),
+            AgentProgress::Warning(msg) => log(TranscriptLevel::Warning, msg),
+            AgentProgress::Error(msg) => log(TranscriptLevel::Error, msg),
+            AgentProgress::VisitedUrl(url) => TranscriptEvent::VisitedUrl { url: url.clone() },
+            AgentProgress::ValidationStep {
+                eval_type,
+                passed,
+                confidence,
+                reasoning,
+                duration_ms,
+            } => TranscriptEvent::Evaluation {
+                eval_type: eval_type.clone(),
+                passed: *passed,
+                confidence: *confidence,
+                reasoning: reasoning.clone(),
+                duration_ms: *duration_ms,
+            },
+            _ => return,
+        };
+        self.record(event);
+    }
+
     /// Emite estatísticas atuais do agente como "persona"
     fn emit_persona_stats(&self, is_active: bool) {
         self.emit(AgentProgress::Persona {
@@ -453,29 +1123,128 @@ impl DeepResearchAgent {
 
     /// Loop principal - consome self e retorna resultado final
     pub async fn run(mut self, question: String) -> ResearchResult {
-        // Inicialização
-        self.context.original_question = question.clone();
-        self.context.gap_questions.push(question.clone());
+        if self.resumed {
+            // Retomando de checkpoint: contexto já inicializado
+            self.emit(AgentProgress::Info(format!(
+                "Retomando pesquisa a partir do step {}: {}",
+                self.context.total_step, self.context.original_question
+            )));
+            self.emit(AgentProgress::Step(self.context.total_step));
+            self.emit(AgentProgress::Tokens(self.token_tracker.total_tokens()));
+        } else {
+            // Pergunta de acompanhamento: torna-se independente do histórico
+            let question = if self.chat_history.is_empty() {
+                question
+            } else {
+                self.emit(AgentProgress::Action("Interpretando a conversa...".into()));
+                let rewriter = QuestionRewriter::new(self.llm_client.clone());
+                let standalone = rewriter
+                    .rewrite(&self.chat_history, &question, &mut self.token_tracker)
+                    .await;
+                if standalone != question {
+                    self.emit(AgentProgress::Info(format!(
+                        "💬 Pergunta reescrita com o histórico: {}",
+                        standalone
+                    )));
+                }
+                standalone
+            };
+
+            // Inicialização
+            self.context.original_question = question.clone();
+            self.context.gap_queue = GapQueue::new(&question);
+
+            // Emitir início
+            self.emit(AgentProgress::Info(format!("Iniciando pesquisa: {}", question)));
+            self.emit(AgentProgress::Step(0));
+            self.emit(AgentProgress::Action("Inicializando...".into()));
+        }
+
+        self.transcript_diary_len = self.context.diary.len();
+        self.transcript_tokens = self.token_tracker.total_tokens();
+        self.record(TranscriptEvent::RunStarted {
+            run_id: self.run_id.clone(),
+            question: self.context.original_question.clone(),
+            resumed: self.resumed,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        });
 
-        // Emitir início
-        self.emit(AgentProgress::Info(format!("Iniciando pesquisa: {}", question)));
-        self.emit(AgentProgress::Step(0));
-        self.emit(AgentProgress::Action("Inicializando...".into()));
+        // Interrupções: cancelamento externo ou prazo rígido
+        self.interrupt_token = self.cancel_token.child_token();
+        let deadline_timer = self.time_budget.map(|budget| {
+            let interrupt = self.interrupt_token.clone();
+            let deadline = tokio::time::Instant::from_std(self.start_time + budget);
+            tokio::spawn(async move {
+                tokio::time::sleep_until(deadline).await;
+                interrupt.cancel();
+            })
+        });
 
         // Loop principal com pattern matching exaustivo
         loop {
+            // Checkpoint do último step concluído
+            self.sync_external_usage();
+            self.save_checkpoint();
+            self.record_step();
+
+            // Cancelamento cooperativo: verificado entre steps
+            if self.cancel_token.is_cancelled() && !self.state.is_terminal() {
+                log::warn!("⏹️ Pesquisa cancelada no step {}", self.context.total_step);
+                self.emit(AgentProgress::Warning(format!(
+                    "⏹️ Pesquisa cancelada ({} itens de conhecimento preservados)",
+                    self.context.knowledge.len()
+                )));
+                self.state = AgentState::Cancelled {
+                    partial_knowledge: self.context.knowledge.clone(),
+                };
+                break;
+            }
+
+            // Prazo rígido: conclui com a melhor resposta disponível
+            let deadline_passed = self.interrupt_token.is_cancelled()
+                || (self.time_budget.is_some() && self.time_budget_used_percentage() >= 1.0);
+            if deadline_passed && !self.state.is_terminal() {
+                self.finish_at_deadline();
+                break;
+            }
+
             match &self.state {
                 AgentState::Processing { .. } if self.token_tracker.should_enter_beast_mode() => {
-                    // Transição para Beast Mode (>= 85% do budget de tokens)
+                    // Transição para Beast Mode (>= 85% do budget de tokens ou de custo)
+                    let token_used = self.token_tracker.budget_used_percentage();
+                    let cost_used = self.token_tracker.cost_budget_used_percentage();
+                    let msg = if cost_used > token_used {
+                        format!(
+                            "Budget de custo em {:.1}% (${:.4}) - entrando em Beast Mode",
+                            cost_used * 100.0,
+                            self.token_tracker.cost().total_usd
+                        )
+                    } else {
+                        format!(
+                            "Budget de tokens em {:.1}% - entrando em Beast Mode",
+                            token_used * 100.0
+                        )
+                    };
+                    log::warn!("⚠️ {}", msg);
+                    self.emit(AgentProgress::Warning(msg));
+                    self.state = AgentState::BeastMode {
+                        attempts: 0,
+                        last_failure: "Budget exhausted".into(),
+                    };
+                }
+
+                AgentState::Processing { .. } if self.time_beast_mode_reached() => {
+                    // Transição para Beast Mode (fração do prazo consumida)
                     let msg = format!(
-                        "Budget de tokens em {:.1}% - entrando em Beast Mode",
-                        self.token_tracker.budget_used_percentage() * 100.0
+                        "Prazo em {:.1}% ({:.1}s) - entrando em Beast Mode",
+                        self.time_budget_used_percentage() * 100.0,
+                        self.start_time.elapsed().as_secs_f64()
                     );
                     log::warn!("⚠️ {}", msg);
                     self.emit(AgentProgress::Warning(msg));
                     self.state = AgentState::BeastMode {
                         attempts: 0,
-                        last_failure: "Budget exhausted".into(),
+                        last_failure: "Time budget exhausted".into(),
                     };
                 }
 
@@ -528,7 +1297,11 @@ impl DeepResearchAgent {
                     ..
                 } => {
                     // Tentar forçar resposta
-                    match self.force_answer().await {
+                    let cancel = self.interrupt_token.clone();
+                    let Some(forced) = cancel.run_until_cancelled(self.force_answer()).await else {
+                        continue;
+                    };
+                    match forced {
                         Ok(answer) => {
                             self.state = AgentState::Completed {
                                 answer: answer.answer,
@@ -560,7 +1333,14 @@ impl DeepResearchAgent {
                     }
 
                     // Esperar resposta com timeout de 60 segundos
-                    match self.interaction_hub.wait_for_response(question_id, Some(60)).await {
+                    let cancel = self.interrupt_token.clone();
+                    let Some(waited) = cancel
+                        .run_until_cancelled(self.interaction_hub.wait_for_response(question_id, Some(60)))
+                        .await
+                    else {
+                        continue;
+                    };
+                    match waited {
                         Ok(response) => {
                             log::info!("📥 Resposta recebida: {}", response.content);
                             self.process_user_response(response).await;
@@ -588,10 +1368,26 @@ impl DeepResearchAgent {
                 }
 
                 // Estados terminais - sair do loop
-                AgentState::Completed { .. } | AgentState::Failed { .. } => break,
+                AgentState::Completed { .. }
+                | AgentState::Failed { .. }
+                | AgentState::Cancelled { .. } => break,
             }
         }
 
+        if let Some(timer) = deadline_timer {
+            timer.abort();
+        }
+        self.sync_external_usage();
+
+        // Execuções concluídas ou falhas não são retomáveis: o checkpoint (com
+        // todo o conhecimento e contexto) é removido. Execuções canceladas
+        // mantêm o checkpoint do último step e podem ser retomadas.
+        if !self.state.is_cancelled() {
+            self.remove_checkpoint();
+        }
+        self.record_step();
+        self.record_finished();
+
         // Construir resultado final
         self.build_result()
     }
@@ -641,29 +1437,55 @@ impl DeepResearchAgent {
         }
 
         // 1. Calcular permissões baseadas no contexto atual
-        let permissions = ActionPermissions::from_context(&self.context);
+        let mut permissions = ActionPermissions::from_context(&self.context);
+        let has_tools = self.tools.allowed(&permissions, &self.context).next().is_some();
+        permissions.tools = has_tools;
+
+        // Aprovação pendente de uma execução retomada: pergunta de novo
+        if let Some((_, action)) = self.pending_approval.take() {
+            return self.request_approval(action).await;
+        }
+
+        // Ação aprovada pelo usuário: executa sem nova decisão do LLM
+        if let Some(action) = self.approved_action.take() {
+            log::info!("🛂 Executando ação aprovada: {}", action.name());
+            return self.run_action(action, &permissions).await;
+        }
 
         // 2. Rotacionar para próxima pergunta
         let current_question = self.rotate_question();
 
         // 3. Gerar prompt e obter decisão do LLM (com timing)
-        let prompt = self.build_prompt(&permissions, &current_question);
+        let knowledge = self.recall_knowledge(&current_question).await;
+        let mut prompt = self.build_prompt(&permissions, &current_question, &knowledge);
+        self.hooks.before_decide(&self.context, &mut prompt).await;
 
         // Capturar tokens antes da chamada
         let tokens_before = self.llm_client.get_total_tokens();
+        let prompt_before = self.llm_client.get_prompt_tokens();
+        let completion_before = self.llm_client.get_completion_tokens();
 
         let llm_timer = ActionTimer::start("LLM decide_action");
-        let action = match self.llm_client.decide_action(&prompt, &permissions).await {
-            Ok(a) => a,
-            Err(e) => return StepResult::Error(format!("LLM error: {}", e)),
+        let decision = self
+            .interrupt_token
+            .run_until_cancelled(self.llm_client.decide_action(&prompt, &permissions))
+            .await;
+        let action = match decision {
+            Some(Ok(a)) => a,
+            Some(Err(e)) => return StepResult::Error(format!("LLM error: {}", e)),
+            // Cancelado: o loop principal encerra no próximo ponto de verificação
+            None => return StepResult::Continue,
         };
         let llm_time = llm_timer.stop();
         self.timing_stats.add_llm_time(llm_time);
 
-        // Rastrear tokens usados nesta operação
+        // Rastrear tokens usados nesta operação (antes dos hooks: vetos também gastam)
         let tokens_after = self.llm_client.get_total_tokens();
-        let prompt_used = self.llm_client.get_prompt_tokens().saturating_sub(tokens_before);
-        let completion_used = tokens_after.saturating_sub(tokens_before).saturating_sub(prompt_used);
+        let prompt_used = self.llm_client.get_prompt_tokens().saturating_sub(prompt_before);
+        let completion_used = self
+            .llm_client
+            .get_completion_tokens()
+            .saturating_sub(completion_before);
         self.token_tracker.track(
             self.context.total_step,
             &format!("decide_action:{}", action.name()),
@@ -674,6 +1496,12 @@ impl DeepResearchAgent {
         // Atualizar budget_used no estado
         self.update_budget_used();
 
+        // Hooks podem vetar ou substituir a ação escolhida
+        let action = match self.hooks.after_decide(&self.context, action).await {
+            Ok(action) => action,
+            Err(veto) => return self.reject_vetoed_action(veto),
+        };
+
         log::debug!("⏱️  LLM decision: {}ms | 🎟️ Tokens: {} ({:.1}% budget)",
             llm_time,
             tokens_after - tokens_before,
@@ -687,6 +1515,18 @@ impl DeepResearchAgent {
             action.think().chars().take(150).collect::<String>()
         );
 
+        self.record(TranscriptEvent::Decision {
+            step: self.context.total_step,
+            question: current_question.clone(),
+            action: action.name().to_string(),
+            think: action.think().to_string(),
+            prompt_chars: prompt.system.chars().count() + prompt.user.chars().count(),
+            prompt_tokens: estimate_tokens(&prompt.system) + estimate_tokens(&prompt.user),
+            prompt_tokens_used: prompt_used,
+            completion_tokens_used: completion_used,
+            duration_ms: llm_time,
+        });
+
         // Emitir progresso para TUI
         self.emit(AgentProgress::Step(self.context.total_step));
         self.emit(AgentProgress::Action(action.name().to_string()));
@@ -697,7 +1537,172 @@ impl DeepResearchAgent {
             self.context.visited_urls.len(),
         ));
 
-        // 4. Executar ação escolhida - pattern matching garante cobertura total
+        // 4. Ações sensíveis aguardam aprovação humana
+        if permissions.mode(&action, &self.approval_policy) == PermissionMode::RequiresApproval {
+            return self.request_approval(action).await;
+        }
+
+        // 5. Executar ação escolhida
+        self.run_action(action, &permissions).await
+    }
+
+    /// Executa a ação com os hooks em volta
+    async fn run_action(&mut self, action: AgentAction, permissions: &ActionPermissions) -> StepResult {
+        self.hooks.before_action(&self.context, &action).await;
+        let action_name = action.name().to_string();
+        let knowledge_before = self.context.knowledge.len();
+
+        let result = self.execute_action(action, permissions).await;
+
+        if self.detect_contradictions {
+            self.detect_new_contradictions(knowledge_before).await;
+        }
+        if !self.hooks.is_empty() {
+            let added = self.context.knowledge.get(knowledge_before..).unwrap_or_default();
+            self.hooks.on_knowledge_added(&self.context, added).await;
+            self.hooks.after_action(&self.context, &action_name, &result).await;
+        }
+        result
+    }
+
+    /// Compara os fatos do conhecimento novo com os já registrados
+    async fn detect_new_contradictions(&mut self, knowledge_before: usize) {
+        let added: Vec<KnowledgeItem> = self
+            .context
+            .knowledge
+            .get(knowledge_before..)
+            .unwrap_or_default()
+            .iter()
+            .filter(|k| k.item_type == KnowledgeType::Url && !k.references.is_empty())
+            .cloned()
+            .collect();
+        if added.is_empty() {
+            return;
+        }
+
+        let known_keys = self.context.fact_ledger.known_keys();
+        let extracted = self
+            .interrupt_token
+            .run_until_cancelled(contradictions::extract_facts(
+                &added,
+                &known_keys,
+                self.llm_client.as_ref(),
+            ))
+            .await;
+        let facts = match extracted {
+            Some(Ok((facts, response))) => {
+                self.token_tracker.add_tokens(
+                    "contradictions",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                facts
+            }
+            Some(Err(e)) => {
+                log::warn!("⚖️ Contradições: falha ao extrair fatos ({})", e);
+                return;
+            }
+            None => return,
+        };
+
+        let before = self.context.fact_ledger.conflicts.len();
+        if self.context.fact_ledger.record(facts) > 0 {
+            let new_conflicts: Vec<String> = self.context.fact_ledger.conflicts[before..]
+                .iter()
+                .map(|c| c.describe())
+                .collect();
+            for conflict in new_conflicts {
+                self.emit(AgentProgress::Warning(format!("⚖️ Fontes discordam: {}", conflict)));
+            }
+        }
+    }
+
+    /// Pede ao usuário confirmação para executar a ação
+    ///
+    /// A ação fica pendente até a resposta; `process_user_response` a libera
+    /// para o próximo step ou a descarta.
+    async fn request_approval(&mut self, action: AgentAction) -> StepResult {
+        if self.user_response_tx.is_none() {
+            return self.refuse_unapprovable_action(action);
+        }
+
+        let targets = approval_targets(&action);
+        let details = if targets.is_empty() {
+            action.think().chars().take(200).collect()
+        } else {
+            targets.join(", ")
+        };
+        let question = format!("Aprovar a ação {}? {}", action.name(), details);
+        log::info!("🛂 Aprovação necessária: {}", question);
+
+        let result = self
+            .execute_ask_user(
+                QuestionType::Confirmation,
+                question,
+                Some(vec!["Sim".into(), "Não".into()]),
+                true,
+                action.think().to_string(),
+            )
+            .await;
+        if let StepResult::InputRequired { question_id, .. } = &result {
+            self.pending_approval = Some((question_id.clone(), action));
+        }
+        result
+    }
+
+    /// Recusa uma ação que exige aprovação quando ninguém pode aprovar
+    ///
+    /// Sem canais de interação (CLI, servidor, sub-agentes) a pergunta nunca
+    /// seria respondida. A recusa vira conhecimento de erro para o LLM
+    /// escolher outra ação.
+    fn refuse_unapprovable_action(&mut self, action: AgentAction) -> StepResult {
+        log::warn!(
+            "🛂 Ação {} exige aprovação, mas não há canal de interação: recusada",
+            action.name()
+        );
+        self.emit(AgentProgress::Warning(format!(
+            "🛂 Ação {} recusada: exige aprovação humana",
+            action.name()
+        )));
+        self.context.add_knowledge(KnowledgeItem {
+            question: format!("[Ação Recusada sem Aprovação] {}", action.name()),
+            answer: format!(
+                "This {} action ({}) requires human approval, but no one can approve it in this session. Choose a different action.",
+                action.name(),
+                approval_targets(&action).join(", ")
+            ),
+            item_type: KnowledgeType::Error,
+            references: vec![],
+        });
+        self.context.total_step += 1;
+        StepResult::Continue
+    }
+
+    /// Descarta uma ação vetada por hook
+    ///
+    /// O motivo vira conhecimento de erro para o LLM escolher outra ação.
+    fn reject_vetoed_action(&mut self, veto: HookVeto) -> StepResult {
+        log::warn!("🪝 Hook {} vetou a ação: {}", veto.hook, veto.reason);
+        self.emit(AgentProgress::Warning(format!(
+            "🪝 Ação vetada por {}: {}",
+            veto.hook, veto.reason
+        )));
+        self.context.add_knowledge(KnowledgeItem {
+            question: format!("[Ação Vetada por {}]", veto.hook),
+            answer: veto.reason,
+            item_type: KnowledgeType::Error,
+            references: vec![],
+        });
+        self.context.total_step += 1;
+        StepResult::Continue
+    }
+
+    /// Executa a ação escolhida - pattern matching garante cobertura total
+    async fn execute_action(
+        &mut self,
+        action: AgentAction,
+        permissions: &ActionPermissions,
+    ) -> StepResult {
         match action {
             AgentAction::Search { queries, think } => {
                 let query_list: Vec<_> = queries.iter().map(|q| q.q.clone()).collect();
@@ -715,6 +1720,9 @@ impl DeepResearchAgent {
                     .filter(|u| !self.context.is_url_visited(u) && !self.context.is_url_bad(u))
                     .collect();
 
+                // URLs escolhidas pelo agente (não pelo LLM) ainda passam pela aprovação
+                let substituted = new_urls.is_empty();
+
                 // Se LLM escolheu URLs já visitadas, usar RERANK para selecionar as melhores
                 if new_urls.is_empty() {
                     let msg = "Usando Jina Rerank para selecionar URLs mais relevantes...";
@@ -754,15 +1762,12 @@ impl DeepResearchAgent {
                     }
                 }
 
-                // Fallback: pegar próximas disponíveis sem rerank
+                // Fallback: pegar as mais bem pontuadas sem rerank
                 if new_urls.is_empty() {
                     new_urls = self
                         .context
-                        .collected_urls
-                        .iter()
-                        .filter(|u| {
-                            !self.context.is_url_visited(&u.url) && !self.context.is_url_bad(&u.url)
-                        })
+                        .ranked_available_urls()
+                        .into_iter()
                         .take(MAX_URLS_PER_STEP)
                         .map(|u| u.url.clone())
                         .collect();
@@ -778,6 +1783,14 @@ impl DeepResearchAgent {
                     return StepResult::Continue;
                 }
 
+                let substitute = AgentAction::Read {
+                    urls: new_urls.clone(),
+                    think: think.clone(),
+                };
+                if substituted && self.approval_policy.requires_approval(&substitute) {
+                    return self.request_approval(substitute).await;
+                }
+
                 self.emit(AgentProgress::Info(format!(
                     "📖 Lendo {} URLs: {}",
                     new_urls.len(),
@@ -806,9 +1819,25 @@ impl DeepResearchAgent {
                 references,
                 think,
             } => {
+                // Resposta a uma pergunta de gap vira conhecimento; só a raiz é avaliada
+                if let Some(gap_id) = self
+                    .context
+                    .gap_queue
+                    .current()
+                    .map(|q| q.id)
+                    .filter(|id| *id != ROOT_GAP_ID)
+                {
+                    return self.execute_gap_answer(gap_id, answer, references, think);
+                }
+
                 // Tentar construir referências semânticas usando embeddings
-                let (final_answer, final_references) =
-                    self.build_semantic_references(&answer, references).await;
+                let (final_answer, final_references) = if self.response_schema.is_some() {
+                    // Marcadores [^N] quebrariam o JSON: mantém o texto, usa só as referências
+                    let (_, references) = self.build_semantic_references(&answer, references).await;
+                    (answer, references)
+                } else {
+                    self.build_semantic_references(&answer, references).await
+                };
 
                 self.emit(AgentProgress::Success(format!(
                     "✍️ Gerando resposta ({} chars, {} refs)",
@@ -850,57 +1879,430 @@ impl DeepResearchAgent {
                 log::debug!("💭 Raciocínio: {}", think);
                 self.execute_ask_user(question_type, question, options, is_blocking, think).await
             }
-            // Ações de integração Paytour - delegar para handlers específicos
-            AgentAction::PaytourListarPasseios { ref think, .. }
-            | AgentAction::PaytourDetalharPasseio { ref think, .. }
-            | AgentAction::PaytourVerificarDisponibilidade { ref think, .. }
-            | AgentAction::PaytourObterHorarios { ref think, .. } => {
-                self.execute_integration_action(&action, think).await
+            AgentAction::ToolCall {
+                tool,
+                arguments,
+                think,
+            } => self.execute_tool_call(tool, arguments, think, permissions).await,
+            AgentAction::Parallel { actions, think } => {
+                self.execute_parallel(actions, think, permissions).await
             }
-            // Ações de integração Digisac - delegar para handlers específicos
-            AgentAction::DigisacEnviarMensagem { ref think, .. }
-            | AgentAction::DigisacListarWebhooks { ref think, .. }
-            | AgentAction::DigisacCriarWebhook { ref think, .. } => {
-                self.execute_integration_action(&action, think).await
+        }
+    }
+
+    /// Executa uma ferramenta externa registrada
+    ///
+    /// Argumentos inválidos, ferramentas desconhecidas ou não permitidas e
+    /// falhas de execução viram conhecimento de erro, para o LLM corrigir a
+    /// chamada no próximo step.
+    async fn execute_tool_call(
+        &mut self,
+        tool: String,
+        arguments: serde_json::Value,
+        think: String,
+        permissions: &ActionPermissions,
+    ) -> StepResult {
+        self.emit(AgentProgress::Info(format!("🧰 Chamando ferramenta: {}", tool)));
+        log::info!("🧰 Ferramenta {} com argumentos {}", tool, arguments);
+
+        let result = if self.is_tool_allowed(&tool, permissions) {
+            let call = self
+                .interrupt_token
+                .run_until_cancelled(self.tools.call(&tool, arguments.clone()))
+                .await;
+            match call {
+                Some(result) => result,
+                // Cancelado: o loop principal encerra no próximo ponto de verificação
+                None => return StepResult::Continue,
             }
+        } else {
+            Err(ToolError::NotAllowed(tool.clone()))
+        };
+
+        self.apply_tool_result(tool, &arguments, think, result);
+        self.context.total_step += 1;
+        StepResult::Continue
+    }
+
+    /// Se a ferramenta pode ser chamada neste step
+    fn is_tool_allowed(&self, tool: &str, permissions: &ActionPermissions) -> bool {
+        match self.tools.get(tool) {
+            Some(registered) => registered.is_allowed(permissions, &self.context),
+            None => true, // `call` reporta NotFound
         }
     }
 
-    /// Executa ações de integração (Paytour/Digisac)
-    async fn execute_integration_action(&mut self, action: &AgentAction, think: &str) -> StepResult {
-        // Registrar no diário baseado no tipo de ação
-        if action.is_paytour() {
-            self.context.diary.push(DiaryEntry::PaytourQuery {
-                query_type: action.name().to_string(),
-                think: think.to_string(),
-                results_count: 0, // Atualizado após execução real
+    /// Registra o resultado de uma ferramenta no knowledge e no diário
+    fn apply_tool_result(
+        &mut self,
+        tool: String,
+        arguments: &serde_json::Value,
+        think: String,
+        result: Result<Vec<KnowledgeItem>, ToolError>,
+    ) {
+        let (success, summary) = match result {
+            Ok(items) => {
+                let summary = format!("{} item(s) de conhecimento", items.len());
+                self.emit(AgentProgress::Success(format!("🧰 {}: {}", tool, summary)));
+                for item in items {
+                    self.context.add_knowledge(item);
+                }
+                (true, summary)
+            }
+            Err(e) => {
+                log::warn!("🧰 Ferramenta {} falhou: {}", tool, e);
+                self.emit(AgentProgress::Warning(format!("🧰 {} falhou: {}", tool, e)));
+                self.context.add_knowledge(KnowledgeItem {
+                    question: format!("[Ferramenta {} Falhou] {}", tool, arguments),
+                    answer: e.to_string(),
+                    item_type: KnowledgeType::Error,
+                    references: vec![],
+                });
+                (false, e.to_string())
+            }
+        };
+
+        self.context.diary.push(DiaryEntry::ToolCall {
+            tool,
+            think,
+            success,
+            summary,
+        });
+    }
+
+    /// Executa um plano de ações independentes em paralelo
+    ///
+    /// Só a coleta (buscas, leituras e ferramentas) roda em paralelo. A
+    /// preparação (dedup de queries, reserva de URLs) e o registro dos
+    /// resultados são sequenciais, na ordem do plano, para que knowledge e
+    /// diário não dependam de qual tarefa terminou primeiro.
+    async fn execute_parallel(
+        &mut self,
+        actions: Vec<AgentAction>,
+        think: String,
+        permissions: &ActionPermissions,
+    ) -> StepResult {
+        use futures::future::join_all;
+        use uuid::Uuid;
+
+        let timer = ActionTimer::start("Parallel");
+        log::info!("⚡ Plano paralelo com {} ações", actions.len());
+        log::debug!("💭 Raciocínio: {}", think);
+
+        // Preparar sub-ações; o limite de URLs vale para o plano inteiro
+        let mut url_budget = MAX_URLS_PER_STEP;
+        let mut tasks = Vec::new();
+        for action in actions {
+            if !permissions.is_allowed(action.name()) {
+                log::warn!("⚠️ Ação {} não permitida, ignorada no plano", action.name());
+                continue;
+            }
+            match action {
+                AgentAction::Search { queries, think } => {
+                    let (queries, embeddings) = self.prepare_search(queries).await;
+                    if !queries.is_empty() {
+                        tasks.push(ParallelTask::Search {
+                            queries,
+                            embeddings,
+                            think,
+                        });
+                    }
+                }
+                AgentAction::Read { urls, think } => {
+                    // Em modo equipe, só lê URLs que nenhum outro agente reservou
+                    let urls: Vec<Url> = urls
+                        .into_iter()
+                        .filter(|u| self.context.claim_url(u))
+                        .take(url_budget)
+                        .collect();
+                    url_budget -= urls.len();
+                    if !urls.is_empty() {
+                        tasks.push(ParallelTask::Read { urls, think });
+                    }
+                }
+                AgentAction::ToolCall {
+                    tool,
+                    arguments,
+                    think,
+                } => {
+                    let allowed = self.is_tool_allowed(&tool, permissions);
+                    tasks.push(ParallelTask::Tool {
+                        tool,
+                        arguments,
+                        think,
+                        allowed,
+                    });
+                }
+                other => log::warn!("⚠️ Ação {} não paralelizável, ignorada no plano", other.name()),
+            }
+        }
+
+        if tasks.is_empty() {
+            self.emit(AgentProgress::Warning(
+                "⚡ Plano paralelo sem ações executáveis".into(),
+            ));
+            self.context.total_step += 1;
+            return StepResult::Continue;
+        }
+
+        let batch_id = Uuid::new_v4().to_string();
+        self.emit(AgentProgress::BatchStart {
+            batch_id: batch_id.clone(),
+            batch_type: "Parallel".to_string(),
+            task_count: tasks.len(),
+        });
+        let task_info: Vec<_> = tasks
+            .iter()
+            .map(|task| {
+                let (task_type, description) = task.describe();
+                (Uuid::new_v4().to_string(), task_type, description)
+            })
+            .collect();
+        for (task_id, task_type, description) in &task_info {
+            self.emit(AgentProgress::TaskUpdate {
+                task_id: task_id.clone(),
+                batch_id: batch_id.clone(),
+                task_type: task_type.to_string(),
+                description: description.clone(),
+                data_info: "Executando...".to_string(),
+                status: "running".to_string(),
+                elapsed_ms: 0,
+                thread_id: None,
+                progress: 0,
+                read_method: "parallel".to_string(),
+                bytes_processed: 0,
+                bytes_total: 0,
             });
-        } else if action.is_digisac() {
-            self.context.diary.push(DiaryEntry::DigisacAction {
-                action_type: action.name().to_string(),
-                think: think.to_string(),
-                success: true, // Atualizado após execução real
+        }
+
+        // Coletar em paralelo
+        let outcomes = join_all(tasks.into_iter().map(|task| self.fetch_parallel_task(task))).await;
+
+        // Registrar na ordem do plano
+        let mut success_count = 0;
+        let mut fail_count = 0;
+        for ((task_id, task_type, description), outcome) in task_info.into_iter().zip(outcomes) {
+            // Cancelada: o loop principal encerra no próximo ponto de verificação
+            let Some((result, elapsed_ms)) = outcome else {
+                continue;
+            };
+            let (success, data_info) = match result {
+                ParallelResult::Search {
+                    queries,
+                    embeddings,
+                    think,
+                    results,
+                } => {
+                    let success = results.iter().any(|r| r.is_ok());
+                    let urls_found: usize = results.iter().flatten().map(|r| r.urls.len()).sum();
+                    self.apply_search(queries, embeddings, results, elapsed_ms, think);
+                    (success, format!("{} URLs encontradas", urls_found))
+                }
+                ParallelResult::Read { urls, think, reads } => {
+                    let mut read_ok = 0;
+                    for (url, (method, result)) in urls.iter().zip(reads) {
+                        if self.apply_url_read(url, method, result) {
+                            read_ok += 1;
+                        }
+                    }
+                    self.timing_stats.add_read_time(elapsed_ms);
+                    self.read_count += 1;
+                    let summary = format!("{}/{} URLs lidas", read_ok, urls.len());
+                    self.context.diary.push(DiaryEntry::Read { urls, think });
+                    (read_ok > 0, summary)
+                }
+                ParallelResult::Tool {
+                    tool,
+                    arguments,
+                    think,
+                    result,
+                } => {
+                    let summary = match &result {
+                        Ok(items) => format!("{} item(s) de conhecimento", items.len()),
+                        Err(e) => format!("Erro: {}", e),
+                    };
+                    let success = result.is_ok();
+                    self.apply_tool_result(tool, &arguments, think, result);
+                    (success, summary)
+                }
+            };
+
+            if success {
+                success_count += 1;
+            } else {
+                fail_count += 1;
+            }
+            self.emit(AgentProgress::TaskUpdate {
+                task_id,
+                batch_id: batch_id.clone(),
+                task_type: task_type.to_string(),
+                description,
+                data_info,
+                status: if success { "completed" } else { "failed" }.to_string(),
+                elapsed_ms,
+                thread_id: None,
+                progress: 100,
+                read_method: "parallel".to_string(),
+                bytes_processed: 0,
+                bytes_total: 0,
             });
         }
 
+        let total_ms = timer.stop();
+        self.emit(AgentProgress::BatchEnd {
+            batch_id: batch_id.clone(),
+            total_ms,
+            success_count,
+            fail_count,
+        });
+        log::info!(
+            "⚡ Plano {} concluído em {}ms | ✅ {} ok | ❌ {} erros",
+            &batch_id[..8],
+            total_ms,
+            success_count,
+            fail_count
+        );
+        self.emit(AgentProgress::Success(format!(
+            "⚡ Plano paralelo: {} ações em {:.2}s | ✅{} ❌{}",
+            success_count + fail_count,
+            total_ms as f64 / 1000.0,
+            success_count,
+            fail_count
+        )));
+
         self.context.total_step += 1;
         StepResult::Continue
     }
 
-    /// Rotaciona para a próxima pergunta na fila
+    /// Coleta o resultado de uma sub-ação do plano paralelo
+    ///
+    /// Retorna `None` se a pesquisa foi cancelada durante a coleta.
+    async fn fetch_parallel_task(&self, task: ParallelTask) -> Option<(ParallelResult, u128)> {
+        let timer = ActionTimer::start("Parallel task");
+        let result = match task {
+            ParallelTask::Search {
+                queries,
+                embeddings,
+                think,
+            } => {
+                let results = self
+                    .interrupt_token
+                    .run_until_cancelled(self.search_client.search_batch(&queries))
+                    .await?;
+                ParallelResult::Search {
+                    queries,
+                    embeddings,
+                    think,
+                    results,
+                }
+            }
+            ParallelTask::Read { urls, think } => {
+                let reads = futures::future::join_all(urls.iter().map(|url| self.fetch_url(url)));
+                let reads = self.interrupt_token.run_until_cancelled(reads).await?;
+                ParallelResult::Read { urls, think, reads }
+            }
+            ParallelTask::Tool {
+                tool,
+                arguments,
+                think,
+                allowed,
+            } => {
+                let result = if allowed {
+                    self.interrupt_token
+                        .run_until_cancelled(self.tools.call(&tool, arguments.clone()))
+                        .await?
+                } else {
+                    Err(ToolError::NotAllowed(tool.clone()))
+                };
+                ParallelResult::Tool {
+                    tool,
+                    arguments,
+                    think,
+                    result,
+                }
+            }
+        };
+        Some((result, timer.stop()))
+    }
+
+    /// Lê uma URL (arquivo ou página web), retornando o método usado
+    async fn fetch_url(&self, url: &Url) -> (&'static str, Result<UrlContent, String>) {
+        use crate::utils::{FileReader, FileType};
+        use std::sync::atomic::AtomicU8;
+
+        if is_file_url(url) {
+            let result = FileReader::new()
+                .read_url(url)
+                .await
+                .map(|file| UrlContent {
+                    title: file
+                        .title
+                        .unwrap_or_else(|| format!("Arquivo {:?}", FileType::from_url(url))),
+                    word_count: file.word_count,
+                    text: file.text,
+                    url: url.clone(),
+                    read_time_ms: None,
+                    source: Some("file".into()),
+                })
+                .map_err(|e| e.to_string());
+            ("file", result)
+        } else {
+            let (result, method, _attempts, _bytes) = self
+                .search_client
+                .read_url_with_fallback_progress(url, Arc::new(AtomicU8::new(0)))
+                .await;
+            (method, result.map_err(|e| e.to_string()))
+        }
+    }
+
+    /// Registra o conteúdo lido de uma URL; retorna se a leitura deu certo
+    fn apply_url_read(&mut self, url: &Url, method: &str, result: Result<UrlContent, String>) -> bool {
+        match result {
+            Ok(content) => {
+                self.context.add_knowledge(KnowledgeItem {
+                    question: self.context.current_question().to_string(),
+                    answer: content.text,
+                    item_type: KnowledgeType::Url,
+                    references: vec![Reference {
+                        url: url.to_string(),
+                        title: content.title,
+                        exact_quote: None,
+                        relevance_score: None,
+                        answer_chunk: None,
+                        answer_position: None,
+                    }],
+                });
+                self.context.visited_urls.push(url.clone());
+                self.emit(AgentProgress::VisitedUrl(url.to_string()));
+                true
+            }
+            Err(e) => {
+                log::warn!("❌ Falha ao ler URL {} via {}: {}", url, method, e);
+                self.context.bad_urls.push(url.clone());
+                false
+            }
+        }
+    }
+
+    /// Escolhe a próxima pergunta da fila pela prioridade
     fn rotate_question(&mut self) -> String {
-        let idx = self.context.total_step % self.context.gap_questions.len();
-        self.context.gap_questions[idx].clone()
+        let question = self.context.gap_queue.select_next().question.clone();
+        self.emit(AgentProgress::GapTree(self.context.gap_queue.tree_rows()));
+        question
     }
 
     /// Constrói o prompt para o LLM decidir a próxima ação
-    fn build_prompt(&self, permissions: &ActionPermissions, question: &str) -> AgentPrompt {
+    fn build_prompt(
+        &self,
+        permissions: &ActionPermissions,
+        question: &str,
+        knowledge: &str,
+    ) -> AgentPrompt {
         // Listar URLs disponíveis (não visitadas)
         let available_urls: Vec<_> = self
             .context
-            .collected_urls
-            .iter()
-            .filter(|u| !self.context.is_url_visited(&u.url) && !self.context.is_url_bad(&u.url))
+            .ranked_available_urls()
+            .into_iter()
             .take(10)
             .map(|u| format!("- {} ({})", u.url, u.title))
             .collect();
@@ -914,6 +2316,16 @@ impl DeepResearchAgent {
             )
         };
 
+        // Pergunta de gap: o LLM precisa saber que ANSWER responde só a ela
+        let question = if question == self.context.original_question {
+            question.to_string()
+        } else {
+            format!(
+                "{}\n(Sub-question of: {})",
+                question, self.context.original_question
+            )
+        };
+
         AgentPrompt {
             system: self.build_system_prompt(permissions),
             user: format!(
@@ -921,7 +2333,7 @@ impl DeepResearchAgent {
                 question,
                 urls_section,
                 self.context.visited_urls.len(),
-                self.format_knowledge()
+                knowledge
             ),
             diary: self.context.diary.clone(),
         }
@@ -969,6 +2381,13 @@ Available actions:
         if permissions.coding {
             prompt.push_str("- CODING: Execute code for data processing\n");
         }
+        if let Some(section) = self
+            .tools
+            .prompt_section(permissions, &self.context)
+            .filter(|_| permissions.tools)
+        {
+            prompt.push_str(&section);
+        }
         if permissions.history {
             prompt.push_str("- HISTORY: Access previous research sessions for context\n");
             prompt.push_str("  → Use when user asks about 'what was researched before' or 'summarize previous'\n");
@@ -988,6 +2407,11 @@ Available actions:
             prompt.push_str("\n🔴 You have done many steps. Consider using ANSWER now!\n");
         }
 
+        // Resposta estruturada: o campo "answer" deve ser JSON válido no schema
+        if let Some(schema) = &self.response_schema {
+            prompt.push_str(&structured_answer_instructions(schema));
+        }
+
         // Adicionar hints de melhoria do AgentAnalyzer (se disponíveis)
         if self.context.has_improvement_hints() {
             prompt.push_str("\n## 💡 IMPROVEMENT HINTS (from previous error analysis):\n");
@@ -998,6 +2422,11 @@ Available actions:
             prompt.push_str("\nPlease take these suggestions into account in your next actions.\n");
         }
 
+        // Fatos em que as fontes discordam
+        if let Some(section) = self.context.fact_ledger.prompt_section() {
+            prompt.push_str(&section);
+        }
+
         prompt
     }
 
@@ -1010,10 +2439,71 @@ Available actions:
             .join("\n\n")
     }
 
+    /// Conhecimento para o prompt
+    ///
+    /// Inclui tudo enquanto couber na franquia da memória; depois, apenas os
+    /// trechos mais relevantes para `question` e a pergunta original.
+    async fn recall_knowledge(&mut self, question: &str) -> String {
+        if self.memory.fits(&self.context.knowledge) {
+            return self.format_knowledge();
+        }
+
+        let llm = self.llm_client.clone();
+        self.memory
+            .index(&self.context.knowledge, llm.as_ref())
+            .await;
+        let original = self.context.original_question.clone();
+        let knowledge = self
+            .memory
+            .recall(&[question, &original], llm.as_ref())
+            .await;
+
+        if let Some(response) = self.memory.compact(llm.as_ref()).await {
+            self.token_tracker.add_tokens(
+                "knowledge_memory",
+                response.prompt_tokens,
+                response.completion_tokens,
+            );
+            self.emit(AgentProgress::Info(format!(
+                "🧠 Conhecimento pouco relevante compactado ({} resumos)",
+                self.memory.summaries()
+            )));
+        }
+        knowledge
+    }
+
     /// Executa ação de busca (em paralelo)
     async fn execute_search(&mut self, queries: Vec<SerpQuery>, think: String) -> StepResult {
-        use crate::personas::PersonaOrchestrator;
         let search_timer = ActionTimer::start("Search");
+        let (unique, new_embeddings) = self.prepare_search(queries).await;
+
+        let num_queries = unique.len();
+        log::info!("🔍 Executando {} buscas em PARALELO...", num_queries);
+        self.emit(AgentProgress::Info(format!(
+            "⚡ Iniciando {} buscas paralelas",
+            num_queries
+        )));
+
+        // Executar buscas em paralelo (abortadas se a pesquisa for cancelada)
+        let Some(results) = self
+            .interrupt_token
+            .run_until_cancelled(self.search_client.search_batch(&unique))
+            .await
+        else {
+            self.timing_stats.add_search_time(search_timer.stop());
+            return StepResult::Continue;
+        };
+
+        self.apply_search(unique, new_embeddings, results, search_timer.stop(), think);
+        self.context.total_step += 1;
+        StepResult::Continue
+    }
+
+    /// Expande as queries com personas e remove duplicatas semânticas
+    ///
+    /// Retorna as queries a executar e seus embeddings.
+    async fn prepare_search(&mut self, queries: Vec<SerpQuery>) -> (Vec<SerpQuery>, Vec<Vec<f32>>) {
+        use crate::personas::PersonaOrchestrator;
 
         // Expandir queries com personas cognitivas
         let orchestrator = PersonaOrchestrator::new();
@@ -1066,15 +2556,19 @@ Available actions:
             )));
         }
 
-        let num_queries = unique.len();
-        log::info!("🔍 Executando {} buscas em PARALELO...", num_queries);
-        self.emit(AgentProgress::Info(format!(
-            "⚡ Iniciando {} buscas paralelas",
-            num_queries
-        )));
+        (unique, new_embeddings)
+    }
 
-        // Executar buscas em paralelo
-        let results = self.search_client.search_batch(&unique).await;
+    /// Registra os resultados de uma busca no contexto e no diário
+    fn apply_search(
+        &mut self,
+        unique: Vec<SerpQuery>,
+        new_embeddings: Vec<Vec<f32>>,
+        results: Vec<Result<SearchResult, SearchError>>,
+        search_time: u128,
+        think: String,
+    ) {
+        let num_queries = unique.len();
 
         // Salvar embeddings das queries executadas para futuras deduplicações
         let executed_query_texts: Vec<String> = unique.iter().map(|q| q.q.clone()).collect();
@@ -1089,9 +2583,10 @@ Available actions:
         for result in results {
             match result {
                 Ok(r) => {
-                    total_urls += r.urls.len();
-                self.context.add_urls(r.urls);
-                self.context.add_snippets(r.snippets);
+                    let urls = self.score_snippets(r.urls);
+                    total_urls += urls.len();
+                    self.context.add_urls(urls);
+                    self.context.add_snippets(r.snippets);
                     success_count += 1;
                 }
                 Err(_) => {
@@ -1100,7 +2595,6 @@ Available actions:
             }
         }
 
-        let search_time = search_timer.stop();
         self.timing_stats.add_search_time(search_time);
 
         // Incrementar contador de buscas
@@ -1143,8 +2637,44 @@ Available actions:
             urls_found: self.context.collected_urls.len(),
         });
 
-        self.context.total_step += 1;
-        StepResult::Continue
+    }
+
+    /// Aplica a credibilidade aos resultados de busca; descarta domínios bloqueados
+    fn score_snippets(&self, urls: Vec<BoostedSearchSnippet>) -> Vec<BoostedSearchSnippet> {
+        urls.into_iter()
+            .filter_map(|mut snippet| {
+                let score = self.credibility.apply(&mut snippet);
+                if score.is_blocked() {
+                    log::info!("🚫 Fonte bloqueada: {} ({})", snippet.url, score.explain());
+                    None
+                } else {
+                    Some(snippet)
+                }
+            })
+            .collect()
+    }
+
+    /// Credibilidade de cada fonte lida, da maior para a menor
+    ///
+    /// Usa os sinais da página (autor, data, citações, anúncios) além do domínio.
+    fn source_scores(&self) -> Vec<CredibilityScore> {
+        let mut scores: Vec<CredibilityScore> = Vec::new();
+        for item in self
+            .context
+            .knowledge
+            .iter()
+            .filter(|k| k.item_type == KnowledgeType::Url)
+        {
+            let Some(reference) = item.references.first() else {
+                continue;
+            };
+            if scores.iter().any(|s| s.url == reference.url) {
+                continue;
+            }
+            scores.push(self.credibility.score_page(&reference.url, &item.answer));
+        }
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores
     }
 
     /// Executa ação de leitura de URL (em paralelo)
@@ -1154,7 +2684,12 @@ Available actions:
         use uuid::Uuid;
 
         let read_timer = ActionTimer::start("Read URLs");
-        let urls_to_read: Vec<_> = urls.into_iter().take(MAX_URLS_PER_STEP).collect();
+        // Em modo equipe, só lê URLs que nenhum outro agente reservou
+        let urls_to_read: Vec<_> = urls
+            .into_iter()
+            .filter(|u| self.context.claim_url(u))
+            .take(MAX_URLS_PER_STEP)
+            .collect();
         let num_urls = urls_to_read.len();
 
         // Gerar batch ID único
@@ -1180,17 +2715,8 @@ Available actions:
         let mut web_urls = Vec::new();
 
         for url in &urls_to_read {
-            let file_type = FileType::from_url(url);
-            let is_file = matches!(
-                file_type,
-                FileType::Pdf
-                    | FileType::Json
-                    | FileType::Xml
-                    | FileType::Text
-                    | FileType::Markdown
-            );
-            if is_file {
-                file_urls.push((url.clone(), file_type));
+            if is_file_url(url) {
+                file_urls.push((url.clone(), FileType::from_url(url)));
             } else {
                 web_urls.push(url.clone());
             }
@@ -1232,7 +2758,7 @@ Available actions:
             let file_read_start = std::time::Instant::now();
             let file_futures: Vec<_> = file_urls
                 .iter()
-                .map(|(url, _)| file_reader.read_url(url))
+                .map(|(url, _)| self.interrupt_token.run_until_cancelled(file_reader.read_url(url)))
                 .collect();
 
             let file_results = join_all(file_futures).await;
@@ -1240,6 +2766,10 @@ Available actions:
             let avg_file_time = file_read_time / file_urls.len().max(1) as u128;
 
             for (result, (url, file_type)) in file_results.into_iter().zip(file_urls.iter()) {
+                // Leitura abortada por cancelamento
+                let Some(result) = result else {
+                    continue;
+                };
                 let task_id = file_task_ids.get(url).cloned().unwrap_or_default();
                 match result {
                     Ok(file_content) => {
@@ -1342,7 +2872,11 @@ Available actions:
                     web_urls.len()
                 )));
 
-                let comparative_results = self.search_client.read_urls_comparative_batch(&web_urls).await;
+                let comparative_results = self
+                    .interrupt_token
+                    .run_until_cancelled(self.search_client.read_urls_comparative_batch(&web_urls))
+                    .await
+                    .unwrap_or_default();
 
                 for result in comparative_results {
                     let task_id = url_task_ids.get(&result.url).cloned().unwrap_or_default();
@@ -1554,14 +3088,19 @@ Available actions:
 
                 // Executar leituras em paralelo com progresso
                 let search_client = self.search_client.clone();
+                let interrupt_token = self.interrupt_token.clone();
                 let futures: Vec<_> = web_urls
                     .iter()
                     .map(|url| {
                         let url = url.clone();
                         let progress = progress_map.get(&url).cloned().unwrap_or_else(|| Arc::new(AtomicU8::new(0)));
                         let client = search_client.clone();
+                        let interrupt_token = interrupt_token.clone();
                         async move {
-                            let result = client.read_url_with_fallback_progress(&url, progress).await;
+                            // Leituras concluídas são mantidas; as pendentes são abortadas
+                            let result = interrupt_token
+                                .run_until_cancelled(client.read_url_with_fallback_progress(&url, progress))
+                                .await;
                             (url, result)
                         }
                     })
@@ -1569,10 +3108,21 @@ Available actions:
 
                 let results = futures::future::join_all(futures).await;
 
-                // Parar monitor
+                // Parar monitor (leituras canceladas nunca chegam a 100%)
                 drop(progress_tx);
+                if self.interrupt_token.is_cancelled() {
+                    monitor_handle.abort();
+                }
                 let _ = monitor_handle.await;
 
+                let cancelled_reads = results.iter().filter(|(_, r)| r.is_none()).count();
+                if cancelled_reads > 0 {
+                    log::warn!("⏹️ {} leituras abortadas por cancelamento", cancelled_reads);
+                }
+                let results = results
+                    .into_iter()
+                    .filter_map(|(url, result)| result.map(|r| (url, r)));
+
                 let avg_time_per_url = read_start.elapsed().as_millis() / web_urls.len().max(1) as u128;
 
                 // Processar resultados
@@ -1685,8 +3235,65 @@ Available actions:
         )));
         self.emit_persona_stats(true);
 
-        self.context.diary.push(DiaryEntry::Read {
-            urls: urls_to_read.clone(),
+        self.context.diary.push(DiaryEntry::Read {
+            urls: urls_to_read.clone(),
+            think,
+        });
+
+        self.context.total_step += 1;
+        StepResult::Continue
+    }
+
+    /// Executa ação de reflexão
+    async fn execute_reflect(&mut self, gap_questions: Vec<String>, think: String) -> StepResult {
+        log::info!("🤔 Refletindo... {} novas perguntas", gap_questions.len());
+
+        // Deduplicar novas perguntas (texto)
+        let unique_questions: Vec<String> = self
+            .dedup_questions(gap_questions)
+            .await
+            .into_iter()
+            .take(MAX_REFLECT_PER_STEP)
+            .collect();
+
+        // Embeddings para descartar perguntas já respondidas (sem embeddings, só texto)
+        let embeddings: Vec<Option<Vec<f32>>> =
+            match self.llm_client.embed_batch(&unique_questions).await {
+                Ok(results) if results.len() == unique_questions.len() => {
+                    results.into_iter().map(|r| Some(r.vector)).collect()
+                }
+                _ => vec![None; unique_questions.len()],
+            };
+
+        // Novas perguntas ficam sob a pergunta que estava sendo trabalhada
+        let parent = self
+            .context
+            .gap_queue
+            .current()
+            .map(|q| q.id)
+            .unwrap_or(ROOT_GAP_ID);
+        let mut skipped = 0;
+        for (rank, (q, embedding)) in unique_questions.into_iter().zip(embeddings).enumerate() {
+            if embedding.as_deref().is_some_and(|e| {
+                self.context
+                    .gap_queue
+                    .is_answered_like(e, gap_queue::GAP_DEDUP_THRESHOLD)
+            }) {
+                skipped += 1;
+                continue;
+            }
+            let step = self.context.total_step;
+            if self.context.gap_queue.add(q, parent, rank, step, embedding).is_none() {
+                skipped += 1;
+            }
+        }
+        if skipped > 0 {
+            log::info!("🎯 {} perguntas de gap já existentes ou respondidas descartadas", skipped);
+        }
+        self.emit(AgentProgress::GapTree(self.context.gap_queue.tree_rows()));
+
+        self.context.diary.push(DiaryEntry::Reflect {
+            questions: self.context.gap_queue.open_questions(),
             think,
         });
 
@@ -1694,24 +3301,48 @@ Available actions:
         StepResult::Continue
     }
 
-    /// Executa ação de reflexão
-    async fn execute_reflect(&mut self, gap_questions: Vec<String>, think: String) -> StepResult {
-        log::info!("🤔 Refletindo... {} novas perguntas", gap_questions.len());
-
-        // Deduplicar novas perguntas
-        let unique_questions = self.dedup_questions(gap_questions).await;
+    /// Registra a resposta a uma pergunta de gap como conhecimento
+    ///
+    /// Respostas intermediárias não passam pela avaliação: servem de contexto
+    /// para a resposta final, avaliada contra a pergunta original.
+    fn execute_gap_answer(
+        &mut self,
+        gap_id: usize,
+        answer: String,
+        references: Vec<Reference>,
+        think: String,
+    ) -> StepResult {
+        let Some(question) = self
+            .context
+            .gap_queue
+            .get(gap_id)
+            .map(|q| q.question.clone())
+        else {
+            return StepResult::Continue;
+        };
 
-        // Adicionar ao gap_questions (máximo MAX_REFLECT_PER_STEP)
-        for q in unique_questions.into_iter().take(MAX_REFLECT_PER_STEP) {
-            if !self.context.gap_questions.contains(&q) {
-                self.context.gap_questions.push(q);
-            }
+        log::info!("🧩 Pergunta de gap respondida: {}", question);
+        log::debug!("💭 Raciocínio: {}", think);
+        self.context.add_knowledge(KnowledgeItem {
+            question: question.clone(),
+            answer: answer.clone(),
+            item_type: KnowledgeType::Qa,
+            references,
+        });
+        if let Some(index) = self
+            .context
+            .knowledge
+            .iter()
+            .position(|k| k.question == question && k.answer == answer)
+        {
+            self.context.gap_queue.mark_answered(gap_id, index);
         }
 
-        self.context.diary.push(DiaryEntry::Reflect {
-            questions: self.context.gap_questions.clone(),
-            think,
-        });
+        self.emit(AgentProgress::Success(format!(
+            "🧩 Pergunta de gap respondida: {}",
+            question
+        )));
+        self.emit(AgentProgress::GapTree(self.context.gap_queue.tree_rows()));
 
         self.context.total_step += 1;
         StepResult::Continue
@@ -1728,8 +3359,17 @@ Available actions:
 
         log::info!("✍️  Avaliando resposta...");
 
-        // Resposta imediata no step 1 = pergunta trivial
-        if self.context.total_step == 1 && self.context.allow_direct_answer {
+        // Resposta imediata no step 1 = pergunta trivial (se já válida no schema)
+        let trivial_answer = match &self.response_schema {
+            Some(schema) => schema
+                .parse_answer(&answer)
+                .ok()
+                .map(|value| serde_json::to_string_pretty(&value).unwrap_or_default()),
+            None => Some(answer.clone()),
+        };
+        if let Some(answer) = trivial_answer.filter(|_| {
+            self.context.total_step == 1 && self.context.allow_direct_answer
+        }) {
             self.answer_count += 1;
             self.emit(AgentProgress::Success("✅ Resposta trivial gerada".into()));
             self.emit_persona_stats(false);
@@ -1745,10 +3385,22 @@ Available actions:
         }
 
         // Obter tipos de avaliação necessários
-        let pipeline = EvaluationPipeline::new(self.llm_client.clone());
-        let eval_types = pipeline
-            .determine_required_evaluations(&self.context.original_question, &*self.llm_client)
-            .await;
+        let pipeline = match &self.response_schema {
+            Some(schema) => {
+                EvaluationPipeline::new(self.llm_client.clone()).with_response_schema(schema.clone())
+            }
+            None => EvaluationPipeline::new(self.llm_client.clone()),
+        };
+        let Some(eval_types) = self
+            .interrupt_token
+            .run_until_cancelled(
+                pipeline.determine_required_evaluations(&self.context.original_question, &*self.llm_client),
+            )
+            .await
+        else {
+            self.remember_candidate(0, &answer, &references);
+            return StepResult::Continue;
+        };
 
         // 🔍 Emitir início de validação fast-fail
         let eval_type_names: Vec<String> = eval_types.iter().map(|t| t.as_str().to_string()).collect();
@@ -1763,13 +3415,21 @@ Available actions:
 
         // Executar avaliações
         let eval_context = self.build_evaluation_context();
-        let result = pipeline
-            .evaluate_sequential(
+        let Some(result) = self
+            .interrupt_token
+            .run_until_cancelled(pipeline.evaluate_sequential(
                 &self.context.original_question,
                 &answer,
                 &eval_context,
                 &eval_types,
-            )
+            ))
+            .await
+        else {
+            self.remember_candidate(0, &answer, &references);
+            return StepResult::Continue;
+        };
+        self.hooks
+            .on_answer_evaluated(&self.context, &answer, &result)
             .await;
 
         // 📊 Emitir resultados de cada validação
@@ -1811,7 +3471,14 @@ Available actions:
             passed_evals: passed_count,
         });
 
-        if result.overall_passed {
+        // 🔎 Números errados passam nas avaliações: verificar afirmação por afirmação
+        let contradiction_hint = if result.overall_passed && self.verify_claims {
+            self.verify_answer(&answer).await
+        } else {
+            None
+        };
+
+        if result.overall_passed && contradiction_hint.is_none() {
             // Incrementar contador de respostas
             self.answer_count += 1;
             // Resetar contador de falhas consecutivas
@@ -1819,6 +3486,12 @@ Available actions:
 
             log::info!("✅ Resposta aprovada na avaliação!");
 
+            // JSON validado (e possivelmente reparado) pelo gate de schema
+            let answer = match &result.structured_answer {
+                Some(value) => serde_json::to_string_pretty(value).unwrap_or(answer),
+                None => answer,
+            };
+
             // 🔗 Validar referências antes de finalizar
             let validated_refs = self.validate_references(references).await;
 
@@ -1827,6 +3500,13 @@ Available actions:
                 validated_refs.len()
             );
 
+            // ✨ Polimento opcional da resposta aprovada (nunca em JSON)
+            let (answer, validated_refs) = if self.finalize_answers && self.response_schema.is_none() {
+                self.finalize_accepted_answer(answer, validated_refs).await
+            } else {
+                (answer, validated_refs)
+            };
+
             // Emitir sucesso e atualizar persona (não ativa pois vai finalizar)
             self.emit(AgentProgress::Success(format!(
                 "✅ Resposta #{} aprovada! ({} refs validadas)",
@@ -1842,17 +3522,26 @@ Available actions:
             })
         } else {
             log::info!("❌ Resposta reprovada, continuando pesquisa...");
+            self.claim_checks.clear();
+
+            // Guardar como melhor resposta disponível para o prazo rígido
+            self.remember_candidate(passed_count, &answer, &references);
 
             // Incrementar contador de falhas consecutivas
             self.consecutive_failures += 1;
 
             // Adicionar falha como conhecimento
-            let failed_type = result.failed_at.unwrap_or(EvaluationType::Definitive);
-            let reasoning = result
-                .results
-                .last()
-                .map(|r| r.reasoning.clone())
-                .unwrap_or_default();
+            let (failed_type, reasoning) = match contradiction_hint {
+                Some(hint) => (EvaluationType::Verify, hint),
+                None => (
+                    result.failed_at.unwrap_or(EvaluationType::Definitive),
+                    result
+                        .results
+                        .last()
+                        .map(|r| r.reasoning.clone())
+                        .unwrap_or_default(),
+                ),
+            };
 
             self.context.knowledge.push(KnowledgeItem {
                 question: self.context.original_question.clone(),
@@ -1928,6 +3617,122 @@ Available actions:
         }
     }
 
+    /// Verifica a resposta afirmação por afirmação
+    ///
+    /// Cada afirmação recebe os trechos mais relevantes do conteúdo lido;
+    /// as que não têm nenhum ganham uma busca direcionada. Guarda os
+    /// vereditos em `claim_checks` e retorna a dica de melhoria se alguma
+    /// afirmação for contradita.
+    async fn verify_answer(&mut self, answer: &str) -> Option<String> {
+        use crate::evaluation::EvaluationType;
+        use crate::tools::claim_verifier::{
+            improvement_hint, retrieve_passages, split_passages, ClaimEvidence, ClaimVerdict,
+            Passage, PASSAGES_PER_CLAIM,
+        };
+        /// Máximo de buscas direcionadas por verificação
+        const MAX_TARGETED_SEARCHES: usize = 3;
+
+        let verify_timer = ActionTimer::start("Verify");
+        let verifier = ClaimVerifier::new(self.llm_client.clone());
+        let claims = verifier
+            .extract_claims(&self.context.original_question, answer, &mut self.token_tracker)
+            .await;
+        if claims.is_empty() {
+            self.claim_checks.clear();
+            return None;
+        }
+        self.emit(AgentProgress::Info(format!(
+            "🔎 Verificando {} afirmações contra as fontes...",
+            claims.len()
+        )));
+
+        let passages: Vec<Passage> = self
+            .context
+            .knowledge
+            .iter()
+            .filter(|k| k.item_type == KnowledgeType::Url)
+            .flat_map(|k| {
+                let source = k.references.first().map(|r| r.url.as_str()).unwrap_or_default();
+                split_passages(source, &k.answer)
+            })
+            .collect();
+        let mut evidence: Vec<ClaimEvidence> = claims
+            .into_iter()
+            .map(|claim| ClaimEvidence {
+                passages: retrieve_passages(&claim, &passages, PASSAGES_PER_CLAIM),
+                claim,
+            })
+            .collect();
+
+        // Afirmações sem trecho no conteúdo lido: buscas direcionadas
+        let missing: Vec<usize> = evidence
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.passages.is_empty())
+            .map(|(i, _)| i)
+            .take(MAX_TARGETED_SEARCHES)
+            .collect();
+        if !missing.is_empty() {
+            let queries: Vec<SerpQuery> = missing
+                .iter()
+                .map(|&i| SerpQuery {
+                    q: evidence[i].claim.clone(),
+                    ..Default::default()
+                })
+                .collect();
+            let results = self
+                .interrupt_token
+                .run_until_cancelled(self.search_client.search_batch(&queries))
+                .await
+                .unwrap_or_default();
+            for (&i, result) in missing.iter().zip(results) {
+                let Ok(result) = result else {
+                    continue;
+                };
+                let snippets: Vec<Passage> = result
+                    .urls
+                    .iter()
+                    .map(|u| Passage {
+                        source: u.url.clone(),
+                        text: format!("{}\n{}", u.title, u.description),
+                    })
+                    .collect();
+                evidence[i].passages =
+                    retrieve_passages(&evidence[i].claim, &snippets, PASSAGES_PER_CLAIM);
+                self.context.add_urls(result.urls);
+            }
+        }
+
+        let checks = verifier.judge(&evidence, &mut self.token_tracker).await;
+        let count = |verdict: ClaimVerdict| checks.iter().filter(|c| c.verdict == verdict).count();
+        let (supported, contradicted, unverified) = (
+            count(ClaimVerdict::Supported),
+            count(ClaimVerdict::Contradicted),
+            count(ClaimVerdict::Unverified),
+        );
+        let summary = format!(
+            "{} suportadas, {} contraditas, {} não verificadas",
+            supported, contradicted, unverified
+        );
+        log::info!("🔎 Verificação: {}", summary);
+        self.emit(AgentProgress::ValidationStep {
+            eval_type: EvaluationType::Verify.as_str().to_string(),
+            passed: contradicted == 0,
+            confidence: supported as f32 / checks.len() as f32,
+            reasoning: summary.clone(),
+            duration_ms: verify_timer.stop(),
+        });
+        if contradicted > 0 {
+            self.emit(AgentProgress::Warning(format!("❌ verify FALHOU: {}", summary)));
+        } else {
+            self.emit(AgentProgress::Success(format!("✅ verify passou: {}", summary)));
+        }
+
+        let hint = improvement_hint(&checks);
+        self.claim_checks = checks;
+        hint
+    }
+
     /// Executa código em sandbox seguro usando Boa Engine
     ///
     /// O sandbox permite ao agente:
@@ -2284,6 +4089,17 @@ Available actions:
 
         let was_spontaneous = response.question_id.is_none();
 
+        // Resposta a um pedido de aprovação libera ou descarta a ação
+        let answers_approval = matches!(
+            (&self.pending_approval, &response.question_id),
+            (Some((pending, _)), Some(qid)) if pending == qid
+        );
+        if answers_approval {
+            if let Some((_, action)) = self.pending_approval.take() {
+                self.resolve_approval(action, &response);
+            }
+        }
+
         // Emitir evento
         self.emit(AgentProgress::UserResponseReceived {
             question_id: response.question_id.clone(),
@@ -2337,6 +4153,35 @@ Available actions:
         StepResult::Continue
     }
 
+    /// Aplica a resposta do usuário a uma ação pendente de aprovação
+    fn resolve_approval(&mut self, action: AgentAction, response: &UserResponse) {
+        let approved = response.is_affirmative() || response.selected_option == Some(0);
+        if approved {
+            log::info!("🛂 Ação {} aprovada pelo usuário", action.name());
+            self.emit(AgentProgress::Success(format!(
+                "🛂 Ação {} aprovada",
+                action.name()
+            )));
+            self.approved_action = Some(action);
+        } else {
+            log::info!("🛂 Ação {} negada pelo usuário", action.name());
+            self.emit(AgentProgress::Warning(format!(
+                "🛂 Ação {} negada",
+                action.name()
+            )));
+            self.context.add_knowledge(KnowledgeItem {
+                question: format!("[Ação Negada pelo Usuário] {}", action.name()),
+                answer: format!(
+                    "The user did not approve this {} action ({}). Choose a different action.",
+                    action.name(),
+                    approval_targets(&action).join(", ")
+                ),
+                item_type: KnowledgeType::Error,
+                references: vec![],
+            });
+        }
+    }
+
     /// Verifica e processa mensagens pendentes do usuário
     ///
     /// Deve ser chamado no início de cada step para processar
@@ -2367,35 +4212,262 @@ Available actions:
 
     /// Força uma resposta em Beast Mode
     async fn force_answer(&mut self) -> Result<AnswerResult, AgentError> {
+        let mut system = String::from(
+            "You MUST provide an answer now. No more searching or reflecting. \
+             Be pragmatic and use what you know.",
+        );
+        if let Some(schema) = &self.response_schema {
+            system.push_str(&structured_answer_instructions(schema));
+            system.push_str("Respond with the JSON value only.\n");
+        }
+
+        let original = self.context.original_question.clone();
+        let knowledge = self.recall_knowledge(&original).await;
         let prompt = AgentPrompt {
-            system: "You MUST provide an answer now. No more searching or reflecting. \
-                     Be pragmatic and use what you know."
-                .into(),
+            system,
             user: format!(
                 "Question: {}\n\nKnowledge:\n{}\n\nProvide your best answer.",
-                self.context.original_question,
-                self.format_knowledge()
+                original, knowledge
             ),
             diary: self.context.diary.clone(),
         };
 
-        let response = self
-            .llm_client
-            .generate_answer(&prompt, 0.7) // Higher temperature
-            .await
-            .map_err(|e| AgentError::LlmError(e.to_string()))?;
+        // Candidatas em paralelo com temperaturas variadas
+        let temperatures = voting::candidate_temperatures(self.beast_mode_candidates);
+        let llm = self.llm_client.clone();
+        let responses = futures::future::join_all(
+            temperatures
+                .iter()
+                .map(|&temperature| llm.generate_answer(&prompt, temperature)),
+        )
+        .await;
+
+        let mut candidates = Vec::with_capacity(responses.len());
+        let mut last_error = None;
+        for (temperature, response) in temperatures.into_iter().zip(responses) {
+            match response {
+                Ok(response) => {
+                    self.token_tracker.add_tokens(
+                        "beast_mode",
+                        response.prompt_tokens,
+                        response.completion_tokens,
+                    );
+                    candidates.push((temperature, response));
+                }
+                Err(e) => last_error = Some(e.to_string()),
+            }
+        }
+        if candidates.is_empty() {
+            return Err(AgentError::LlmError(last_error.unwrap_or_default()));
+        }
+
+        // O prazo rígido pode chegar durante a votação: a candidata de menor
+        // temperatura já fica guardada para `finish_at_deadline`
+        let (_, first) = &candidates[0];
+        self.remember_candidate(0, &first.answer, &first.references);
+
+        let (answer, references) = if self.beast_mode_candidates == 1 {
+            let (_, response) = candidates.remove(0);
+            (response.answer, response.references)
+        } else {
+            self.vote_candidates(candidates).await
+        };
+
+        // Beast Mode também passa pelo gate de schema
+        let answer = match &self.response_schema {
+            Some(schema) => {
+                let pipeline = crate::evaluation::EvaluationPipeline::new(self.llm_client.clone());
+                let (value, _) = pipeline
+                    .enforce_schema(schema, &self.context.original_question, &answer)
+                    .await
+                    .map_err(|failure| AgentError::LlmError(failure.reasoning))?;
+                serde_json::to_string_pretty(&value).unwrap_or(answer)
+            }
+            None => answer,
+        };
 
         Ok(AnswerResult {
-            answer: response.answer,
-            references: response.references,
+            answer,
+            references,
             trivial: false,
         })
     }
 
+    /// Avalia as candidatas do Beast Mode e escolhe (ou mescla) a resposta
+    ///
+    /// A votação fica em `beast_mode_vote` para o `ResearchResult`.
+    async fn vote_candidates(
+        &mut self,
+        candidates: Vec<(f32, crate::llm::LlmResponse)>,
+    ) -> (String, Vec<Reference>) {
+        use crate::evaluation::EvaluationPipeline;
+        use voting::{agreeing_with, agreement_scores, candidate_score, pick_winner, AGREEMENT_THRESHOLD};
+
+        let question = self.context.original_question.clone();
+        self.emit(AgentProgress::Info(format!(
+            "🗳️ Beast Mode: avaliando {} candidatas",
+            candidates.len()
+        )));
+
+        // Avaliações exigidas, executadas em paralelo para cada candidata
+        let pipeline = EvaluationPipeline::new(self.llm_client.clone());
+        let eval_types = pipeline
+            .determine_required_evaluations(&question, &*self.llm_client)
+            .await;
+        let eval_context = self.build_evaluation_context();
+        let evaluations = futures::future::join_all(candidates.iter().map(|(_, response)| {
+            pipeline.evaluate_sequential(&question, &response.answer, &eval_context, &eval_types)
+        }))
+        .await;
+        for ((_, response), evaluation) in candidates.iter().zip(&evaluations) {
+            self.hooks
+                .on_answer_evaluated(&self.context, &response.answer, evaluation)
+                .await;
+        }
+
+        // Concordância entre candidatas via embeddings
+        let texts: Vec<String> = candidates.iter().map(|(_, r)| r.answer.clone()).collect();
+        let embeddings: Vec<Vec<f32>> = match self.llm_client.embed_batch(&texts).await {
+            Ok(results) if results.len() == texts.len() => {
+                results.into_iter().map(|e| e.vector).collect()
+            }
+            _ => vec![],
+        };
+        let agreement = if embeddings.is_empty() {
+            vec![0.0; texts.len()]
+        } else {
+            agreement_scores(&embeddings)
+        };
+
+        let mut votes: Vec<CandidateVote> = candidates
+            .iter()
+            .zip(&evaluations)
+            .zip(&agreement)
+            .map(|(((temperature, response), evaluation), &agreement)| {
+                let passed_evals = evaluation.results.iter().filter(|r| r.passed).count();
+                let confidence = if evaluation.results.is_empty() {
+                    0.0
+                } else {
+                    evaluation.results.iter().map(|r| r.confidence).sum::<f32>()
+                        / evaluation.results.len() as f32
+                };
+                CandidateVote {
+                    temperature: *temperature,
+                    passed_evals,
+                    required_evals: eval_types.len(),
+                    confidence,
+                    agreement,
+                    score: candidate_score(passed_evals, eval_types.len(), agreement),
+                    chars: response.answer.chars().count(),
+                    selected: false,
+                }
+            })
+            .collect();
+        let winner = pick_winner(&votes).unwrap_or(0);
+
+        // Vencedora reprovada: mescla as candidatas que concordam com ela
+        let group = if votes.len() > 1
+            && !votes[winner].passed_all()
+            && self.response_schema.is_none()
+            && !embeddings.is_empty()
+        {
+            agreeing_with(&embeddings, winner, AGREEMENT_THRESHOLD)
+        } else {
+            vec![winner]
+        };
+
+        let mut merged = None;
+        if group.len() > 1 {
+            let answers: Vec<String> = group.iter().map(|&i| texts[i].clone()).collect();
+            let reducer = ResponseReducer::new(self.llm_client.clone());
+            match reducer.reduce_answers(&answers, &mut self.token_tracker).await {
+                Ok(answer) => {
+                    let mut references: Vec<Reference> = Vec::new();
+                    for &i in &group {
+                        for reference in &candidates[i].1.references {
+                            if !references.iter().any(|r| r.url == reference.url) {
+                                references.push(reference.clone());
+                            }
+                        }
+                    }
+                    merged = Some((answer, references));
+                }
+                Err(e) => log::warn!("⚠️ Beast Mode: falha ao mesclar candidatas ({})", e),
+            }
+        }
+
+        let (strategy, selected, answer, references) = match merged {
+            Some((answer, references)) => (VoteStrategy::Merged, group, answer, references),
+            None => {
+                let strategy = if votes.len() == 1 {
+                    VoteStrategy::Single
+                } else {
+                    VoteStrategy::Best
+                };
+                let (_, response) = &candidates[winner];
+                (
+                    strategy,
+                    vec![winner],
+                    response.answer.clone(),
+                    response.references.clone(),
+                )
+            }
+        };
+        for &i in &selected {
+            votes[i].selected = true;
+        }
+
+        log::info!(
+            "🗳️ Beast Mode: {:?} (vencedora #{} com {}/{} avaliações, concordância {:.0}%)",
+            strategy,
+            winner + 1,
+            votes[winner].passed_evals,
+            votes[winner].required_evals,
+            votes[winner].agreement * 100.0
+        );
+        self.emit(AgentProgress::Success(format!(
+            "🗳️ Beast Mode: {} de {} candidatas na resposta final",
+            selected.len(),
+            votes.len()
+        )));
+
+        self.beast_mode_vote = Some(BeastModeVote {
+            candidates: votes,
+            strategy,
+            winner,
+        });
+        (answer, references)
+    }
+
     /// Constrói o resultado final
+    /// Registra o fim da execução no transcript
+    fn record_finished(&self) {
+        let (state, answer, references, error) = match &self.state {
+            AgentState::Completed {
+                answer, references, ..
+            } => (
+                "completed",
+                Some(answer.clone()),
+                references.iter().map(|r| r.url.clone()).collect(),
+                None,
+            ),
+            AgentState::Failed { reason, .. } => ("failed", None, vec![], Some(reason.clone())),
+            AgentState::Cancelled { .. } => ("cancelled", None, vec![], None),
+            _ => return,
+        };
+        self.record(TranscriptEvent::RunFinished {
+            state: state.to_string(),
+            answer,
+            references,
+            error,
+            tokens_used: self.token_tracker.total_tokens(),
+        });
+    }
+
     fn build_result(self) -> ResearchResult {
         // Usar tokens do tracker (rastreados durante execução)
         let token_usage = self.token_tracker.get_total_usage();
+        let cost = self.token_tracker.cost();
 
         log::info!(
             "📊 Token usage final: {} prompt + {} completion = {} total ({:.1}% do budget)",
@@ -2404,6 +4476,7 @@ Available actions:
             token_usage.total_tokens,
             self.token_tracker.budget_used_percentage() * 100.0
         );
+        log::info!("💰 Custo final: {}", cost);
 
         // Calcular tempos
         let total_time_ms = self.start_time.elapsed().as_millis();
@@ -2411,6 +4484,21 @@ Available actions:
         let read_time_ms: u128 = self.timing_stats.read_times.iter().sum();
         let llm_time_ms: u128 = self.timing_stats.llm_times.iter().sum();
 
+        let knowledge: Vec<KnowledgeItem> = self
+            .context
+            .knowledge
+            .iter()
+            .filter(|k| k.item_type != KnowledgeType::ChatHistory)
+            .cloned()
+            .collect();
+        let source_credibility = self.source_scores();
+
+        // Resposta aprovada alimenta o histórico (compartilhado com o servidor)
+        if let AgentState::Completed { references, .. } = &self.state {
+            let cited: Vec<String> = references.iter().map(|r| r.url.clone()).collect();
+            self.credibility.record_answer(&self.context.visited_urls, &cited);
+        }
+
         match self.state {
             AgentState::Completed {
                 answer,
@@ -2418,12 +4506,26 @@ Available actions:
                 trivial,
             } => ResearchResult {
                 success: true,
+                structured: self
+                    .response_schema
+                    .as_ref()
+                    .and_then(|schema| schema.parse_answer(&answer).ok())
+                    .map(|value| StructuredAnswer::new(value, &references)),
+                report: None,
+                beast_mode_vote: self.beast_mode_vote,
+                claim_checks: self.claim_checks,
+                disputed_facts: self.context.fact_ledger.conflicts,
+                source_credibility,
                 answer: Some(answer),
                 references,
                 trivial,
                 token_usage,
+                cost,
+                knowledge,
                 visited_urls: self.context.visited_urls,
                 error: None,
+                cancelled: false,
+                partial_knowledge: vec![],
                 total_time_ms,
                 search_time_ms,
                 read_time_ms,
@@ -2431,15 +4533,48 @@ Available actions:
             },
             AgentState::Failed {
                 reason,
-                partial_knowledge: _partial_knowledge,
+                partial_knowledge,
             } => ResearchResult {
                 success: false,
                 answer: None,
                 references: vec![],
                 trivial: false,
                 token_usage,
+                cost,
+                structured: None,
+                report: None,
+                beast_mode_vote: None,
+                claim_checks: vec![],
+                disputed_facts: self.context.fact_ledger.conflicts,
+                source_credibility,
+                knowledge,
                 visited_urls: self.context.visited_urls,
                 error: Some(reason),
+                cancelled: false,
+                partial_knowledge,
+                total_time_ms,
+                search_time_ms,
+                read_time_ms,
+                llm_time_ms,
+            },
+            AgentState::Cancelled { partial_knowledge } => ResearchResult {
+                success: false,
+                answer: None,
+                references: vec![],
+                trivial: false,
+                token_usage,
+                cost,
+                structured: None,
+                report: None,
+                beast_mode_vote: None,
+                claim_checks: vec![],
+                disputed_facts: self.context.fact_ledger.conflicts,
+                source_credibility,
+                knowledge,
+                visited_urls: self.context.visited_urls,
+                error: Some("Pesquisa cancelada".into()),
+                cancelled: true,
+                partial_knowledge,
                 total_time_ms,
                 search_time_ms,
                 read_time_ms,
@@ -2451,6 +4586,99 @@ Available actions:
 
     // Métodos auxiliares...
 
+    /// Fração do budget de tempo já consumida (0.0 sem prazo)
+    fn time_budget_used_percentage(&self) -> f64 {
+        match self.time_budget {
+            Some(budget) if !budget.is_zero() => {
+                self.start_time.elapsed().as_secs_f64() / budget.as_secs_f64()
+            }
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Verifica se o prazo atingiu a fração que dispara o Beast Mode
+    fn time_beast_mode_reached(&self) -> bool {
+        self.time_budget.is_some()
+            && self.time_budget_used_percentage() >= self.time_beast_mode_fraction
+    }
+
+    /// Guarda uma resposta não aprovada como candidata para o prazo rígido
+    ///
+    /// Mantém a que passou em mais avaliações; em empate, a mais recente.
+    fn remember_candidate(&mut self, passed_evals: usize, answer: &str, references: &[Reference]) {
+        let is_better = self
+            .best_candidate
+            .as_ref()
+            .is_none_or(|(best_passed, _)| passed_evals >= *best_passed);
+
+        if self.time_budget.is_some() && is_better {
+            self.best_candidate = Some((
+                passed_evals,
+                AnswerResult {
+                    answer: answer.to_string(),
+                    references: references.to_vec(),
+                    trivial: false,
+                },
+            ));
+        }
+    }
+
+    /// Prazo rígido atingido: usa a melhor resposta reprovada, se houver
+    fn finish_at_deadline(&mut self) {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        // Com JSON Schema, só vale um candidato que já valida
+        let schema = self.response_schema.as_ref();
+        let best_candidate = self.best_candidate.take().filter(|(_, best)| {
+            schema.is_none_or(|schema| schema.parse_answer(&best.answer).is_ok())
+        });
+        match best_candidate {
+            Some((passed_evals, best)) => {
+                log::warn!(
+                    "⏱️ Prazo esgotado ({:.1}s) - retornando melhor resposta ({} avaliações aprovadas)",
+                    elapsed,
+                    passed_evals
+                );
+                self.emit(AgentProgress::Warning(format!(
+                    "⏱️ Prazo esgotado ({:.1}s) - usando melhor resposta disponível",
+                    elapsed
+                )));
+                self.state = AgentState::Completed {
+                    answer: best.answer,
+                    references: best.references,
+                    trivial: best.trivial,
+                };
+            }
+            None => {
+                log::warn!("⏱️ Prazo esgotado ({:.1}s) sem resposta", elapsed);
+                self.emit(AgentProgress::Warning(format!(
+                    "⏱️ Prazo esgotado ({:.1}s) sem resposta",
+                    elapsed
+                )));
+                self.state = AgentState::Failed {
+                    reason: "Time budget exhausted".into(),
+                    partial_knowledge: self.context.knowledge.clone(),
+                };
+            }
+        }
+    }
+
+    /// Registra embeddings e créditos de busca consumidos desde a última leitura
+    ///
+    /// Esses contadores vivem nos clientes (podem ser chamados de vários
+    /// lugares), então o tracker recebe apenas a diferença.
+    fn sync_external_usage(&mut self) {
+        let embedding_tokens = self.llm_client.get_embedding_tokens();
+        self.token_tracker
+            .track_embedding(embedding_tokens.saturating_sub(self.embedding_tokens_seen));
+        self.embedding_tokens_seen = embedding_tokens;
+
+        let credits = self.search_client.get_credits();
+        self.token_tracker
+            .track_search_credits(credits.saturating_sub(self.search_credits_seen));
+        self.search_credits_seen = credits;
+    }
+
     /// Atualiza o budget_used no estado baseado no token_tracker
     fn update_budget_used(&mut self) {
         if let AgentState::Processing { budget_used, .. } = &mut self.state {
@@ -2694,6 +4922,12 @@ Available actions:
             80,   // min_chunk_length
             10,   // max_references
             0.65, // min_relevance_score (um pouco mais permissivo)
+        )
+        .with_source_weights(
+            self.source_scores()
+                .into_iter()
+                .map(|s| (s.url, s.score))
+                .collect(),
         );
 
         let builder = ReferenceBuilder::new(self.llm_client.clone(), config);
@@ -2760,6 +4994,79 @@ Available actions:
         }
     }
 
+    /// Polimento pós-aprovação com `ResponseFinalizer`
+    ///
+    /// Mantém a resposta aprovada se o polimento falhar ou perder algum
+    /// marcador `[^N]`; caso contrário recalcula as posições das referências.
+    async fn finalize_accepted_answer(
+        &mut self,
+        answer: String,
+        mut references: Vec<Reference>,
+    ) -> (String, Vec<Reference>) {
+        use crate::tools::finalizer::{preserves_citations, realign_references};
+        use crate::tools::ResponseFinalizer;
+
+        let original_chars = answer.chars().count();
+        self.emit(AgentProgress::FinalizeStart {
+            chars: original_chars,
+        });
+        self.emit(AgentProgress::Action("Polindo resposta...".into()));
+
+        let tokens_before = self.token_tracker.total_tokens();
+        let timer = ActionTimer::start("ResponseFinalizer");
+
+        let finalizer = ResponseFinalizer::new(self.llm_client.clone());
+        let polished = match finalizer
+            .finalize_answer(
+                &answer,
+                &self.context.knowledge,
+                &self.response_language,
+                &mut self.token_tracker,
+            )
+            .await
+        {
+            Ok(polished) if !preserves_citations(&answer, &polished) => {
+                log::warn!("✨ Polimento descartado: marcadores de citação alterados");
+                self.emit(AgentProgress::Warning(
+                    "⚠️ Polimento descartado (citações alteradas)".into(),
+                ));
+                None
+            }
+            Ok(polished) if polished != answer => Some(polished),
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("✨ ResponseFinalizer falhou: {}", e);
+                self.emit(AgentProgress::Warning(format!("⚠️ Polimento falhou: {}", e)));
+                None
+            }
+        };
+
+        let duration_ms = timer.stop();
+        self.timing_stats.add_llm_time(duration_ms);
+        self.update_budget_used();
+
+        let preserved_original = polished.is_none();
+        let answer = match polished {
+            Some(polished) => {
+                realign_references(&polished, &mut references);
+                polished
+            }
+            None => answer,
+        };
+
+        let tokens = self.token_tracker.total_tokens().saturating_sub(tokens_before);
+        self.emit(AgentProgress::FinalizeEnd {
+            original_chars,
+            final_chars: answer.chars().count(),
+            preserved_original,
+            tokens,
+            duration_ms,
+        });
+        self.emit(AgentProgress::Tokens(self.token_tracker.total_tokens()));
+
+        (answer, references)
+    }
+
     /// Extrai referências do conhecimento coletado (fallback legado)
     fn extract_references_from_knowledge(&self) -> Vec<Reference> {
         use std::collections::HashSet;
@@ -2951,3 +5258,26 @@ Available actions:
         validated
     }
 }
+
+/// Instruções de resposta estruturada anexadas aos prompts do agente
+fn structured_answer_instructions(schema: &JsonSchema) -> String {
+    format!(
+        "\nSTRUCTURED ANSWER REQUIRED:\n\
+         The answer MUST be a JSON value that validates against this JSON Schema:\n{}\n\
+         → In the ANSWER action, put the JSON value itself in the \"answer\" field (not prose)\n\
+         → Do NOT put citation markers inside the JSON; list sources in \"references\"\n",
+        schema.as_value()
+    )
+}
+
+/// Se a URL aponta para um arquivo lido pelo `FileReader` (PDF, JSON, ...)
+///
+/// URLs `file://` ficam com o cliente de busca (ex.: `LocalCorpusClient`).
+fn is_file_url(url: &str) -> bool {
+    use crate::utils::FileType;
+    !url.starts_with("file://")
+        && matches!(
+            FileType::from_url(url),
+            FileType::Pdf | FileType::Json | FileType::Xml | FileType::Text | FileType::Markdown
+        )
+}
@@ -2,7 +2,8 @@
 // PERMISSÕES DE AÇÕES
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-use super::AgentContext;
+use super::{AgentAction, AgentContext};
+use serde::{Deserialize, Serialize};
 
 /// Limites de ações por step
 /// Máximo de reflexões permitidas por step
@@ -11,12 +12,14 @@ pub const MAX_REFLECT_PER_STEP: usize = 2;
 pub const MAX_URLS_BEFORE_DISABLE_SEARCH: usize = 50;
 /// Máximo de URLs para mostrar nos resultados
 pub const MAX_URLS_TO_SHOW: usize = 20;
+/// Máximo de sub-ações em um plano paralelo
+pub const MAX_PARALLEL_ACTIONS: usize = 4;
 
 /// Estado das permissões - imutável, criado a cada iteração
 ///
 /// Este struct determina quais ações o agente pode tomar no passo atual.
 /// As permissões são calculadas dinamicamente baseadas no contexto.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ActionPermissions {
     /// Pode executar busca na web
     pub search: bool,
@@ -32,6 +35,8 @@ pub struct ActionPermissions {
     pub history: bool,
     /// Pode perguntar ao usuário (interação)
     pub ask_user: bool,
+    /// Pode chamar ferramentas externas registradas (`ToolRegistry`)
+    pub tools: bool,
 }
 
 impl ActionPermissions {
@@ -44,6 +49,7 @@ impl ActionPermissions {
     /// - `answer`: Habilitada após min_steps (carregado do .env) ou se allow_direct_answer
     /// - `coding`: Sempre habilitada (por padrão)
     /// - `history`: Sempre habilitada (permite consultar sessões anteriores)
+    /// - `tools`: Sempre habilitada (o agente desliga sem ferramentas registradas)
     pub fn from_context(ctx: &AgentContext) -> Self {
         // Carregar configuração do agente do .env
         let agent_config = crate::config::load_agent_config();
@@ -51,13 +57,14 @@ impl ActionPermissions {
         Self {
             search: ctx.collected_urls.len() < MAX_URLS_BEFORE_DISABLE_SEARCH,
             read: ctx.available_urls() > 0,
-            reflect: ctx.gap_questions.len() <= MAX_REFLECT_PER_STEP,
+            reflect: ctx.gap_queue.open_count() <= MAX_REFLECT_PER_STEP,
             // ANSWER só é permitido após min_steps OU se allow_direct_answer está habilitado
             answer: ctx.total_step >= agent_config.min_steps_before_answer
                 || (ctx.allow_direct_answer && agent_config.allow_direct_answer),
             coding: true, // Coding geralmente está habilitado
             history: true, // History sempre habilitado
             ask_user: true, // Ask user sempre habilitado
+            tools: true,
         }
     }
 
@@ -66,12 +73,13 @@ impl ActionPermissions {
         Self {
             search: ctx.collected_urls.len() < MAX_URLS_BEFORE_DISABLE_SEARCH,
             read: ctx.available_urls() > 0,
-            reflect: ctx.gap_questions.len() <= MAX_REFLECT_PER_STEP,
+            reflect: ctx.gap_queue.open_count() <= MAX_REFLECT_PER_STEP,
             answer: ctx.total_step >= config.min_steps_before_answer
                 || (ctx.allow_direct_answer && config.allow_direct_answer),
             coding: true,
             history: true,
             ask_user: true,
+            tools: true,
         }
     }
 
@@ -85,6 +93,7 @@ impl ActionPermissions {
             coding: true,
             history: true,
             ask_user: true,
+            tools: true,
         }
     }
 
@@ -98,6 +107,7 @@ impl ActionPermissions {
             coding: false,
             history: false,
             ask_user: false,
+            tools: false,
         }
     }
 
@@ -111,12 +121,13 @@ impl ActionPermissions {
             coding: false,
             history: false,
             ask_user: true, // Pode perguntar mesmo em beast mode
+            tools: false,
         }
     }
 
     /// Lista de ações permitidas (para logging/debug)
     pub fn allowed_actions(&self) -> Vec<&'static str> {
-        let mut actions = Vec::with_capacity(7);
+        let mut actions = Vec::with_capacity(8);
         if self.search {
             actions.push("search");
         }
@@ -138,6 +149,9 @@ impl ActionPermissions {
         if self.ask_user {
             actions.push("ask_user");
         }
+        if self.tools {
+            actions.push("tool");
+        }
         actions
     }
 
@@ -151,6 +165,7 @@ impl ActionPermissions {
             self.coding,
             self.history,
             self.ask_user,
+            self.tools,
         ]
         .iter()
         .filter(|&&x| x)
@@ -159,7 +174,7 @@ impl ActionPermissions {
 
     /// Verifica se pelo menos uma ação está permitida
     pub fn has_any_allowed(&self) -> bool {
-        self.search || self.read || self.reflect || self.answer || self.coding || self.history || self.ask_user
+        self.search || self.read || self.reflect || self.answer || self.coding || self.history || self.ask_user || self.tools
     }
 
     /// Verifica se uma ação específica está permitida
@@ -172,6 +187,9 @@ impl ActionPermissions {
             "coding" => self.coding,
             "history" => self.history,
             "ask_user" => self.ask_user,
+            "tool" => self.tools,
+            // Cada sub-ação é verificada de novo na execução
+            "parallel" => self.search || self.read || self.tools,
             _ => false,
         }
     }
@@ -199,6 +217,179 @@ impl ActionPermissions {
         self.answer = false;
         self
     }
+
+    /// Modo de permissão da ação escolhida, considerando a política de aprovação
+    pub fn mode(&self, action: &AgentAction, policy: &ApprovalPolicy) -> PermissionMode {
+        if !self.is_allowed(action.name()) {
+            PermissionMode::Denied
+        } else if policy.requires_approval(action) {
+            PermissionMode::RequiresApproval
+        } else {
+            PermissionMode::Allowed
+        }
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// APROVAÇÃO HUMANA
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Modo de permissão de uma ação
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PermissionMode {
+    /// Executa direto
+    Allowed,
+    /// Executa só após confirmação do usuário
+    RequiresApproval,
+    /// Não permitida no step atual
+    Denied,
+}
+
+/// Regra de aprovação: ação e, opcionalmente, padrão sobre o alvo
+///
+/// O alvo depende da ação: host das URLs (`read`), queries (`search`),
+/// problema (`coding`), perguntas (`reflect`), filtro (`history`) e
+/// `ferramenta` ou `ferramenta:acao` (`tool`, com `acao` vinda do argumento
+/// `action`). O padrão aceita `*` e ignora maiúsculas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    /// Nome da ação (`read`, `coding`, `tool`, ...)
+    pub action: String,
+    /// Padrão do alvo; `None` exige aprovação sempre
+    pub pattern: Option<String>,
+}
+
+impl ApprovalRule {
+    /// Verifica se a regra se aplica à ação
+    pub fn matches(&self, action: &AgentAction) -> bool {
+        if let AgentAction::Parallel { actions, .. } = action {
+            return actions.iter().any(|a| self.matches(a));
+        }
+        if !self.action.eq_ignore_ascii_case(action.name()) {
+            return false;
+        }
+        match &self.pattern {
+            None => true,
+            Some(pattern) => approval_targets(action)
+                .iter()
+                .any(|target| wildcard_match(pattern, target)),
+        }
+    }
+}
+
+/// Política de aprovação humana por ação e padrão
+///
+/// # Exemplo
+/// ```rust,ignore
+/// let policy = ApprovalPolicy::new()
+///     .require("coding")
+///     .require_matching("read", "*.gov.br")
+///     .require_matching("tool", "digisac:enviar_mensagem");
+/// // ou: ApprovalPolicy::parse("coding, read:*.gov.br, tool:digisac:enviar_mensagem")
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Regras; basta uma casar para exigir aprovação
+    pub rules: Vec<ApprovalRule>,
+}
+
+impl ApprovalPolicy {
+    /// Cria uma política vazia (nada exige aprovação)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Exige aprovação para todas as execuções da ação
+    pub fn require(mut self, action: impl Into<String>) -> Self {
+        self.rules.push(ApprovalRule {
+            action: action.into(),
+            pattern: None,
+        });
+        self
+    }
+
+    /// Exige aprovação quando algum alvo da ação casa com o padrão
+    pub fn require_matching(mut self, action: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.rules.push(ApprovalRule {
+            action: action.into(),
+            pattern: Some(pattern.into()),
+        });
+        self
+    }
+
+    /// Interpreta regras separadas por vírgula no formato `acao[:padrao]`
+    pub fn parse(spec: &str) -> Self {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .fold(Self::new(), |policy, rule| match rule.split_once(':') {
+                Some((action, pattern)) => policy.require_matching(action.trim(), pattern.trim()),
+                None => policy.require(rule),
+            })
+    }
+
+    /// Se não há regras
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Se a ação exige aprovação
+    pub fn requires_approval(&self, action: &AgentAction) -> bool {
+        self.rules.iter().any(|rule| rule.matches(action))
+    }
+}
+
+/// Alvos de uma ação avaliados pelos padrões de aprovação
+pub fn approval_targets(action: &AgentAction) -> Vec<String> {
+    match action {
+        AgentAction::Search { queries, .. } => queries.iter().map(|q| q.q.clone()).collect(),
+        AgentAction::Read { urls, .. } => urls
+            .iter()
+            .map(|u| {
+                url::Url::parse(u)
+                    .ok()
+                    .and_then(|parsed| parsed.host_str().map(str::to_string))
+                    .unwrap_or_else(|| u.clone())
+            })
+            .collect(),
+        AgentAction::Reflect { gap_questions, .. } => gap_questions.clone(),
+        AgentAction::Answer { .. } | AgentAction::AskUser { .. } => vec![],
+        AgentAction::Coding { problem, .. } => vec![problem.clone()],
+        AgentAction::History { filter, .. } => filter.iter().cloned().collect(),
+        AgentAction::ToolCall { tool, arguments, .. } => {
+            let mut targets = vec![tool.clone()];
+            if let Some(sub) = arguments.get("action").and_then(|a| a.as_str()) {
+                targets.push(format!("{}:{}", tool, sub));
+            }
+            targets
+        }
+        AgentAction::Parallel { actions, .. } => {
+            actions.iter().flat_map(approval_targets).collect()
+        }
+    }
+}
+
+/// Casa `text` com um padrão com `*`, ignorando maiúsculas
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // Sem `*`: igualdade exata
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
 }
 
 impl Default for ActionPermissions {
@@ -227,7 +418,86 @@ mod tests {
         assert!(perms.coding);
         assert!(perms.history);
         assert!(perms.ask_user);
-        assert_eq!(perms.count_allowed(), 7);
+        assert!(perms.tools);
+        assert_eq!(perms.count_allowed(), 8);
+    }
+
+    #[test]
+    fn test_approval_policy_modes() {
+        let policy = ApprovalPolicy::parse("coding, read:*.gov.br, tool:digisac:enviar_*");
+        assert_eq!(policy.rules.len(), 3);
+        let perms = ActionPermissions::all_enabled();
+
+        let read = |url: &str| AgentAction::Read {
+            urls: vec![url.into()],
+            think: String::new(),
+        };
+        assert_eq!(
+            perms.mode(&read("https://www.receita.gov.br/x"), &policy),
+            PermissionMode::RequiresApproval
+        );
+        assert_eq!(
+            perms.mode(&read("https://example.com/gov.br"), &policy),
+            PermissionMode::Allowed
+        );
+        assert_eq!(
+            perms.without_read().mode(&read("https://www.receita.gov.br"), &policy),
+            PermissionMode::Denied
+        );
+
+        let tool = |action: &str| AgentAction::ToolCall {
+            tool: "digisac".into(),
+            arguments: serde_json::json!({ "action": action }),
+            think: String::new(),
+        };
+        assert!(policy.requires_approval(&tool("enviar_mensagem")));
+        assert!(!policy.requires_approval(&tool("listar_webhooks")));
+
+        // Um plano paralelo exige aprovação se qualquer sub-ação exigir
+        let plan = AgentAction::Parallel {
+            actions: vec![read("https://example.com"), tool("enviar_mensagem")],
+            think: String::new(),
+        };
+        assert_eq!(perms.mode(&plan, &policy), PermissionMode::RequiresApproval);
+
+        assert!(wildcard_match("a*b*c", "aXXbYc"));
+        assert!(!wildcard_match("a*b", "ab c"));
+    }
+
+    #[tokio::test]
+    async fn test_substituted_read_urls_require_approval() {
+        use crate::agent::DeepResearchAgent;
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use crate::types::KnowledgeType;
+        use std::sync::Arc;
+
+        let mut agent = DeepResearchAgent::new(
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+            Some(10_000),
+        )
+        .with_approval_policy(ApprovalPolicy::parse("read:*.example.org"));
+        agent.context.visited_urls.push("https://seen.com/a".into());
+        agent.context.add_url(
+            "https://docs.example.org/private".into(),
+            "Private".into(),
+            "Gated page".into(),
+        );
+
+        // LLM propõe só URLs visitadas: o substituto cai no host restrito
+        let action = AgentAction::Read {
+            urls: vec!["https://seen.com/a".into()],
+            think: String::new(),
+        };
+        agent
+            .execute_action(action, &ActionPermissions::all_enabled())
+            .await;
+
+        assert!(!agent.context.is_url_visited("https://docs.example.org/private"));
+        let refusal = agent.context.knowledge.last().unwrap();
+        assert_eq!(refusal.item_type, KnowledgeType::Error);
+        assert!(refusal.answer.contains("docs.example.org"));
     }
 
     #[test]
@@ -240,6 +510,7 @@ mod tests {
         assert!(!perms.coding);
         assert!(!perms.history);
         assert!(!perms.ask_user);
+        assert!(!perms.tools);
         assert_eq!(perms.count_allowed(), 0);
     }
 
@@ -253,6 +524,7 @@ mod tests {
         assert!(!perms.coding);
         assert!(!perms.history);
         assert!(perms.ask_user);
+        assert!(!perms.tools);
         assert_eq!(perms.count_allowed(), 2);
     }
 
@@ -266,6 +538,7 @@ mod tests {
             coding: true,
             history: true,
             ask_user: false,
+            tools: false,
         };
         let actions = perms.allowed_actions();
         assert_eq!(actions, vec!["search", "reflect", "coding", "history"]);
@@ -296,6 +569,7 @@ mod tests {
             coding: false,
             history: true,
             ask_user: true,
+            tools: true,
         };
 
         assert!(perms.is_allowed("search"));
@@ -305,6 +579,7 @@ mod tests {
         assert!(!perms.is_allowed("coding"));
         assert!(perms.is_allowed("history"));
         assert!(perms.is_allowed("ask_user"));
+        assert!(perms.is_allowed("tool"));
         assert!(!perms.is_allowed("unknown"));
     }
 }
@@ -0,0 +1,570 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// MODO RELATÓRIO - Documento longo com esboço e seções
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Fluxo:
+// 1. `ReportWriter` gera o esboço: título e seções, cada uma com uma
+//    pergunta independente
+// 2. Um `DeepResearchAgent` por seção, em sequência: cada seção tem suas
+//    próprias gap questions e avaliações, e herda o conhecimento e as URLs
+//    visitadas das seções anteriores
+// 3. `ReportWriter` escreve o sumário executivo a partir das seções
+// 4. O documento é montado com sumário, índice e citações por seção
+//
+// Budget: a reserva `REPORT_OVERHEAD_SHARE` fica para o esboço e o sumário;
+// o restante (tokens, tempo e custo) é dividido entre as seções que faltam,
+// então o que uma seção não usa passa para as seguintes.
+//
+// Seções que falham entram no documento com uma nota; o relatório só falha
+// se nenhuma seção for escrita.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::team::merge_results;
+use super::{
+    history_knowledge, AgentProgress, ApprovalPolicy, CancellationToken, ConversationState,
+    DeepResearchAgent, ProgressCallback, ResearchResult, SharedUrlSet,
+    DEFAULT_TIME_BEAST_MODE_FRACTION,
+};
+use crate::credibility::SourceCredibility;
+use crate::llm::LlmClient;
+use crate::search::SearchClient;
+use crate::tools::finalizer::citation_markers;
+use crate::tools::{QuestionRewriter, ReportWriter, DEFAULT_REPORT_SECTIONS};
+use crate::types::{KnowledgeItem, Reference};
+use crate::utils::{PricingTable, TokenTracker, DEFAULT_TOKEN_BUDGET};
+
+/// Fração do budget reservada para o esboço e o sumário executivo
+const REPORT_OVERHEAD_SHARE: f64 = 0.10;
+
+/// Seção pesquisada do relatório
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSection {
+    /// Título da seção
+    pub heading: String,
+    /// Pergunta pesquisada
+    pub question: String,
+    /// Texto da seção com marcadores `[^N]`, se a pesquisa respondeu
+    pub content: Option<String>,
+    /// Referências da seção (o marcador `[^k]` aponta para a k-ésima)
+    pub references: Vec<Reference>,
+    /// Motivo da falha, quando a seção ficou sem conteúdo
+    pub error: Option<String>,
+}
+
+/// Relatório longo montado a partir das seções
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResearchReport {
+    /// Título do relatório
+    pub title: String,
+    /// Sumário executivo
+    pub executive_summary: Option<String>,
+    /// Seções na ordem do esboço
+    pub sections: Vec<ReportSection>,
+}
+
+impl ResearchReport {
+    /// Número de seções com conteúdo
+    pub fn completed_sections(&self) -> usize {
+        self.sections.iter().filter(|s| s.content.is_some()).count()
+    }
+
+    /// Renderiza o relatório em Markdown
+    ///
+    /// Os marcadores `[^N]` de cada seção viram `[^S.N]` (S = número da
+    /// seção), com as fontes listadas ao final da própria seção.
+    pub fn to_markdown(&self) -> String {
+        let mut doc = format!("# {}\n\n", self.title);
+
+        if let Some(summary) = &self.executive_summary {
+            doc.push_str(&format!("## Executive Summary\n\n{}\n\n", summary.trim()));
+        }
+
+        doc.push_str("## Table of Contents\n\n");
+        for (i, section) in self.sections.iter().enumerate() {
+            let heading = section_heading(i, &section.heading);
+            doc.push_str(&format!(
+                "{}. [{}](#{})\n",
+                i + 1,
+                section.heading,
+                anchor(&heading)
+            ));
+        }
+
+        for (i, section) in self.sections.iter().enumerate() {
+            doc.push_str(&format!(
+                "\n## {}\n\n",
+                section_heading(i, &section.heading)
+            ));
+            let Some(content) = &section.content else {
+                doc.push_str(&format!(
+                    "_This section could not be researched ({})._\n",
+                    section.error.as_deref().unwrap_or("no answer")
+                ));
+                continue;
+            };
+
+            doc.push_str(&prefix_citations(content.trim(), i + 1));
+            doc.push('\n');
+
+            let sources = citation_order(&section.references);
+            if !sources.is_empty() {
+                doc.push_str("\n**Sources**\n\n");
+                for (n, reference) in sources.iter().enumerate() {
+                    let title = if reference.title.trim().is_empty() {
+                        &reference.url
+                    } else {
+                        &reference.title
+                    };
+                    doc.push_str(&format!(
+                        "[^{}.{}]: [{}]({})\n",
+                        i + 1,
+                        n + 1,
+                        title,
+                        reference.url
+                    ));
+                }
+            }
+        }
+
+        doc
+    }
+}
+
+/// Título numerado da seção (`"2. Players"`)
+fn section_heading(index: usize, heading: &str) -> String {
+    format!("{}. {}", index + 1, heading)
+}
+
+/// Âncora no estilo GitHub para um título
+fn anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Troca `[^N]` por `[^S.N]` para que seções diferentes não colidam
+fn prefix_citations(content: &str, section: usize) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut last = 0;
+    for (number, start, end) in citation_markers(content) {
+        text.push_str(&content[last..start]);
+        text.push_str(&format!("[^{}.{}]", section, number));
+        last = end;
+    }
+    text.push_str(&content[last..]);
+    text
+}
+
+/// Referências na ordem dos marcadores (mesma regra do `ReferenceBuilder`)
+pub(super) fn citation_order(references: &[Reference]) -> Vec<&Reference> {
+    let mut ordered: Vec<&Reference> = references.iter().collect();
+    if references.iter().any(|r| r.answer_position.is_some()) {
+        ordered.sort_by_key(|r| {
+            r.answer_position
+                .map(|(start, _)| start)
+                .unwrap_or(usize::MAX)
+        });
+    }
+    ordered
+}
+
+/// Pesquisa em modo relatório: esboço, uma pesquisa por seção e montagem
+///
+/// # Exemplo
+/// ```rust,ignore
+/// let result = ReportResearch::new(llm_client, search_client, Some(1_000_000))
+///     .with_max_sections(6)
+///     .run("Estudo de mercado de cafés especiais no Brasil".into())
+///     .await;
+/// let report = result.report.unwrap();
+/// ```
+pub struct ReportResearch {
+    llm_client: Arc<dyn LlmClient>,
+    search_client: Arc<dyn SearchClient>,
+    token_budget: u64,
+    /// Número máximo de seções do esboço
+    max_sections: usize,
+    progress_callback: Option<ProgressCallback>,
+    /// Cancela o esboço, as seções (tokens filhos) e o sumário
+    cancel_token: CancellationToken,
+    /// Prazo total do relatório
+    time_budget: Option<Duration>,
+    time_beast_mode_fraction: f64,
+    /// Preços aplicados ao relatório e às seções
+    pricing: PricingTable,
+    /// Credibilidade das fontes repassada às seções
+    credibility: SourceCredibility,
+    /// Budget total em USD
+    cost_budget: Option<f64>,
+    /// Conversa em andamento (histórico, conhecimento e URLs visitadas)
+    conversation: Option<ConversationState>,
+    /// Ações que exigem aprovação (seções não têm canal: são recusadas)
+    approval_policy: ApprovalPolicy,
+}
+
+impl ReportResearch {
+    /// Cria um relatório com até `DEFAULT_REPORT_SECTIONS` seções
+    pub fn new(
+        llm_client: Arc<dyn LlmClient>,
+        search_client: Arc<dyn SearchClient>,
+        token_budget: Option<u64>,
+    ) -> Self {
+        Self {
+            llm_client,
+            search_client,
+            token_budget: token_budget.unwrap_or(DEFAULT_TOKEN_BUDGET),
+            max_sections: DEFAULT_REPORT_SECTIONS,
+            progress_callback: None,
+            cancel_token: CancellationToken::new(),
+            time_budget: None,
+            time_beast_mode_fraction: DEFAULT_TIME_BEAST_MODE_FRACTION,
+            pricing: PricingTable::builtin(),
+            credibility: SourceCredibility::default(),
+            cost_budget: None,
+            conversation: None,
+            approval_policy: ApprovalPolicy::new(),
+        }
+    }
+
+    /// Define o número máximo de seções (limitado a `MAX_REPORT_SECTIONS`)
+    pub fn with_max_sections(mut self, max_sections: usize) -> Self {
+        self.max_sections = max_sections;
+        self
+    }
+
+    /// Configura callback de progresso (eventos das seções chegam como `SubAgent`)
+    pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// Usa um token de cancelamento externo (propagado às seções)
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    /// Define o prazo total do relatório
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Fração do prazo que dispara o Beast Mode nas seções
+    pub fn with_time_beast_mode_fraction(mut self, fraction: f64) -> Self {
+        self.time_beast_mode_fraction = fraction;
+        self
+    }
+
+    /// Usa uma tabela de preços própria (repassada às seções)
+    pub fn with_pricing(mut self, pricing: &PricingTable) -> Self {
+        self.pricing = pricing.clone();
+        self
+    }
+
+    /// Usa uma pontuação de credibilidade própria (repassada às seções)
+    pub fn with_credibility(mut self, credibility: SourceCredibility) -> Self {
+        self.credibility = credibility;
+        self
+    }
+
+    /// Define o budget total em USD
+    pub fn with_cost_budget(mut self, budget_usd: f64) -> Self {
+        self.cost_budget = Some(budget_usd);
+        self
+    }
+
+    /// Repassa a política de aprovação às pesquisas das seções
+    ///
+    /// As seções rodam sem canal de interação: as ações que exigem
+    /// aprovação são recusadas em vez de executadas.
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.approval_policy = policy;
+        self
+    }
+
+    /// Continua uma conversa
+    ///
+    /// A pergunta é reescrita com o histórico antes do esboço; as seções
+    /// recebem o conhecimento anterior e não releem URLs já visitadas.
+    pub fn with_conversation(mut self, conversation: &ConversationState) -> Self {
+        self.conversation = Some(conversation.clone());
+        self
+    }
+
+    fn emit(&self, event: AgentProgress) {
+        if let Some(cb) = &self.progress_callback {
+            cb(event);
+        }
+    }
+
+    /// Executa a pesquisa e retorna o relatório em `answer` (Markdown) e `report`
+    pub async fn run(self, question: String) -> ResearchResult {
+        let start_time = std::time::Instant::now();
+        let mut tracker = TokenTracker::new(Some(self.token_budget));
+        tracker.set_rates(self.pricing.rates_for(
+            self.llm_client.model_name(),
+            self.llm_client.embedding_model_name(),
+        ));
+        let usage_before = (
+            self.search_client.get_credits(),
+            self.llm_client.get_embedding_tokens(),
+        );
+
+        let (mut prior_knowledge, prior_urls): (Vec<KnowledgeItem>, Vec<String>) =
+            match &self.conversation {
+                Some(c) => {
+                    let mut knowledge = history_knowledge(&c.turns);
+                    knowledge.extend(c.knowledge.iter().cloned());
+                    (knowledge, c.visited_urls.clone())
+                }
+                None => (Vec::new(), Vec::new()),
+            };
+
+        // Pergunta de acompanhamento: torna-se independente do histórico
+        let question = match &self.conversation {
+            Some(conversation) if !conversation.turns.is_empty() => {
+                QuestionRewriter::new(self.llm_client.clone())
+                    .rewrite(&conversation.turns, &question, &mut tracker)
+                    .await
+            }
+            _ => question,
+        };
+
+        self.emit(AgentProgress::Info(
+            "📑 Modo relatório: gerando esboço".to_string(),
+        ));
+
+        let writer = ReportWriter::new(self.llm_client.clone());
+        let Some(outline) = self
+            .cancel_token
+            .run_until_cancelled(writer.outline(&question, self.max_sections, &mut tracker))
+            .await
+        else {
+            tracker.track_shared_usage(
+                self.search_client.as_ref(),
+                self.llm_client.as_ref(),
+                usage_before,
+            );
+            return merge_results(
+                Vec::new(),
+                None,
+                tracker,
+                start_time.elapsed().as_millis(),
+                true,
+            );
+        };
+
+        self.emit(AgentProgress::ReportOutline {
+            title: outline.title.clone(),
+            sections: outline.sections.iter().map(|s| s.heading.clone()).collect(),
+        });
+
+        let overhead = ((self.token_budget as f64) * REPORT_OVERHEAD_SHARE) as u64;
+        let shared_urls: SharedUrlSet = Arc::new(Mutex::new(prior_urls.into_iter().collect()));
+        let mut results: Vec<ResearchResult> = Vec::with_capacity(outline.sections.len());
+        let mut sections = Vec::with_capacity(outline.sections.len());
+        let (mut tokens_spent, mut cost_spent) = (0u64, 0.0f64);
+
+        for (index, planned) in outline.sections.iter().enumerate() {
+            if self.cancel_token.is_cancelled() {
+                break;
+            }
+            let remaining = (outline.sections.len() - index) as u64;
+
+            self.emit(AgentProgress::Info(format!(
+                "📑 Seção {}/{}: {}",
+                index + 1,
+                outline.sections.len(),
+                planned.heading
+            )));
+
+            // Budgets: o que resta dividido entre as seções que faltam
+            let section_budget = self
+                .token_budget
+                .saturating_sub(overhead.max(tracker.total_tokens()) + tokens_spent)
+                / remaining;
+            let mut agent = DeepResearchAgent::new(
+                self.llm_client.clone(),
+                self.search_client.clone(),
+                Some(section_budget),
+            )
+            .with_shared_visited_urls(shared_urls.clone())
+            .with_prior_research(prior_knowledge.clone(), Vec::new())
+            .with_cancellation_token(self.cancel_token.child_token())
+            .with_time_beast_mode_fraction(self.time_beast_mode_fraction)
+            .with_pricing(&self.pricing)
+            .with_credibility(self.credibility.clone())
+            .with_approval_policy(self.approval_policy.clone());
+
+            if let Some(budget) = self.time_budget {
+                let left = budget
+                    .mul_f64(1.0 - REPORT_OVERHEAD_SHARE)
+                    .saturating_sub(start_time.elapsed());
+                agent = agent.with_time_budget(left / remaining as u32);
+            }
+            if let Some(budget) = self.cost_budget {
+                let left =
+                    budget * (1.0 - REPORT_OVERHEAD_SHARE) - tracker.cost().total_usd - cost_spent;
+                agent = agent.with_cost_budget(left.max(0.0) / remaining as f64);
+            }
+            if let Some(cb) = &self.progress_callback {
+                let cb = cb.clone();
+                agent = agent.with_progress_callback(Arc::new(move |event| {
+                    cb(AgentProgress::SubAgent {
+                        agent_index: index,
+                        event: Box::new(event),
+                    })
+                }));
+            }
+
+            let result = agent.run(planned.question.clone()).await;
+            tokens_spent += result.token_usage.total_tokens;
+            cost_spent += result.cost.total_usd;
+
+            // Seções seguintes partem do que já foi aprendido
+            let known: HashSet<(String, String)> = prior_knowledge
+                .iter()
+                .map(|k| (k.question.clone(), k.answer.clone()))
+                .collect();
+            prior_knowledge.extend(
+                result
+                    .knowledge
+                    .iter()
+                    .filter(|k| !known.contains(&(k.question.clone(), k.answer.clone())))
+                    .cloned(),
+            );
+
+            sections.push(ReportSection {
+                heading: planned.heading.clone(),
+                question: planned.question.clone(),
+                content: result.answer.clone().filter(|_| result.success),
+                references: if result.success {
+                    result.references.clone()
+                } else {
+                    Vec::new()
+                },
+                error: if result.success {
+                    None
+                } else {
+                    result.error.clone()
+                },
+            });
+            results.push(result);
+        }
+
+        let mut report = ResearchReport {
+            title: outline.title,
+            executive_summary: None,
+            sections,
+        };
+
+        self.emit(AgentProgress::Info(format!(
+            "📑 {}/{} seções escritas, montando relatório",
+            report.completed_sections(),
+            report.sections.len()
+        )));
+
+        let written: Vec<(&str, &str)> = report
+            .sections
+            .iter()
+            .filter_map(|s| s.content.as_deref().map(|c| (s.heading.as_str(), c)))
+            .collect();
+        if !written.is_empty() {
+            report.executive_summary = self
+                .cancel_token
+                .run_until_cancelled(writer.executive_summary(
+                    &report.title,
+                    &written,
+                    &mut tracker,
+                ))
+                .await
+                .flatten();
+        }
+
+        // Seções já escritas valem mesmo se o cancelamento chegou depois
+        let answer = (report.completed_sections() > 0).then(|| report.to_markdown());
+        let cancelled = answer.is_none() && self.cancel_token.is_cancelled();
+        tracker.track_shared_usage(
+            self.search_client.as_ref(),
+            self.llm_client.as_ref(),
+            usage_before,
+        );
+        let mut result = merge_results(
+            results,
+            answer,
+            tracker,
+            start_time.elapsed().as_millis(),
+            cancelled,
+        );
+        if result.success {
+            result.report = Some(report);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(url: &str, title: &str, start: usize) -> Reference {
+        Reference {
+            url: url.into(),
+            title: title.into(),
+            answer_position: Some((start, start + 1)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_report_markdown_prefixes_citations_per_section() {
+        let report = ResearchReport {
+            title: "Mercado de café".into(),
+            executive_summary: Some("O mercado cresce.".into()),
+            sections: vec![
+                ReportSection {
+                    heading: "Tamanho do mercado".into(),
+                    question: "Qual o tamanho?".into(),
+                    content: Some("Cresceu 5%[^1] e exporta muito[^2].".into()),
+                    // Ordem por posição: b é citada primeiro
+                    references: vec![
+                        reference("https://a", "A", 20),
+                        reference("https://b", "", 5),
+                    ],
+                    error: None,
+                },
+                ReportSection {
+                    heading: "Players".into(),
+                    question: "Quem lidera?".into(),
+                    content: None,
+                    references: vec![],
+                    error: Some("budget esgotado".into()),
+                },
+            ],
+        };
+
+        let doc = report.to_markdown();
+        assert!(doc.starts_with("# Mercado de café\n\n## Executive Summary\n\nO mercado cresce."));
+        assert!(doc.contains("1. [Tamanho do mercado](#1-tamanho-do-mercado)\n"));
+        assert!(doc.contains("Cresceu 5%[^1.1] e exporta muito[^1.2]."));
+        assert!(doc.contains("[^1.1]: [https://b](https://b)\n[^1.2]: [A](https://a)\n"));
+        assert!(doc.contains(
+            "## 2. Players\n\n_This section could not be researched (budget esgotado)._"
+        ));
+        assert_eq!(report.completed_sections(), 1);
+    }
+}
@@ -3,13 +3,24 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use crate::agent::interaction::QuestionType;
+use crate::agent::report::ResearchReport;
+use crate::agent::contradictions::FactConflict;
+use crate::agent::voting::BeastModeVote;
+use crate::credibility::CredibilityScore;
+use crate::tools::ClaimCheck;
 use crate::types::{KnowledgeItem, Reference};
+use crate::utils::{json_leaves, CostBreakdown};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Tamanho mínimo do valor de um campo para buscá-lo nas fontes
+const MIN_FIELD_MATCH_CHARS: usize = 3;
 
 /// Estado do agente - transições explícitas
 ///
 /// A máquina de estados garante que o agente só pode estar em um estado válido.
 /// Pattern matching exaustivo força o tratamento de todos os casos.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AgentState {
     /// Processando normalmente
     ///
@@ -73,17 +84,31 @@ pub enum AgentState {
         /// Conhecimento parcial acumulado
         partial_knowledge: Vec<KnowledgeItem>,
     },
+
+    /// Cancelada pelo chamador (`CancellationToken`)
+    ///
+    /// Estado terminal - a execução foi interrompida entre steps ou no meio
+    /// de um batch; o conhecimento coletado até ali é preservado.
+    Cancelled {
+        /// Conhecimento parcial acumulado
+        partial_knowledge: Vec<KnowledgeItem>,
+    },
 }
 
 impl AgentState {
-    /// Verifica se o estado é terminal (Completed ou Failed)
+    /// Verifica se o estado é terminal (Completed, Failed ou Cancelled)
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
-            AgentState::Completed { .. } | AgentState::Failed { .. }
+            AgentState::Completed { .. } | AgentState::Failed { .. } | AgentState::Cancelled { .. }
         )
     }
 
+    /// Verifica se a execução foi cancelada
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, AgentState::Cancelled { .. })
+    }
+
     /// Verifica se o estado é de processamento normal
     pub fn is_processing(&self) -> bool {
         matches!(self, AgentState::Processing { .. })
@@ -110,6 +135,10 @@ impl AgentState {
     pub fn can_transition_to(&self, target: &AgentState) -> bool {
         matches!(
             (self, target),
+            // Qualquer estado não terminal pode ser cancelado
+            (AgentState::Processing { .. }, AgentState::Cancelled { .. }) |
+            (AgentState::BeastMode { .. }, AgentState::Cancelled { .. }) |
+            (AgentState::InputRequired { .. }, AgentState::Cancelled { .. }) |
             // De Processing pode ir para BeastMode, Completed, Failed ou InputRequired
             (AgentState::Processing { .. }, AgentState::BeastMode { .. }) |
             (AgentState::Processing { .. }, AgentState::Completed { .. }) |
@@ -251,6 +280,53 @@ pub struct ResearchResult {
     /// Útil para monitorar custos e otimizar prompts.
     pub token_usage: TokenUsage,
 
+    /// Custo monetário da pesquisa, em USD.
+    ///
+    /// Calculado com a tabela de preços do modelo (prompt, completion,
+    /// embedding) mais os créditos de busca/leitura.
+    pub cost: CostBreakdown,
+
+    /// Resposta estruturada, quando a pesquisa recebeu um JSON Schema.
+    ///
+    /// `answer` contém o mesmo JSON serializado; aqui ele vem parseado
+    /// junto das referências que sustentam cada campo.
+    pub structured: Option<StructuredAnswer>,
+
+    /// Relatório por seções, quando a pesquisa rodou no modo relatório.
+    ///
+    /// `answer` contém o mesmo relatório renderizado em Markdown.
+    pub report: Option<ResearchReport>,
+
+    /// Votação das respostas candidatas, quando a resposta veio do Beast Mode.
+    ///
+    /// Mostra a pontuação de cada candidata e se a resposta final é a
+    /// melhor delas ou a mescla das que concordam.
+    pub beast_mode_vote: Option<BeastModeVote>,
+
+    /// Vereditos por afirmação da resposta, quando a verificação está ativa.
+    ///
+    /// Cada afirmação atômica da resposta aparece como suportada,
+    /// contradita ou não verificada, com o trecho e a fonte usados.
+    pub claim_checks: Vec<ClaimCheck>,
+
+    /// Fatos em que as fontes discordam, com o valor e a URL de cada lado.
+    ///
+    /// Vazio quando a detecção de contradições está desligada ou nenhuma
+    /// divergência foi encontrada.
+    pub disputed_facts: Vec<FactConflict>,
+
+    /// Credibilidade de cada fonte lida, da maior para a menor.
+    ///
+    /// Os motivos explicam o peso: camada do domínio, TLD, sinais da
+    /// página e histórico de citações.
+    pub source_credibility: Vec<CredibilityScore>,
+
+    /// Conhecimento acumulado na pesquisa (sem o histórico de conversa).
+    ///
+    /// Permite reaproveitar o que foi aprendido em perguntas seguintes
+    /// da mesma conversa (ver `ConversationState`).
+    pub knowledge: Vec<KnowledgeItem>,
+
     /// Lista de todas as URLs visitadas durante a pesquisa.
     ///
     /// Inclui URLs que foram lidas com sucesso e também
@@ -263,6 +339,17 @@ pub struct ResearchResult {
     /// Descreve o motivo da falha (timeout, budget, etc).
     pub error: Option<String>,
 
+    /// Indica se a pesquisa foi cancelada pelo chamador.
+    ///
+    /// Quando `true`, `success = false` e `partial_knowledge` contém
+    /// o que foi coletado até o cancelamento.
+    pub cancelled: bool,
+
+    /// Conhecimento acumulado quando a pesquisa terminou sem resposta.
+    ///
+    /// Preenchido em falhas e cancelamentos; vazio quando `success = true`.
+    pub partial_knowledge: Vec<KnowledgeItem>,
+
     /// Tempo total da pesquisa em milissegundos.
     pub total_time_ms: u128,
 
@@ -276,6 +363,59 @@ pub struct ResearchResult {
     pub llm_time_ms: u128,
 }
 
+/// Resposta validada contra um JSON Schema, com as fontes de cada campo.
+///
+/// Cada campo folha (string, número, booleano) é identificado pelo seu
+/// JSON Pointer (ex: `/products/0/name`). Uma referência sustenta o campo
+/// quando a citação da fonte ou o trecho da resposta associado a ela
+/// contém o valor do campo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredAnswer {
+    /// JSON validado contra o schema.
+    pub value: serde_json::Value,
+
+    /// Referências por campo (JSON Pointer).
+    ///
+    /// Campos sem fonte identificada aparecem com lista vazia.
+    pub field_references: BTreeMap<String, Vec<Reference>>,
+}
+
+impl StructuredAnswer {
+    /// Associa cada campo do JSON às referências que citam seu valor
+    pub fn new(value: serde_json::Value, references: &[Reference]) -> Self {
+        let field_references = json_leaves(&value)
+            .into_iter()
+            .map(|(pointer, leaf)| {
+                let needle = match leaf {
+                    serde_json::Value::String(s) => s.trim().to_lowercase(),
+                    serde_json::Value::Number(n) => n.to_string(),
+                    _ => String::new(),
+                };
+                let backing = if needle.chars().count() < MIN_FIELD_MATCH_CHARS {
+                    vec![]
+                } else {
+                    references
+                        .iter()
+                        .filter(|r| {
+                            [&r.exact_quote, &r.answer_chunk]
+                                .into_iter()
+                                .flatten()
+                                .any(|text| text.to_lowercase().contains(&needle))
+                        })
+                        .cloned()
+                        .collect()
+                };
+                (pointer, backing)
+            })
+            .collect();
+
+        Self {
+            value,
+            field_references,
+        }
+    }
+}
+
 /// Estatísticas de uso de tokens durante a pesquisa.
 ///
 /// Tokens são a unidade de medida usada por LLMs para cobrar pelo uso.
@@ -299,7 +439,7 @@ pub struct ResearchResult {
 /// let custo_estimado = (usage.total_tokens as f64) * 0.00003; // GPT-4
 /// println!("Custo: ${:.4}", custo_estimado);
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
     /// Tokens consumidos pelos prompts enviados ao modelo.
     ///
@@ -433,4 +573,52 @@ mod tests {
         assert!(completed.is_terminal());
         assert!(failed.is_terminal());
     }
+
+    #[test]
+    fn test_cancelled_state() {
+        let processing = AgentState::Processing {
+            step: 0,
+            total_step: 0,
+            current_question: "test".into(),
+            budget_used: 0.0,
+        };
+
+        let cancelled = AgentState::Cancelled {
+            partial_knowledge: vec![],
+        };
+
+        assert!(processing.can_transition_to(&cancelled));
+        assert!(cancelled.is_terminal());
+        assert!(cancelled.is_cancelled());
+        assert!(!cancelled.can_transition_to(&processing));
+    }
+
+    #[test]
+    fn test_structured_answer_field_references() {
+        let reference = |url: &str, quote: &str| Reference {
+            url: url.into(),
+            title: url.into(),
+            exact_quote: Some(quote.into()),
+            ..Default::default()
+        };
+        let references = vec![
+            reference("https://a.example", "Rust was created by Graydon Hoare at Mozilla."),
+            reference("https://b.example", "Rust 1.0 was released in 2015."),
+        ];
+
+        let structured = StructuredAnswer::new(
+            serde_json::json!({ "creator": "Graydon Hoare", "release": 2015, "stable": true }),
+            &references,
+        );
+
+        let urls = |pointer: &str| -> Vec<String> {
+            structured.field_references[pointer]
+                .iter()
+                .map(|r| r.url.clone())
+                .collect()
+        };
+        assert_eq!(urls("/creator"), vec!["https://a.example"]);
+        assert_eq!(urls("/release"), vec!["https://b.example"]);
+        assert!(urls("/stable").is_empty());
+    }
 }
@@ -0,0 +1,868 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// MODO EQUIPE - Pesquisa multi-agente
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Fluxo:
+// 1. `ResearchPlanner` divide a pergunta em N subproblemas ortogonais
+// 2. N `DeepResearchAgent`s rodam em paralelo, cada um com uma fatia do
+//    budget de tokens e um conjunto de URLs visitadas compartilhado
+// 3. `ResponseReducer` mescla as respostas em um único `ResearchResult`
+//
+// Em conversas, a pergunta é reescrita com o histórico antes do planejamento
+// e os sub-agentes herdam o conhecimento e as URLs visitadas anteriores.
+//
+// Com JSON Schema, só a resposta mesclada é estruturada: os sub-agentes
+// respondem em texto livre e o gate de schema converte/repara o resultado.
+//
+// Custo: cada sub-agente reporta o próprio custo de LLM; créditos de busca
+// vêm do `search_client` compartilhado e são contados uma vez pela equipe.
+//
+// Perguntas simples demais para decomposição caem no agente único.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::report::citation_order;
+use super::{
+    history_knowledge, AgentProgress, ApprovalPolicy, CancellationToken, ConversationState,
+    DeepResearchAgent, ProgressCallback, ReportResearch, ResearchResult, SharedUrlSet,
+    StructuredAnswer, DEFAULT_TIME_BEAST_MODE_FRACTION,
+};
+use crate::credibility::{CredibilityScore, SourceCredibility};
+use crate::evaluation::EvaluationPipeline;
+use crate::llm::LlmClient;
+use crate::search::SearchClient;
+use crate::tools::finalizer::{citation_markers, realign_references};
+use crate::tools::{QuestionRewriter, ResearchPlanner, ResponseReducer};
+use crate::types::{KnowledgeItem, Reference};
+use crate::utils::{JsonSchema, PricingTable, TokenTracker, DEFAULT_TOKEN_BUDGET};
+
+/// Fração do budget reservada para o planner e o reducer
+const TEAM_OVERHEAD_SHARE: f64 = 0.10;
+
+/// Fábrica de clientes LLM (um cliente isolado por sub-agente)
+pub type LlmClientFactory = Arc<dyn Fn() -> Arc<dyn LlmClient> + Send + Sync>;
+
+/// Pesquisa em equipe: planeja, executa sub-agentes em paralelo e reduz
+///
+/// # Exemplo
+/// ```rust,ignore
+/// let result = TeamResearch::new(llm_client, search_client, 3, Some(600_000))
+///     .with_progress_callback(callback)
+///     .run("Qual o futuro da IA na saúde?".into())
+///     .await;
+/// ```
+pub struct TeamResearch {
+    llm_client: Arc<dyn LlmClient>,
+    search_client: Arc<dyn SearchClient>,
+    /// Cria clientes LLM isolados para os sub-agentes (contagem de tokens precisa)
+    llm_factory: Option<LlmClientFactory>,
+    team_size: usize,
+    token_budget: u64,
+    progress_callback: Option<ProgressCallback>,
+    /// Cancela planner, sub-agentes (tokens filhos) e reducer
+    cancel_token: CancellationToken,
+    /// Prazo total da equipe (sub-agentes recebem o restante menos a reserva)
+    time_budget: Option<Duration>,
+    time_beast_mode_fraction: f64,
+    /// Preços aplicados à equipe e aos sub-agentes
+    pricing: PricingTable,
+    /// Credibilidade das fontes repassada aos sub-agentes
+    credibility: SourceCredibility,
+    /// Budget total em USD (dividido como o budget de tokens)
+    cost_budget: Option<f64>,
+    /// JSON Schema exigido da resposta mesclada
+    response_schema: Option<JsonSchema>,
+    /// Conversa em andamento (histórico, conhecimento e URLs visitadas)
+    conversation: Option<ConversationState>,
+    /// Ações que exigem aprovação (sub-agentes não têm canal: são recusadas)
+    approval_policy: ApprovalPolicy,
+}
+
+impl TeamResearch {
+    /// Cria uma equipe de `team_size` agentes dividindo `token_budget`
+    pub fn new(
+        llm_client: Arc<dyn LlmClient>,
+        search_client: Arc<dyn SearchClient>,
+        team_size: usize,
+        token_budget: Option<u64>,
+    ) -> Self {
+        Self {
+            llm_client,
+            search_client,
+            llm_factory: None,
+            team_size,
+            token_budget: token_budget.unwrap_or(DEFAULT_TOKEN_BUDGET),
+            progress_callback: None,
+            cancel_token: CancellationToken::new(),
+            time_budget: None,
+            time_beast_mode_fraction: DEFAULT_TIME_BEAST_MODE_FRACTION,
+            pricing: PricingTable::builtin(),
+            credibility: SourceCredibility::default(),
+            cost_budget: None,
+            response_schema: None,
+            conversation: None,
+            approval_policy: ApprovalPolicy::new(),
+        }
+    }
+
+    /// Usa um cliente LLM próprio por sub-agente
+    ///
+    /// Sem a fábrica os sub-agentes compartilham `llm_client`, e os contadores
+    /// de tokens do cliente misturam chamadas concorrentes.
+    pub fn with_llm_factory(mut self, factory: LlmClientFactory) -> Self {
+        self.llm_factory = Some(factory);
+        self
+    }
+
+    /// Configura callback de progresso (eventos de sub-agentes chegam como `SubAgent`)
+    pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    /// Usa um token de cancelamento externo (propagado aos sub-agentes)
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    /// Define o prazo total da equipe
+    ///
+    /// Os sub-agentes recebem o tempo restante após o planejamento, menos a
+    /// reserva do reducer (`TEAM_OVERHEAD_SHARE`).
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Fração do prazo que dispara o Beast Mode nos sub-agentes
+    pub fn with_time_beast_mode_fraction(mut self, fraction: f64) -> Self {
+        self.time_beast_mode_fraction = fraction;
+        self
+    }
+
+    /// Usa uma tabela de preços própria (repassada aos sub-agentes)
+    pub fn with_pricing(mut self, pricing: &PricingTable) -> Self {
+        self.pricing = pricing.clone();
+        self
+    }
+
+    /// Usa uma pontuação de credibilidade própria (repassada aos sub-agentes)
+    pub fn with_credibility(mut self, credibility: SourceCredibility) -> Self {
+        self.credibility = credibility;
+        self
+    }
+
+    /// Define o budget total em USD
+    ///
+    /// Os sub-agentes dividem o budget menos a reserva do planner/reducer.
+    pub fn with_cost_budget(mut self, budget_usd: f64) -> Self {
+        self.cost_budget = Some(budget_usd);
+        self
+    }
+
+    /// Exige que a resposta mesclada valide contra `schema`
+    ///
+    /// Os sub-agentes continuam em texto livre; o gate de schema (com
+    /// reparos) converte a resposta do reducer.
+    pub fn with_response_schema(mut self, schema: JsonSchema) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+
+    /// Repassa a política de aprovação aos sub-agentes
+    ///
+    /// Sub-agentes rodam sem canal de interação: as ações que exigem
+    /// aprovação são recusadas em vez de executadas.
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.approval_policy = policy;
+        self
+    }
+
+    /// Continua uma conversa
+    ///
+    /// A pergunta é reescrita com o histórico antes do planejamento; os
+    /// sub-agentes recebem o conhecimento anterior e não releem URLs já
+    /// visitadas na conversa.
+    pub fn with_conversation(mut self, conversation: &ConversationState) -> Self {
+        self.conversation = Some(conversation.clone());
+        self
+    }
+
+    /// Conhecimento anterior repassado aos sub-agentes (com o histórico)
+    fn prior_knowledge(&self) -> Vec<KnowledgeItem> {
+        self.conversation
+            .as_ref()
+            .map(|c| {
+                let mut knowledge = history_knowledge(&c.turns);
+                knowledge.extend(c.knowledge.iter().cloned());
+                knowledge
+            })
+            .unwrap_or_default()
+    }
+
+    /// URLs já visitadas na conversa
+    fn prior_visited_urls(&self) -> Vec<String> {
+        self.conversation
+            .as_ref()
+            .map(|c| c.visited_urls.clone())
+            .unwrap_or_default()
+    }
+
+    /// Aplica o gate de schema à resposta mesclada
+    async fn enforce_schema(&self, question: &str, mut result: ResearchResult) -> ResearchResult {
+        let (Some(schema), Some(answer)) = (&self.response_schema, &result.answer) else {
+            return result;
+        };
+
+        let pipeline = EvaluationPipeline::new(self.llm_client.clone());
+        let gate = self
+            .cancel_token
+            .run_until_cancelled(pipeline.enforce_schema(schema, question, answer))
+            .await;
+        match gate {
+            Some(Ok((value, _))) => {
+                result.answer = Some(serde_json::to_string_pretty(&value).unwrap_or_default());
+                result.structured = Some(StructuredAnswer::new(value, &result.references));
+            }
+            Some(Err(failure)) => {
+                self.emit(AgentProgress::Error(format!(
+                    "Resposta mesclada fora do schema: {}",
+                    failure.reasoning
+                )));
+                result.success = false;
+                result.answer = None;
+                result.error = Some(failure.reasoning);
+            }
+            None => {
+                result.success = false;
+                result.answer = None;
+                result.cancelled = true;
+                result.error = Some("Pesquisa cancelada".into());
+            }
+        }
+        result
+    }
+
+    fn emit(&self, event: AgentProgress) {
+        if let Some(cb) = &self.progress_callback {
+            cb(event);
+        }
+    }
+
+    /// Executa a pesquisa em equipe e retorna um resultado único
+    pub async fn run(self, question: String) -> ResearchResult {
+        let start_time = std::time::Instant::now();
+        let mut tracker = TokenTracker::new(Some(self.token_budget));
+        tracker.set_rates(self.pricing.rates_for(
+            self.llm_client.model_name(),
+            self.llm_client.embedding_model_name(),
+        ));
+        let usage_before = (
+            self.search_client.get_credits(),
+            self.llm_client.get_embedding_tokens(),
+        );
+
+        // Pergunta de acompanhamento: torna-se independente do histórico
+        let question = match &self.conversation {
+            Some(conversation) if !conversation.turns.is_empty() => {
+                QuestionRewriter::new(self.llm_client.clone())
+                    .rewrite(&conversation.turns, &question, &mut tracker)
+                    .await
+            }
+            _ => question,
+        };
+
+        self.emit(AgentProgress::Info(format!(
+            "👥 Modo equipe: planejando {} subproblemas",
+            self.team_size
+        )));
+
+        let planner = ResearchPlanner::new(self.llm_client.clone());
+        let plan = self
+            .cancel_token
+            .run_until_cancelled(planner.plan_research(&question, self.team_size, "", &mut tracker))
+            .await;
+        let subproblems = match plan {
+            None => {
+                tracker.track_shared_usage(
+                    self.search_client.as_ref(),
+                    self.llm_client.as_ref(),
+                    usage_before,
+                );
+                return merge_results(
+                    Vec::new(),
+                    None,
+                    tracker,
+                    start_time.elapsed().as_millis(),
+                    true,
+                )
+            }
+            Some(Ok(subproblems)) if subproblems.len() > 1 => subproblems,
+            Some(Ok(_)) => return self.run_single(question, start_time.elapsed()).await,
+            Some(Err(e)) => {
+                self.emit(AgentProgress::Warning(format!(
+                    "Planejamento em equipe indisponível ({}), usando agente único",
+                    e
+                )));
+                return self.run_single(question, start_time.elapsed()).await;
+            }
+        };
+
+        self.emit(AgentProgress::TeamPlan {
+            subproblems: subproblems.clone(),
+        });
+
+        // Budget: reserva para planner/reducer, restante dividido igualmente
+        let overhead = ((self.token_budget as f64) * TEAM_OVERHEAD_SHARE) as u64;
+        let per_agent_budget = self
+            .token_budget
+            .saturating_sub(overhead.max(tracker.total_tokens()))
+            / subproblems.len() as u64;
+        let per_agent_cost = self.cost_budget.map(|budget| {
+            (budget * (1.0 - TEAM_OVERHEAD_SHARE) - tracker.cost().total_usd).max(0.0)
+                / subproblems.len() as f64
+        });
+
+        let shared_urls: SharedUrlSet =
+            Arc::new(Mutex::new(self.prior_visited_urls().into_iter().collect()));
+        let prior_knowledge = self.prior_knowledge();
+
+        // Prazo: mesma reserva proporcional do budget de tokens
+        let per_agent_time = self.time_budget.map(|budget| {
+            budget
+                .mul_f64(1.0 - TEAM_OVERHEAD_SHARE)
+                .saturating_sub(start_time.elapsed())
+        });
+
+        let handles: Vec<_> = subproblems
+            .iter()
+            .enumerate()
+            .map(|(agent_index, subproblem)| {
+                let llm_client = match &self.llm_factory {
+                    Some(factory) => factory(),
+                    None => self.llm_client.clone(),
+                };
+
+                let mut agent = DeepResearchAgent::new(
+                    llm_client,
+                    self.search_client.clone(),
+                    Some(per_agent_budget),
+                )
+                .with_shared_visited_urls(shared_urls.clone())
+                .with_prior_research(prior_knowledge.clone(), Vec::new())
+                .with_cancellation_token(self.cancel_token.child_token())
+                .with_time_beast_mode_fraction(self.time_beast_mode_fraction)
+                .with_pricing(&self.pricing)
+                .with_credibility(self.credibility.clone())
+                .with_approval_policy(self.approval_policy.clone());
+
+                if let Some(budget) = per_agent_time {
+                    agent = agent.with_time_budget(budget);
+                }
+                if let Some(budget) = per_agent_cost {
+                    agent = agent.with_cost_budget(budget);
+                }
+
+                if let Some(cb) = &self.progress_callback {
+                    let cb = cb.clone();
+                    agent = agent.with_progress_callback(Arc::new(move |event| {
+                        cb(AgentProgress::SubAgent {
+                            agent_index,
+                            event: Box::new(event),
+                        })
+                    }));
+                }
+
+                let subproblem = subproblem.clone();
+                tokio::spawn(async move { agent.run(subproblem).await })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (agent_index, handle) in handles.into_iter().enumerate() {
+            match handle.await {
+                Ok(result) => results.push(result),
+                Err(e) => self.emit(AgentProgress::Error(format!(
+                    "Sub-agente {} abortou: {}",
+                    agent_index + 1,
+                    e
+                ))),
+            }
+        }
+
+        // Marcadores `[^k]` de cada sub-agente passam à numeração mesclada
+        let (_, numbering) = merge_references(&results);
+        let answers: Vec<String> = results
+            .iter()
+            .zip(&numbering)
+            .filter(|(r, _)| r.success)
+            .filter_map(|(r, numbers)| {
+                r.answer
+                    .as_deref()
+                    .map(|answer| renumber_citations(answer, numbers))
+            })
+            .collect();
+
+        self.emit(AgentProgress::Info(format!(
+            "👥 {}/{} sub-agentes responderam, mesclando respostas",
+            answers.len(),
+            subproblems.len()
+        )));
+
+        let reducer = ResponseReducer::new(self.llm_client.clone());
+        let reduced = self
+            .cancel_token
+            .run_until_cancelled(reducer.reduce_answers(&answers, &mut tracker))
+            .await;
+        let answer = match reduced {
+            Some(Ok(answer)) => Some(answer),
+            None => None,
+            Some(Err(e)) => {
+                self.emit(AgentProgress::Error(format!(
+                    "Falha ao mesclar respostas: {}",
+                    e
+                )));
+                None
+            }
+        };
+
+        // Resposta já reduzida vale mesmo se o cancelamento chegou depois
+        let cancelled = answer.is_none() && self.cancel_token.is_cancelled();
+        tracker.track_shared_usage(
+            self.search_client.as_ref(),
+            self.llm_client.as_ref(),
+            usage_before,
+        );
+        let result = merge_results(
+            results,
+            answer,
+            tracker,
+            start_time.elapsed().as_millis(),
+            cancelled,
+        );
+        self.enforce_schema(&question, result).await
+    }
+
+    /// Fallback: pergunta não decomponível, roda um agente com o budget inteiro
+    async fn run_single(self, question: String, elapsed: Duration) -> ResearchResult {
+        self.single_agent(elapsed).run(question).await
+    }
+
+    /// Agente único com toda a configuração da equipe
+    fn single_agent(self, elapsed: Duration) -> DeepResearchAgent {
+        let prior_knowledge = self.prior_knowledge();
+        let prior_visited_urls = self.prior_visited_urls();
+        let mut agent =
+            DeepResearchAgent::new(self.llm_client, self.search_client, Some(self.token_budget))
+                .with_prior_research(prior_knowledge, prior_visited_urls)
+                .with_cancellation_token(self.cancel_token)
+                .with_time_beast_mode_fraction(self.time_beast_mode_fraction)
+                .with_pricing(&self.pricing)
+                .with_credibility(self.credibility)
+                .with_approval_policy(self.approval_policy);
+        if let Some(budget) = self.time_budget {
+            agent = agent.with_time_budget(budget.saturating_sub(elapsed));
+        }
+        if let Some(budget) = self.cost_budget {
+            agent = agent.with_cost_budget(budget);
+        }
+        if let Some(schema) = self.response_schema {
+            agent = agent.with_response_schema(schema);
+        }
+        if let Some(cb) = self.progress_callback {
+            agent = agent.with_progress_callback(cb);
+        }
+        agent
+    }
+}
+
+/// Pesquisa a executar: agente único, equipe ou relatório
+pub enum ResearchRun {
+    /// Um único `DeepResearchAgent`
+    Single(Box<DeepResearchAgent>),
+    /// Equipe de agentes coordenada por `TeamResearch`
+    Team(Box<TeamResearch>),
+    /// Relatório longo por seções (`ReportResearch`)
+    Report(Box<ReportResearch>),
+}
+
+impl ResearchRun {
+    /// Configura callback de progresso no agente, na equipe ou no relatório
+    pub fn with_progress_callback(self, callback: ProgressCallback) -> Self {
+        match self {
+            Self::Single(agent) => Self::Single(Box::new(agent.with_progress_callback(callback))),
+            Self::Team(team) => Self::Team(Box::new(team.with_progress_callback(callback))),
+            Self::Report(report) => Self::Report(Box::new(report.with_progress_callback(callback))),
+        }
+    }
+
+    /// Usa um token de cancelamento externo no agente, na equipe ou no relatório
+    pub fn with_cancellation_token(self, token: CancellationToken) -> Self {
+        match self {
+            Self::Single(agent) => Self::Single(Box::new(agent.with_cancellation_token(token))),
+            Self::Team(team) => Self::Team(Box::new(team.with_cancellation_token(token))),
+            Self::Report(report) => Self::Report(Box::new(report.with_cancellation_token(token))),
+        }
+    }
+
+    /// Continua uma conversa no agente, na equipe ou no relatório
+    pub fn with_conversation(self, conversation: &ConversationState) -> Self {
+        match self {
+            Self::Single(agent) => Self::Single(Box::new(agent.with_conversation(conversation))),
+            Self::Team(team) => Self::Team(Box::new(team.with_conversation(conversation))),
+            Self::Report(report) => Self::Report(Box::new(report.with_conversation(conversation))),
+        }
+    }
+
+    /// Executa a pesquisa
+    pub async fn run(self, question: String) -> ResearchResult {
+        match self {
+            Self::Single(agent) => agent.run(question).await,
+            Self::Team(team) => team.run(question).await,
+            Self::Report(report) => report.run(question).await,
+        }
+    }
+}
+
+impl From<DeepResearchAgent> for ResearchRun {
+    fn from(agent: DeepResearchAgent) -> Self {
+        Self::Single(Box::new(agent))
+    }
+}
+
+impl From<TeamResearch> for ResearchRun {
+    fn from(team: TeamResearch) -> Self {
+        Self::Team(Box::new(team))
+    }
+}
+
+impl From<ReportResearch> for ResearchRun {
+    fn from(report: ReportResearch) -> Self {
+        Self::Report(Box::new(report))
+    }
+}
+
+/// Referências dos sub-agentes aprovados, sem URLs repetidas
+///
+/// Também retorna, por resultado, o mapa do marcador `[^k]` do sub-agente
+/// para o número na lista mesclada. Entre duplicatas fica a mais relevante.
+pub(super) fn merge_references(
+    results: &[ResearchResult],
+) -> (Vec<Reference>, Vec<HashMap<usize, usize>>) {
+    let mut references: Vec<Reference> = Vec::new();
+    let mut reference_index: HashMap<String, usize> = HashMap::new();
+    let mut numbering = Vec::with_capacity(results.len());
+
+    for result in results {
+        let mut numbers = HashMap::new();
+        if result.success {
+            for (k, reference) in citation_order(&result.references).into_iter().enumerate() {
+                let mut reference = reference.clone();
+                // Posições apontavam para a resposta do sub-agente, não a mesclada
+                reference.answer_chunk = None;
+                reference.answer_position = None;
+                let index = match reference_index.get(&reference.url) {
+                    Some(&i) => {
+                        if reference.relevance_score > references[i].relevance_score {
+                            references[i] = reference;
+                        }
+                        i
+                    }
+                    None => {
+                        reference_index.insert(reference.url.clone(), references.len());
+                        references.push(reference);
+                        references.len() - 1
+                    }
+                };
+                numbers.insert(k + 1, index + 1);
+            }
+        }
+        numbering.push(numbers);
+    }
+
+    (references, numbering)
+}
+
+/// Troca os marcadores `[^k]` de um sub-agente pela numeração mesclada
+///
+/// Marcadores sem referência correspondente são removidos.
+fn renumber_citations(answer: &str, numbers: &HashMap<usize, usize>) -> String {
+    let mut text = String::with_capacity(answer.len());
+    let mut last = 0;
+    for (number, start, end) in citation_markers(answer) {
+        text.push_str(&answer[last..start]);
+        if let Some(merged) = numbers.get(&number) {
+            text.push_str(&format!("[^{}]", merged));
+        }
+        last = end;
+    }
+    text.push_str(&answer[last..]);
+    text
+}
+
+/// Combina os resultados dos sub-agentes com a resposta reduzida
+///
+/// A resposta deve usar a numeração de `merge_references`; as posições das
+/// referências são recalculadas sobre ela.
+pub(super) fn merge_results(
+    results: Vec<ResearchResult>,
+    answer: Option<String>,
+    tracker: TokenTracker,
+    total_time_ms: u128,
+    cancelled: bool,
+) -> ResearchResult {
+    let mut token_usage = tracker.get_total_usage();
+    // Créditos de busca já contados pela equipe (cliente compartilhado)
+    let mut cost = tracker.cost();
+    let mut visited_urls = Vec::new();
+    let mut seen_urls = HashSet::new();
+    let (mut references, _) = merge_references(&results);
+    if let Some(answer) = &answer {
+        realign_references(answer, &mut references);
+    }
+    let mut errors = Vec::new();
+    let mut partial_knowledge = Vec::new();
+    let mut knowledge: Vec<KnowledgeItem> = Vec::new();
+    let mut source_credibility: Vec<CredibilityScore> = Vec::new();
+    let (mut search_time_ms, mut read_time_ms, mut llm_time_ms) = (0, 0, 0);
+
+    for result in results {
+        token_usage.prompt_tokens += result.token_usage.prompt_tokens;
+        token_usage.completion_tokens += result.token_usage.completion_tokens;
+        token_usage.total_tokens += result.token_usage.total_tokens;
+        cost += result.cost.without_search();
+        search_time_ms += result.search_time_ms;
+        read_time_ms += result.read_time_ms;
+        llm_time_ms += result.llm_time_ms;
+
+        for item in result.knowledge {
+            if !knowledge
+                .iter()
+                .any(|k| k.question == item.question && k.answer == item.answer)
+            {
+                knowledge.push(item);
+            }
+        }
+
+        for url in result.visited_urls {
+            if seen_urls.insert(url.clone()) {
+                visited_urls.push(url);
+            }
+        }
+
+        for score in result.source_credibility {
+            if !source_credibility.iter().any(|s| s.url == score.url) {
+                source_credibility.push(score);
+            }
+        }
+
+        if !result.success {
+            partial_knowledge.extend(result.partial_knowledge);
+            if let Some(error) = result.error {
+                errors.push(error);
+            }
+        }
+    }
+
+    let success = answer.is_some() && !cancelled;
+    source_credibility.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ResearchResult {
+        success,
+        answer,
+        references,
+        trivial: false,
+        token_usage,
+        cost,
+        structured: None,
+        report: None,
+        beast_mode_vote: None,
+        claim_checks: vec![],
+        disputed_facts: vec![],
+        source_credibility,
+        knowledge,
+        visited_urls,
+        error: if success {
+            None
+        } else if cancelled {
+            Some("Pesquisa cancelada".into())
+        } else if errors.is_empty() {
+            Some("Nenhum sub-agente produziu resposta".into())
+        } else {
+            Some(errors.join("; "))
+        },
+        cancelled,
+        partial_knowledge: if success {
+            Vec::new()
+        } else {
+            partial_knowledge
+        },
+        total_time_ms,
+        search_time_ms,
+        read_time_ms,
+        llm_time_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::TokenUsage;
+    use crate::agent::AgentAction;
+    use crate::utils::CostBreakdown;
+
+    fn result(success: bool, answer: &str, urls: &[&str], tokens: u64) -> ResearchResult {
+        ResearchResult {
+            success,
+            answer: success.then(|| answer.to_string()),
+            references: urls
+                .iter()
+                .map(|u| Reference {
+                    url: u.to_string(),
+                    title: u.to_string(),
+                    answer_position: Some((0, 4)),
+                    ..Default::default()
+                })
+                .collect(),
+            trivial: false,
+            token_usage: TokenUsage {
+                prompt_tokens: tokens,
+                completion_tokens: 0,
+                total_tokens: tokens,
+            },
+            cost: CostBreakdown::new(0.01, 0.02, 0.0, 0.05),
+            structured: None,
+            report: None,
+            beast_mode_vote: None,
+            claim_checks: vec![],
+            disputed_facts: vec![],
+            source_credibility: vec![],
+            knowledge: vec![],
+            visited_urls: urls.iter().map(|u| u.to_string()).collect(),
+            error: (!success).then(|| "budget".to_string()),
+            cancelled: false,
+            partial_knowledge: vec![],
+            total_time_ms: 10,
+            search_time_ms: 1,
+            read_time_ms: 2,
+            llm_time_ms: 3,
+        }
+    }
+
+    #[test]
+    fn test_merge_results_dedups_urls_and_sums_tokens() {
+        let results = vec![
+            result(true, "A", &["https://a.com", "https://b.com"], 100),
+            result(true, "B", &["https://b.com", "https://c.com"], 200),
+            result(false, "", &["https://d.com"], 50),
+        ];
+
+        let merged = merge_results(
+            results,
+            Some("A + B".into()),
+            TokenTracker::new(None),
+            42,
+            false,
+        );
+
+        assert!(merged.success);
+        assert_eq!(merged.answer.as_deref(), Some("A + B"));
+        assert_eq!(merged.token_usage.total_tokens, 350);
+        // Busca é contada pela equipe, não somada dos sub-agentes
+        assert!((merged.cost.total_usd - 0.09).abs() < 1e-9);
+        assert_eq!(merged.cost.search_usd, 0.0);
+        assert_eq!(
+            merged.visited_urls,
+            vec![
+                "https://a.com",
+                "https://b.com",
+                "https://c.com",
+                "https://d.com"
+            ]
+        );
+        assert_eq!(merged.references.len(), 3);
+        assert!(merged
+            .references
+            .iter()
+            .all(|r| r.answer_position.is_none()));
+        assert_eq!(merged.total_time_ms, 42);
+        assert_eq!(merged.llm_time_ms, 9);
+    }
+
+    #[test]
+    fn test_merge_renumbers_sub_agent_citations() {
+        let cited = |answer: &str, url: &str| {
+            let mut r = result(true, answer, &[url], 10);
+            r.references[0].answer_position = Some((0, answer.len()));
+            r
+        };
+        let results = vec![
+            cited("Rust é rápido.[^1]", "https://rust.org"),
+            cited("Go é simples.[^1]", "https://go.dev"),
+        ];
+
+        let (_, numbering) = merge_references(&results);
+        let answers: Vec<String> = results
+            .iter()
+            .zip(&numbering)
+            .map(|(r, numbers)| renumber_citations(r.answer.as_deref().unwrap(), numbers))
+            .collect();
+        assert_eq!(answers, vec!["Rust é rápido.[^1]", "Go é simples.[^2]"]);
+
+        // Reducer reordena o texto; cada marcador continua na sua fonte
+        let reduced = format!("{}\n\n{}", answers[1], answers[0]);
+        let merged = merge_results(results, Some(reduced), TokenTracker::new(None), 1, false);
+
+        let rust = &merged.references[0];
+        assert_eq!(rust.url, "https://rust.org");
+        assert_eq!(rust.answer_chunk.as_deref(), Some("Rust é rápido."));
+        let go = &merged.references[1];
+        assert_eq!(go.url, "https://go.dev");
+        assert_eq!(go.answer_chunk.as_deref(), Some("Go é simples."));
+    }
+
+    #[test]
+    fn test_merge_results_without_answer_fails() {
+        let results = vec![result(false, "", &[], 10)];
+        let merged = merge_results(results, None, TokenTracker::new(None), 1, false);
+
+        assert!(!merged.success);
+        assert_eq!(merged.error.as_deref(), Some("budget"));
+    }
+
+    #[test]
+    fn test_merge_results_cancelled() {
+        let results = vec![result(true, "A", &["https://a.com"], 10)];
+        let merged = merge_results(results, Some("A".into()), TokenTracker::new(None), 1, true);
+
+        assert!(!merged.success);
+        assert!(merged.cancelled);
+        assert_eq!(merged.token_usage.total_tokens, 10);
+        assert_eq!(merged.error.as_deref(), Some("Pesquisa cancelada"));
+    }
+
+    #[test]
+    fn test_single_agent_fallback_keeps_approval_policy() {
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+
+        let team = TeamResearch::new(
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+            3,
+            None,
+        )
+        .with_approval_policy(ApprovalPolicy::new().require("coding"));
+
+        let agent = team.single_agent(Duration::ZERO);
+        assert!(agent.approval_policy.requires_approval(&AgentAction::Coding {
+            problem: "somar valores".into(),
+            context_vars: None,
+            language: None,
+            think: "calcular".into(),
+        }));
+    }
+}
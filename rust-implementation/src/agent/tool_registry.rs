@@ -0,0 +1,351 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// FERRAMENTAS EXTERNAS - Registro plugável de ações de integração
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Integrações (Paytour, Digisac, ...) implementam `Tool` e são registradas
+// em um `ToolRegistry` no agente. O registro:
+// - Lista as ferramentas permitidas no system prompt (nome, descrição e
+//   JSON Schema dos argumentos)
+// - Valida os argumentos da ação `tool` contra o schema antes de executar
+// - Converte o resultado em itens de conhecimento
+//
+// Adicionar uma integração não exige mudar `AgentAction`, o parser do LLM
+// nem as permissões: basta implementar `Tool` e registrar.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use super::{ActionPermissions, AgentContext};
+use crate::types::{KnowledgeItem, KnowledgeType};
+use crate::utils::{JsonSchema, SchemaError};
+
+/// Nomes reservados pelas ações nativas do agente
+const RESERVED_NAMES: &[&str] = &[
+    "search", "read", "reflect", "answer", "coding", "history", "ask_user", "tool",
+];
+
+/// Erros do registro e da execução de ferramentas
+#[derive(Debug, Error)]
+pub enum ToolError {
+    /// Nenhuma ferramenta registrada com esse nome
+    #[error("Unknown tool: {0}")]
+    NotFound(String),
+
+    /// Nome vazio, reservado ou já registrado
+    #[error("Invalid tool name: {0}")]
+    InvalidName(String),
+
+    /// JSON Schema dos parâmetros inválido
+    #[error("Invalid parameters schema for tool {tool}: {source}")]
+    InvalidSchema {
+        /// Ferramenta
+        tool: String,
+        /// Erro do schema
+        source: SchemaError,
+    },
+
+    /// Ferramenta não permitida no step atual
+    #[error("Tool not allowed in this step: {0}")]
+    NotAllowed(String),
+
+    /// Argumentos fora do schema
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    /// Falha durante a execução
+    #[error("Tool execution failed: {0}")]
+    Execution(String),
+}
+
+/// Ferramenta externa que o agente pode chamar com a ação `tool`
+///
+/// # Exemplo
+/// ```rust,ignore
+/// struct Weather;
+///
+/// #[async_trait]
+/// impl Tool for Weather {
+///     fn name(&self) -> &str { "weather" }
+///     fn description(&self) -> &str { "Current weather for a city" }
+///     fn parameters(&self) -> serde_json::Value {
+///         json!({"type": "object", "properties": {"city": {"type": "string"}}, "required": ["city"]})
+///     }
+///     async fn execute(&self, arguments: serde_json::Value) -> Result<Vec<KnowledgeItem>, ToolError> {
+///         // ...
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Tool: Send + Sync {
+    /// Nome usado pelo LLM em `{"action": "tool", "tool": "<nome>"}`
+    fn name(&self) -> &str;
+
+    /// Descrição exibida no system prompt
+    fn description(&self) -> &str;
+
+    /// JSON Schema dos argumentos
+    fn parameters(&self) -> serde_json::Value;
+
+    /// Se a ferramenta pode ser oferecida no step atual
+    ///
+    /// Padrão: segue `permissions.tools` (desabilitado em Beast Mode).
+    fn is_allowed(&self, permissions: &ActionPermissions, _context: &AgentContext) -> bool {
+        permissions.tools
+    }
+
+    /// Executa a ferramenta com argumentos já validados contra `parameters`
+    async fn execute(&self, arguments: serde_json::Value) -> Result<Vec<KnowledgeItem>, ToolError>;
+}
+
+/// Converte o resultado JSON de uma ferramenta em item de conhecimento
+pub fn tool_knowledge(
+    tool: &str,
+    arguments: &serde_json::Value,
+    result: &serde_json::Value,
+) -> KnowledgeItem {
+    KnowledgeItem {
+        question: format!("[{}] {}", tool, arguments),
+        answer: serde_json::to_string_pretty(result).unwrap_or_else(|_| result.to_string()),
+        item_type: KnowledgeType::Tool,
+        references: vec![],
+    }
+}
+
+/// Ferramenta registrada com o schema já compilado
+#[derive(Clone)]
+struct RegisteredTool {
+    tool: Arc<dyn Tool>,
+    schema: JsonSchema,
+}
+
+/// Ferramentas disponíveis para o agente, na ordem de registro
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<RegisteredTool>,
+}
+
+impl ToolRegistry {
+    /// Cria um registro vazio
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra uma ferramenta
+    ///
+    /// Falha se o nome estiver vazio, for de uma ação nativa ou já estiver
+    /// registrado, ou se o schema dos parâmetros for inválido.
+    pub fn register(&mut self, tool: Arc<dyn Tool>) -> Result<(), ToolError> {
+        let name = tool.name().to_string();
+        if name.trim().is_empty()
+            || RESERVED_NAMES.contains(&name.as_str())
+            || self.get(&name).is_some()
+        {
+            return Err(ToolError::InvalidName(name));
+        }
+
+        let schema =
+            JsonSchema::new(tool.parameters()).map_err(|source| ToolError::InvalidSchema {
+                tool: name.clone(),
+                source,
+            })?;
+
+        log::info!("🧰 Ferramenta registrada: {}", name);
+        self.tools.push(RegisteredTool { tool, schema });
+        Ok(())
+    }
+
+    /// Variante encadeável de `register`
+    pub fn with_tool(mut self, tool: Arc<dyn Tool>) -> Result<Self, ToolError> {
+        self.register(tool)?;
+        Ok(self)
+    }
+
+    /// Busca uma ferramenta pelo nome
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn Tool>> {
+        self.find(name).map(|registered| &registered.tool)
+    }
+
+    /// Nomes das ferramentas registradas
+    pub fn names(&self) -> Vec<&str> {
+        self.tools.iter().map(|t| t.tool.name()).collect()
+    }
+
+    /// Número de ferramentas registradas
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Verifica se não há ferramentas
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Ferramentas permitidas no step atual
+    pub fn allowed<'a>(
+        &'a self,
+        permissions: &'a ActionPermissions,
+        context: &'a AgentContext,
+    ) -> impl Iterator<Item = &'a Arc<dyn Tool>> + 'a {
+        self.tools
+            .iter()
+            .map(|t| &t.tool)
+            .filter(move |tool| tool.is_allowed(permissions, context))
+    }
+
+    /// Seção do system prompt com as ferramentas permitidas
+    ///
+    /// Retorna `None` se nenhuma ferramenta estiver disponível.
+    pub fn prompt_section(
+        &self,
+        permissions: &ActionPermissions,
+        context: &AgentContext,
+    ) -> Option<String> {
+        let lines: Vec<String> = self
+            .allowed(permissions, context)
+            .map(|tool| {
+                format!(
+                    "  • {}: {}\n    arguments (JSON Schema): {}",
+                    tool.name(),
+                    tool.description(),
+                    tool.parameters()
+                )
+            })
+            .collect();
+
+        if lines.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "- TOOL: Call an external tool with JSON arguments matching its schema\n{}\n",
+            lines.join("\n")
+        ))
+    }
+
+    /// Valida os argumentos e executa a ferramenta
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> Result<Vec<KnowledgeItem>, ToolError> {
+        let registered = self
+            .find(name)
+            .ok_or_else(|| ToolError::NotFound(name.to_string()))?;
+
+        registered
+            .schema
+            .validate(&arguments)
+            .map_err(|violations| {
+                ToolError::InvalidArguments(
+                    violations
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                )
+            })?;
+
+        registered.tool.execute(arguments).await
+    }
+
+    fn find(&self, name: &str) -> Option<&RegisteredTool> {
+        self.tools.iter().find(|t| t.tool.name() == name)
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    #[async_trait]
+    impl Tool for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Repeats the text"
+        }
+
+        fn parameters(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            })
+        }
+
+        async fn execute(
+            &self,
+            arguments: serde_json::Value,
+        ) -> Result<Vec<KnowledgeItem>, ToolError> {
+            Ok(vec![KnowledgeItem {
+                question: "echo".into(),
+                answer: arguments["text"].as_str().unwrap_or_default().into(),
+                item_type: KnowledgeType::Tool,
+                references: vec![],
+            }])
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates_and_reserved_names() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(Echo)).unwrap();
+
+        assert!(matches!(
+            registry.register(Arc::new(Echo)),
+            Err(ToolError::InvalidName(_))
+        ));
+        assert_eq!(registry.names(), vec!["echo"]);
+        assert!(RESERVED_NAMES.contains(&"search"));
+    }
+
+    #[tokio::test]
+    async fn test_call_validates_arguments() {
+        let registry = ToolRegistry::new().with_tool(Arc::new(Echo)).unwrap();
+
+        let items = registry
+            .call("echo", serde_json::json!({ "text": "oi" }))
+            .await
+            .unwrap();
+        assert_eq!(items[0].answer, "oi");
+
+        assert!(matches!(
+            registry
+                .call("echo", serde_json::json!({ "text": 1 }))
+                .await,
+            Err(ToolError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            registry.call("missing", serde_json::json!({})).await,
+            Err(ToolError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_prompt_section_respects_permissions() {
+        let registry = ToolRegistry::new().with_tool(Arc::new(Echo)).unwrap();
+        let context = AgentContext::new();
+
+        let section = registry
+            .prompt_section(&ActionPermissions::all_enabled(), &context)
+            .unwrap();
+        assert!(section.contains("• echo: Repeats the text"));
+        assert!(registry
+            .prompt_section(&ActionPermissions::beast_mode(), &context)
+            .is_none());
+    }
+}
@@ -0,0 +1,469 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// TRANSCRIPT - Registro append-only de cada execução (JSONL)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Cada execução grava `<dir>/<run_id>.jsonl`, um evento por linha:
+// - Decisão de cada step (ação, raciocínio, tamanho do prompt, tokens)
+// - Resultado do step (entradas do diário, delta de tokens, URLs)
+// - Veredictos da avaliação e logs de progresso
+// - Início e fim da execução
+//
+// O arquivo só recebe anexos, então sobrevive a crashes (no máximo a última
+// linha fica incompleta e é ignorada na leitura). Uma execução retomada de
+// checkpoint continua no mesmo arquivo. A TUI reproduz transcripts com
+// `tui::run_replay` e a CLI imprime uma linha do tempo com `format_timeline`.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::DiaryEntry;
+
+/// Diretório padrão dos transcripts (relativo ao diretório atual)
+pub const DEFAULT_TRANSCRIPT_DIR: &str = "transcripts";
+
+/// Erros de leitura de transcripts
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptError {
+    /// Falha de I/O ao ler ou gravar o arquivo
+    #[error("Transcript I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Nenhum transcript encontrado para o id/caminho informado
+    #[error("Transcript not found: {0}")]
+    NotFound(String),
+
+    /// Arquivo sem nenhum evento válido
+    #[error("Transcript is empty: {0}")]
+    Empty(String),
+}
+
+/// Nível de um log de progresso
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptLevel {
+    /// Informação geral
+    Info,
+    /// Operação bem sucedida
+    Success,
+    /// Aviso
+    Warning,
+    /// Erro
+    Error,
+}
+
+/// Evento registrado no transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TranscriptEvent {
+    /// Início (ou retomada) da execução
+    RunStarted {
+        /// ID da execução
+        run_id: String,
+        /// Pergunta original
+        question: String,
+        /// Se a execução foi retomada de um checkpoint
+        resumed: bool,
+        /// Data/hora (ISO 8601)
+        started_at: String,
+    },
+    /// Decisão do LLM em um step
+    Decision {
+        /// Step em que a decisão foi tomada
+        step: usize,
+        /// Pergunta sendo trabalhada (original ou gap)
+        question: String,
+        /// Nome da ação escolhida
+        action: String,
+        /// Raciocínio do LLM
+        think: String,
+        /// Tamanho do prompt (system + user), em caracteres
+        prompt_chars: usize,
+        /// Tokens do prompt estimados
+        prompt_tokens: usize,
+        /// Tokens de prompt cobrados pela decisão
+        prompt_tokens_used: u64,
+        /// Tokens de completion cobrados pela decisão
+        completion_tokens_used: u64,
+        /// Duração da chamada ao LLM
+        duration_ms: u128,
+    },
+    /// Resultado de um step concluído
+    StepCompleted {
+        /// Número do step após a conclusão
+        step: usize,
+        /// Entradas do diário geradas pelo step
+        entries: Vec<DiaryEntry>,
+        /// Tokens consumidos até aqui
+        tokens_used: u64,
+        /// Tokens consumidos pelo step
+        tokens_delta: u64,
+        /// Itens de conhecimento acumulados
+        knowledge_items: usize,
+        /// URLs coletadas
+        total_urls: usize,
+        /// URLs visitadas
+        visited_urls: usize,
+    },
+    /// Veredicto de uma etapa da avaliação
+    Evaluation {
+        /// Tipo de avaliação
+        eval_type: String,
+        /// Se passou
+        passed: bool,
+        /// Confiança (0.0 - 1.0)
+        confidence: f32,
+        /// Explicação do avaliador
+        reasoning: String,
+        /// Duração em ms
+        duration_ms: u128,
+    },
+    /// URL lida com sucesso
+    VisitedUrl {
+        /// URL
+        url: String,
+    },
+    /// Log de progresso
+    Log {
+        /// Nível
+        level: TranscriptLevel,
+        /// Mensagem
+        message: String,
+    },
+    /// Fim da execução
+    RunFinished {
+        /// Estado final ("completed", "failed", "cancelled")
+        state: String,
+        /// Resposta final, se houver
+        answer: Option<String>,
+        /// URLs das referências
+        references: Vec<String>,
+        /// Motivo da falha, se houver
+        error: Option<String>,
+        /// Tokens consumidos
+        tokens_used: u64,
+    },
+}
+
+/// Linha do transcript: evento + tempo desde o início da execução
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptRecord {
+    /// Milissegundos desde o início da execução
+    pub elapsed_ms: u64,
+    /// Evento
+    #[serde(flatten)]
+    pub event: TranscriptEvent,
+}
+
+/// Caminho do transcript de uma execução
+pub fn transcript_path(dir: &Path, run_id: &str) -> PathBuf {
+    dir.join(format!("{}.jsonl", run_id))
+}
+
+/// Grava eventos em um transcript JSONL (somente anexos)
+#[derive(Debug)]
+pub struct TranscriptWriter {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl TranscriptWriter {
+    /// Abre (ou cria) o transcript em modo append
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TranscriptError> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Caminho do arquivo
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Anexa um evento. Falhas apenas geram log.
+    pub fn append(&self, elapsed_ms: u64, event: TranscriptEvent) {
+        let record = TranscriptRecord { elapsed_ms, event };
+        let line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("⚠️ Falha ao serializar evento do transcript: {}", e);
+                return;
+            }
+        };
+
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+        if let Err(e) = writeln!(file, "{}", line) {
+            log::warn!(
+                "⚠️ Falha ao gravar transcript {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Lê um transcript, ignorando linhas inválidas (ex: última linha truncada)
+pub fn read_transcript(path: &Path) -> Result<Vec<TranscriptRecord>, TranscriptError> {
+    if !path.is_file() {
+        return Err(TranscriptError::NotFound(path.display().to_string()));
+    }
+
+    let reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<TranscriptRecord>(&line) {
+            Ok(record) => records.push(record),
+            Err(e) => log::warn!("⚠️ Transcript linha {} ignorada: {}", index + 1, e),
+        }
+    }
+
+    if records.is_empty() {
+        return Err(TranscriptError::Empty(path.display().to_string()));
+    }
+    Ok(records)
+}
+
+/// Localiza um transcript por caminho, run_id ou prefixo do run_id
+pub fn find_transcript(dir: &Path, id_or_path: &str) -> Result<PathBuf, TranscriptError> {
+    let explicit = PathBuf::from(id_or_path);
+    if explicit.is_file() {
+        return Ok(explicit);
+    }
+
+    let exact = transcript_path(dir, id_or_path);
+    if exact.is_file() {
+        return Ok(exact);
+    }
+
+    let mut matches: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+                .filter(|p| {
+                    p.file_stem()
+                        .and_then(|s| s.to_str())
+                        .is_some_and(|s| s.starts_with(id_or_path))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        _ => Err(TranscriptError::NotFound(id_or_path.to_string())),
+    }
+}
+
+/// Pergunta original registrada no transcript
+pub fn transcript_question(records: &[TranscriptRecord]) -> Option<&str> {
+    records.iter().find_map(|r| match &r.event {
+        TranscriptEvent::RunStarted { question, .. } => Some(question.as_str()),
+        _ => None,
+    })
+}
+
+/// Linha do tempo compacta (uma linha por evento relevante)
+///
+/// Logs de progresso e URLs visitadas são omitidos.
+pub fn format_timeline(records: &[TranscriptRecord]) -> String {
+    let mut lines = Vec::new();
+
+    for record in records {
+        let time = format!("[{:>7.1}s]", record.elapsed_ms as f64 / 1000.0);
+        let line = match &record.event {
+            TranscriptEvent::RunStarted {
+                run_id,
+                question,
+                resumed,
+                ..
+            } => format!(
+                "{} ▶ {} {}: {}",
+                time,
+                if *resumed { "resume" } else { "start" },
+                run_id,
+                question
+            ),
+            TranscriptEvent::Decision {
+                step,
+                action,
+                think,
+                prompt_tokens,
+                duration_ms,
+                ..
+            } => format!(
+                "{} #{:<3} → {:<8} prompt ~{} tok | {}ms | {}",
+                time,
+                step,
+                action,
+                prompt_tokens,
+                duration_ms,
+                truncate(think, 80)
+            ),
+            TranscriptEvent::StepCompleted {
+                step,
+                entries,
+                tokens_delta,
+                tokens_used,
+                knowledge_items,
+                visited_urls,
+                ..
+            } => {
+                let summary = entries
+                    .last()
+                    .map(|e| e.format().lines().next().unwrap_or_default().to_string())
+                    .unwrap_or_else(|| "-".into());
+                format!(
+                    "{} #{:<3} ✓ {} | +{} tok ({} total) | {} itens | {} URLs lidas",
+                    time,
+                    step,
+                    truncate(&summary, 80),
+                    tokens_delta,
+                    tokens_used,
+                    knowledge_items,
+                    visited_urls
+                )
+            }
+            TranscriptEvent::Evaluation {
+                eval_type,
+                passed,
+                confidence,
+                ..
+            } => format!(
+                "{}      {} eval {} ({:.0}%)",
+                time,
+                if *passed { "✅" } else { "❌" },
+                eval_type,
+                confidence * 100.0
+            ),
+            TranscriptEvent::RunFinished {
+                state,
+                tokens_used,
+                error,
+                ..
+            } => match error {
+                Some(error) => format!("{} ■ {} ({} tok): {}", time, state, tokens_used, error),
+                None => format!("{} ■ {} ({} tok)", time, state, tokens_used),
+            },
+            TranscriptEvent::VisitedUrl { .. } | TranscriptEvent::Log { .. } => continue,
+        };
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Trunca texto em uma linha com no máximo `max` caracteres
+fn truncate(text: &str, max: usize) -> String {
+    let single_line = text.replace('\n', " ");
+    if single_line.chars().count() <= max {
+        single_line
+    } else {
+        format!("{}…", single_line.chars().take(max).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("deep-research-transcript-{}", uuid::Uuid::new_v4()))
+            .join("run.jsonl")
+    }
+
+    #[test]
+    fn test_write_and_read_roundtrip() {
+        let path = temp_path();
+        let writer = TranscriptWriter::open(&path).unwrap();
+        writer.append(
+            0,
+            TranscriptEvent::RunStarted {
+                run_id: "run".into(),
+                question: "Qual a capital da França?".into(),
+                resumed: false,
+                started_at: "2024-01-01T00:00:00Z".into(),
+            },
+        );
+        writer.append(
+            1500,
+            TranscriptEvent::StepCompleted {
+                step: 1,
+                entries: vec![DiaryEntry::Search {
+                    queries: vec![],
+                    think: "buscar".into(),
+                    urls_found: 3,
+                }],
+                tokens_used: 120,
+                tokens_delta: 120,
+                knowledge_items: 0,
+                total_urls: 3,
+                visited_urls: 0,
+            },
+        );
+        // Linha truncada por um crash é ignorada
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"elapsed_ms\": 2000, \"event\": \"run_fin")
+            .unwrap();
+
+        let records = read_transcript(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            transcript_question(&records),
+            Some("Qual a capital da França?")
+        );
+        assert!(matches!(
+            &records[1].event,
+            TranscriptEvent::StepCompleted { entries, .. } if entries.len() == 1
+        ));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_format_timeline_skips_logs() {
+        let records = vec![
+            TranscriptRecord {
+                elapsed_ms: 0,
+                event: TranscriptEvent::Log {
+                    level: TranscriptLevel::Info,
+                    message: "ruído".into(),
+                },
+            },
+            TranscriptRecord {
+                elapsed_ms: 2500,
+                event: TranscriptEvent::RunFinished {
+                    state: "completed".into(),
+                    answer: Some("Paris".into()),
+                    references: vec![],
+                    error: None,
+                    tokens_used: 42,
+                },
+            },
+        ];
+
+        assert_eq!(format_timeline(&records), "[    2.5s] ■ completed (42 tok)");
+    }
+}
@@ -0,0 +1,228 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// VOTAÇÃO POR AUTOCONSISTÊNCIA - Beast Mode com múltiplos candidatos
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Quando o budget acaba, o Beast Mode gera K respostas em paralelo com
+// temperaturas variadas. Cada candidata recebe:
+// - a fração das avaliações exigidas em que passou
+// - a concordância média (cosseno dos embeddings) com as demais
+//
+// Vence a maior pontuação. Se a vencedora não passou em todas as avaliações
+// e outras candidatas concordam com ela, as concordantes são mescladas pelo
+// `ResponseReducer`.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+
+use crate::performance::cosine_similarity;
+
+/// Número padrão de candidatas no Beast Mode
+pub const DEFAULT_BEAST_MODE_CANDIDATES: usize = 3;
+
+/// Número máximo de candidatas no Beast Mode
+pub const MAX_BEAST_MODE_CANDIDATES: usize = 7;
+
+/// Similaridade mínima para duas candidatas concordarem
+pub const AGREEMENT_THRESHOLD: f32 = 0.85;
+
+/// Peso da concordância na pontuação (a avaliação pesa 1.0)
+const AGREEMENT_WEIGHT: f32 = 0.5;
+
+/// Menor e maior temperatura usadas nas candidatas
+const MIN_TEMPERATURE: f32 = 0.3;
+const MAX_TEMPERATURE: f32 = 1.0;
+
+/// Como a resposta final foi escolhida
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteStrategy {
+    /// Apenas uma candidata válida
+    Single,
+    /// Candidata de maior pontuação
+    Best,
+    /// Mescla das candidatas que concordam com a melhor
+    Merged,
+}
+
+/// Resultado de uma candidata na votação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateVote {
+    /// Temperatura usada na geração
+    pub temperature: f32,
+    /// Avaliações aprovadas
+    pub passed_evals: usize,
+    /// Avaliações exigidas
+    pub required_evals: usize,
+    /// Confiança média das avaliações executadas
+    pub confidence: f32,
+    /// Similaridade média com as demais candidatas (0.0 - 1.0)
+    pub agreement: f32,
+    /// Pontuação final
+    pub score: f32,
+    /// Tamanho da resposta (caracteres)
+    pub chars: usize,
+    /// Se entrou na resposta final
+    pub selected: bool,
+}
+
+impl CandidateVote {
+    /// Se passou em todas as avaliações exigidas
+    pub fn passed_all(&self) -> bool {
+        self.passed_evals >= self.required_evals
+    }
+}
+
+/// Votação do Beast Mode exposta no `ResearchResult`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeastModeVote {
+    /// Candidatas, na ordem de geração
+    pub candidates: Vec<CandidateVote>,
+    /// Estratégia usada
+    pub strategy: VoteStrategy,
+    /// Índice da candidata vencedora
+    pub winner: usize,
+}
+
+impl BeastModeVote {
+    /// Índices das candidatas que entraram na resposta final
+    pub fn selected(&self) -> Vec<usize> {
+        self.candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.selected)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Temperaturas distribuídas entre `MIN_TEMPERATURE` e `MAX_TEMPERATURE`
+pub fn candidate_temperatures(count: usize) -> Vec<f32> {
+    match count {
+        0 => vec![],
+        1 => vec![0.7],
+        _ => (0..count)
+            .map(|i| {
+                MIN_TEMPERATURE + (MAX_TEMPERATURE - MIN_TEMPERATURE) * i as f32 / (count - 1) as f32
+            })
+            .collect(),
+    }
+}
+
+/// Similaridade média de cada candidata com as demais
+///
+/// Sem embeddings (ou com uma candidata só), todas recebem 0.0.
+pub fn agreement_scores(embeddings: &[Vec<f32>]) -> Vec<f32> {
+    let n = embeddings.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+    (0..n)
+        .map(|i| {
+            let total: f32 = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| cosine_similarity(&embeddings[i], &embeddings[j]).max(0.0))
+                .sum();
+            total / (n - 1) as f32
+        })
+        .collect()
+}
+
+/// Pontuação de uma candidata: fração aprovada + concordância ponderada
+pub fn candidate_score(passed_evals: usize, required_evals: usize, agreement: f32) -> f32 {
+    let passed = if required_evals == 0 {
+        1.0
+    } else {
+        passed_evals as f32 / required_evals as f32
+    };
+    passed + AGREEMENT_WEIGHT * agreement
+}
+
+/// Escolhe a vencedora: maior pontuação, desempate pela confiança
+pub fn pick_winner(candidates: &[CandidateVote]) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            a.score
+                .total_cmp(&b.score)
+                .then(a.confidence.total_cmp(&b.confidence))
+        })
+        .map(|(i, _)| i)
+}
+
+/// Candidatas que concordam com `winner` (inclui a própria)
+pub fn agreeing_with(embeddings: &[Vec<f32>], winner: usize, threshold: f32) -> Vec<usize> {
+    let Some(reference) = embeddings.get(winner) else {
+        return vec![winner];
+    };
+    (0..embeddings.len())
+        .filter(|&i| i == winner || cosine_similarity(reference, &embeddings[i]) >= threshold)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(passed: usize, agreement: f32, confidence: f32) -> CandidateVote {
+        CandidateVote {
+            temperature: 0.7,
+            passed_evals: passed,
+            required_evals: 2,
+            confidence,
+            agreement,
+            score: candidate_score(passed, 2, agreement),
+            chars: 100,
+            selected: false,
+        }
+    }
+
+    #[test]
+    fn test_candidate_temperatures() {
+        assert_eq!(candidate_temperatures(1), vec![0.7]);
+        let temps = candidate_temperatures(3);
+        assert_eq!(temps.len(), 3);
+        assert!((temps[0] - 0.3).abs() < 1e-6);
+        assert!((temps[1] - 0.65).abs() < 1e-6);
+        assert!((temps[2] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_voting_prefers_evaluation_then_agreement() {
+        let embeddings = vec![vec![1.0, 0.0], vec![0.99, 0.1], vec![0.0, 1.0]];
+        let agreement = agreement_scores(&embeddings);
+        assert!(agreement[0] > agreement[2]);
+        assert!(agreement[1] > agreement[2]);
+
+        // Avaliação domina; concordância desempata
+        let candidates = vec![
+            vote(1, agreement[0], 0.6),
+            vote(1, agreement[1], 0.9),
+            vote(2, agreement[2], 0.5),
+        ];
+        assert_eq!(pick_winner(&candidates), Some(2));
+        assert_eq!(pick_winner(&candidates[..2]), Some(1));
+
+        assert_eq!(agreeing_with(&embeddings, 0, AGREEMENT_THRESHOLD), vec![0, 1]);
+        assert_eq!(agreeing_with(&embeddings, 2, AGREEMENT_THRESHOLD), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_during_voting_keeps_candidate() {
+        use crate::agent::DeepResearchAgent;
+        use crate::llm::MockLlmClient;
+        use crate::search::MockSearchClient;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        // Beast Mode imediato; a votação leva mais que o prazo inteiro
+        let llm = MockLlmClient::new().with_evaluation_delay(Duration::from_secs(5));
+        let agent = DeepResearchAgent::new(Arc::new(llm), Arc::new(MockSearchClient::new()), None)
+            .with_time_budget(Duration::from_millis(300))
+            .with_time_beast_mode_fraction(0.0);
+
+        let result = agent.run("Qual a capital da França?".into()).await;
+        assert!(result.success);
+        assert_eq!(result.answer.as_deref(), Some("Mock generated answer"));
+    }
+}
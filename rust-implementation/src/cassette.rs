@@ -0,0 +1,988 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CASSETTES - Gravação e replay de chamadas LLM/busca
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Wrappers de gravação envolvem qualquer `Arc<dyn LlmClient>` ou
+// `Arc<dyn SearchClient>` e gravam cada par requisição/resposta em um arquivo
+// JSONL (uma interação por linha). Os clientes de replay tocam o cassette de
+// volta sem rede, permitindo reexecutar um `DeepResearchAgent::run` inteiro
+// offline e transformar sessões reais em testes de regressão.
+//
+// A primeira linha é um cabeçalho com os modelos da gravação, para que o
+// replay seja precificado como a execução original.
+//
+// Matching no replay:
+// 1. Primeira interação não consumida com mesmo método e mesma requisição
+// 2. Senão (modo leniente), primeira interação não consumida do mesmo método
+//    na ordem de gravação - tolera prompts com datas/horários
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::agent::{ActionPermissions, AgentAction, AgentPrompt, SandboxLanguage};
+use crate::evaluation::EvaluationType;
+use crate::llm::{
+    CodeGenResponse, EmbeddingResult, EvaluationResponse, LlmClient, LlmError, LlmResponse,
+};
+use crate::search::{
+    ComparativeReadResult, ReadMethod, SearchClient, SearchError, SearchResult, UrlContent,
+};
+use crate::types::{BoostedSearchSnippet, SerpQuery, Url};
+
+/// Erros de leitura/escrita de cassettes
+#[derive(Debug, thiserror::Error)]
+pub enum CassetteError {
+    /// Falha de I/O ao ler ou gravar o arquivo
+    #[error("Cassette I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON inválido ou resposta incompatível com o método
+    #[error("Cassette serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Nenhuma interação gravada corresponde à chamada
+    #[error("No recorded interaction left for {method}")]
+    Miss {
+        /// Método chamado no replay
+        method: String,
+    },
+}
+
+/// Par de clientes (LLM, busca) ligados ao mesmo cassette
+pub type ClientPair = (Arc<dyn LlmClient>, Arc<dyn SearchClient>);
+
+/// Uma chamada gravada (requisição + resposta)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Ordem de gravação (0, 1, 2...)
+    pub seq: u64,
+    /// Método do trait (ex: "llm.decide_action", "search.read_url")
+    pub method: String,
+    /// Argumentos da chamada
+    pub request: Value,
+    /// Valor retornado (inclui erros, ex: `{"Err": ...}`)
+    pub response: Value,
+    /// Tokens de prompt consumidos pela chamada
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    /// Tokens de completion consumidos pela chamada
+    #[serde(default)]
+    pub completion_tokens: u64,
+    /// Tokens de embedding consumidos pela chamada
+    #[serde(default)]
+    pub embedding_tokens: u64,
+    /// Créditos de busca/leitura consumidos pela chamada
+    #[serde(default)]
+    pub credits: u64,
+}
+
+/// Metadados gravados no cabeçalho do cassette
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CassetteHeader {
+    /// Modelo LLM da gravação (define o preço dos tokens)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Modelo de embeddings da gravação
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+}
+
+/// Linha de cabeçalho (`{"header": {...}}`), distinta das interações
+#[derive(Serialize)]
+struct HeaderLine<'a> {
+    header: &'a CassetteHeader,
+}
+
+/// Contadores de uso de uma chamada (diferença antes/depois)
+#[derive(Debug, Clone, Copy, Default)]
+struct CallUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    embedding_tokens: u64,
+    credits: u64,
+}
+
+impl CallUsage {
+    fn since(self, before: Self) -> Self {
+        Self {
+            prompt_tokens: self.prompt_tokens.saturating_sub(before.prompt_tokens),
+            completion_tokens: self.completion_tokens.saturating_sub(before.completion_tokens),
+            embedding_tokens: self.embedding_tokens.saturating_sub(before.embedding_tokens),
+            credits: self.credits.saturating_sub(before.credits),
+        }
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// GRAVAÇÃO
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+struct RecorderInner {
+    file: std::fs::File,
+    next_seq: u64,
+}
+
+/// Gravador de cassette compartilhável entre clientes LLM e de busca
+///
+/// Cada interação é anexada ao arquivo assim que a chamada termina,
+/// então um cassette sobrevive a um crash no meio da execução.
+#[derive(Clone)]
+pub struct CassetteRecorder {
+    inner: Arc<Mutex<RecorderInner>>,
+}
+
+impl CassetteRecorder {
+    /// Cria (ou sobrescreve) um cassette em `path`
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let file = std::fs::File::create(path)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RecorderInner { file, next_seq: 0 })),
+        })
+    }
+
+    /// Grava o cabeçalho com os modelos. Falhas apenas geram log.
+    fn write_header(&self, header: &CassetteHeader) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let written = serde_json::to_string(&HeaderLine { header })
+            .map_err(CassetteError::from)
+            .and_then(|line| writeln!(inner.file, "{}", line).map_err(CassetteError::from));
+        if let Err(e) = written {
+            log::warn!("⚠️ Falha ao gravar cabeçalho do cassette: {}", e);
+        }
+    }
+
+    /// Anexa uma interação ao cassette. Falhas apenas geram log.
+    fn record<T: Serialize>(
+        &self,
+        method: &str,
+        request: Value,
+        response: &T,
+        usage: CallUsage,
+    ) {
+        let response = match serde_json::to_value(response) {
+            Ok(value) => value,
+            Err(e) => {
+                log::warn!("⚠️ Cassette: resposta de {} não serializável: {}", method, e);
+                return;
+            }
+        };
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let interaction = Interaction {
+            seq: inner.next_seq,
+            method: method.to_string(),
+            request,
+            response,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            embedding_tokens: usage.embedding_tokens,
+            credits: usage.credits,
+        };
+        inner.next_seq += 1;
+
+        let written = serde_json::to_string(&interaction)
+            .map_err(CassetteError::from)
+            .and_then(|line| writeln!(inner.file, "{}", line).map_err(CassetteError::from));
+        if let Err(e) = written {
+            log::warn!("⚠️ Falha ao gravar cassette ({}): {}", method, e);
+        }
+    }
+}
+
+/// Wrapper que grava cada chamada de um `LlmClient`
+pub struct RecordingLlmClient {
+    inner: Arc<dyn LlmClient>,
+    recorder: CassetteRecorder,
+}
+
+impl RecordingLlmClient {
+    /// Envolve `inner`, gravando em `recorder` (inclui o cabeçalho de modelos)
+    pub fn new(inner: Arc<dyn LlmClient>, recorder: CassetteRecorder) -> Self {
+        recorder.write_header(&CassetteHeader {
+            model: inner.model_name().map(str::to_string),
+            embedding_model: inner.embedding_model_name().map(str::to_string),
+        });
+        Self { inner, recorder }
+    }
+
+    fn token_snapshot(&self) -> CallUsage {
+        CallUsage {
+            prompt_tokens: self.inner.get_prompt_tokens(),
+            completion_tokens: self.inner.get_completion_tokens(),
+            embedding_tokens: self.inner.get_embedding_tokens(),
+            credits: 0,
+        }
+    }
+
+    fn record<T: Serialize>(&self, method: &str, request: Value, before: CallUsage, response: &T) {
+        let usage = self.token_snapshot().since(before);
+        self.recorder.record(method, request, response, usage);
+    }
+}
+
+#[async_trait]
+impl LlmClient for RecordingLlmClient {
+    async fn decide_action(
+        &self,
+        prompt: &AgentPrompt,
+        permissions: &ActionPermissions,
+    ) -> Result<AgentAction, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.decide_action(prompt, permissions).await;
+        let request = json!({ "prompt": prompt, "permissions": permissions });
+        self.record("llm.decide_action", request, before, &result);
+        result
+    }
+
+    async fn generate_answer(
+        &self,
+        prompt: &AgentPrompt,
+        temperature: f32,
+    ) -> Result<LlmResponse, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.generate_answer(prompt, temperature).await;
+        let request = json!({ "prompt": prompt, "temperature": temperature });
+        self.record("llm.generate_answer", request, before, &result);
+        result
+    }
+
+    async fn embed(&self, text: &str) -> Result<EmbeddingResult, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.embed(text).await;
+        self.record("llm.embed", json!({ "text": text }), before, &result);
+        result
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<EmbeddingResult>, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.embed_batch(texts).await;
+        self.record("llm.embed_batch", json!({ "texts": texts }), before, &result);
+        result
+    }
+
+    async fn evaluate(
+        &self,
+        question: &str,
+        answer: &str,
+        criteria: &str,
+    ) -> Result<EvaluationResponse, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.evaluate(question, answer, criteria).await;
+        let request = json!({ "question": question, "answer": answer, "criteria": criteria });
+        self.record("llm.evaluate", request, before, &result);
+        result
+    }
+
+    async fn determine_eval_types(
+        &self,
+        question: &str,
+    ) -> Result<Vec<EvaluationType>, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.determine_eval_types(question).await;
+        let request = json!({ "question": question });
+        self.record("llm.determine_eval_types", request, before, &result);
+        result
+    }
+
+    async fn generate_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let before = self.token_snapshot();
+        let result = self
+            .inner
+            .generate_code(problem, available_vars, previous_attempts)
+            .await;
+        let request = json!({
+            "problem": problem,
+            "available_vars": available_vars,
+            "previous_attempts": previous_attempts,
+        });
+        self.record("llm.generate_code", request, before, &result);
+        result
+    }
+
+    async fn generate_python_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let before = self.token_snapshot();
+        let result = self
+            .inner
+            .generate_python_code(problem, available_vars, previous_attempts)
+            .await;
+        let request = json!({
+            "problem": problem,
+            "available_vars": available_vars,
+            "previous_attempts": previous_attempts,
+        });
+        self.record("llm.generate_python_code", request, before, &result);
+        result
+    }
+
+    async fn choose_coding_language(&self, problem: &str) -> Result<SandboxLanguage, LlmError> {
+        let before = self.token_snapshot();
+        let result = self.inner.choose_coding_language(problem).await;
+        let request = json!({ "problem": problem });
+        self.record("llm.choose_coding_language", request, before, &result);
+        result
+    }
+
+    fn get_prompt_tokens(&self) -> u64 {
+        self.inner.get_prompt_tokens()
+    }
+
+    fn get_completion_tokens(&self) -> u64 {
+        self.inner.get_completion_tokens()
+    }
+
+    fn get_embedding_tokens(&self) -> u64 {
+        self.inner.get_embedding_tokens()
+    }
+
+    fn model_name(&self) -> Option<&str> {
+        self.inner.model_name()
+    }
+
+    fn embedding_model_name(&self) -> Option<&str> {
+        self.inner.embedding_model_name()
+    }
+}
+
+/// Wrapper que grava cada chamada de um `SearchClient`
+pub struct RecordingSearchClient {
+    inner: Arc<dyn SearchClient>,
+    recorder: CassetteRecorder,
+}
+
+impl RecordingSearchClient {
+    /// Envolve `inner`, gravando em `recorder`
+    pub fn new(inner: Arc<dyn SearchClient>, recorder: CassetteRecorder) -> Self {
+        Self { inner, recorder }
+    }
+
+    fn record<T: Serialize>(&self, method: &str, request: Value, credits_before: u64, response: &T) {
+        let usage = CallUsage {
+            credits: self.inner.get_credits().saturating_sub(credits_before),
+            ..CallUsage::default()
+        };
+        self.recorder.record(method, request, response, usage);
+    }
+}
+
+#[async_trait]
+impl SearchClient for RecordingSearchClient {
+    async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
+        let before = self.inner.get_credits();
+        let result = self.inner.search(query).await;
+        self.record("search.search", json!({ "query": query }), before, &result);
+        result
+    }
+
+    async fn search_batch(&self, queries: &[SerpQuery]) -> Vec<Result<SearchResult, SearchError>> {
+        let before = self.inner.get_credits();
+        let results = self.inner.search_batch(queries).await;
+        self.record("search.search_batch", json!({ "queries": queries }), before, &results);
+        results
+    }
+
+    async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+        let before = self.inner.get_credits();
+        let result = self.inner.read_url(url).await;
+        self.record("search.read_url", json!({ "url": url }), before, &result);
+        result
+    }
+
+    async fn read_urls_batch(&self, urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+        let before = self.inner.get_credits();
+        let results = self.inner.read_urls_batch(urls).await;
+        self.record("search.read_urls_batch", json!({ "urls": urls }), before, &results);
+        results
+    }
+
+    async fn rerank(&self, query: &str, urls: &[BoostedSearchSnippet]) -> Vec<BoostedSearchSnippet> {
+        let before = self.inner.get_credits();
+        let result = self.inner.rerank(query, urls).await;
+        let request = json!({ "query": query, "urls": urls });
+        self.record("search.rerank", request, before, &result);
+        result
+    }
+
+    async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+        let before = self.inner.get_credits();
+        let result = self.inner.read_url_comparative(url).await;
+        self.record("search.read_url_comparative", json!({ "url": url }), before, &result);
+        result
+    }
+
+    async fn read_urls_comparative_batch(&self, urls: &[Url]) -> Vec<ComparativeReadResult> {
+        let before = self.inner.get_credits();
+        let results = self.inner.read_urls_comparative_batch(urls).await;
+        let request = json!({ "urls": urls });
+        self.record("search.read_urls_comparative_batch", request, before, &results);
+        results
+    }
+
+    async fn read_url_with_fallback_progress(
+        &self,
+        url: &Url,
+        progress: Arc<AtomicU8>,
+    ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+        let before = self.inner.get_credits();
+        let (result, method, attempts, bytes) =
+            self.inner.read_url_with_fallback_progress(url, progress).await;
+        let recorded = (&result, method, attempts, bytes);
+        self.record(
+            "search.read_url_with_fallback_progress",
+            json!({ "url": url }),
+            before,
+            &recorded,
+        );
+        (result, method, attempts, bytes)
+    }
+
+    fn get_credits(&self) -> u64 {
+        self.inner.get_credits()
+    }
+}
+
+/// Envolve um par de clientes com gravação no mesmo cassette
+pub fn recording_clients(
+    llm_client: Arc<dyn LlmClient>,
+    search_client: Arc<dyn SearchClient>,
+    path: impl AsRef<Path>,
+) -> Result<ClientPair, CassetteError> {
+    let recorder = CassetteRecorder::create(path)?;
+    Ok((
+        Arc::new(RecordingLlmClient::new(llm_client, recorder.clone())),
+        Arc::new(RecordingSearchClient::new(search_client, recorder)),
+    ))
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// REPLAY
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Player de cassette compartilhável entre clientes de replay
+#[derive(Clone)]
+pub struct CassettePlayer {
+    /// Interações ainda não consumidas (None = já tocada)
+    interactions: Arc<Mutex<Vec<Option<Interaction>>>>,
+    /// Se true, exige requisição idêntica à gravada
+    strict: bool,
+    /// Cabeçalho gravado (vazio em cassettes antigos)
+    header: CassetteHeader,
+}
+
+impl CassettePlayer {
+    /// Carrega um cassette gravado por `CassetteRecorder`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let file = std::fs::File::open(path)?;
+        let mut interactions = Vec::new();
+        let mut header = CassetteHeader::default();
+
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut value: Value = serde_json::from_str(&line)?;
+            match value.get_mut("header") {
+                Some(recorded) => header = serde_json::from_value(recorded.take())?,
+                None => interactions.push(serde_json::from_value::<Interaction>(value)?),
+            }
+        }
+
+        Ok(Self::from_interactions(interactions).with_header(header))
+    }
+
+    /// Cria um player a partir de interações em memória
+    pub fn from_interactions(interactions: Vec<Interaction>) -> Self {
+        Self {
+            interactions: Arc::new(Mutex::new(interactions.into_iter().map(Some).collect())),
+            strict: false,
+            header: CassetteHeader::default(),
+        }
+    }
+
+    /// Define o cabeçalho (modelos) a usar no replay
+    pub fn with_header(mut self, header: CassetteHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Cabeçalho gravado
+    pub fn header(&self) -> &CassetteHeader {
+        &self.header
+    }
+
+    /// Exige que cada chamada tenha exatamente a requisição gravada
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Quantidade de interações ainda não tocadas
+    pub fn remaining(&self) -> usize {
+        let interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        interactions.iter().filter(|i| i.is_some()).count()
+    }
+
+    /// Consome a próxima interação compatível com `method`/`request`
+    fn take<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        request: &Value,
+    ) -> Result<(T, CallUsage), CassetteError> {
+        let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+
+        let same_method = |slot: &Option<Interaction>| {
+            slot.as_ref().map(|i| i.method == method).unwrap_or(false)
+        };
+        let exact = interactions
+            .iter()
+            .position(|slot| same_method(slot) && slot.as_ref().map(|i| &i.request) == Some(request));
+        let index = match exact {
+            Some(index) => Some(index),
+            None if !self.strict => interactions.iter().position(same_method),
+            None => None,
+        };
+
+        let interaction = index
+            .and_then(|index| interactions[index].take())
+            .ok_or_else(|| CassetteError::Miss {
+                method: method.to_string(),
+            })?;
+
+        let response = serde_json::from_value(interaction.response)?;
+        let usage = CallUsage {
+            prompt_tokens: interaction.prompt_tokens,
+            completion_tokens: interaction.completion_tokens,
+            embedding_tokens: interaction.embedding_tokens,
+            credits: interaction.credits,
+        };
+        Ok((response, usage))
+    }
+}
+
+/// `LlmClient` que toca um cassette gravado, sem rede
+pub struct ReplayLlmClient {
+    player: CassettePlayer,
+    /// Modelos do cabeçalho, para precificar como na gravação
+    model: Option<String>,
+    embedding_model: Option<String>,
+    prompt_tokens: AtomicU64,
+    completion_tokens: AtomicU64,
+    embedding_tokens: AtomicU64,
+}
+
+impl ReplayLlmClient {
+    /// Cria um cliente de replay a partir de um player
+    pub fn new(player: CassettePlayer) -> Self {
+        Self {
+            model: player.header.model.clone(),
+            embedding_model: player.header.embedding_model.clone(),
+            player,
+            prompt_tokens: AtomicU64::new(0),
+            completion_tokens: AtomicU64::new(0),
+            embedding_tokens: AtomicU64::new(0),
+        }
+    }
+
+    fn replay<T: DeserializeOwned>(&self, method: &str, request: Value) -> Result<T, LlmError> {
+        let (result, usage) = self
+            .player
+            .take::<Result<T, LlmError>>(method, &request)
+            .map_err(|e| LlmError::ApiError(e.to_string()))?;
+
+        self.prompt_tokens
+            .fetch_add(usage.prompt_tokens, Ordering::Relaxed);
+        self.completion_tokens
+            .fetch_add(usage.completion_tokens, Ordering::Relaxed);
+        self.embedding_tokens
+            .fetch_add(usage.embedding_tokens, Ordering::Relaxed);
+        result
+    }
+}
+
+#[async_trait]
+impl LlmClient for ReplayLlmClient {
+    async fn decide_action(
+        &self,
+        prompt: &AgentPrompt,
+        permissions: &ActionPermissions,
+    ) -> Result<AgentAction, LlmError> {
+        let request = json!({ "prompt": prompt, "permissions": permissions });
+        self.replay("llm.decide_action", request)
+    }
+
+    async fn generate_answer(
+        &self,
+        prompt: &AgentPrompt,
+        temperature: f32,
+    ) -> Result<LlmResponse, LlmError> {
+        let request = json!({ "prompt": prompt, "temperature": temperature });
+        self.replay("llm.generate_answer", request)
+    }
+
+    async fn embed(&self, text: &str) -> Result<EmbeddingResult, LlmError> {
+        self.replay("llm.embed", json!({ "text": text }))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<EmbeddingResult>, LlmError> {
+        self.replay("llm.embed_batch", json!({ "texts": texts }))
+    }
+
+    async fn evaluate(
+        &self,
+        question: &str,
+        answer: &str,
+        criteria: &str,
+    ) -> Result<EvaluationResponse, LlmError> {
+        let request = json!({ "question": question, "answer": answer, "criteria": criteria });
+        self.replay("llm.evaluate", request)
+    }
+
+    async fn determine_eval_types(
+        &self,
+        question: &str,
+    ) -> Result<Vec<EvaluationType>, LlmError> {
+        self.replay("llm.determine_eval_types", json!({ "question": question }))
+    }
+
+    async fn generate_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let request = json!({
+            "problem": problem,
+            "available_vars": available_vars,
+            "previous_attempts": previous_attempts,
+        });
+        self.replay("llm.generate_code", request)
+    }
+
+    async fn generate_python_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let request = json!({
+            "problem": problem,
+            "available_vars": available_vars,
+            "previous_attempts": previous_attempts,
+        });
+        self.replay("llm.generate_python_code", request)
+    }
+
+    async fn choose_coding_language(&self, problem: &str) -> Result<SandboxLanguage, LlmError> {
+        self.replay("llm.choose_coding_language", json!({ "problem": problem }))
+    }
+
+    fn get_prompt_tokens(&self) -> u64 {
+        self.prompt_tokens.load(Ordering::Relaxed)
+    }
+
+    fn get_completion_tokens(&self) -> u64 {
+        self.completion_tokens.load(Ordering::Relaxed)
+    }
+
+    fn get_embedding_tokens(&self) -> u64 {
+        self.embedding_tokens.load(Ordering::Relaxed)
+    }
+
+    fn model_name(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    fn embedding_model_name(&self) -> Option<&str> {
+        self.embedding_model.as_deref()
+    }
+}
+
+/// `SearchClient` que toca um cassette gravado, sem rede
+pub struct ReplaySearchClient {
+    player: CassettePlayer,
+    credits: AtomicU64,
+}
+
+impl ReplaySearchClient {
+    /// Cria um cliente de replay a partir de um player
+    pub fn new(player: CassettePlayer) -> Self {
+        Self {
+            player,
+            credits: AtomicU64::new(0),
+        }
+    }
+
+    fn replay<T: DeserializeOwned>(&self, method: &str, request: Value) -> Result<T, CassetteError> {
+        let (value, usage) = self.player.take::<T>(method, &request)?;
+        self.credits.fetch_add(usage.credits, Ordering::Relaxed);
+        Ok(value)
+    }
+
+    fn replay_result<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        request: Value,
+    ) -> Result<T, SearchError> {
+        self.replay::<Result<T, SearchError>>(method, request)
+            .map_err(|e| SearchError::ApiError(e.to_string()))?
+    }
+}
+
+/// Converte o método de leitura gravado de volta para `&'static str`
+fn static_read_method(method: &str) -> &'static str {
+    match method {
+        "jina" => "jina",
+        "jina_forced" => "jina_forced",
+        "jina_forced_partial" => "jina_forced_partial",
+        "jina_forced_failed" => "jina_forced_failed",
+        "rust_local" => "rust_local",
+        "failed" => "failed",
+        "mock" => "mock",
+        _ => "replay",
+    }
+}
+
+#[async_trait]
+impl SearchClient for ReplaySearchClient {
+    async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
+        self.replay_result("search.search", json!({ "query": query }))
+    }
+
+    async fn search_batch(&self, queries: &[SerpQuery]) -> Vec<Result<SearchResult, SearchError>> {
+        self.replay("search.search_batch", json!({ "queries": queries }))
+            .unwrap_or_else(|e| {
+                queries
+                    .iter()
+                    .map(|_| Err(SearchError::ApiError(e.to_string())))
+                    .collect()
+            })
+    }
+
+    async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+        self.replay_result("search.read_url", json!({ "url": url }))
+    }
+
+    async fn read_urls_batch(&self, urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+        self.replay("search.read_urls_batch", json!({ "urls": urls }))
+            .unwrap_or_else(|e| {
+                urls.iter()
+                    .map(|_| Err(SearchError::ApiError(e.to_string())))
+                    .collect()
+            })
+    }
+
+    async fn rerank(&self, query: &str, urls: &[BoostedSearchSnippet]) -> Vec<BoostedSearchSnippet> {
+        self.replay("search.rerank", json!({ "query": query, "urls": urls }))
+            .unwrap_or_else(|e| {
+                log::warn!("⚠️ Cassette: {} - mantendo ordem original", e);
+                urls.to_vec()
+            })
+    }
+
+    async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+        self.replay("search.read_url_comparative", json!({ "url": url }))
+            .unwrap_or_else(|e| {
+                log::warn!("⚠️ Cassette: {}", e);
+                ComparativeReadResult {
+                    url: url.clone(),
+                    jina_result: None,
+                    rust_result: None,
+                    jina_time_ms: 0,
+                    rust_time_ms: 0,
+                    speed_diff_ms: 0,
+                    faster: ReadMethod::Tie,
+                }
+            })
+    }
+
+    async fn read_urls_comparative_batch(&self, urls: &[Url]) -> Vec<ComparativeReadResult> {
+        match self.replay("search.read_urls_comparative_batch", json!({ "urls": urls })) {
+            Ok(results) => results,
+            Err(e) => {
+                log::warn!("⚠️ Cassette: {}", e);
+                let mut results = Vec::with_capacity(urls.len());
+                for url in urls {
+                    results.push(self.read_url_comparative(url).await);
+                }
+                results
+            }
+        }
+    }
+
+    async fn read_url_with_fallback_progress(
+        &self,
+        url: &Url,
+        progress: Arc<AtomicU8>,
+    ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+        progress.store(100, Ordering::Relaxed);
+
+        match self.replay::<(Result<UrlContent, SearchError>, String, u8, usize)>(
+            "search.read_url_with_fallback_progress",
+            json!({ "url": url }),
+        ) {
+            Ok((result, method, attempts, bytes)) => {
+                (result, static_read_method(&method), attempts, bytes)
+            }
+            Err(e) => (Err(SearchError::ApiError(e.to_string())), "failed", 0, 0),
+        }
+    }
+
+    fn get_credits(&self) -> u64 {
+        self.credits.load(Ordering::Relaxed)
+    }
+}
+
+/// Cria o par de clientes de replay a partir de um único cassette
+pub fn replay_clients(
+    path: impl AsRef<Path>,
+) -> Result<ClientPair, CassetteError> {
+    let player = CassettePlayer::load(path)?;
+    Ok((
+        Arc::new(ReplayLlmClient::new(player.clone())),
+        Arc::new(ReplaySearchClient::new(player)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::DeepResearchAgent;
+    use crate::llm::{MockLlmClient, OpenAiClient};
+    use crate::search::MockSearchClient;
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("deep-research-cassette-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_calls() {
+        let path = temp_path();
+        let (llm, search) = recording_clients(
+            Arc::new(MockLlmClient::new()),
+            Arc::new(MockSearchClient::new()),
+            &path,
+        )
+        .unwrap();
+
+        let recorded_eval = llm.evaluate("q", "a", "c").await.unwrap();
+        let recorded_read = search.read_url(&"https://example.com".to_string()).await.unwrap();
+        drop((llm, search));
+
+        let player = CassettePlayer::load(&path).unwrap();
+        assert_eq!(player.remaining(), 2);
+
+        let llm = ReplayLlmClient::new(player.clone());
+        let search = ReplaySearchClient::new(player.clone());
+
+        let replayed_read = search.read_url(&"https://example.com".to_string()).await.unwrap();
+        let replayed_eval = llm.evaluate("q", "a", "c").await.unwrap();
+        assert_eq!(replayed_read.text, recorded_read.text);
+        assert_eq!(replayed_eval.reasoning, recorded_eval.reasoning);
+        assert_eq!(player.remaining(), 0);
+
+        // Cassette esgotado vira erro, não chamada de rede
+        assert!(llm.evaluate("q", "a", "c").await.is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_requires_same_request() {
+        let path = temp_path();
+        let recorder = CassetteRecorder::create(&path).unwrap();
+        let llm = RecordingLlmClient::new(Arc::new(MockLlmClient::new()), recorder);
+        llm.embed("original").await.unwrap();
+
+        let lenient = ReplayLlmClient::new(CassettePlayer::load(&path).unwrap());
+        assert!(lenient.embed("changed").await.is_ok());
+
+        let strict = ReplayLlmClient::new(CassettePlayer::load(&path).unwrap().strict());
+        assert!(strict.embed("changed").await.is_err());
+        assert!(strict.embed("original").await.is_ok());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_recorded_models() {
+        let path = temp_path();
+        let inner = Arc::new(OpenAiClient::new("test-key".into()));
+        let recorder = CassetteRecorder::create(&path).unwrap();
+        let llm = RecordingLlmClient::new(inner.clone(), recorder);
+        assert_eq!(llm.model_name(), inner.model_name());
+        drop(llm);
+
+        let player = CassettePlayer::load(&path).unwrap();
+        assert_eq!(player.remaining(), 0);
+        assert_eq!(player.header().model.as_deref(), inner.model_name());
+
+        // Mesmo modelo => mesmo preço por token que a execução gravada
+        let replay = ReplayLlmClient::new(player);
+        assert_eq!(replay.model_name(), inner.model_name());
+        assert_eq!(replay.embedding_model_name(), inner.embedding_model_name());
+
+        // Cassettes sem cabeçalho continuam carregando
+        let replay = ReplayLlmClient::new(CassettePlayer::from_interactions(vec![]));
+        assert_eq!(replay.model_name(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_replay_full_agent_run() {
+        let path = temp_path();
+        let question = "Qual a capital da França?".to_string();
+        let answer = AgentAction::Answer {
+            answer: "Paris é a capital da França.".into(),
+            references: vec![],
+            think: "Resposta direta".into(),
+        };
+
+        let (llm, search) = recording_clients(
+            Arc::new(MockLlmClient::with_action(answer)),
+            Arc::new(MockSearchClient::new()),
+            &path,
+        )
+        .unwrap();
+        let recorded = DeepResearchAgent::new(llm, search, Some(10_000))
+            .run(question.clone())
+            .await;
+
+        let (llm, search) = replay_clients(&path).unwrap();
+        let replayed = DeepResearchAgent::new(llm, search, Some(10_000))
+            .run(question)
+            .await;
+
+        assert_eq!(replayed.success, recorded.success);
+        assert_eq!(replayed.answer, recorded.answer);
+        assert_eq!(replayed.visited_urls, recorded.visited_urls);
+        assert_eq!(replayed.token_usage.total_tokens, recorded.token_usage.total_tokens);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -43,6 +43,23 @@ impl LlmProvider {
             Self::Local => "Local",
         }
     }
+
+    /// Modelo usado quando `LLM_MODEL` não é definido.
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Self::OpenAI | Self::Local => "gpt-4.1-mini",
+            Self::Anthropic => "claude-sonnet-4-5",
+        }
+    }
+
+    /// Variável de ambiente com a chave da API (`None` para provider local).
+    pub fn api_key_variable(&self) -> Option<&'static str> {
+        match self {
+            Self::OpenAI => Some("OPENAI_API_KEY"),
+            Self::Anthropic => Some("ANTHROPIC_API_KEY"),
+            Self::Local => None,
+        }
+    }
 }
 
 impl fmt::Display for LlmProvider {
@@ -92,7 +109,7 @@ pub struct LlmConfig {
     pub provider: LlmProvider,
 
     /// Modelo principal para geração de texto.
-    /// Padrão: "gpt-4.1-mini" para OpenAI
+    /// Padrão: "gpt-4.1-mini" para OpenAI, "claude-sonnet-4-5" para Anthropic
     pub model: String,
 
     /// Provider de embeddings (pode ser diferente do LLM)
@@ -118,7 +135,7 @@ impl Default for LlmConfig {
     fn default() -> Self {
         Self {
             provider: LlmProvider::default(),
-            model: "gpt-4.1-mini".to_string(),
+            model: LlmProvider::default().default_model().to_string(),
             embedding_provider: EmbeddingProvider::default(),
             embedding_model: "text-embedding-3-small".to_string(),
             jina_embedding_model: "jina-embeddings-v4".to_string(),
@@ -188,6 +205,63 @@ pub struct AgentConfig {
     /// Máximo de falhas consecutivas antes de forçar resposta.
     /// Padrão: 3
     pub max_consecutive_failures: usize,
+
+    /// Diretório onde o agente grava um checkpoint após cada step.
+    /// O arquivo é removido quando a execução conclui ou falha.
+    /// `None` desabilita checkpoints.
+    /// Padrão: Some("checkpoints")
+    pub checkpoint_dir: Option<String>,
+
+    /// Se polir a resposta aprovada com o ResponseFinalizer.
+    /// Pode ser sobrescrito por requisição no servidor.
+    /// Padrão: false
+    pub finalize_answer: bool,
+
+    /// Se verificar afirmação por afirmação as respostas aprovadas.
+    /// Pode ser sobrescrito por requisição no servidor.
+    /// Padrão: false
+    pub verify_claims: bool,
+
+    /// Se detectar contradições entre fontes no conhecimento acumulado.
+    /// Pode ser sobrescrito por requisição no servidor.
+    /// Padrão: false
+    pub detect_contradictions: bool,
+
+    /// Budget de tempo de parede por pesquisa, em segundos.
+    /// `None` = sem prazo. Pode ser sobrescrito por requisição no servidor.
+    /// Padrão: None
+    pub time_budget_secs: Option<u64>,
+
+    /// Fração do prazo que dispara o Beast Mode (0.0 - 1.0).
+    /// Padrão: 0.85
+    pub time_beast_mode_fraction: f64,
+
+    /// Budget de custo por pesquisa, em USD.
+    /// `None` = sem limite. Pode ser sobrescrito por requisição no servidor.
+    /// Padrão: None
+    pub cost_budget_usd: Option<f64>,
+
+    /// Arquivo JSON com preços por modelo (sobrescreve a tabela embutida).
+    /// Padrão: None
+    pub pricing_file: Option<String>,
+
+    /// Arquivo JSON com camadas de credibilidade por domínio
+    /// (sobrescreve a tabela embutida).
+    /// Padrão: None
+    pub credibility_file: Option<String>,
+
+    /// Diretório onde cada execução grava seu transcript JSONL.
+    /// `None` desabilita transcripts.
+    /// Padrão: Some("transcripts")
+    pub transcript_dir: Option<String>,
+
+    /// Ações que exigem aprovação humana (modo interativo).
+    /// Sem interface interativa (CLI, servidor, equipe, relatório) essas
+    /// ações são recusadas.
+    /// Formato: `acao[:padrao]` separados por vírgula,
+    /// ex.: `coding, read:*.gov.br, tool:digisac:enviar_mensagem`.
+    /// Padrão: vazio
+    pub approval_policy: crate::agent::ApprovalPolicy,
 }
 
 impl Default for AgentConfig {
@@ -199,6 +273,17 @@ impl Default for AgentConfig {
             max_urls_per_step: 10,
             max_queries_per_step: 5,
             max_consecutive_failures: 3,
+            checkpoint_dir: Some(crate::agent::DEFAULT_CHECKPOINT_DIR.to_string()),
+            finalize_answer: false,
+            verify_claims: false,
+            detect_contradictions: false,
+            time_budget_secs: None,
+            time_beast_mode_fraction: crate::agent::DEFAULT_TIME_BEAST_MODE_FRACTION,
+            cost_budget_usd: None,
+            pricing_file: None,
+            credibility_file: None,
+            transcript_dir: Some(crate::agent::DEFAULT_TRANSCRIPT_DIR.to_string()),
+            approval_policy: crate::agent::ApprovalPolicy::new(),
         }
     }
 }
@@ -256,6 +341,106 @@ impl fmt::Display for WebReaderPreference {
     }
 }
 
+/// Provedor de busca web.
+///
+/// A leitura de URLs, o rerank e os embeddings continuam no Jina/Rust local;
+/// o provedor troca apenas a busca (veja `search_providers`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchProvider {
+    /// Jina Search (padrão)
+    #[default]
+    Jina,
+    /// Instância SearXNG (`SEARXNG_URL`)
+    Searxng,
+    /// Brave Search API (`BRAVE_API_KEY`)
+    Brave,
+    /// Serper.dev (`SERPER_API_KEY`)
+    Serper,
+    /// SerpAPI (`SERPAPI_API_KEY`)
+    SerpApi,
+    /// DuckDuckGo HTML (sem chave)
+    DuckDuckGo,
+}
+
+impl SearchProvider {
+    /// Todos os provedores, na ordem de exibição
+    pub const ALL: [SearchProvider; 6] = [
+        Self::Jina,
+        Self::Searxng,
+        Self::Brave,
+        Self::Serper,
+        Self::SerpApi,
+        Self::DuckDuckGo,
+    ];
+
+    /// Converte string do .env (ou da requisição) para SearchProvider.
+    ///
+    /// Retorna `None` para nomes desconhecidos.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().trim() {
+            "jina" => Some(Self::Jina),
+            "searxng" | "searx" => Some(Self::Searxng),
+            "brave" => Some(Self::Brave),
+            "serper" => Some(Self::Serper),
+            "serpapi" | "google" => Some(Self::SerpApi),
+            "duckduckgo" | "ddg" => Some(Self::DuckDuckGo),
+            _ => None,
+        }
+    }
+
+    /// Converte string do .env, com Jina como padrão.
+    pub fn from_env(value: &str) -> Self {
+        Self::parse(value).unwrap_or_default()
+    }
+
+    /// Converte uma lista separada por vírgula (`"brave,searxng"`).
+    ///
+    /// Retorna `None` se algum nome for desconhecido ou a lista estiver vazia.
+    /// Nomes repetidos aparecem uma vez só.
+    pub fn parse_list(value: &str) -> Option<Vec<Self>> {
+        let mut providers = Vec::new();
+        for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let provider = Self::parse(name)?;
+            if !providers.contains(&provider) {
+                providers.push(provider);
+            }
+        }
+        (!providers.is_empty()).then_some(providers)
+    }
+
+    /// Converte a lista do .env, ignorando nomes desconhecidos (padrão: Jina).
+    pub fn list_from_env(value: &str) -> Vec<Self> {
+        let mut providers = Vec::new();
+        for provider in value.split(',').filter_map(Self::parse) {
+            if !providers.contains(&provider) {
+                providers.push(provider);
+            }
+        }
+        if providers.is_empty() {
+            providers.push(Self::default());
+        }
+        providers
+    }
+
+    /// Identificador usado em logs, métricas e requisições.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jina => "jina",
+            Self::Searxng => "searxng",
+            Self::Brave => "brave",
+            Self::Serper => "serper",
+            Self::SerpApi => "serpapi",
+            Self::DuckDuckGo => "duckduckgo",
+        }
+    }
+}
+
+impl fmt::Display for SearchProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Configuração do runtime Tokio.
 ///
 /// Controla número de threads e comportamento do async runtime.
@@ -278,6 +463,9 @@ pub struct RuntimeConfig {
 
     /// Preferência de WebReader.
     pub webreader: WebReaderPreference,
+
+    /// Provedores de busca web (dois ou mais ativam a meta-busca).
+    pub search_providers: Vec<SearchProvider>,
 }
 
 impl Default for RuntimeConfig {
@@ -288,6 +476,7 @@ impl Default for RuntimeConfig {
             max_blocking_threads: 512,
             thread_name: "deep-research".to_string(),
             webreader: WebReaderPreference::default(),
+            search_providers: vec![SearchProvider::default()],
         }
     }
 }
@@ -298,6 +487,15 @@ impl RuntimeConfig {
         Self::default()
     }
 
+    /// Provedores de busca separados por vírgula (para logs e banner).
+    pub fn search_providers_label(&self) -> String {
+        self.search_providers
+            .iter()
+            .map(|p| p.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     /// Calcula número efetivo de worker threads.
     ///
     /// Se `worker_threads` está definido, usa esse valor.
@@ -319,6 +517,8 @@ impl RuntimeConfig {
 /// - `TOKIO_MAX_THREADS`: Máximo de threads para cálculo dinâmico (padrão: 16)
 /// - `TOKIO_MAX_BLOCKING`: Máximo de blocking threads (padrão: 512)
 /// - `WEBREADER`: Preferência de reader ("jina", "rust", "compare")
+/// - `SEARCH_PROVIDER`: Provedor de busca ("jina", "searxng", "brave", "serper",
+///   "serpapi", "duckduckgo"); vários separados por vírgula ativam a meta-busca
 ///
 /// # Exemplo
 ///
@@ -371,6 +571,12 @@ pub fn load_runtime_config() -> RuntimeConfig {
         log::info!("📦 WEBREADER={}", config.webreader);
     }
 
+    // SEARCH_PROVIDER: provedor(es) de busca web
+    if let Ok(provider_str) = std::env::var("SEARCH_PROVIDER") {
+        config.search_providers = SearchProvider::list_from_env(&provider_str);
+        log::info!("📦 SEARCH_PROVIDER={}", config.search_providers_label());
+    }
+
     // Log da configuração efetiva
     let effective_threads = config.effective_worker_threads();
     let cpu_cores = num_cpus::get();
@@ -512,6 +718,7 @@ pub fn load_llm_config() -> LlmConfig {
     // LLM_PROVIDER: provider de LLM
     if let Ok(provider_str) = std::env::var("LLM_PROVIDER") {
         config.provider = LlmProvider::from_env(&provider_str);
+        config.model = config.provider.default_model().to_string();
         log::info!("📦 LLM_PROVIDER={}", config.provider);
     }
 
@@ -586,6 +793,15 @@ pub fn load_llm_config() -> LlmConfig {
 /// - `AGENT_MAX_URLS_PER_STEP`: Máximo de URLs por step - padrão: 10
 /// - `AGENT_MAX_QUERIES_PER_STEP`: Máximo de queries por step - padrão: 5
 /// - `AGENT_MAX_FAILURES`: Máximo de falhas consecutivas - padrão: 3
+/// - `AGENT_CHECKPOINT_DIR`: Diretório de checkpoints ("off" desabilita) - padrão: checkpoints
+/// - `AGENT_FINALIZE_ANSWER`: Polir resposta aprovada ("true"/"false") - padrão: false
+/// - `AGENT_VERIFY_CLAIMS`: Verificar afirmações da resposta aprovada ("true"/"false") - padrão: false
+/// - `AGENT_DETECT_CONTRADICTIONS`: Detectar fontes que discordam ("true"/"false") - padrão: false
+/// - `AGENT_TIME_BUDGET_SECS`: Prazo por pesquisa em segundos - padrão: sem prazo
+/// - `AGENT_TIME_BEAST_MODE_FRACTION`: Fração do prazo para Beast Mode - padrão: 0.85
+/// - `AGENT_COST_BUDGET_USD`: Budget de custo por pesquisa em USD - padrão: sem limite
+/// - `AGENT_PRICING_FILE`: JSON com preços por modelo - padrão: tabela embutida
+/// - `AGENT_CREDIBILITY_FILE`: JSON com camadas de credibilidade por domínio - padrão: tabela embutida
 ///
 /// # Exemplo
 ///
@@ -655,6 +871,105 @@ pub fn load_agent_config() -> AgentConfig {
         }
     }
 
+    // AGENT_CHECKPOINT_DIR: diretório de checkpoints ("off" desabilita)
+    if let Ok(dir) = std::env::var("AGENT_CHECKPOINT_DIR") {
+        let dir = dir.trim();
+        if dir.is_empty() || matches!(dir.to_lowercase().as_str(), "off" | "none" | "false" | "0") {
+            config.checkpoint_dir = None;
+            log::info!("📦 AGENT_CHECKPOINT_DIR=off");
+        } else {
+            config.checkpoint_dir = Some(dir.to_string());
+            log::info!("📦 AGENT_CHECKPOINT_DIR={}", dir);
+        }
+    }
+
+    // AGENT_FINALIZE_ANSWER: polimento pós-aprovação da resposta
+    if let Ok(finalize_str) = std::env::var("AGENT_FINALIZE_ANSWER") {
+        let finalize = matches!(finalize_str.to_lowercase().trim(), "true" | "1" | "yes" | "sim");
+        config.finalize_answer = finalize;
+        log::info!("📦 AGENT_FINALIZE_ANSWER={}", finalize);
+    }
+
+    // AGENT_VERIFY_CLAIMS: verificação por afirmação da resposta aprovada
+    if let Ok(verify_str) = std::env::var("AGENT_VERIFY_CLAIMS") {
+        let verify = matches!(verify_str.to_lowercase().trim(), "true" | "1" | "yes" | "sim");
+        config.verify_claims = verify;
+        log::info!("📦 AGENT_VERIFY_CLAIMS={}", verify);
+    }
+
+    // AGENT_DETECT_CONTRADICTIONS: fatos conflitantes entre fontes
+    if let Ok(detect_str) = std::env::var("AGENT_DETECT_CONTRADICTIONS") {
+        let detect = matches!(detect_str.to_lowercase().trim(), "true" | "1" | "yes" | "sim");
+        config.detect_contradictions = detect;
+        log::info!("📦 AGENT_DETECT_CONTRADICTIONS={}", detect);
+    }
+
+    // AGENT_TIME_BUDGET_SECS: prazo de parede por pesquisa
+    if let Ok(secs_str) = std::env::var("AGENT_TIME_BUDGET_SECS") {
+        if let Ok(secs) = secs_str.parse::<u64>() {
+            if secs > 0 {
+                config.time_budget_secs = Some(secs);
+                log::info!("📦 AGENT_TIME_BUDGET_SECS={}", secs);
+            }
+        }
+    }
+
+    // AGENT_TIME_BEAST_MODE_FRACTION: fração do prazo que dispara Beast Mode
+    if let Ok(fraction_str) = std::env::var("AGENT_TIME_BEAST_MODE_FRACTION") {
+        if let Ok(fraction) = fraction_str.parse::<f64>() {
+            if (0.0..=1.0).contains(&fraction) {
+                config.time_beast_mode_fraction = fraction;
+                log::info!("📦 AGENT_TIME_BEAST_MODE_FRACTION={}", fraction);
+            }
+        }
+    }
+
+    // AGENT_COST_BUDGET_USD: budget de custo por pesquisa
+    if let Ok(budget_str) = std::env::var("AGENT_COST_BUDGET_USD") {
+        if let Ok(budget) = budget_str.parse::<f64>() {
+            if budget > 0.0 {
+                config.cost_budget_usd = Some(budget);
+                log::info!("📦 AGENT_COST_BUDGET_USD={}", budget);
+            }
+        }
+    }
+
+    // AGENT_PRICING_FILE: tabela de preços por modelo
+    if let Ok(path) = std::env::var("AGENT_PRICING_FILE") {
+        let path = path.trim();
+        if !path.is_empty() {
+            config.pricing_file = Some(path.to_string());
+            log::info!("📦 AGENT_PRICING_FILE={}", path);
+        }
+    }
+
+    // AGENT_CREDIBILITY_FILE: camadas de credibilidade por domínio
+    if let Ok(path) = std::env::var("AGENT_CREDIBILITY_FILE") {
+        let path = path.trim();
+        if !path.is_empty() {
+            config.credibility_file = Some(path.to_string());
+            log::info!("📦 AGENT_CREDIBILITY_FILE={}", path);
+        }
+    }
+
+    // AGENT_TRANSCRIPT_DIR: diretório de transcripts JSONL ("off" desabilita)
+    if let Ok(dir) = std::env::var("AGENT_TRANSCRIPT_DIR") {
+        let dir = dir.trim();
+        if dir.is_empty() || matches!(dir.to_lowercase().as_str(), "off" | "none" | "false" | "0") {
+            config.transcript_dir = None;
+            log::info!("📦 AGENT_TRANSCRIPT_DIR=off");
+        } else {
+            config.transcript_dir = Some(dir.to_string());
+            log::info!("📦 AGENT_TRANSCRIPT_DIR={}", dir);
+        }
+    }
+
+    // AGENT_APPROVAL_RULES: ações que exigem aprovação humana
+    if let Ok(rules) = std::env::var("AGENT_APPROVAL_RULES") {
+        config.approval_policy = crate::agent::ApprovalPolicy::parse(&rules);
+        log::info!("📦 AGENT_APPROVAL_RULES={}", rules.trim());
+    }
+
     config
 }
 
@@ -673,6 +988,28 @@ mod tests {
         assert_eq!(WebReaderPreference::from_env(""), WebReaderPreference::Compare);
     }
 
+    #[test]
+    fn test_search_provider_from_env() {
+        assert_eq!(SearchProvider::from_env("Brave"), SearchProvider::Brave);
+        assert_eq!(SearchProvider::from_env("ddg"), SearchProvider::DuckDuckGo);
+        assert_eq!(SearchProvider::from_env("unknown"), SearchProvider::Jina);
+        assert_eq!(SearchProvider::parse("unknown"), None);
+        for provider in SearchProvider::ALL {
+            assert_eq!(SearchProvider::parse(provider.as_str()), Some(provider));
+        }
+
+        assert_eq!(
+            SearchProvider::parse_list("brave, searxng,brave"),
+            Some(vec![SearchProvider::Brave, SearchProvider::Searxng])
+        );
+        assert_eq!(SearchProvider::parse_list("brave,unknown"), None);
+        assert_eq!(
+            SearchProvider::list_from_env("unknown,ddg"),
+            vec![SearchProvider::DuckDuckGo]
+        );
+        assert_eq!(SearchProvider::list_from_env(""), vec![SearchProvider::Jina]);
+    }
+
     #[test]
     fn test_runtime_config_default() {
         let config = RuntimeConfig::default();
@@ -680,6 +1017,7 @@ mod tests {
         assert_eq!(config.max_threads, 16);
         assert_eq!(config.max_blocking_threads, 512);
         assert_eq!(config.webreader, WebReaderPreference::Compare);
+        assert_eq!(config.search_providers, vec![SearchProvider::Jina]);
     }
 
     #[test]
@@ -749,5 +1087,14 @@ mod tests {
         assert_eq!(config.max_urls_per_step, 10);
         assert_eq!(config.max_queries_per_step, 5);
         assert_eq!(config.max_consecutive_failures, 3);
+        assert_eq!(config.checkpoint_dir.as_deref(), Some("checkpoints"));
+        assert!(!config.finalize_answer);
+        assert!(!config.verify_claims);
+        assert!(!config.detect_contradictions);
+        assert!(config.time_budget_secs.is_none());
+        assert_eq!(config.time_beast_mode_fraction, 0.85);
+        assert!(config.cost_budget_usd.is_none());
+        assert!(config.pricing_file.is_none());
+        assert!(config.credibility_file.is_none());
     }
 }
@@ -0,0 +1,775 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CORPUS LOCAL - Busca offline com BM25
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Indexa um diretório de arquivos (PDF, HTML, Markdown, TXT, JSON, XML) em um
+// índice invertido salvo em JSON. O texto é extraído pelo `FileReader`, o
+// mesmo usado pelo agente para arquivos remotos.
+//
+// `LocalCorpusClient` implementa `SearchClient` sobre o índice:
+// - `search`: BM25 (k1 = 1.2, b = 0.75), resultados como URLs `file://`
+// - `read_url`: lê o arquivo do disco (só dentro da raiz do corpus)
+// - `rerank`: reordena snippets pelo BM25 do documento
+//
+// Com um `LlmClient` local, o agente inteiro roda sem internet.
+//
+// Atualização incremental: arquivos com mesma data de modificação e tamanho
+// são mantidos; os alterados são reindexados e os apagados, removidos.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::search::{
+    ComparativeReadResult, ReadMethod, SearchClient, SearchError, SearchResult, UrlContent,
+};
+use crate::types::{BoostedSearchSnippet, SerpQuery, Url};
+use crate::utils::{FileContent, FileReader, FileType};
+
+/// Saturação da frequência de termo no BM25
+const BM25_K1: f32 = 1.2;
+
+/// Normalização pelo tamanho do documento no BM25
+const BM25_B: f32 = 0.75;
+
+/// Resultados por busca
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+/// Tamanho do preview guardado no índice (caracteres)
+const PREVIEW_CHARS: usize = 300;
+
+/// Nome do método de leitura reportado ao agente
+const READ_METHOD: &str = "local_corpus";
+
+/// Palavras ignoradas na indexação (inglês e português)
+const STOPWORDS: &[&str] = &[
+    "the", "and", "or", "of", "to", "in", "on", "for", "is", "are", "was", "were", "be", "by",
+    "with", "as", "at", "from", "that", "this", "it", "its", "an", "de", "da", "do", "das", "dos",
+    "em", "no", "na", "nos", "nas", "um", "uma", "para", "por", "com", "que", "se", "ao", "os",
+];
+
+/// Erros do corpus local
+#[derive(Debug, thiserror::Error)]
+pub enum CorpusError {
+    /// Falha de I/O ao ler o diretório ou o índice
+    #[error("Corpus I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Índice corrompido ou em formato inválido
+    #[error("Corpus index serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Raiz do corpus não é um diretório
+    #[error("Corpus root is not a directory: {0}")]
+    NotADirectory(String),
+}
+
+/// Documento indexado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    /// Caminho absoluto do arquivo
+    pub path: String,
+    /// Título (do arquivo ou primeiro cabeçalho)
+    pub title: String,
+    /// Data de modificação (segundos desde epoch)
+    pub modified: u64,
+    /// Tamanho do arquivo em bytes
+    pub size_bytes: u64,
+    /// Número de termos indexados
+    pub length: u32,
+    /// Início do texto, usado como descrição nos resultados
+    pub preview: String,
+}
+
+impl IndexedDocument {
+    /// URL `file://` do documento
+    pub fn url(&self) -> String {
+        file_url(Path::new(&self.path))
+    }
+}
+
+/// Ocorrência de um termo em um documento
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Posting {
+    doc: u32,
+    tf: u32,
+}
+
+/// Resultado de uma busca no índice
+#[derive(Debug, Clone)]
+pub struct CorpusHit {
+    /// URL `file://` do documento
+    pub url: String,
+    /// Título do documento
+    pub title: String,
+    /// Preview do texto
+    pub preview: String,
+    /// Score BM25
+    pub score: f32,
+}
+
+/// Resumo de uma atualização do índice
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Arquivos novos
+    pub added: usize,
+    /// Arquivos alterados e reindexados
+    pub updated: usize,
+    /// Arquivos apagados do disco
+    pub removed: usize,
+    /// Arquivos sem alteração
+    pub unchanged: usize,
+    /// Arquivos que o `FileReader` não conseguiu ler
+    pub failed: usize,
+}
+
+/// Índice invertido de um diretório, persistido em JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusIndex {
+    /// Diretório raiz (canônico)
+    root: PathBuf,
+    documents: BTreeMap<u32, IndexedDocument>,
+    postings: HashMap<String, Vec<Posting>>,
+    next_id: u32,
+    total_length: u64,
+}
+
+impl CorpusIndex {
+    /// Cria um índice vazio para `root`
+    pub fn new(root: impl AsRef<Path>) -> Result<Self, CorpusError> {
+        let root = std::fs::canonicalize(root.as_ref())?;
+        if !root.is_dir() {
+            return Err(CorpusError::NotADirectory(root.display().to_string()));
+        }
+        Ok(Self {
+            root,
+            documents: BTreeMap::new(),
+            postings: HashMap::new(),
+            next_id: 0,
+            total_length: 0,
+        })
+    }
+
+    /// Indexa todos os arquivos suportados de `root`
+    pub fn build(root: impl AsRef<Path>) -> Result<(Self, IndexStats), CorpusError> {
+        let mut index = Self::new(root)?;
+        let stats = index.update()?;
+        Ok((index, stats))
+    }
+
+    /// Carrega um índice salvo com `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CorpusError> {
+        let data = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// Salva o índice em JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CorpusError> {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Diretório raiz do corpus
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Número de documentos indexados
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Se o índice está vazio
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Documentos indexados
+    pub fn documents(&self) -> impl Iterator<Item = &IndexedDocument> {
+        self.documents.values()
+    }
+
+    /// Sincroniza o índice com o disco
+    ///
+    /// Reindexa só os arquivos novos ou alterados (data ou tamanho) e remove
+    /// os apagados. Arquivos ilegíveis mantêm a versão anterior, se houver.
+    pub fn update(&mut self) -> Result<IndexStats, CorpusError> {
+        let files = collect_files(&self.root)?;
+        let by_path: HashMap<String, u32> = self
+            .documents
+            .iter()
+            .map(|(id, doc)| (doc.path.clone(), *id))
+            .collect();
+
+        let reader = FileReader::new();
+        let mut stats = IndexStats::default();
+        let mut seen = HashSet::new();
+        let mut stale = HashSet::new();
+
+        for (path, modified, size_bytes) in files {
+            let key = path.to_string_lossy().to_string();
+            seen.insert(key.clone());
+
+            let existing = by_path.get(&key).copied();
+            if let Some(id) = existing {
+                let doc = &self.documents[&id];
+                if doc.modified == modified && doc.size_bytes == size_bytes {
+                    stats.unchanged += 1;
+                    continue;
+                }
+            }
+
+            match reader.read_file(&key) {
+                Ok(content) => {
+                    match existing {
+                        Some(id) => {
+                            stale.insert(id);
+                            stats.updated += 1;
+                        }
+                        None => stats.added += 1,
+                    }
+                    self.insert(key, modified, size_bytes, content);
+                }
+                Err(e) => {
+                    log::warn!("⚠️ Corpus: falha ao ler {}: {}", key, e);
+                    stats.failed += 1;
+                }
+            }
+        }
+
+        for (path, id) in &by_path {
+            if !seen.contains(path) {
+                stale.insert(*id);
+                stats.removed += 1;
+            }
+        }
+        self.remove_documents(&stale);
+
+        log::info!(
+            "📚 Corpus: {} documentos | +{} ~{} -{} ={} ✗{}",
+            self.documents.len(),
+            stats.added,
+            stats.updated,
+            stats.removed,
+            stats.unchanged,
+            stats.failed
+        );
+        Ok(stats)
+    }
+
+    /// Busca BM25, do maior score para o menor
+    pub fn search(&self, query: &str, limit: usize) -> Vec<CorpusHit> {
+        let mut scored: Vec<(u32, f32)> = self.bm25(&tokenize(query)).into_iter().collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(id, score)| {
+                let doc = &self.documents[&id];
+                CorpusHit {
+                    url: doc.url(),
+                    title: doc.title.clone(),
+                    preview: doc.preview.clone(),
+                    score,
+                }
+            })
+            .collect()
+    }
+
+    /// Score BM25 de cada documento que contém algum termo
+    fn bm25(&self, terms: &[String]) -> HashMap<u32, f32> {
+        let mut scores = HashMap::new();
+        if self.documents.is_empty() {
+            return scores;
+        }
+
+        let n = self.documents.len() as f32;
+        let avg_length = (self.total_length as f32 / n).max(1.0);
+        let unique: HashSet<&String> = terms.iter().collect();
+
+        for term in unique {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let df = postings.len() as f32;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+
+            for posting in postings {
+                let length = self.documents[&posting.doc].length as f32;
+                let tf = posting.tf as f32;
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * length / avg_length);
+                *scores.entry(posting.doc).or_insert(0.0) +=
+                    idf * tf * (BM25_K1 + 1.0) / (tf + norm);
+            }
+        }
+        scores
+    }
+
+    fn insert(&mut self, path: String, modified: u64, size_bytes: u64, content: FileContent) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        let mut length = 0u32;
+        for token in tokenize(&content.text) {
+            *frequencies.entry(token).or_insert(0) += 1;
+            length += 1;
+        }
+        for (term, tf) in frequencies {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push(Posting { doc: id, tf });
+        }
+
+        let title = document_title(&content, Path::new(&path));
+        let preview = content
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+
+        self.total_length += length as u64;
+        self.documents.insert(
+            id,
+            IndexedDocument {
+                path,
+                title,
+                modified,
+                size_bytes,
+                length,
+                preview,
+            },
+        );
+    }
+
+    fn remove_documents(&mut self, ids: &HashSet<u32>) {
+        if ids.is_empty() {
+            return;
+        }
+        for id in ids {
+            if let Some(doc) = self.documents.remove(id) {
+                self.total_length -= doc.length as u64;
+            }
+        }
+        self.postings.retain(|_, postings| {
+            postings.retain(|p| !ids.contains(&p.doc));
+            !postings.is_empty()
+        });
+    }
+
+    /// Documento de uma URL `file://` (ou caminho) indexada
+    fn document_for(&self, url: &str) -> Option<u32> {
+        let path = url_to_path(url)?;
+        let path = path.to_string_lossy();
+        self.documents
+            .iter()
+            .find(|(_, doc)| doc.path == path)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Termos indexáveis: minúsculas, alfanuméricos, sem stopwords
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 2 && !STOPWORDS.contains(t))
+        .map(String::from)
+        .collect()
+}
+
+/// URL `file://` de um caminho absoluto
+pub fn file_url(path: &Path) -> String {
+    url::Url::from_file_path(path)
+        .map(|u| u.to_string())
+        .unwrap_or_else(|_| format!("file://{}", path.display()))
+}
+
+/// Caminho local de uma URL `file://` ou de um caminho absoluto
+fn url_to_path(url: &str) -> Option<PathBuf> {
+    if url.starts_with("file://") {
+        url::Url::parse(url).ok()?.to_file_path().ok()
+    } else if Path::new(url).is_absolute() {
+        Some(PathBuf::from(url))
+    } else {
+        None
+    }
+}
+
+fn is_indexable(path: &Path) -> bool {
+    matches!(
+        FileType::from_url(&path.to_string_lossy()),
+        FileType::Pdf
+            | FileType::Html
+            | FileType::Text
+            | FileType::Markdown
+            | FileType::Json
+            | FileType::Xml
+    )
+}
+
+/// Arquivos suportados sob `root` com (caminho, modificação, tamanho)
+///
+/// Ignora arquivos e diretórios ocultos; não segue links simbólicos.
+fn collect_files(root: &Path) -> Result<Vec<(PathBuf, u64, u64)>, CorpusError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() && is_indexable(&path) {
+                let metadata = entry.metadata()?;
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                files.push((path, modified, metadata.len()));
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Título do `FileReader`, primeiro cabeçalho Markdown ou nome do arquivo
+fn document_title(content: &FileContent, path: &Path) -> String {
+    if let Some(title) = content.title.as_ref().filter(|t| !t.trim().is_empty()) {
+        return title.trim().to_string();
+    }
+    if matches!(content.file_type, FileType::Markdown) {
+        let heading = content
+            .text
+            .lines()
+            .map(str::trim)
+            .find(|l| l.starts_with('#'))
+            .map(|l| l.trim_start_matches('#').trim());
+        if let Some(heading) = heading.filter(|h| !h.is_empty()) {
+            return heading.to_string();
+        }
+    }
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CLIENTE DE BUSCA
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// `SearchClient` offline sobre um `CorpusIndex`
+#[derive(Debug, Clone)]
+pub struct LocalCorpusClient {
+    index: Arc<CorpusIndex>,
+}
+
+impl LocalCorpusClient {
+    /// Cria o cliente a partir de um índice em memória
+    pub fn new(index: CorpusIndex) -> Self {
+        Self {
+            index: Arc::new(index),
+        }
+    }
+
+    /// Carrega o índice salvo em `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CorpusError> {
+        let index = CorpusIndex::load(path)?;
+        log::info!(
+            "📚 Corpus local: {} documentos em {}",
+            index.len(),
+            index.root().display()
+        );
+        Ok(Self::new(index))
+    }
+
+    /// Índice usado pelo cliente
+    pub fn index(&self) -> &CorpusIndex {
+        &self.index
+    }
+
+    /// Caminho de uma URL, desde que esteja dentro da raiz do corpus
+    fn resolve(&self, url: &str) -> Result<PathBuf, SearchError> {
+        let path = url_to_path(url).ok_or_else(|| {
+            SearchError::InvalidUrl(format!("{} (corpus local só lê file://)", url))
+        })?;
+        let path = std::fs::canonicalize(&path)
+            .map_err(|e| SearchError::FetchError(format!("{}: {}", path.display(), e)))?;
+        if !path.starts_with(self.index.root()) {
+            return Err(SearchError::FetchError(format!(
+                "{} está fora do corpus {}",
+                path.display(),
+                self.index.root().display()
+            )));
+        }
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl SearchClient for LocalCorpusClient {
+    async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
+        let hits = self.index.search(&query.q, DEFAULT_SEARCH_LIMIT);
+        let max = hits
+            .first()
+            .map(|h| h.score)
+            .unwrap_or(0.0)
+            .max(f32::EPSILON);
+
+        let urls: Vec<BoostedSearchSnippet> = hits
+            .iter()
+            .map(|hit| {
+                let score = hit.score / max;
+                BoostedSearchSnippet {
+                    url: hit.url.clone(),
+                    title: hit.title.clone(),
+                    description: hit.preview.clone(),
+                    weight: score,
+                    final_score: score,
+                    score,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        log::info!("📚 Corpus: '{}' → {} documentos", query.q, urls.len());
+        Ok(SearchResult {
+            snippets: hits.into_iter().map(|h| h.preview).collect(),
+            total_results: urls.len() as u64,
+            urls,
+        })
+    }
+
+    async fn search_batch(&self, queries: &[SerpQuery]) -> Vec<Result<SearchResult, SearchError>> {
+        let mut results = Vec::with_capacity(queries.len());
+        for query in queries {
+            results.push(self.search(query).await);
+        }
+        results
+    }
+
+    async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+        let path = self.resolve(url)?;
+        let start = Instant::now();
+
+        let read_path = path.clone();
+        let content = tokio::task::spawn_blocking(move || {
+            FileReader::new().read_file(&read_path.to_string_lossy())
+        })
+        .await
+        .map_err(|e| SearchError::ExtractionError(e.to_string()))?
+        .map_err(|e| SearchError::ExtractionError(e.to_string()))?;
+
+        Ok(UrlContent {
+            title: document_title(&content, &path),
+            word_count: content.word_count,
+            text: content.text,
+            url: url.clone(),
+            read_time_ms: Some(start.elapsed().as_millis()),
+            source: Some(READ_METHOD.to_string()),
+        })
+    }
+
+    async fn read_urls_batch(&self, urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+        futures::future::join_all(urls.iter().map(|url| self.read_url(url))).await
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        urls: &[BoostedSearchSnippet],
+    ) -> Vec<BoostedSearchSnippet> {
+        let scores = self.index.bm25(&tokenize(query));
+        let raw: Vec<f32> = urls
+            .iter()
+            .map(|snippet| {
+                self.index
+                    .document_for(&snippet.url)
+                    .and_then(|id| scores.get(&id).copied())
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        let max = raw.iter().copied().fold(0.0f32, f32::max).max(f32::EPSILON);
+
+        let mut reranked: Vec<BoostedSearchSnippet> = urls
+            .iter()
+            .zip(raw)
+            .map(|(snippet, score)| {
+                let mut updated = snippet.clone();
+                updated.jina_rerank_boost = score / max;
+                updated.final_score = updated.weight
+                    * updated.freq_boost
+                    * updated.hostname_boost
+                    * updated.path_boost
+                    * updated.jina_rerank_boost;
+                updated.score = updated.final_score;
+                updated
+            })
+            .collect();
+
+        reranked.sort_by(|a, b| b.final_score.total_cmp(&a.final_score));
+        reranked
+    }
+
+    async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+        let start = Instant::now();
+        let result = self.read_url(url).await.ok();
+        ComparativeReadResult {
+            url: url.clone(),
+            jina_result: None,
+            rust_result: result,
+            jina_time_ms: 0,
+            rust_time_ms: start.elapsed().as_millis(),
+            speed_diff_ms: 0,
+            faster: ReadMethod::RustLocal,
+        }
+    }
+
+    async fn read_urls_comparative_batch(&self, urls: &[Url]) -> Vec<ComparativeReadResult> {
+        futures::future::join_all(urls.iter().map(|url| self.read_url_comparative(url))).await
+    }
+
+    async fn read_url_with_fallback_progress(
+        &self,
+        url: &Url,
+        progress: Arc<AtomicU8>,
+    ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+        let result = self.read_url(url).await;
+        progress.store(100, Ordering::Relaxed);
+        let bytes = result.as_ref().map(|c| c.text.len()).unwrap_or(0);
+        (result, READ_METHOD, 1, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_corpus() -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("deep-research-corpus-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        std::fs::write(
+            dir.join("rust.md"),
+            "# Rust ownership\nOwnership and borrowing make Rust memory safe. Ownership rules.",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("notes/python.txt"),
+            "Python uses garbage collection for memory.",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".hidden.txt"), "ownership ownership ownership").unwrap();
+        std::fs::write(dir.join("image.png"), [0u8; 4]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_bm25_search_and_incremental_update() {
+        let dir = temp_corpus();
+        let (mut index, stats) = CorpusIndex::build(&dir).unwrap();
+        assert_eq!(stats.added, 2);
+        assert_eq!(index.len(), 2);
+
+        let hits = index.search("rust ownership", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].title, "Rust ownership");
+        assert!(hits[0].url.starts_with("file://"));
+
+        let hits = index.search("memory", 10);
+        assert_eq!(hits.len(), 2);
+
+        // Persistência
+        let index_path = dir.join(".index/corpus.json");
+        index.save(&index_path).unwrap();
+        let loaded = CorpusIndex::load(&index_path).unwrap();
+        assert_eq!(loaded.search("garbage", 10).len(), 1);
+
+        // Alterar um arquivo, apagar outro e criar um novo
+        std::fs::write(
+            dir.join("notes/python.txt"),
+            "Python ownership is handled by reference counting and garbage collection.",
+        )
+        .unwrap();
+        std::fs::remove_file(dir.join("rust.md")).unwrap();
+        std::fs::write(
+            dir.join("go.html"),
+            "<html><head><title>Go</title></head><body><article><p>Goroutines are \
+             lightweight threads managed by the Go runtime.</p></article></body></html>",
+        )
+        .unwrap();
+
+        let stats = index.update().unwrap();
+        assert_eq!(stats.updated, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(stats.added, 1);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.search("rust", 10).len(), 0);
+        assert_eq!(index.search("ownership", 10).len(), 1);
+        assert_eq!(index.search("goroutines", 10).len(), 1);
+
+        let stats = index.update().unwrap();
+        assert_eq!(stats.unchanged, 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_client_searches_reads_and_reranks_offline() {
+        let dir = temp_corpus();
+        let (index, _) = CorpusIndex::build(&dir).unwrap();
+        let client = LocalCorpusClient::new(index);
+
+        let result = client
+            .search(&SerpQuery {
+                q: "memory".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.urls.len(), 2);
+        assert!((result.urls[0].score - 1.0).abs() < 1e-6);
+
+        let content = client.read_url(&result.urls[0].url).await.unwrap();
+        assert!(content.text.contains("memory"));
+        assert_eq!(content.source.as_deref(), Some("local_corpus"));
+
+        // Fora do corpus ou na web: erro, nunca rede
+        assert!(client
+            .read_url(&"https://example.com".to_string())
+            .await
+            .is_err());
+        let outside = file_url(&std::env::temp_dir());
+        assert!(client.read_url(&outside).await.is_err());
+
+        let reranked = client.rerank("python garbage", &result.urls).await;
+        assert!(reranked[0].url.ends_with("python.txt"));
+        assert!(reranked[0].final_score > reranked[1].final_score);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
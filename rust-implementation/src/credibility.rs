@@ -0,0 +1,678 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CREDIBILIDADE DE FONTES - Pontuação de domínios e páginas
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Substitui a tabela fixa de `hostname_boost` por um score explicável:
+// - Camadas por domínio (trusted → blocked), configuráveis em JSON
+// - TLD institucional (.gov, .edu, .int, .mil, inclusive .gov.br etc.)
+// - Sinais da página lida: autor, data, citações e proporção de conteúdo
+// - Histórico: hosts citados em respostas aprovadas ganham peso; hosts
+//   visitados e nunca citados perdem
+//
+// O score vira o `hostname_boost` de cada `BoostedSearchSnippet` (e portanto
+// entra no `final_score`) e pondera a ordem das referências semânticas.
+// Cada ajuste deixa um motivo legível em `CredibilityScore::reasons`.
+//
+// O mesmo arquivo também define os domínios que só o Jina Reader consegue
+// ler (JS, paywall, encurtadores).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::search::extract_hostname;
+use crate::tui::ResearchSession;
+use crate::types::BoostedSearchSnippet;
+
+/// Peso de domínios com TLD institucional sem camada explícita
+const INSTITUTIONAL_TLD_BOOST: f32 = 1.3;
+
+/// Ajuste máximo do histórico, para mais ou para menos
+const HISTORY_MAX_ADJUSTMENT: f32 = 0.2;
+
+/// Respostas aprovadas em que o host precisa ter aparecido para o histórico contar
+const HISTORY_MIN_ANSWERS: u32 = 2;
+
+/// Citações a partir das quais a página conta como bem referenciada
+const MIN_CITATIONS: usize = 3;
+
+/// Proporção de conteúdo abaixo da qual a página é penalizada
+const MIN_CONTENT_RATIO: f32 = 0.5;
+
+/// Linhas do topo da página onde autor e data são procurados
+const HEADER_LINES: usize = 40;
+
+/// TLDs de instituições públicas e acadêmicas
+const INSTITUTIONAL_TLDS: &[&str] = &["gov", "edu", "int", "mil"];
+
+/// Domínios confiáveis da tabela embutida
+const DEFAULT_TRUSTED_DOMAINS: &[&str] = &[
+    "wikipedia.org",
+    "arxiv.org",
+    "github.com",
+    "stackoverflow.com",
+    "docs.rs",
+    "rust-lang.org",
+];
+
+/// Domínios que requerem Jina Reader na tabela embutida
+const DEFAULT_READER_DOMAINS: &[&str] = &[
+    // Redes sociais que requerem JS
+    "tiktok.com",
+    "instagram.com",
+    "twitter.com",
+    "x.com",
+    "facebook.com",
+    "threads.net",
+    "snapchat.com",
+    "pinterest.com",
+    // Plataformas de vídeo (requerem JS)
+    "youtube.com",
+    "youtu.be",
+    "vimeo.com",
+    "twitch.tv",
+    "dailymotion.com",
+    // Apps e SPAs
+    "linkedin.com",
+    "discord.com",
+    "slack.com",
+    "notion.so",
+    "figma.com",
+    "canva.com",
+    "medium.com",
+    // Paywalls que Jina consegue ler
+    "wsj.com",
+    "ft.com",
+    "nytimes.com",
+    "washingtonpost.com",
+    "bloomberg.com",
+    "economist.com",
+    "reuters.com",
+    // URL shorteners (precisam resolver redirect)
+    "t.co",
+    "bit.ly",
+    "goo.gl",
+    "tinyurl.com",
+    "ow.ly",
+    // Google Apps (requerem JS)
+    "maps.google.com",
+    "drive.google.com",
+    "docs.google.com",
+    "sheets.google.com",
+];
+
+/// Marcadores de linhas de anúncio, navegação e cadastro
+const BOILERPLATE_MARKERS: &[&str] = &[
+    "advertisement",
+    "sponsored",
+    "publicidade",
+    "patrocinado",
+    "subscribe",
+    "assine",
+    "newsletter",
+    "cookie",
+    "sign up",
+    "cadastre-se",
+];
+
+/// Nomes e abreviações de meses (inglês e português)
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september",
+    "october", "november", "december", "janeiro", "fevereiro", "março", "abril", "maio",
+    "junho", "julho", "agosto", "setembro", "outubro", "novembro", "dezembro", "jan", "feb",
+    "fev", "mar", "apr", "abr", "mai", "jun", "jul", "aug", "ago", "sep", "sept", "set",
+    "oct", "out", "nov", "dec", "dez",
+];
+
+/// Erros ao carregar a configuração de credibilidade
+#[derive(Debug, Error)]
+pub enum CredibilityError {
+    /// Falha ao ler o arquivo
+    #[error("Failed to read credibility file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON inválido
+    #[error("Invalid credibility file: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Camada de credibilidade de um domínio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredibilityTier {
+    /// Referência na área (enciclopédias, documentação oficial)
+    Trusted,
+    /// Veículos e publicações reconhecidos
+    Reputable,
+    /// Sem informação
+    Neutral,
+    /// Fazendas de conteúdo, agregadores
+    Low,
+    /// Nunca usar
+    Blocked,
+}
+
+impl CredibilityTier {
+    /// Peso multiplicativo da camada
+    pub fn weight(self) -> f32 {
+        match self {
+            Self::Trusted => 1.5,
+            Self::Reputable => 1.25,
+            Self::Neutral => 1.0,
+            Self::Low => 0.6,
+            Self::Blocked => 0.0,
+        }
+    }
+
+    /// Nome da camada
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trusted => "trusted",
+            Self::Reputable => "reputable",
+            Self::Neutral => "neutral",
+            Self::Low => "low",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+/// Camadas por domínio e domínios que requerem Jina Reader
+///
+/// Um domínio vale para os seus subdomínios; havendo mais de um, vence o
+/// mais específico.
+///
+/// # Exemplo de arquivo
+/// ```json
+/// {
+///   "domains": { "who.int": "trusted", "content-farm.com": "blocked" },
+///   "reader_required": ["paywalled-news.com"]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredibilityConfig {
+    /// Camada de cada domínio
+    #[serde(default)]
+    pub domains: HashMap<String, CredibilityTier>,
+    /// Domínios que só o Jina Reader consegue ler
+    #[serde(default)]
+    pub reader_required: Vec<String>,
+}
+
+impl Default for CredibilityConfig {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl CredibilityConfig {
+    /// Tabela embutida
+    pub fn builtin() -> Self {
+        Self {
+            domains: DEFAULT_TRUSTED_DOMAINS
+                .iter()
+                .map(|d| (d.to_string(), CredibilityTier::Trusted))
+                .collect(),
+            reader_required: DEFAULT_READER_DOMAINS.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    /// Carrega um arquivo JSON sobre a tabela embutida
+    ///
+    /// Domínios do arquivo substituem os embutidos; `reader_required` é somado.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CredibilityError> {
+        let overrides: CredibilityConfig = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let mut config = Self::builtin();
+        config.domains.extend(
+            overrides
+                .domains
+                .into_iter()
+                .map(|(domain, tier)| (domain.to_lowercase(), tier)),
+        );
+        for domain in overrides.reader_required {
+            let domain = domain.to_lowercase();
+            if !config.reader_required.contains(&domain) {
+                config.reader_required.push(domain);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Carrega o arquivo configurado ou usa a tabela embutida em caso de erro
+    pub fn load(path: Option<&str>) -> Self {
+        match path {
+            Some(path) => Self::from_file(path).unwrap_or_else(|e| {
+                log::warn!("⚠️ {} ({}), usando credibilidade embutida", e, path);
+                Self::builtin()
+            }),
+            None => Self::builtin(),
+        }
+    }
+
+    /// Domínio mais específico com camada explícita para o host
+    pub fn domain_tier(&self, hostname: &str) -> Option<(&str, CredibilityTier)> {
+        self.domains
+            .iter()
+            .filter(|(domain, _)| host_matches(hostname, domain))
+            .max_by_key(|(domain, _)| domain.len())
+            .map(|(domain, tier)| (domain.as_str(), *tier))
+    }
+
+    /// Peso do host só pela camada e pelo TLD (sem página nem histórico)
+    pub fn host_weight(&self, hostname: &str) -> f32 {
+        let hostname = hostname.to_lowercase();
+        match self.domain_tier(&hostname) {
+            Some((_, tier)) => tier.weight(),
+            None if institutional_tld(&hostname).is_some() => INSTITUTIONAL_TLD_BOOST,
+            None => 1.0,
+        }
+    }
+
+    /// Se a URL precisa do Jina Reader (JS-heavy, paywalls, redes sociais)
+    pub fn requires_reader(&self, url: &str) -> bool {
+        extract_hostname(url)
+            .map(|host| host.to_lowercase())
+            .is_some_and(|host| self.reader_required.iter().any(|d| host_matches(&host, d)))
+    }
+}
+
+/// Sinais de qualidade extraídos do texto de uma página
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageSignals {
+    /// Autor identificado no topo da página
+    pub has_author: bool,
+    /// Data de publicação no topo da página
+    pub has_date: bool,
+    /// Citações (DOI, "et al.", notas `[n]`)
+    pub citations: usize,
+    /// Fração das linhas que é conteúdo, não anúncio ou navegação (0.0 - 1.0)
+    pub content_ratio: f32,
+}
+
+impl PageSignals {
+    /// Extrai os sinais do texto limpo da página
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        let header = &lines[..lines.len().min(HEADER_LINES)];
+
+        let lower = text.to_lowercase();
+        let citations = lower.matches("doi.org/").count()
+            + lower.matches("doi:").count()
+            + lower.matches("et al.").count()
+            + count_footnotes(text);
+
+        let boilerplate = lines.iter().filter(|l| is_boilerplate(l)).count();
+        let content_ratio = if lines.is_empty() {
+            1.0
+        } else {
+            1.0 - boilerplate as f32 / lines.len() as f32
+        };
+
+        Self {
+            has_author: header.iter().any(|l| is_byline(l)),
+            has_date: header.iter().any(|l| has_date(l)),
+            citations,
+            content_ratio,
+        }
+    }
+}
+
+/// Credibilidade de uma fonte, com os motivos de cada ajuste
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredibilityScore {
+    /// URL avaliada
+    pub url: String,
+    /// Host da URL
+    pub host: String,
+    /// Camada do domínio
+    pub tier: CredibilityTier,
+    /// Peso final (1.0 = neutro, 0.0 = bloqueado)
+    pub score: f32,
+    /// Motivos legíveis de cada ajuste
+    pub reasons: Vec<String>,
+}
+
+impl CredibilityScore {
+    /// Se a fonte está bloqueada
+    pub fn is_blocked(&self) -> bool {
+        self.tier == CredibilityTier::Blocked
+    }
+
+    /// Motivos em uma linha
+    pub fn explain(&self) -> String {
+        if self.reasons.is_empty() {
+            "sem sinais".into()
+        } else {
+            self.reasons.join("; ")
+        }
+    }
+}
+
+/// Histórico de um host em respostas aprovadas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostRecord {
+    /// Respostas aprovadas em que o host foi lido ou citado
+    pub answers: u32,
+    /// Respostas aprovadas em que o host foi citado
+    pub cited: u32,
+}
+
+/// Pontuação de credibilidade de fontes
+///
+/// # Exemplo
+/// ```rust,ignore
+/// let mut credibility = SourceCredibility::new(CredibilityConfig::load(Some("credibility.json")));
+/// credibility.learn_from_sessions(&sessions);
+///
+/// // Clones compartilham o histórico: respostas aprovadas por um agente
+/// // valem para todos os que receberam o mesmo `SourceCredibility`
+/// credibility.clone().record_answer(&visited_urls, &cited_urls);
+///
+/// let score = credibility.score_url("https://www.nasa.gov/missions");
+/// println!("{:.2} ({})", score.score, score.explain());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceCredibility {
+    config: CredibilityConfig,
+    /// Histórico por host, compartilhado entre clones
+    hosts: Arc<RwLock<HashMap<String, HostRecord>>>,
+}
+
+impl SourceCredibility {
+    /// Cria com a configuração dada e sem histórico
+    pub fn new(config: CredibilityConfig) -> Self {
+        Self {
+            config,
+            hosts: Arc::default(),
+        }
+    }
+
+    /// Configuração em uso
+    pub fn config(&self) -> &CredibilityConfig {
+        &self.config
+    }
+
+    /// Histórico de um host
+    pub fn host_record(&self, hostname: &str) -> Option<HostRecord> {
+        self.hosts
+            .read()
+            .ok()?
+            .get(&hostname.to_lowercase())
+            .copied()
+    }
+
+    /// Registra uma resposta aprovada: hosts lidos e hosts citados
+    pub fn record_answer(&self, visited: &[String], cited: &[String]) {
+        let cited: HashSet<String> = cited.iter().filter_map(|u| normalized_host(u)).collect();
+        let mut hosts: HashSet<String> = visited.iter().filter_map(|u| normalized_host(u)).collect();
+        hosts.extend(cited.iter().cloned());
+
+        let Ok(mut records) = self.hosts.write() else {
+            return;
+        };
+        for host in hosts {
+            let record = records.entry(host.clone()).or_default();
+            record.answers += 1;
+            if cited.contains(&host) {
+                record.cited += 1;
+            }
+        }
+    }
+
+    /// Aprende com as sessões bem-sucedidas do histórico
+    ///
+    /// Retorna quantas sessões foram usadas.
+    pub fn learn_from_sessions(&self, sessions: &[ResearchSession]) -> usize {
+        let mut learned = 0;
+        for session in sessions.iter().filter(|s| s.success && s.answer.is_some()) {
+            // Referências da sessão são texto livre ("título - url")
+            let cited: Vec<String> = session
+                .references
+                .iter()
+                .flat_map(|r| r.split_whitespace())
+                .filter(|t| t.starts_with("http://") || t.starts_with("https://"))
+                .map(String::from)
+                .collect();
+            self.record_answer(&session.visited_urls, &cited);
+            learned += 1;
+        }
+        learned
+    }
+
+    /// Credibilidade pela URL: camada do domínio, TLD e histórico
+    pub fn score_url(&self, url: &str) -> CredibilityScore {
+        let host = normalized_host(url).unwrap_or_default();
+        let mut reasons = Vec::new();
+
+        let (tier, mut score) = match self.config.domain_tier(&host) {
+            Some((domain, tier)) => {
+                reasons.push(format!("{} listado como {}", domain, tier.as_str()));
+                (tier, tier.weight())
+            }
+            None => match institutional_tld(&host) {
+                Some(tld) => {
+                    reasons.push(format!("TLD institucional .{}", tld));
+                    (CredibilityTier::Neutral, INSTITUTIONAL_TLD_BOOST)
+                }
+                None => (CredibilityTier::Neutral, 1.0),
+            },
+        };
+
+        if tier != CredibilityTier::Blocked {
+            if let Some(record) = self
+                .host_record(&host)
+                .filter(|r| r.answers >= HISTORY_MIN_ANSWERS)
+            {
+                // Suavização de Laplace: sem citações nem ausências, peso 1.0
+                let rate = (record.cited + 1) as f32 / (record.answers + 2) as f32;
+                score *= 1.0 + HISTORY_MAX_ADJUSTMENT * (2.0 * rate - 1.0);
+                reasons.push(format!(
+                    "citado em {} de {} respostas aprovadas",
+                    record.cited, record.answers
+                ));
+            }
+        }
+
+        CredibilityScore {
+            url: url.to_string(),
+            host,
+            tier,
+            score,
+            reasons,
+        }
+    }
+
+    /// Credibilidade da URL ajustada pelos sinais da página lida
+    pub fn score_page(&self, url: &str, text: &str) -> CredibilityScore {
+        let mut result = self.score_url(url);
+        if result.is_blocked() {
+            return result;
+        }
+
+        let signals = PageSignals::from_text(text);
+        if signals.has_author {
+            result.score *= 1.05;
+            result.reasons.push("autor identificado".into());
+        }
+        if signals.has_date {
+            result.score *= 1.05;
+            result.reasons.push("data de publicação".into());
+        }
+        if signals.citations >= MIN_CITATIONS {
+            result.score *= 1.1;
+            result.reasons.push(format!("{} citações", signals.citations));
+        }
+        if signals.content_ratio < MIN_CONTENT_RATIO {
+            result.score *= 0.8;
+            result.reasons.push(format!(
+                "{:.0}% do texto é anúncio ou navegação",
+                (1.0 - signals.content_ratio) * 100.0
+            ));
+        }
+        result
+    }
+
+    /// Aplica a credibilidade ao snippet e recalcula o `final_score`
+    pub fn apply(&self, snippet: &mut BoostedSearchSnippet) -> CredibilityScore {
+        let score = self.score_url(&snippet.url);
+        snippet.hostname_boost = score.score;
+        snippet.final_score = snippet.weight
+            * snippet.freq_boost
+            * snippet.hostname_boost
+            * snippet.path_boost
+            * snippet.jina_rerank_boost;
+        snippet.score = snippet.final_score;
+        score
+    }
+}
+
+/// Host da URL em minúsculas
+fn normalized_host(url: &str) -> Option<String> {
+    extract_hostname(url).map(|h| h.to_lowercase())
+}
+
+/// Se o host é o domínio ou um subdomínio dele
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// TLD institucional do host (`gov`, `gov.br`, `edu.au`...)
+fn institutional_tld(host: &str) -> Option<String> {
+    let labels: Vec<&str> = host.split('.').collect();
+    let n = labels.len();
+    if n >= 2 && INSTITUTIONAL_TLDS.contains(&labels[n - 1]) {
+        return Some(labels[n - 1].to_string());
+    }
+    // Domínio de segundo nível sob TLD de país (ex: saude.gov.br)
+    if n >= 3 && labels[n - 1].len() == 2 && INSTITUTIONAL_TLDS.contains(&labels[n - 2]) {
+        return Some(labels[n - 2..].join("."));
+    }
+    None
+}
+
+/// Linha curta de autoria ("By Jane Doe", "Por Fulano", "Autor: ...")
+fn is_byline(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let short = lower.split_whitespace().count() <= 8;
+    (short && (lower.starts_with("by ") || lower.starts_with("por ")))
+        || lower.contains("author:")
+        || lower.contains("autor:")
+        || lower.contains("written by")
+        || lower.contains("escrito por")
+}
+
+/// Linha com data: ano plausível junto de um mês ou no formato numérico
+fn has_date(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has_year = words.iter().any(|w| {
+        w.len() == 4 && w.parse::<u32>().is_ok_and(|y| (1900..=2099).contains(&y))
+    });
+    if !has_year {
+        return false;
+    }
+
+    let numeric = lower.split_whitespace().any(|token| {
+        token.matches(['-', '/']).count() == 2
+            && token.chars().filter(|c| c.is_ascii_digit()).count() >= 6
+    });
+    numeric || words.iter().any(|w| MONTHS.contains(w))
+}
+
+/// Linha de anúncio, cadastro ou link de navegação solto
+fn is_boilerplate(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let nav_link = line.starts_with('[')
+        && line.ends_with(')')
+        && line.contains("](")
+        && line.split_whitespace().count() <= 6;
+    nav_link || BOILERPLATE_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+/// Notas numéricas no estilo `[12]`
+fn count_footnotes(text: &str) -> usize {
+    text.match_indices('[')
+        .filter(|(i, _)| {
+            let rest = &text[i + 1..];
+            let digits = rest.chars().take_while(char::is_ascii_digit).count();
+            (1..=3).contains(&digits) && rest[digits..].starts_with(']')
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_url_tiers_and_tld() {
+        let credibility = SourceCredibility::default();
+        let wiki = credibility.score_url("https://en.wikipedia.org/wiki/Rust");
+        assert_eq!(wiki.tier, CredibilityTier::Trusted);
+        assert!((wiki.score - 1.5).abs() < 1e-6);
+        assert_eq!(credibility.score_url("https://random-site.com/a").score, 1.0);
+
+        let gov = credibility.score_url("https://portal.saude.gov.br/noticias");
+        assert!((gov.score - INSTITUTIONAL_TLD_BOOST).abs() < 1e-6);
+        assert!(gov.explain().contains(".gov.br"));
+
+        let mut config = CredibilityConfig::builtin();
+        config.domains.insert("farm.com".into(), CredibilityTier::Blocked);
+        config.domains.insert("docs.github.com".into(), CredibilityTier::Reputable);
+        let credibility = SourceCredibility::new(config);
+        assert!(credibility.score_url("https://news.farm.com/x").is_blocked());
+        assert!(!credibility.score_url("https://notfarm.com/x").is_blocked());
+        // O domínio mais específico vence
+        assert_eq!(
+            credibility.score_url("https://docs.github.com/en").tier,
+            CredibilityTier::Reputable
+        );
+
+        assert!(credibility.config().requires_reader("https://x.com/user/status/1"));
+        assert!(!credibility.config().requires_reader("https://microsoft.com/t.co"));
+    }
+
+    #[test]
+    fn test_page_signals_and_history() {
+        let page = "Why the sky is blue\nBy Jane Doe\nPublished March 3, 2024\n\n\
+                    Rayleigh scattering explains it [1]. See Smith et al. (2019) [2] and doi:10.1000/xyz.\n\
+                    Advertisement\n";
+        let signals = PageSignals::from_text(page);
+        assert!(signals.has_author);
+        assert!(signals.has_date);
+        assert_eq!(signals.citations, 4);
+        assert!(signals.content_ratio > 0.7);
+
+        let credibility = SourceCredibility::default();
+        let url = "https://blog.example.com/sky";
+        assert!(credibility.score_page(url, page).score > credibility.score_url(url).score);
+
+        let visited = vec![url.to_string(), "https://spam.example.net/a".to_string()];
+        let cited = vec![url.to_string()];
+        credibility.record_answer(&visited, &cited);
+        // Uma resposta só não basta
+        assert_eq!(credibility.score_url(url).score, 1.0);
+
+        // Registrado num clone (ex.: pelo agente), visto pelo original
+        credibility.clone().record_answer(&visited, &cited);
+        assert!(credibility.score_url(url).score > 1.0);
+        assert!(credibility.score_url("https://spam.example.net/b").score < 1.0);
+        assert_eq!(
+            credibility.host_record("blog.example.com"),
+            Some(HostRecord { answers: 2, cited: 2 })
+        );
+    }
+}
@@ -39,6 +39,8 @@ pub struct EvaluationContext {
 /// - Plurality: Se pediu N exemplos, tem N exemplos?
 /// - Completeness: Todos os aspectos da pergunta foram cobertos?
 /// - Strict: Avaliação brutal - tem insights reais e profundos?
+/// - Schema: A resposta estruturada valida contra o JSON Schema pedido?
+/// - Verify: Cada afirmação é suportada pelas fontes lidas?
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum EvaluationType {
     /// Verifica se a resposta é confiante e definitiva
@@ -51,6 +53,10 @@ pub enum EvaluationType {
     Completeness,
     /// Avaliação brutal - rejeita respostas mediocres
     Strict,
+    /// Valida a resposta contra o JSON Schema (gate obrigatório, com reparo)
+    Schema,
+    /// Confronta cada afirmação com as fontes lidas (etapa do agente)
+    Verify,
 }
 
 impl EvaluationType {
@@ -62,6 +68,8 @@ impl EvaluationType {
             Self::Plurality => "plurality",
             Self::Completeness => "completeness",
             Self::Strict => "strict",
+            Self::Schema => "schema",
+            Self::Verify => "verify",
         }
     }
 
@@ -98,6 +106,18 @@ impl EvaluationType {
                 timeout: Duration::from_secs(45),
                 weight: 1.5, // Mais importante
             },
+            Self::Schema => EvaluationConfig {
+                eval_type: self,
+                max_retries: 2, // Tentativas de reparo pelo LLM
+                timeout: Duration::from_secs(30),
+                weight: 2.0,
+            },
+            Self::Verify => EvaluationConfig {
+                eval_type: self,
+                max_retries: 1,
+                timeout: Duration::from_secs(60),
+                weight: 1.5,
+            },
         }
     }
 
@@ -271,6 +291,8 @@ mod tests {
         assert_eq!(EvaluationType::Plurality.as_str(), "plurality");
         assert_eq!(EvaluationType::Completeness.as_str(), "completeness");
         assert_eq!(EvaluationType::Strict.as_str(), "strict");
+        assert_eq!(EvaluationType::Schema.as_str(), "schema");
+        assert_eq!(EvaluationType::Verify.as_str(), "verify");
     }
 
     #[test]
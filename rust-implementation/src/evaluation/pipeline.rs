@@ -5,7 +5,9 @@
 use std::sync::Arc;
 
 use super::{EvalError, EvaluationContext, EvaluationResult, EvaluationType, PromptPair};
+use crate::agent::AgentPrompt;
 use crate::llm::LlmClient;
+use crate::utils::{JsonSchema, SchemaViolation};
 
 /// Resultado do pipeline de avaliação
 #[derive(Debug)]
@@ -16,6 +18,8 @@ pub struct EvaluationPipelineResult {
     pub results: Vec<EvaluationResult>,
     /// Tipo de avaliação onde falhou (se aplicável)
     pub failed_at: Option<EvaluationType>,
+    /// Resposta estruturada validada (apenas com JSON Schema configurado)
+    pub structured_answer: Option<serde_json::Value>,
 }
 
 impl EvaluationPipelineResult {
@@ -25,6 +29,7 @@ impl EvaluationPipelineResult {
             overall_passed: true,
             results,
             failed_at: None,
+            structured_answer: None,
         }
     }
 
@@ -34,9 +39,16 @@ impl EvaluationPipelineResult {
             overall_passed: false,
             results,
             failed_at: Some(failed_at),
+            structured_answer: None,
         }
     }
 
+    /// Anexa a resposta estruturada que passou no gate de schema
+    pub fn with_structured_answer(mut self, value: serde_json::Value) -> Self {
+        self.structured_answer = Some(value);
+        self
+    }
+
     /// Retorna o motivo da falha formatado
     pub fn failure_reason(&self) -> Option<String> {
         self.results
@@ -76,14 +88,30 @@ impl EvaluationPipelineResult {
 ///     println!("Failed at: {:?}", result.failed_at);
 /// }
 /// ```
+///
+/// Com um JSON Schema configurado (`with_response_schema`), a validação do
+/// schema roda antes de todas as outras como gate obrigatório.
 pub struct EvaluationPipeline {
     llm: Arc<dyn LlmClient>,
+    response_schema: Option<JsonSchema>,
 }
 
 impl EvaluationPipeline {
     /// Cria um novo pipeline com o cliente LLM fornecido
     pub fn new(llm: Arc<dyn LlmClient>) -> Self {
-        Self { llm }
+        Self {
+            llm,
+            response_schema: None,
+        }
+    }
+
+    /// Exige que a resposta seja JSON válido contra `schema`
+    ///
+    /// Respostas inválidas são reparadas pelo LLM até
+    /// `EvaluationType::Schema.default_config().max_retries` vezes.
+    pub fn with_response_schema(mut self, schema: JsonSchema) -> Self {
+        self.response_schema = Some(schema);
+        self
     }
 
     /// Executa avaliações em sequência - FALHA RÁPIDA
@@ -100,6 +128,25 @@ impl EvaluationPipeline {
     ) -> EvaluationPipelineResult {
         let mut results = Vec::new();
 
+        // GATE DE SCHEMA - as demais avaliações veem o JSON já reparado
+        let mut structured_answer = None;
+        let canonical;
+        let answer = match &self.response_schema {
+            Some(schema) => match self.enforce_schema(schema, question, answer).await {
+                Ok((value, eval_result)) => {
+                    results.push(eval_result);
+                    canonical = serde_json::to_string_pretty(&value).unwrap_or_default();
+                    structured_answer = Some(value);
+                    canonical.as_str()
+                }
+                Err(eval_result) => {
+                    results.push(eval_result);
+                    return EvaluationPipelineResult::failure(results, EvaluationType::Schema);
+                }
+            },
+            None => answer,
+        };
+
         for &eval_type in required_types {
             let result = self
                 .evaluate_single(eval_type, question, answer, context)
@@ -112,7 +159,11 @@ impl EvaluationPipeline {
 
                     // FALHA RÁPIDA - retorna imediatamente se falhou
                     if !passed {
-                        return EvaluationPipelineResult::failure(results, eval_type);
+                        let failure = EvaluationPipelineResult::failure(results, eval_type);
+                        return match structured_answer {
+                            Some(value) => failure.with_structured_answer(value),
+                            None => failure,
+                        };
                     }
                 }
                 Err(_) => {
@@ -129,7 +180,67 @@ impl EvaluationPipeline {
         }
 
         // Todas passaram
-        EvaluationPipelineResult::success(results)
+        let success = EvaluationPipelineResult::success(results);
+        match structured_answer {
+            Some(value) => success.with_structured_answer(value),
+            None => success,
+        }
+    }
+
+    /// Gate de schema: valida a resposta e pede reparos ao LLM se preciso
+    ///
+    /// Retorna o JSON validado ou o resultado de falha com as violações
+    /// da última tentativa como sugestões.
+    pub async fn enforce_schema(
+        &self,
+        schema: &JsonSchema,
+        question: &str,
+        answer: &str,
+    ) -> Result<(serde_json::Value, EvaluationResult), EvaluationResult> {
+        let start = std::time::Instant::now();
+        let max_repairs = EvaluationType::Schema.default_config().max_retries;
+        let mut candidate = answer.to_string();
+        let mut repairs = 0;
+
+        loop {
+            let violations = match schema.parse_answer(&candidate) {
+                Ok(value) => {
+                    let reasoning = match repairs {
+                        0 => "Answer validates against the JSON Schema".to_string(),
+                        n => format!("Answer validates against the JSON Schema after {} repair(s)", n),
+                    };
+                    let result = EvaluationResult::success(EvaluationType::Schema, reasoning, 1.0)
+                        .with_duration(start.elapsed());
+                    return Ok((value, result));
+                }
+                Err(violations) => violations,
+            };
+
+            if repairs >= max_repairs {
+                return Err(schema_failure(&violations, repairs, start.elapsed()));
+            }
+            repairs += 1;
+
+            log::info!(
+                "🧩 Resposta fora do schema ({} violações), reparo {}/{}",
+                violations.len(),
+                repairs,
+                max_repairs
+            );
+            let prompt = self.schema_repair_prompt(schema, question, &candidate, &violations);
+            let repair_prompt = AgentPrompt {
+                system: prompt.system,
+                user: prompt.user,
+                diary: vec![],
+            };
+            candidate = match self.llm.generate_answer(&repair_prompt, 0.0).await {
+                Ok(response) => response.answer,
+                Err(e) => {
+                    log::warn!("🧩 Reparo de schema falhou: {}", e);
+                    return Err(schema_failure(&violations, repairs, start.elapsed()));
+                }
+            };
+        }
     }
 
     /// Executa uma única avaliação
@@ -175,6 +286,47 @@ impl EvaluationPipeline {
             EvaluationType::Plurality => self.plurality_prompt(question, answer),
             EvaluationType::Completeness => self.completeness_prompt(question, answer),
             EvaluationType::Strict => self.strict_prompt(question, answer, context),
+            EvaluationType::Schema => match &self.response_schema {
+                Some(schema) => self.schema_repair_prompt(schema, question, answer, &[]),
+                None => self.definitive_prompt(question, answer),
+            },
+            // Executada pelo agente com o `ClaimVerifier`, não pelo pipeline
+            EvaluationType::Verify => self.definitive_prompt(question, answer),
+        }
+    }
+
+    fn schema_repair_prompt(
+        &self,
+        schema: &JsonSchema,
+        question: &str,
+        answer: &str,
+        violations: &[SchemaViolation],
+    ) -> PromptPair {
+        let errors = violations
+            .iter()
+            .map(|v| format!("- {}", v))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        PromptPair {
+            system: format!(
+                r#"
+You repair structured answers so they validate against a JSON Schema.
+
+JSON Schema:
+{}
+
+Rules:
+- Output ONLY the corrected JSON value, no markdown fences and no commentary
+- Keep every fact from the original answer; do not invent new information
+- Use null only where the schema allows it
+"#,
+                schema.as_value()
+            ),
+            user: format!(
+                "Question: {}\n\nAnswer to repair:\n{}\n\nValidation errors:\n{}",
+                question, answer, errors
+            ),
         }
     }
 
@@ -361,6 +513,30 @@ Respond with:
     }
 }
 
+/// Resultado reprovado do gate de schema
+fn schema_failure(
+    violations: &[SchemaViolation],
+    repairs: u8,
+    duration: std::time::Duration,
+) -> EvaluationResult {
+    EvaluationResult::failure(
+        EvaluationType::Schema,
+        format!(
+            "Answer does not validate against the JSON Schema after {} repair(s): {}",
+            repairs,
+            violations
+                .iter()
+                .take(5)
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+        violations.iter().map(|v| format!("Fix {}", v)).collect(),
+        1.0,
+    )
+    .with_duration(duration)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +596,64 @@ mod tests {
         assert_eq!(suggestions.len(), 3);
         assert!(suggestions.contains(&"Be more confident".to_string()));
     }
+
+    fn name_schema() -> JsonSchema {
+        JsonSchema::new(serde_json::json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        }))
+        .unwrap()
+    }
+
+    fn empty_context() -> EvaluationContext {
+        EvaluationContext {
+            topic: crate::types::TopicCategory::General,
+            knowledge_items: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_gate_passes_valid_json() {
+        let pipeline = EvaluationPipeline::new(Arc::new(crate::llm::MockLlmClient::new()))
+            .with_response_schema(name_schema());
+
+        let result = pipeline
+            .evaluate_sequential(
+                "Who created Rust?",
+                "```json\n{\"name\": \"Graydon Hoare\"}\n```",
+                &empty_context(),
+                &[EvaluationType::Definitive],
+            )
+            .await;
+
+        assert!(result.overall_passed);
+        assert_eq!(result.results[0].eval_type, EvaluationType::Schema);
+        assert_eq!(
+            result.structured_answer,
+            Some(serde_json::json!({"name": "Graydon Hoare"}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_gate_fails_after_repairs() {
+        // O mock "repara" com texto livre, que nunca valida
+        let pipeline = EvaluationPipeline::new(Arc::new(crate::llm::MockLlmClient::new()))
+            .with_response_schema(name_schema());
+
+        let result = pipeline
+            .evaluate_sequential(
+                "Who created Rust?",
+                "Graydon Hoare",
+                &empty_context(),
+                &[EvaluationType::Definitive],
+            )
+            .await;
+
+        assert!(!result.overall_passed);
+        assert_eq!(result.failed_at, Some(EvaluationType::Schema));
+        assert_eq!(result.results.len(), 1);
+        assert!(result.results[0].reasoning.contains("after 2 repair(s)"));
+        assert!(result.structured_answer.is_none());
+    }
 }
@@ -3,6 +3,7 @@
 //! Estruturas para coletar e reportar evidências de operações de busca,
 //! incluindo detalhes de cada query, resultados e métricas.
 
+use crate::credibility::CredibilityScore;
 use crate::types::SerpQuery;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -25,6 +26,9 @@ pub struct UrlEvidence {
     pub path_boost: f32,
     /// Score final calculado
     pub final_score: f32,
+    /// Motivos do boost de hostname (camada, TLD, histórico, sinais da página)
+    #[serde(default)]
+    pub credibility_reasons: Vec<String>,
 }
 
 impl UrlEvidence {
@@ -36,6 +40,7 @@ impl UrlEvidence {
             hostname_boost: 1.0,
             path_boost: 1.0,
             final_score: 1.0,
+            credibility_reasons: vec![],
         }
     }
     
@@ -46,6 +51,14 @@ impl UrlEvidence {
         self.final_score = hostname_boost * path_boost;
         self
     }
+
+    /// Usa a credibilidade da fonte como boost de hostname, com os motivos
+    pub fn with_credibility(mut self, credibility: &CredibilityScore) -> Self {
+        self.hostname_boost = credibility.score;
+        self.final_score = credibility.score * self.path_boost;
+        self.credibility_reasons = credibility.reasons.clone();
+        self
+    }
 }
 
 /// Evidência de uma query de busca individual
@@ -343,6 +356,13 @@ mod tests {
         assert_eq!(url.hostname_boost, 1.3);
         assert_eq!(url.path_boost, 1.2);
         assert!((url.final_score - 1.56).abs() < 0.01); // 1.3 * 1.2 = 1.56
+
+        let credibility = crate::credibility::SourceCredibility::default()
+            .score_url("https://wikipedia.org/wiki/Rust");
+        let url = url.with_credibility(&credibility);
+        assert_eq!(url.hostname_boost, 1.5);
+        assert!((url.final_score - 1.8).abs() < 0.01); // 1.5 * 1.2 = 1.8
+        assert_eq!(url.credibility_reasons, vec!["wikipedia.org listado como trusted"]);
     }
 
     // ========================================================================
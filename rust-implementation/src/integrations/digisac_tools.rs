@@ -20,7 +20,8 @@ use digisac::{
 };
 use serde_json;
 
-use super::paytour_tools::AgentTool;
+use crate::agent::{tool_knowledge, Tool, ToolError};
+use crate::types::KnowledgeItem;
 
 /// Erro que pode ocorrer nas operações Digisac.
 #[derive(Debug, thiserror::Error)]
@@ -277,17 +278,58 @@ impl DigisacTools {
 }
 
 #[async_trait]
-impl AgentTool for DigisacTools {
-    fn name(&self) -> &'static str {
+impl Tool for DigisacTools {
+    fn name(&self) -> &str {
         "digisac"
     }
 
-    fn description(&self) -> &'static str {
+    fn description(&self) -> &str {
         "Ferramenta para enviar mensagens via WhatsApp e gerenciar webhooks usando Digisac. \
-        Permite enviar mensagens de texto e mídia, listar e criar webhooks."
+        Permite enviar mensagens de texto, listar e criar webhooks."
     }
 
-    async fn execute(&self, params: serde_json::Value) -> Result<serde_json::Value, String> {
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": [
+                        "enviar_mensagem",
+                        "listar_webhooks",
+                        "criar_webhook",
+                        "listar_contatos",
+                        "buscar_contato"
+                    ]
+                },
+                "service_id": { "type": "string" },
+                "contact_id": { "type": "string" },
+                "texto": { "type": "string" },
+                "url": { "type": "string" },
+                "eventos": { "type": "array", "items": { "type": "string" } },
+                "limit": { "type": "integer", "minimum": 0 },
+                "offset": { "type": "integer", "minimum": 0 },
+                "phone": { "type": "string", "description": "Telefone com DDI" }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> Result<Vec<KnowledgeItem>, ToolError> {
+        let result = self
+            .dispatch(&arguments)
+            .await
+            .map_err(ToolError::Execution)?;
+        Ok(vec![tool_knowledge(self.name(), &arguments, &result)])
+    }
+}
+
+impl DigisacTools {
+    /// Executa a ação pedida em `params["action"]`.
+    async fn dispatch(&self, params: &serde_json::Value) -> Result<serde_json::Value, String> {
         let action = params.get("action")
             .and_then(|v| v.as_str())
             .ok_or("Missing 'action' parameter")?;
@@ -7,6 +7,16 @@
 //!
 //! - [`PaytourTools`]: Integração com API Paytour para passeios turísticos
 //! - [`DigisacTools`]: Integração com API Digisac para mensagens WhatsApp
+//!
+//! Ambas implementam [`crate::agent::Tool`] e são expostas ao agente
+//! registrando-as em um [`crate::agent::ToolRegistry`]:
+//!
+//! ```rust,ignore
+//! let tools = ToolRegistry::new()
+//!     .with_tool(Arc::new(PaytourTools::new().await?))?
+//!     .with_tool(Arc::new(DigisacTools::new().await?))?;
+//! let agent = DeepResearchAgent::new(llm, search).with_tool_registry(tools);
+//! ```
 
 pub mod paytour_tools;
 pub mod digisac_tools;
@@ -22,6 +22,9 @@ use paytour::{
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+use crate::agent::{tool_knowledge, Tool, ToolError};
+use crate::types::KnowledgeItem;
+
 /// Erro que pode ocorrer nas operações Paytour.
 #[derive(Debug, thiserror::Error)]
 pub enum PaytourError {
@@ -249,34 +252,63 @@ impl PaytourTools {
     }
 }
 
-/// Trait para ferramentas do agente.
-///
-/// Define a interface que as ferramentas devem implementar
-/// para serem usadas pelo agente de pesquisa.
-#[async_trait]
-pub trait AgentTool: Send + Sync {
-    /// Nome da ferramenta.
-    fn name(&self) -> &'static str;
-
-    /// Descrição da ferramenta.
-    fn description(&self) -> &'static str;
-
-    /// Executa a ferramenta com os parâmetros fornecidos.
-    async fn execute(&self, params: serde_json::Value) -> Result<serde_json::Value, String>;
-}
-
 #[async_trait]
-impl AgentTool for PaytourTools {
-    fn name(&self) -> &'static str {
+impl Tool for PaytourTools {
+    fn name(&self) -> &str {
         "paytour"
     }
 
-    fn description(&self) -> &'static str {
+    fn description(&self) -> &str {
         "Ferramenta para consultar passeios turísticos via Paytour. \
         Permite listar passeios, ver detalhes, verificar disponibilidade e horários."
     }
 
-    async fn execute(&self, params: serde_json::Value) -> Result<serde_json::Value, String> {
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["listar", "detalhar", "disponibilidade", "horarios"]
+                },
+                "id": { "type": "integer", "minimum": 0, "description": "ID do passeio" },
+                "mes": { "type": "integer", "minimum": 1, "maximum": 12 },
+                "ano": { "type": "integer", "minimum": 2000 },
+                "dia": { "type": "string", "description": "Data no formato YYYY-MM-DD" },
+                "filtros": {
+                    "type": "object",
+                    "properties": {
+                        "cidade_id": { "type": "integer" },
+                        "categoria_id": { "type": "integer" },
+                        "data_inicio": { "type": "string" },
+                        "data_fim": { "type": "string" },
+                        "preco_min": { "type": "number" },
+                        "preco_max": { "type": "number" },
+                        "busca": { "type": "string" },
+                        "pagina": { "type": "integer" },
+                        "limite": { "type": "integer" }
+                    }
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Value,
+    ) -> Result<Vec<KnowledgeItem>, ToolError> {
+        let result = self
+            .dispatch(&arguments)
+            .await
+            .map_err(ToolError::Execution)?;
+        Ok(vec![tool_knowledge(self.name(), &arguments, &result)])
+    }
+}
+
+impl PaytourTools {
+    /// Executa a ação pedida em `params["action"]`.
+    async fn dispatch(&self, params: &serde_json::Value) -> Result<serde_json::Value, String> {
         let action = params.get("action")
             .and_then(|v| v.as_str())
             .ok_or("Missing 'action' parameter")?;
@@ -119,6 +119,7 @@ pub mod performance;
 ///
 /// Define a trait `LlmClient` e implementações para:
 /// - OpenAI (GPT-4, GPT-3.5)
+/// - Anthropic (Messages API; embeddings via OpenAI ou Jina)
 /// - Mock para testes
 ///
 /// Responsável por:
@@ -140,6 +141,44 @@ pub mod llm;
 /// - Reranking de resultados por relevância
 pub mod search;
 
+/// Provedores de busca web além do Jina.
+///
+/// SearXNG, Brave, Serper, SerpAPI e DuckDuckGo como `SearchClient`:
+/// - [`search_providers::SearchBackend`]: mapeamento de requisição e resposta
+/// - [`search_providers::WebSearchClient`]: busca no provedor, leitura pelo Jina/Rust
+/// - Seleção por `SEARCH_PROVIDER` ou `search_provider` na requisição
+pub mod search_providers;
+
+/// Meta-busca em vários provedores.
+///
+/// [`meta_search::MetaSearchClient`] consulta os provedores em paralelo e
+/// funde os resultados por URL normalizada com Reciprocal Rank Fusion.
+/// Ativada por uma lista em `SEARCH_PROVIDER` (ex.: `brave,searxng`).
+pub mod meta_search;
+
+/// Credibilidade de fontes.
+///
+/// Pontua domínios e páginas para ordenar URLs e referências:
+/// - [`credibility::CredibilityConfig`]: camadas por domínio (JSON configurável)
+/// - [`credibility::PageSignals`]: autor, data, citações e anúncios da página
+/// - [`credibility::SourceCredibility`]: score final com histórico e motivos
+pub mod credibility;
+
+/// Gravação e replay de chamadas LLM/busca (cassettes).
+///
+/// Permite reexecutar uma pesquisa inteira offline:
+/// - [`cassette::RecordingLlmClient`] / [`cassette::RecordingSearchClient`]: gravam cada chamada
+/// - [`cassette::ReplayLlmClient`] / [`cassette::ReplaySearchClient`]: tocam o cassette de volta
+/// - Útil para transformar sessões reais em testes de regressão
+pub mod cassette;
+
+/// Corpus local com busca offline (BM25).
+///
+/// Indexa um diretório de arquivos e expõe um `SearchClient` sem rede:
+/// - [`corpus::CorpusIndex`]: índice invertido em disco, atualizado incrementalmente
+/// - [`corpus::LocalCorpusClient`]: busca BM25, leitura de `file://` e rerank
+pub mod corpus;
+
 /// Sistema de rastreamento de busca (SearchTrace).
 ///
 /// Permite rastrear o fluxo de dados de cada operação de busca:
@@ -209,10 +248,12 @@ pub mod tui;
 /// - `TOKIO_MAX_THREADS`: Máximo de threads (padrão: 16)
 /// - `TOKIO_MAX_BLOCKING`: Máximo de blocking threads (padrão: 512)
 /// - `WEBREADER`: Preferência de leitor ("jina", "rust", "compare")
+/// - `SEARCH_PROVIDER`: Provedor(es) de busca ("jina", "searxng", "brave", "serper", "serpapi", "duckduckgo"), separados por vírgula
 ///
 /// **LLM:**
 /// - `LLM_PROVIDER`: Provider ("openai", "anthropic", "local") - padrão: "openai"
-/// - `LLM_MODEL`: Modelo principal (padrão: "gpt-4.1-mini")
+/// - `ANTHROPIC_API_KEY`: Chave da Anthropic (embeddings seguem `EMBEDDING_PROVIDER`)
+/// - `LLM_MODEL`: Modelo principal (padrão: "gpt-4.1-mini"; "claude-sonnet-4-5" com anthropic)
 /// - `LLM_EMBEDDING_MODEL`: Modelo de embeddings (padrão: "text-embedding-3-small")
 /// - `LLM_API_BASE_URL`: URL base customizada (opcional)
 /// - `LLM_TEMPERATURE`: Temperatura padrão (padrão: 0.7)
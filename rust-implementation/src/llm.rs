@@ -6,7 +6,7 @@
 // Suporta múltiplos provedores: OpenAI, Anthropic, local, etc.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-use crate::agent::{ActionPermissions, AgentAction, AgentPrompt};
+use crate::agent::{ActionPermissions, AgentAction, AgentPrompt, MAX_PARALLEL_ACTIONS};
 use crate::types::{Reference, SerpQuery};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 /// - `RateLimitError`: Aguardar e tentar novamente
 /// - `NetworkError`: Verificar conectividade
 /// - `TokenLimitError`: Reduzir tamanho do prompt
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, thiserror::Error, Serialize, Deserialize)]
 pub enum LlmError {
     /// Erro retornado pela API do provedor.
     ///
@@ -63,7 +63,7 @@ pub enum LlmError {
 ///
 /// Contém o texto da resposta, referências extraídas,
 /// e estatísticas detalhadas de uso de tokens para monitoramento.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmResponse {
     /// Texto completo da resposta gerada.
     pub answer: String,
@@ -89,7 +89,7 @@ pub struct LlmResponse {
 /// Imagine transformar uma frase em uma lista de 1536 números.
 /// Frases com significado similar terão números parecidos.
 /// Isso permite comparar textos matematicamente.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingResult {
     /// Vetor de embedding (geralmente 1536 dimensões para OpenAI).
     ///
@@ -199,9 +199,24 @@ pub trait LlmClient: Send + Sync {
         0
     }
 
+    /// Retorna tokens de embedding acumulados (implementação opcional)
+    fn get_embedding_tokens(&self) -> u64 {
+        0
+    }
+
     /// Retorna total de tokens acumulados (implementação opcional)
     fn get_total_tokens(&self) -> u64 {
-        self.get_prompt_tokens() + self.get_completion_tokens()
+        self.get_prompt_tokens() + self.get_completion_tokens() + self.get_embedding_tokens()
+    }
+
+    /// Nome do modelo de texto, usado para precificação (implementação opcional)
+    fn model_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Nome do modelo de embedding, usado para precificação (implementação opcional)
+    fn embedding_model_name(&self) -> Option<&str> {
+        None
     }
 }
 
@@ -209,7 +224,7 @@ pub trait LlmClient: Send + Sync {
 ///
 /// Quando pedimos ao LLM para avaliar se uma resposta
 /// atende a certos critérios, ele retorna esta estrutura.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluationResponse {
     /// Se a resposta passou na avaliação.
     pub passed: bool,
@@ -226,7 +241,7 @@ pub struct EvaluationResponse {
 /// Resposta de geração de código pelo LLM.
 ///
 /// Usada pelo CodeSandbox para executar código gerado dinamicamente.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeGenResponse {
     /// Código JavaScript gerado.
     pub code: String,
@@ -246,6 +261,8 @@ pub struct CodeGenResponse {
 pub struct MockLlmClient {
     /// Ação padrão a retornar quando `decide_action` é chamado.
     pub default_action: Option<AgentAction>,
+    /// Atraso simulado em `determine_eval_types` e `evaluate`.
+    pub evaluation_delay: Option<std::time::Duration>,
 }
 
 #[cfg(test)]
@@ -257,9 +274,7 @@ impl MockLlmClient {
     /// let client = MockLlmClient::new();
     /// ```
     pub fn new() -> Self {
-        Self {
-            default_action: None,
-        }
+        Self::default()
     }
 
     /// Cria um novo cliente MockLlmClient com uma ação padrão.
@@ -277,8 +292,15 @@ impl MockLlmClient {
     pub fn with_action(action: AgentAction) -> Self {
         Self {
             default_action: Some(action.clone()),
+            ..Self::default()
         }
     }
+
+    /// Simula avaliações lentas (ex.: votação que estoura o prazo).
+    pub fn with_evaluation_delay(mut self, delay: std::time::Duration) -> Self {
+        self.evaluation_delay = Some(delay);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -347,6 +369,9 @@ impl LlmClient for MockLlmClient {
         _answer: &str,
         _criteria: &str,
     ) -> Result<EvaluationResponse, LlmError> {
+        if let Some(delay) = self.evaluation_delay {
+            tokio::time::sleep(delay).await;
+        }
         Ok(EvaluationResponse {
             passed: true,
             reasoning: "Mock evaluation passed".into(),
@@ -358,6 +383,9 @@ impl LlmClient for MockLlmClient {
         &self,
         _question: &str,
     ) -> Result<Vec<crate::evaluation::EvaluationType>, LlmError> {
+        if let Some(delay) = self.evaluation_delay {
+            tokio::time::sleep(delay).await;
+        }
         Ok(vec![crate::evaluation::EvaluationType::Definitive])
     }
 
@@ -415,6 +443,8 @@ pub struct OpenAiClient {
     total_prompt_tokens: std::sync::atomic::AtomicU64,
     /// Contador de tokens de completion (thread-safe)
     total_completion_tokens: std::sync::atomic::AtomicU64,
+    /// Contador de tokens de embedding (thread-safe)
+    total_embedding_tokens: std::sync::atomic::AtomicU64,
 }
 
 impl OpenAiClient {
@@ -445,6 +475,7 @@ impl OpenAiClient {
                 .unwrap_or_else(|_| reqwest::Client::new()),
             total_prompt_tokens: std::sync::atomic::AtomicU64::new(0),
             total_completion_tokens: std::sync::atomic::AtomicU64::new(0),
+            total_embedding_tokens: std::sync::atomic::AtomicU64::new(0),
         }
     }
 
@@ -473,6 +504,7 @@ impl OpenAiClient {
                 .unwrap_or_else(|_| reqwest::Client::new()),
             total_prompt_tokens: std::sync::atomic::AtomicU64::new(0),
             total_completion_tokens: std::sync::atomic::AtomicU64::new(0),
+            total_embedding_tokens: std::sync::atomic::AtomicU64::new(0),
         }
     }
 
@@ -488,9 +520,12 @@ impl OpenAiClient {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
-    /// Retorna o total de todos os tokens acumulados
+    /// Retorna o total de todos os tokens acumulados (incluindo embeddings)
     pub fn get_total_tokens(&self) -> u64 {
-        self.get_total_prompt_tokens() + self.get_total_completion_tokens()
+        self.get_total_prompt_tokens()
+            + self.get_total_completion_tokens()
+            + self.total_embedding_tokens
+                .load(std::sync::atomic::Ordering::Relaxed)
     }
 
     /// Retorna o modelo atual em uso
@@ -598,57 +633,1051 @@ struct EmbeddingUsage {
     total_tokens: u64,
 }
 
-#[derive(Deserialize)]
-struct EmbeddingResponse {
-    data: Vec<EmbeddingData>,
-    usage: EmbeddingUsage,
-}
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+    usage: EmbeddingUsage,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct ActionJson {
+    action: String,
+    queries: Option<Vec<ActionQuery>>,
+    urls: Option<Vec<String>>,
+    gap_questions: Option<Vec<String>>,
+    /// Texto livre ou, com JSON Schema, o próprio valor estruturado
+    answer: Option<serde_json::Value>,
+    references: Option<Vec<ActionReference>>,
+    code: Option<String>,
+    /// Linguagem para coding: "javascript", "python" ou None para auto
+    language: Option<String>,
+    /// Quantidade de sessões para history
+    count: Option<usize>,
+    /// Filtro de texto para history
+    filter: Option<String>,
+    /// Campos para ask_user
+    #[serde(rename = "questionType")]
+    question_type: Option<String>,
+    question: Option<String>,
+    options: Option<Vec<String>>,
+    #[serde(rename = "isBlocking")]
+    is_blocking: Option<bool>,
+    /// Nome da ferramenta para tool
+    tool: Option<String>,
+    /// Argumentos da ferramenta (validados pelo ToolRegistry)
+    arguments: Option<serde_json::Value>,
+    /// Sub-ações para parallel
+    actions: Option<Vec<ActionJson>>,
+    #[serde(default)]
+    think: String,
+}
+
+impl ActionJson {
+    /// Converte o JSON da decisão em `AgentAction`
+    fn into_action(self) -> Result<AgentAction, LlmError> {
+        match self.action.as_str() {
+            "search" => {
+                let queries = self
+                    .queries
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|q| SerpQuery {
+                        q: q.q,
+                        tbs: q.tbs,
+                        location: q.location,
+                    })
+                    .collect();
+                Ok(AgentAction::Search {
+                    queries,
+                    think: self.think,
+                })
+            }
+            "read" => Ok(AgentAction::Read {
+                urls: self.urls.unwrap_or_default(),
+                think: self.think,
+            }),
+            "reflect" => Ok(AgentAction::Reflect {
+                gap_questions: self.gap_questions.unwrap_or_default(),
+                think: self.think,
+            }),
+            "answer" => {
+                let references = self
+                    .references
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|r| Reference {
+                        url: r.url,
+                        title: r.title,
+                        exact_quote: r.exact_quote,
+                        relevance_score: r.relevance_score,
+                        answer_chunk: None,
+                        answer_position: None,
+                    })
+                    .collect();
+                let answer = match self.answer {
+                    Some(serde_json::Value::String(text)) => text,
+                    Some(serde_json::Value::Null) | None => String::new(),
+                    Some(structured) => structured.to_string(),
+                };
+                Ok(AgentAction::Answer {
+                    answer,
+                    references,
+                    think: self.think,
+                })
+            }
+            "coding" => Ok(AgentAction::Coding {
+                problem: self.code.unwrap_or_default(),
+                context_vars: None, // LLM não especifica context_vars, usa todo o knowledge
+                language: self.language, // LLM pode especificar "javascript", "python" ou deixar None para Auto
+                think: self.think,
+            }),
+            "history" => Ok(AgentAction::History {
+                count: self.count.unwrap_or(5),
+                filter: self.filter,
+                think: self.think,
+            }),
+            "ask_user" | "askuser" | "ask" => {
+                // Parsear tipo de pergunta
+                let question_type = self
+                    .question_type
+                    .as_deref()
+                    .and_then(crate::agent::QuestionType::from_str)
+                    .unwrap_or(crate::agent::QuestionType::Clarification);
+
+                // Por padrão, Clarification e Confirmation são blocking
+                let is_blocking = self.is_blocking.unwrap_or_else(|| {
+                    question_type.is_blocking_by_default()
+                });
+
+                Ok(AgentAction::AskUser {
+                    question_type,
+                    question: self.question.unwrap_or_else(|| {
+                        "Poderia fornecer mais informações?".into()
+                    }),
+                    options: self.options,
+                    is_blocking,
+                    think: self.think,
+                })
+            }
+            "tool" => Ok(AgentAction::ToolCall {
+                tool: self.tool.unwrap_or_default(),
+                arguments: self
+                    .arguments
+                    .unwrap_or_else(|| serde_json::json!({})),
+                think: self.think,
+            }),
+            "parallel" => {
+                let think = self.think;
+                let mut actions = Vec::new();
+                for mut child in self.actions.unwrap_or_default() {
+                    if child.think.is_empty() {
+                        child.think = think.clone();
+                    }
+                    let action = child.into_action()?;
+                    if action.is_parallelizable() {
+                        actions.push(action);
+                    } else {
+                        log::warn!("⚠️ Ação {} ignorada no plano paralelo", action.name());
+                    }
+                }
+                actions.truncate(MAX_PARALLEL_ACTIONS);
+                match actions.len() {
+                    0 => Err(LlmError::ParseError("Empty parallel plan".into())),
+                    1 => Ok(actions.remove(0)),
+                    _ => Ok(AgentAction::Parallel { actions, think }),
+                }
+            }
+            // Alguns modelos usam o nome da ferramenta como ação
+            other if self.arguments.is_some() => Ok(AgentAction::ToolCall {
+                tool: other.to_string(),
+                arguments: self.arguments.unwrap_or_default(),
+                think: self.think,
+            }),
+            _ => Err(LlmError::ParseError(format!(
+                "Unknown action: {}",
+                self.action
+            ))),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ActionQuery {
+    q: String,
+    tbs: Option<String>,
+    location: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ActionReference {
+    url: String,
+    title: String,
+    #[serde(rename = "exactQuote")]
+    exact_quote: Option<String>,
+    #[serde(rename = "relevanceScore")]
+    relevance_score: Option<f32>,
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// PROMPTS E PARSERS COMPARTILHADOS ENTRE PROVEDORES
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Monta o system prompt da decisão com o formato JSON de cada ação permitida
+fn action_system_prompt(prompt: &AgentPrompt, permissions: &ActionPermissions) -> String {
+    let mut system_prompt = prompt.system.clone();
+    system_prompt.push_str("\n\nYou must respond with a valid JSON object containing the action. Available actions:\n");
+
+    if permissions.search {
+        system_prompt.push_str("- search: {\"action\": \"search\", \"queries\": [{\"q\": \"query text\", \"tbs\": \"optional\", \"location\": \"optional\"}], \"think\": \"reasoning\"}\n");
+    }
+    if permissions.read {
+        system_prompt.push_str("- read: {\"action\": \"read\", \"urls\": [\"url1\", \"url2\"], \"think\": \"reasoning\"}\n");
+    }
+    if permissions.reflect {
+        system_prompt.push_str("- reflect: {\"action\": \"reflect\", \"gap_questions\": [\"question1\"], \"think\": \"reasoning\"}\n");
+    }
+    if permissions.answer {
+        system_prompt.push_str("- answer: {\"action\": \"answer\", \"answer\": \"response text\", \"references\": [{\"url\": \"...\", \"title\": \"...\"}], \"think\": \"reasoning\"}\n");
+    }
+    if permissions.coding {
+        system_prompt.push_str("- coding: {\"action\": \"coding\", \"code\": \"problem description for code generation\", \"language\": \"javascript|python|auto\" (optional), \"think\": \"reasoning\"}\n");
+        system_prompt.push_str("  Language selection: javascript (fast, JSON/string ops), python (data analysis, statistics, complex regex). Default: auto (LLM chooses best).\n");
+    }
+    if permissions.history {
+        system_prompt.push_str("- history: {\"action\": \"history\", \"count\": 5, \"filter\": \"optional search term\", \"think\": \"reasoning\"}\n");
+    }
+    if permissions.ask_user {
+        system_prompt.push_str("- ask_user: {\"action\": \"ask_user\", \"questionType\": \"clarification|confirmation|preference|suggestion\", \"question\": \"question for user\", \"options\": [\"opt1\", \"opt2\"] (optional), \"isBlocking\": true, \"think\": \"why asking\"}\n");
+        system_prompt.push_str("  Use ask_user when you need CRITICAL information from the user that cannot be found through search.\n");
+        system_prompt.push_str("  Types: clarification (missing vital info), confirmation (before important action), preference (choose between options), suggestion (non-critical feedback)\n");
+    }
+    if permissions.tools {
+        system_prompt.push_str("- tool: {\"action\": \"tool\", \"tool\": \"tool name\", \"arguments\": {...}, \"think\": \"reasoning\"}\n");
+        system_prompt.push_str("  Arguments must match the JSON Schema of the tool listed in the instructions.\n");
+    }
+    if permissions.is_allowed("parallel") {
+        system_prompt.push_str(&format!("- parallel: {{\"action\": \"parallel\", \"actions\": [{{\"action\": \"search\", ...}}, {{\"action\": \"read\", ...}}], \"think\": \"reasoning\"}}\n  Use parallel to run up to {} INDEPENDENT search/read/tool actions at once.\n", MAX_PARALLEL_ACTIONS));
+    }
+
+    system_prompt.push_str("\nRespond ONLY with valid JSON, no other text.");
+
+    system_prompt
+}
+
+/// Mensagem do usuário: prompt seguido do diário de passos
+fn user_message(prompt: &AgentPrompt) -> String {
+    format!(
+        "{}\n\nDiary:\n{}",
+        prompt.user,
+        prompt
+            .diary
+            .iter()
+            .map(|e| e.format())
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+/// System prompt do avaliador para um critério
+fn evaluation_system_prompt(criteria: &str) -> String {
+    format!(
+        "You are an evaluator. Evaluate if the answer meets the criteria: {}\n\nRespond with JSON: {{\"passed\": true/false, \"reasoning\": \"explanation\", \"confidence\": 0.0-1.0}}",
+        criteria
+    )
+}
+
+/// System prompt que escolhe os tipos de avaliação de uma pergunta
+const EVAL_TYPES_SYSTEM_PROMPT: &str = r#"You are an evaluator selector. Determine which evaluation types are needed for this question.
+Respond with JSON: {"needs_definitive": true/false, "needs_freshness": true/false, "needs_plurality": true/false, "needs_completeness": true/false}
+- definitive: Does the question need a clear, confident answer?
+- freshness: Does the question require recent/current information?
+- plurality: Does the question ask for multiple items/examples?
+- completeness: Does the question have multiple aspects that need coverage?"#;
+
+/// System prompt que escolhe JavaScript ou Python para o sandbox
+const LANGUAGE_CHOICE_SYSTEM_PROMPT: &str = r#"You are a programming language expert. Given a problem description, decide whether JavaScript or Python is more suitable.
+
+Choose JavaScript for:
+- JSON manipulation and parsing
+- Simple string operations
+- Basic calculations
+- Quick data transformations
+
+Choose Python for:
+- Data analysis and statistics
+- Complex regex operations
+- Scientific calculations
+- Working with collections and aggregations
+- Text processing with complex patterns
+
+Respond with JSON: {"language": "javascript"} or {"language": "python"}"#;
+
+/// Contexto das tentativas anteriores de geração de código (para retry)
+fn previous_attempts_context(previous_attempts: &[(String, Option<String>)]) -> String {
+    if !previous_attempts.is_empty() {
+        let attempts_text: Vec<String> = previous_attempts
+            .iter()
+            .enumerate()
+            .map(|(i, (code, error))| {
+                format!(
+                    "<bad-attempt-{}>\n{}\n{}</bad-attempt-{}>",
+                    i + 1,
+                    code,
+                    error
+                        .as_ref()
+                        .map(|e| format!("Error: {}", e))
+                        .unwrap_or_default(),
+                    i + 1
+                )
+            })
+            .collect();
+        format!(
+            "\nPrevious attempts and their errors:\n{}\n",
+            attempts_text.join("\n")
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// System prompt de geração de código JavaScript
+fn javascript_code_prompt(
+    available_vars: &str,
+    previous_attempts: &[(String, Option<String>)],
+) -> String {
+    format!(
+        r#"You are an expert JavaScript programmer. Your task is to generate JavaScript code to solve the given problem.
+
+<rules>
+1. Generate plain JavaScript code that returns the result directly
+2. You can access any of these available variables directly:
+{}
+3. You don't have access to any third party libraries that need to be installed, so you must write complete, self-contained code.
+4. Must have a return statement.
+</rules>
+{}
+<example>
+Available variables:
+numbers (Array<number>) e.g. [1, 2, 3, 4, 5, 6]
+threshold (number) e.g. 4
+
+Problem: Sum all numbers above threshold
+
+Response:
+{{"think": "I need to filter numbers above threshold and sum them", "code": "return numbers.filter(n => n > threshold).reduce((a, b) => a + b, 0);"}}
+</example>"#,
+        available_vars,
+        previous_attempts_context(previous_attempts)
+    )
+}
+
+/// System prompt de geração de código Python
+fn python_code_prompt(available_vars: &str, previous_attempts: &[(String, Option<String>)]) -> String {
+    format!(
+        r#"You are an expert Python programmer. Your task is to generate Python code to solve the given problem.
+
+<rules>
+1. Generate plain Python code that prints the final result using print()
+2. You can access these pre-defined variables directly (already loaded as Python objects):
+{}
+3. Available modules: json, re, math, collections (Counter, defaultdict)
+4. Do NOT use: os, sys, subprocess, open, exec, eval, import for dangerous modules
+5. Must end with a print() statement for the final result
+6. Keep code simple and efficient
+</rules>
+{}
+<example>
+Available variables:
+numbers (list) e.g. [1, 2, 3, 4, 5, 6]
+threshold (int) e.g. 4
+
+Problem: Sum all numbers above threshold
+
+Response:
+{{"think": "I need to filter numbers above threshold and sum them using list comprehension", "code": "result = sum(n for n in numbers if n > threshold)\nprint(result)"}}
+</example>"#,
+        available_vars,
+        previous_attempts_context(previous_attempts)
+    )
+}
+
+/// Extrai o objeto JSON de uma resposta em texto livre
+///
+/// Modelos sem modo JSON às vezes cercam o objeto com ```json ou com
+/// uma frase; usamos do primeiro `{` ao último `}`.
+fn extract_json_object(text: &str) -> &str {
+    match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => text.trim(),
+    }
+}
+
+/// Converte o JSON da avaliação
+fn parse_evaluation(content: &str) -> Result<EvaluationResponse, LlmError> {
+    #[derive(Deserialize)]
+    struct EvalJson {
+        passed: bool,
+        reasoning: String,
+        confidence: f32,
+    }
+
+    let eval_json: EvalJson = serde_json::from_str(content)
+        .map_err(|e| LlmError::ParseError(format!("Failed to parse evaluation JSON: {}", e)))?;
+
+    Ok(EvaluationResponse {
+        passed: eval_json.passed,
+        reasoning: eval_json.reasoning,
+        confidence: eval_json.confidence,
+    })
+}
+
+/// Converte o JSON de seleção de avaliações (Strict entra junto com as demais)
+fn parse_eval_types(content: &str) -> Result<Vec<crate::evaluation::EvaluationType>, LlmError> {
+    #[derive(Deserialize)]
+    struct EvalTypesJson {
+        needs_definitive: bool,
+        needs_freshness: bool,
+        needs_plurality: bool,
+        needs_completeness: bool,
+    }
+
+    let eval_types_json: EvalTypesJson = serde_json::from_str(content)
+        .map_err(|e| LlmError::ParseError(format!("Failed to parse eval types JSON: {}", e)))?;
+
+    let mut types = Vec::new();
+    if eval_types_json.needs_definitive {
+        types.push(crate::evaluation::EvaluationType::Definitive);
+    }
+    if eval_types_json.needs_freshness {
+        types.push(crate::evaluation::EvaluationType::Freshness);
+    }
+    if eval_types_json.needs_plurality {
+        types.push(crate::evaluation::EvaluationType::Plurality);
+    }
+    if eval_types_json.needs_completeness {
+        types.push(crate::evaluation::EvaluationType::Completeness);
+    }
+
+    // Sempre adiciona Strict se houver outros tipos
+    if !types.is_empty() {
+        types.push(crate::evaluation::EvaluationType::Strict);
+    }
+
+    Ok(types)
+}
+
+/// Converte o JSON de geração de código
+fn parse_code_gen(content: &str) -> Result<CodeGenResponse, LlmError> {
+    #[derive(Deserialize)]
+    struct CodeGenJson {
+        code: String,
+        think: String,
+    }
+
+    let code_gen_json: CodeGenJson = serde_json::from_str(content)
+        .map_err(|e| LlmError::ParseError(format!("Failed to parse code gen JSON: {}", e)))?;
+
+    Ok(CodeGenResponse {
+        code: code_gen_json.code,
+        think: code_gen_json.think,
+    })
+}
+
+/// Converte o JSON de escolha de linguagem (JavaScript em caso de dúvida)
+fn parse_language(content: &str) -> crate::agent::SandboxLanguage {
+    #[derive(Deserialize)]
+    struct LanguageChoice {
+        language: String,
+    }
+
+    let choice: LanguageChoice = serde_json::from_str(content)
+        .unwrap_or(LanguageChoice { language: "javascript".into() });
+
+    if choice.language.to_lowercase() == "python" {
+        crate::agent::SandboxLanguage::Python
+    } else {
+        crate::agent::SandboxLanguage::JavaScript
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// IMPLEMENTAÇÃO ANTHROPIC (MESSAGES API)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Versão da Messages API enviada no header `anthropic-version`
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+
+/// Limite padrão de tokens de saída por chamada (`max_tokens` é obrigatório)
+const ANTHROPIC_DEFAULT_MAX_TOKENS: u32 = 8192;
+
+/// Provedor de embeddings usado pelo `AnthropicClient`
+///
+/// A Anthropic não tem endpoint de embeddings; os vetores vêm do
+/// `EmbeddingProvider` configurado.
+pub enum EmbeddingBackend {
+    /// OpenAI Embeddings (`OPENAI_API_KEY`)
+    OpenAI(OpenAiClient),
+    /// Jina Embeddings (`JINA_API_KEY`)
+    Jina(crate::search::JinaClient),
+    /// Sem provedor: `embed` retorna erro
+    Disabled,
+}
+
+impl EmbeddingBackend {
+    /// Escolhe o backend do `EmbeddingProvider` configurado
+    ///
+    /// Sem a chave do provedor, os embeddings ficam desabilitados.
+    pub fn from_config(config: &crate::config::LlmConfig, keys: &LlmApiKeys) -> Self {
+        use crate::config::EmbeddingProvider;
+
+        match (&config.embedding_provider, &keys.openai, &keys.jina) {
+            (EmbeddingProvider::OpenAI, Some(key), _) => Self::OpenAI(
+                OpenAiClient::new(key.clone()).with_embedding_model(&config.embedding_model),
+            ),
+            (EmbeddingProvider::Jina, _, Some(key)) => Self::Jina(crate::search::JinaClient::with_config(
+                key.clone(),
+                crate::config::WebReaderPreference::default(),
+                config.jina_embedding_model.clone(),
+            )),
+            (provider, _, _) => {
+                log::warn!(
+                    "⚠️ Embeddings {} sem chave de API; deduplicação semântica desabilitada",
+                    provider
+                );
+                Self::Disabled
+            }
+        }
+    }
+
+    /// Modelo de embedding em uso
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::OpenAI(client) => Some(client.embedding_model()),
+            Self::Jina(client) => Some(client.embedding_model()),
+            Self::Disabled => None,
+        }
+    }
+
+    /// Gera embeddings em batch no provedor configurado
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<EmbeddingResult>, LlmError> {
+        match self {
+            Self::OpenAI(client) => client.embed_batch(texts).await,
+            Self::Jina(client) => client
+                .embed_batch(texts)
+                .await
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|r| EmbeddingResult {
+                            vector: r.vector,
+                            tokens_used: r.tokens_used,
+                        })
+                        .collect()
+                })
+                .map_err(|e| match e {
+                    crate::search::SearchError::RateLimitError => LlmError::RateLimitError,
+                    crate::search::SearchError::NetworkError(msg) => LlmError::NetworkError(msg),
+                    other => LlmError::ApiError(other.to_string()),
+                }),
+            Self::Disabled => Err(LlmError::ApiError(
+                "No embedding provider configured for Anthropic".into(),
+            )),
+        }
+    }
+}
+
+/// Cliente para a Anthropic Messages API
+pub struct AnthropicClient {
+    /// Chave da API Anthropic
+    api_key: String,
+    /// Modelo de texto (ex: "claude-sonnet-4-5")
+    model: String,
+    /// URL base da API
+    api_base_url: String,
+    /// Temperatura padrão
+    default_temperature: f32,
+    /// Limite de tokens de saída por chamada
+    max_tokens: u32,
+    /// Cliente HTTP
+    client: reqwest::Client,
+    /// Provedor de embeddings delegado
+    embeddings: EmbeddingBackend,
+    /// Contador de tokens de prompt (thread-safe)
+    total_prompt_tokens: std::sync::atomic::AtomicU64,
+    /// Contador de tokens de completion (thread-safe)
+    total_completion_tokens: std::sync::atomic::AtomicU64,
+    /// Contador de tokens de embedding (thread-safe)
+    total_embedding_tokens: std::sync::atomic::AtomicU64,
+}
+
+impl AnthropicClient {
+    /// Cria um cliente Anthropic com o modelo padrão e sem embeddings.
+    ///
+    /// # Exemplo
+    /// ```rust,ignore
+    /// let client = AnthropicClient::new("sk-ant-...".into())
+    ///     .with_embeddings(EmbeddingBackend::OpenAI(OpenAiClient::new(openai_key)));
+    /// ```
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            model: crate::config::LlmProvider::Anthropic
+                .default_model()
+                .into(),
+            api_base_url: "https://api.anthropic.com/v1".into(),
+            default_temperature: 0.7,
+            max_tokens: ANTHROPIC_DEFAULT_MAX_TOKENS,
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(120)) // 2 minutos de timeout
+                .connect_timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            embeddings: EmbeddingBackend::Disabled,
+            total_prompt_tokens: std::sync::atomic::AtomicU64::new(0),
+            total_completion_tokens: std::sync::atomic::AtomicU64::new(0),
+            total_embedding_tokens: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    /// Cria um cliente Anthropic a partir de LlmConfig (modelo, URL, temperatura).
+    pub fn from_config(api_key: String, config: &crate::config::LlmConfig) -> Self {
+        let mut client = Self::new(api_key);
+        client.model = config.model.clone();
+        client.api_base_url = config.api_url().to_string();
+        client.default_temperature = config.default_temperature;
+        client
+    }
+
+    /// Define o provedor de embeddings.
+    pub fn with_embeddings(mut self, embeddings: EmbeddingBackend) -> Self {
+        self.embeddings = embeddings;
+        self
+    }
+
+    /// Altera o modelo de texto usado pelo cliente.
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Altera o limite de tokens de saída por chamada.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Altera a temperatura padrão.
+    pub fn with_temperature(mut self, temp: f32) -> Self {
+        self.default_temperature = temp;
+        self
+    }
+
+    /// Retorna o modelo atual em uso
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Retorna a URL completa para um endpoint.
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.api_base_url.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+
+    /// Envia uma mensagem (system + user) e acumula os tokens usados
+    ///
+    /// `operation` identifica a chamada nos logs.
+    async fn message(
+        &self,
+        system: &str,
+        user: String,
+        temperature: f32,
+        operation: &str,
+    ) -> Result<(String, AnthropicUsage), LlmError> {
+        let request = AnthropicRequest {
+            model: &self.model,
+            max_tokens: self.max_tokens,
+            system,
+            messages: vec![AnthropicMessage {
+                role: "user",
+                content: user,
+            }],
+            // A Messages API aceita temperatura entre 0 e 1
+            temperature: temperature.clamp(0.0, 1.0),
+        };
+
+        let response = self
+            .client
+            .post(self.endpoint("messages"))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_API_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| LlmError::NetworkError(e.to_string()))?;
+
+        // 529 = API sobrecarregada; tratado como rate limit (retry)
+        if response.status() == 429 || response.status().as_u16() == 529 {
+            return Err(LlmError::RateLimitError);
+        }
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::ApiError(format!(
+                "Anthropic API error: {}",
+                error_text
+            )));
+        }
+
+        let message: AnthropicResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse response: {}", e)))?;
+
+        self.total_prompt_tokens.fetch_add(
+            message.usage.prompt_tokens(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.total_completion_tokens.fetch_add(
+            message.usage.output_tokens,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        log::debug!(
+            "🎫 {} tokens: prompt={}, completion={} | Acumulado: {} | Model: {}",
+            operation,
+            message.usage.prompt_tokens(),
+            message.usage.output_tokens,
+            self.get_total_tokens(),
+            self.model
+        );
+
+        if message.stop_reason.as_deref() == Some("max_tokens") {
+            log::warn!(
+                "⚠️ {}: resposta truncada em {} tokens (max_tokens)",
+                operation,
+                self.max_tokens
+            );
+        }
+
+        let text = message.text();
+        if text.is_empty() {
+            return Err(LlmError::ParseError("No text content in response".into()));
+        }
+        Ok((text, message.usage))
+    }
+}
+
+// Estruturas para serialização/deserialização da Messages API
+#[derive(Serialize)]
+struct AnthropicRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    system: &'a str,
+    messages: Vec<AnthropicMessage>,
+    temperature: f32,
+}
+
+#[derive(Serialize)]
+struct AnthropicMessage {
+    role: &'static str,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContent>,
+    usage: AnthropicUsage,
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+impl AnthropicResponse {
+    /// Concatena os blocos de texto (ignora `thinking`, `tool_use`, etc.)
+    fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter(|block| block.kind == "text")
+            .map(|block| block.text.as_str())
+            .collect::<Vec<_>>()
+            .join("")
+    }
+}
+
+#[derive(Deserialize)]
+struct AnthropicContent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String,
+}
+
+/// Usage da Messages API; tokens de cache também contam como prompt
+#[derive(Deserialize, Debug, Default)]
+struct AnthropicUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    /// Tokens de entrada, incluindo escrita e leitura de cache
+    fn prompt_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + self.cache_read_input_tokens.unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl LlmClient for AnthropicClient {
+    async fn decide_action(
+        &self,
+        prompt: &AgentPrompt,
+        permissions: &ActionPermissions,
+    ) -> Result<AgentAction, LlmError> {
+        let system_prompt = action_system_prompt(prompt, permissions);
+        let (content, usage) = self
+            .message(&system_prompt, user_message(prompt), 0.7, "decide_action")
+            .await?;
+
+        log::info!(
+            "🎫 Tokens: prompt={}, completion={}, total={} | Acumulado: {} | Model: {}",
+            usage.prompt_tokens(),
+            usage.output_tokens,
+            usage.prompt_tokens() + usage.output_tokens,
+            self.get_total_tokens(),
+            self.model
+        );
+
+        let action_json: ActionJson = serde_json::from_str(extract_json_object(&content))
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse action JSON: {}", e)))?;
+
+        action_json.into_action()
+    }
+
+    async fn generate_answer(
+        &self,
+        prompt: &AgentPrompt,
+        temperature: f32,
+    ) -> Result<LlmResponse, LlmError> {
+        let (answer, usage) = self
+            .message(&prompt.system, user_message(prompt), temperature, "generate_answer")
+            .await?;
+
+        Ok(LlmResponse {
+            answer,
+            references: vec![], // References devem ser extraídas do contexto
+            prompt_tokens: usage.prompt_tokens(),
+            completion_tokens: usage.output_tokens,
+            total_tokens: usage.prompt_tokens() + usage.output_tokens,
+        })
+    }
+
+    async fn embed(&self, text: &str) -> Result<EmbeddingResult, LlmError> {
+        self.embed_batch(&[text.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| LlmError::ParseError("No embedding data in response".into()))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<EmbeddingResult>, LlmError> {
+        let results = self.embeddings.embed_batch(texts).await?;
+        self.total_embedding_tokens.fetch_add(
+            results.iter().map(|r| r.tokens_used).sum(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        Ok(results)
+    }
+
+    async fn evaluate(
+        &self,
+        question: &str,
+        answer: &str,
+        criteria: &str,
+    ) -> Result<EvaluationResponse, LlmError> {
+        let (content, _) = self
+            .message(
+                &evaluation_system_prompt(criteria),
+                format!("Question: {}\n\nAnswer: {}", question, answer),
+                0.3,
+                "evaluate",
+            )
+            .await?;
+
+        parse_evaluation(extract_json_object(&content))
+    }
+
+    async fn determine_eval_types(
+        &self,
+        question: &str,
+    ) -> Result<Vec<crate::evaluation::EvaluationType>, LlmError> {
+        let (content, _) = self
+            .message(
+                EVAL_TYPES_SYSTEM_PROMPT,
+                format!("Question: {}", question),
+                0.3,
+                "determine_eval_types",
+            )
+            .await?;
+
+        parse_eval_types(extract_json_object(&content))
+    }
+
+    async fn generate_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let (content, _) = self
+            .message(
+                &javascript_code_prompt(available_vars, previous_attempts),
+                format!("Problem: {}", problem),
+                0.2, // Baixa temperatura para código mais consistente
+                "generate_code",
+            )
+            .await?;
+
+        parse_code_gen(extract_json_object(&content))
+    }
+
+    async fn generate_python_code(
+        &self,
+        problem: &str,
+        available_vars: &str,
+        previous_attempts: &[(String, Option<String>)],
+    ) -> Result<CodeGenResponse, LlmError> {
+        let (content, _) = self
+            .message(
+                &python_code_prompt(available_vars, previous_attempts),
+                format!("Problem: {}", problem),
+                0.2,
+                "generate_python_code",
+            )
+            .await?;
+
+        parse_code_gen(extract_json_object(&content))
+    }
+
+    async fn choose_coding_language(
+        &self,
+        problem: &str,
+    ) -> Result<crate::agent::SandboxLanguage, LlmError> {
+        match self
+            .message(
+                LANGUAGE_CHOICE_SYSTEM_PROMPT,
+                format!("Problem: {}", problem),
+                0.1,
+                "choose_coding_language",
+            )
+            .await
+        {
+            Ok((content, _)) => Ok(parse_language(extract_json_object(&content))),
+            // Default to JavaScript on error
+            Err(_) => Ok(crate::agent::SandboxLanguage::JavaScript),
+        }
+    }
+
+    fn get_prompt_tokens(&self) -> u64 {
+        self.total_prompt_tokens
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn get_completion_tokens(&self) -> u64 {
+        self.total_completion_tokens
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn get_embedding_tokens(&self) -> u64 {
+        self.total_embedding_tokens
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
 
-#[derive(Deserialize)]
-struct EmbeddingData {
-    embedding: Vec<f32>,
+    fn model_name(&self) -> Option<&str> {
+        Some(&self.model)
+    }
+
+    fn embedding_model_name(&self) -> Option<&str> {
+        self.embeddings.model()
+    }
 }
 
-#[derive(Deserialize)]
-struct ActionJson {
-    action: String,
-    queries: Option<Vec<ActionQuery>>,
-    urls: Option<Vec<String>>,
-    gap_questions: Option<Vec<String>>,
-    answer: Option<String>,
-    references: Option<Vec<ActionReference>>,
-    code: Option<String>,
-    /// Linguagem para coding: "javascript", "python" ou None para auto
-    language: Option<String>,
-    /// Quantidade de sessões para history
-    count: Option<usize>,
-    /// Filtro de texto para history
-    filter: Option<String>,
-    /// Campos para ask_user
-    #[serde(rename = "questionType")]
-    question_type: Option<String>,
-    question: Option<String>,
-    options: Option<Vec<String>>,
-    #[serde(rename = "isBlocking")]
-    is_blocking: Option<bool>,
-    think: String,
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// SELEÇÃO DE PROVEDOR
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Chaves de API dos provedores de LLM e de embeddings
+#[derive(Debug, Clone, Default)]
+pub struct LlmApiKeys {
+    /// `OPENAI_API_KEY`
+    pub openai: Option<String>,
+    /// `ANTHROPIC_API_KEY`
+    pub anthropic: Option<String>,
+    /// `JINA_API_KEY` (embeddings Jina)
+    pub jina: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct ActionQuery {
-    q: String,
-    tbs: Option<String>,
-    location: Option<String>,
+impl LlmApiKeys {
+    /// Lê as chaves do ambiente (valores vazios são ignorados)
+    pub fn from_env() -> Self {
+        let read = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            openai: read("OPENAI_API_KEY"),
+            anthropic: read("ANTHROPIC_API_KEY"),
+            jina: read("JINA_API_KEY"),
+        }
+    }
 }
 
-#[derive(Deserialize)]
-struct ActionReference {
-    url: String,
-    title: String,
-    #[serde(rename = "exactQuote")]
-    exact_quote: Option<String>,
-    #[serde(rename = "relevanceScore")]
-    relevance_score: Option<f32>,
+/// Cria o cliente LLM do `LlmProvider` configurado
+///
+/// - OpenAI: `OpenAiClient` (texto e embeddings)
+/// - Anthropic: `AnthropicClient`, embeddings pelo `EmbeddingProvider`
+/// - Local: `OpenAiClient` apontando para `api_url()` (chave opcional)
+pub fn llm_client_for(
+    config: &crate::config::LlmConfig,
+    keys: &LlmApiKeys,
+) -> Result<std::sync::Arc<dyn LlmClient>, LlmError> {
+    use crate::config::LlmProvider;
+
+    let missing = || {
+        LlmError::ApiError(format!(
+            "{} not set (LLM_PROVIDER={})",
+            config.provider.api_key_variable().unwrap_or_default(),
+            config.provider
+        ))
+    };
+
+    Ok(match config.provider {
+        LlmProvider::OpenAI => {
+            let key = keys.openai.clone().ok_or_else(missing)?;
+            std::sync::Arc::new(OpenAiClient::from_config(key, config))
+        }
+        LlmProvider::Anthropic => {
+            let key = keys.anthropic.clone().ok_or_else(missing)?;
+            std::sync::Arc::new(
+                AnthropicClient::from_config(key, config)
+                    .with_embeddings(EmbeddingBackend::from_config(config, keys)),
+            )
+        }
+        LlmProvider::Local => std::sync::Arc::new(OpenAiClient::from_config(
+            keys.openai.clone().unwrap_or_default(),
+            config,
+        )),
+    })
 }
 
 #[async_trait]
@@ -658,35 +1687,7 @@ impl LlmClient for OpenAiClient {
         prompt: &AgentPrompt,
         permissions: &ActionPermissions,
     ) -> Result<AgentAction, LlmError> {
-        let mut system_prompt = prompt.system.clone();
-        system_prompt.push_str("\n\nYou must respond with a valid JSON object containing the action. Available actions:\n");
-
-        if permissions.search {
-            system_prompt.push_str("- search: {\"action\": \"search\", \"queries\": [{\"q\": \"query text\", \"tbs\": \"optional\", \"location\": \"optional\"}], \"think\": \"reasoning\"}\n");
-        }
-        if permissions.read {
-            system_prompt.push_str("- read: {\"action\": \"read\", \"urls\": [\"url1\", \"url2\"], \"think\": \"reasoning\"}\n");
-        }
-        if permissions.reflect {
-            system_prompt.push_str("- reflect: {\"action\": \"reflect\", \"gap_questions\": [\"question1\"], \"think\": \"reasoning\"}\n");
-        }
-        if permissions.answer {
-            system_prompt.push_str("- answer: {\"action\": \"answer\", \"answer\": \"response text\", \"references\": [{\"url\": \"...\", \"title\": \"...\"}], \"think\": \"reasoning\"}\n");
-        }
-        if permissions.coding {
-            system_prompt.push_str("- coding: {\"action\": \"coding\", \"code\": \"problem description for code generation\", \"language\": \"javascript|python|auto\" (optional), \"think\": \"reasoning\"}\n");
-            system_prompt.push_str("  Language selection: javascript (fast, JSON/string ops), python (data analysis, statistics, complex regex). Default: auto (LLM chooses best).\n");
-        }
-        if permissions.history {
-            system_prompt.push_str("- history: {\"action\": \"history\", \"count\": 5, \"filter\": \"optional search term\", \"think\": \"reasoning\"}\n");
-        }
-        if permissions.ask_user {
-            system_prompt.push_str("- ask_user: {\"action\": \"ask_user\", \"questionType\": \"clarification|confirmation|preference|suggestion\", \"question\": \"question for user\", \"options\": [\"opt1\", \"opt2\"] (optional), \"isBlocking\": true, \"think\": \"why asking\"}\n");
-            system_prompt.push_str("  Use ask_user when you need CRITICAL information from the user that cannot be found through search.\n");
-            system_prompt.push_str("  Types: clarification (missing vital info), confirmation (before important action), preference (choose between options), suggestion (non-critical feedback)\n");
-        }
-
-        system_prompt.push_str("\nRespond ONLY with valid JSON, no other text.");
+        let system_prompt = action_system_prompt(prompt, permissions);
 
         let messages = vec![
             ChatMessage {
@@ -695,16 +1696,7 @@ impl LlmClient for OpenAiClient {
             },
             ChatMessage {
                 role: "user".into(),
-                content: format!(
-                    "{}\n\nDiary:\n{}",
-                    prompt.user,
-                    prompt
-                        .diary
-                        .iter()
-                        .map(|e| e.format())
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                ),
+                content: user_message(prompt),
             },
         ];
 
@@ -773,90 +1765,7 @@ impl LlmClient for OpenAiClient {
         let action_json: ActionJson = serde_json::from_str(&content)
             .map_err(|e| LlmError::ParseError(format!("Failed to parse action JSON: {}", e)))?;
 
-        match action_json.action.as_str() {
-            "search" => {
-                let queries = action_json
-                    .queries
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|q| SerpQuery {
-                        q: q.q,
-                        tbs: q.tbs,
-                        location: q.location,
-                    })
-                    .collect();
-                Ok(AgentAction::Search {
-                    queries,
-                    think: action_json.think,
-                })
-            }
-            "read" => Ok(AgentAction::Read {
-                urls: action_json.urls.unwrap_or_default(),
-                think: action_json.think,
-            }),
-            "reflect" => Ok(AgentAction::Reflect {
-                gap_questions: action_json.gap_questions.unwrap_or_default(),
-                think: action_json.think,
-            }),
-            "answer" => {
-                let references = action_json
-                    .references
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|r| Reference {
-                        url: r.url,
-                        title: r.title,
-                        exact_quote: r.exact_quote,
-                        relevance_score: r.relevance_score,
-                        answer_chunk: None,
-                        answer_position: None,
-                    })
-                    .collect();
-                Ok(AgentAction::Answer {
-                    answer: action_json.answer.unwrap_or_default(),
-                    references,
-                    think: action_json.think,
-                })
-            }
-            "coding" => Ok(AgentAction::Coding {
-                problem: action_json.code.unwrap_or_default(),
-                context_vars: None, // LLM não especifica context_vars, usa todo o knowledge
-                language: action_json.language, // LLM pode especificar "javascript", "python" ou deixar None para Auto
-                think: action_json.think,
-            }),
-            "history" => Ok(AgentAction::History {
-                count: action_json.count.unwrap_or(5),
-                filter: action_json.filter,
-                think: action_json.think,
-            }),
-            "ask_user" | "askuser" | "ask" => {
-                // Parsear tipo de pergunta
-                let question_type = action_json
-                    .question_type
-                    .as_deref()
-                    .and_then(crate::agent::QuestionType::from_str)
-                    .unwrap_or(crate::agent::QuestionType::Clarification);
-
-                // Por padrão, Clarification e Confirmation são blocking
-                let is_blocking = action_json.is_blocking.unwrap_or_else(|| {
-                    question_type.is_blocking_by_default()
-                });
-
-                Ok(AgentAction::AskUser {
-                    question_type,
-                    question: action_json.question.unwrap_or_else(|| {
-                        "Poderia fornecer mais informações?".into()
-                    }),
-                    options: action_json.options,
-                    is_blocking,
-                    think: action_json.think,
-                })
-            }
-            _ => Err(LlmError::ParseError(format!(
-                "Unknown action: {}",
-                action_json.action
-            ))),
-        }
+        action_json.into_action()
     }
 
     async fn generate_answer(
@@ -871,16 +1780,7 @@ impl LlmClient for OpenAiClient {
             },
             ChatMessage {
                 role: "user".into(),
-                content: format!(
-                    "{}\n\nDiary:\n{}",
-                    prompt.user,
-                    prompt
-                        .diary
-                        .iter()
-                        .map(|e| e.format())
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                ),
+                content: user_message(prompt),
             },
         ];
 
@@ -989,8 +1889,8 @@ impl LlmClient for OpenAiClient {
             .first()
             .ok_or_else(|| LlmError::ParseError("No embedding data in response".into()))?;
 
-        // Rastrear tokens de embedding no acumulador (prompt_tokens = input)
-        self.total_prompt_tokens.fetch_add(
+        // Rastrear tokens de embedding no acumulador próprio (preço diferente do prompt)
+        self.total_embedding_tokens.fetch_add(
             embedding_response.usage.prompt_tokens,
             std::sync::atomic::Ordering::Relaxed,
         );
@@ -1051,8 +1951,8 @@ impl LlmClient for OpenAiClient {
         let data_len = embedding_response.data.len() as u64;
         let tokens_per_embedding = embedding_response.usage.total_tokens / data_len.max(1);
 
-        // Rastrear tokens de embedding batch no acumulador (prompt_tokens = input)
-        self.total_prompt_tokens.fetch_add(
+        // Rastrear tokens de embedding batch no acumulador próprio
+        self.total_embedding_tokens.fetch_add(
             embedding_response.usage.prompt_tokens,
             std::sync::atomic::Ordering::Relaxed,
         );
@@ -1085,10 +1985,7 @@ impl LlmClient for OpenAiClient {
         answer: &str,
         criteria: &str,
     ) -> Result<EvaluationResponse, LlmError> {
-        let system_prompt = format!(
-            "You are an evaluator. Evaluate if the answer meets the criteria: {}\n\nRespond with JSON: {{\"passed\": true/false, \"reasoning\": \"explanation\", \"confidence\": 0.0-1.0}}",
-            criteria
-        );
+        let system_prompt = evaluation_system_prompt(criteria);
 
         let messages = vec![
             ChatMessage {
@@ -1158,38 +2055,17 @@ impl LlmClient for OpenAiClient {
             .content
             .clone();
 
-        #[derive(Deserialize)]
-        struct EvalJson {
-            passed: bool,
-            reasoning: String,
-            confidence: f32,
-        }
-
-        let eval_json: EvalJson = serde_json::from_str(&content)
-            .map_err(|e| LlmError::ParseError(format!("Failed to parse evaluation JSON: {}", e)))?;
-
-        Ok(EvaluationResponse {
-            passed: eval_json.passed,
-            reasoning: eval_json.reasoning,
-            confidence: eval_json.confidence,
-        })
+        parse_evaluation(&content)
     }
 
     async fn determine_eval_types(
         &self,
         question: &str,
     ) -> Result<Vec<crate::evaluation::EvaluationType>, LlmError> {
-        let system_prompt = r#"You are an evaluator selector. Determine which evaluation types are needed for this question.
-Respond with JSON: {"needs_definitive": true/false, "needs_freshness": true/false, "needs_plurality": true/false, "needs_completeness": true/false}
-- definitive: Does the question need a clear, confident answer?
-- freshness: Does the question require recent/current information?
-- plurality: Does the question ask for multiple items/examples?
-- completeness: Does the question have multiple aspects that need coverage?"#;
-
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: system_prompt.into(),
+                content: EVAL_TYPES_SYSTEM_PROMPT.into(),
             },
             ChatMessage {
                 role: "user".into(),
@@ -1254,37 +2130,7 @@ Respond with JSON: {"needs_definitive": true/false, "needs_freshness": true/fals
             .content
             .clone();
 
-        #[derive(Deserialize)]
-        struct EvalTypesJson {
-            needs_definitive: bool,
-            needs_freshness: bool,
-            needs_plurality: bool,
-            needs_completeness: bool,
-        }
-
-        let eval_types_json: EvalTypesJson = serde_json::from_str(&content)
-            .map_err(|e| LlmError::ParseError(format!("Failed to parse eval types JSON: {}", e)))?;
-
-        let mut types = Vec::new();
-        if eval_types_json.needs_definitive {
-            types.push(crate::evaluation::EvaluationType::Definitive);
-        }
-        if eval_types_json.needs_freshness {
-            types.push(crate::evaluation::EvaluationType::Freshness);
-        }
-        if eval_types_json.needs_plurality {
-            types.push(crate::evaluation::EvaluationType::Plurality);
-        }
-        if eval_types_json.needs_completeness {
-            types.push(crate::evaluation::EvaluationType::Completeness);
-        }
-
-        // Sempre adiciona Strict se houver outros tipos
-        if !types.is_empty() {
-            types.push(crate::evaluation::EvaluationType::Strict);
-        }
-
-        Ok(types)
+        parse_eval_types(&content)
     }
 
     async fn generate_code(
@@ -1293,55 +2139,7 @@ Respond with JSON: {"needs_definitive": true/false, "needs_freshness": true/fals
         available_vars: &str,
         previous_attempts: &[(String, Option<String>)],
     ) -> Result<CodeGenResponse, LlmError> {
-        // Construir contexto de tentativas anteriores
-        let previous_context = if !previous_attempts.is_empty() {
-            let attempts_text: Vec<String> = previous_attempts
-                .iter()
-                .enumerate()
-                .map(|(i, (code, error))| {
-                    format!(
-                        "<bad-attempt-{}>\n{}\n{}</bad-attempt-{}>",
-                        i + 1,
-                        code,
-                        error
-                            .as_ref()
-                            .map(|e| format!("Error: {}", e))
-                            .unwrap_or_default(),
-                        i + 1
-                    )
-                })
-                .collect();
-            format!(
-                "\nPrevious attempts and their errors:\n{}\n",
-                attempts_text.join("\n")
-            )
-        } else {
-            String::new()
-        };
-
-        let system_prompt = format!(
-            r#"You are an expert JavaScript programmer. Your task is to generate JavaScript code to solve the given problem.
-
-<rules>
-1. Generate plain JavaScript code that returns the result directly
-2. You can access any of these available variables directly:
-{}
-3. You don't have access to any third party libraries that need to be installed, so you must write complete, self-contained code.
-4. Must have a return statement.
-</rules>
-{}
-<example>
-Available variables:
-numbers (Array<number>) e.g. [1, 2, 3, 4, 5, 6]
-threshold (number) e.g. 4
-
-Problem: Sum all numbers above threshold
-
-Response:
-{{"think": "I need to filter numbers above threshold and sum them", "code": "return numbers.filter(n => n > threshold).reduce((a, b) => a + b, 0);"}}
-</example>"#,
-            available_vars, previous_context
-        );
+        let system_prompt = javascript_code_prompt(available_vars, previous_attempts);
 
         let messages = vec![
             ChatMessage {
@@ -1411,19 +2209,7 @@ Response:
             .content
             .clone();
 
-        #[derive(Deserialize)]
-        struct CodeGenJson {
-            code: String,
-            think: String,
-        }
-
-        let code_gen_json: CodeGenJson = serde_json::from_str(&content)
-            .map_err(|e| LlmError::ParseError(format!("Failed to parse code gen JSON: {}", e)))?;
-
-        Ok(CodeGenResponse {
-            code: code_gen_json.code,
-            think: code_gen_json.think,
-        })
+        parse_code_gen(&content)
     }
 
     async fn generate_python_code(
@@ -1432,57 +2218,7 @@ Response:
         available_vars: &str,
         previous_attempts: &[(String, Option<String>)],
     ) -> Result<CodeGenResponse, LlmError> {
-        // Construir contexto de tentativas anteriores
-        let previous_context = if !previous_attempts.is_empty() {
-            let attempts_text: Vec<String> = previous_attempts
-                .iter()
-                .enumerate()
-                .map(|(i, (code, error))| {
-                    format!(
-                        "<bad-attempt-{}>\n{}\n{}</bad-attempt-{}>",
-                        i + 1,
-                        code,
-                        error
-                            .as_ref()
-                            .map(|e| format!("Error: {}", e))
-                            .unwrap_or_default(),
-                        i + 1
-                    )
-                })
-                .collect();
-            format!(
-                "\nPrevious attempts and their errors:\n{}\n",
-                attempts_text.join("\n")
-            )
-        } else {
-            String::new()
-        };
-
-        let system_prompt = format!(
-            r#"You are an expert Python programmer. Your task is to generate Python code to solve the given problem.
-
-<rules>
-1. Generate plain Python code that prints the final result using print()
-2. You can access these pre-defined variables directly (already loaded as Python objects):
-{}
-3. Available modules: json, re, math, collections (Counter, defaultdict)
-4. Do NOT use: os, sys, subprocess, open, exec, eval, import for dangerous modules
-5. Must end with a print() statement for the final result
-6. Keep code simple and efficient
-</rules>
-{}
-<example>
-Available variables:
-numbers (list) e.g. [1, 2, 3, 4, 5, 6]
-threshold (int) e.g. 4
-
-Problem: Sum all numbers above threshold
-
-Response:
-{{"think": "I need to filter numbers above threshold and sum them using list comprehension", "code": "result = sum(n for n in numbers if n > threshold)\nprint(result)"}}
-</example>"#,
-            available_vars, previous_context
-        );
+        let system_prompt = python_code_prompt(available_vars, previous_attempts);
 
         let messages = vec![
             ChatMessage {
@@ -1552,46 +2288,17 @@ Response:
             .content
             .clone();
 
-        #[derive(Deserialize)]
-        struct CodeGenJson {
-            code: String,
-            think: String,
-        }
-
-        let code_gen_json: CodeGenJson = serde_json::from_str(&content)
-            .map_err(|e| LlmError::ParseError(format!("Failed to parse Python code gen JSON: {}", e)))?;
-
-        Ok(CodeGenResponse {
-            code: code_gen_json.code,
-            think: code_gen_json.think,
-        })
+        parse_code_gen(&content)
     }
 
     async fn choose_coding_language(
         &self,
         problem: &str,
     ) -> Result<crate::agent::SandboxLanguage, LlmError> {
-        let system_prompt = r#"You are a programming language expert. Given a problem description, decide whether JavaScript or Python is more suitable.
-
-Choose JavaScript for:
-- JSON manipulation and parsing
-- Simple string operations
-- Basic calculations
-- Quick data transformations
-
-Choose Python for:
-- Data analysis and statistics
-- Complex regex operations
-- Scientific calculations
-- Working with collections and aggregations
-- Text processing with complex patterns
-
-Respond with JSON: {"language": "javascript"} or {"language": "python"}"#;
-
         let messages = vec![
             ChatMessage {
                 role: "system".into(),
-                content: system_prompt.to_string(),
+                content: LANGUAGE_CHOICE_SYSTEM_PROMPT.into(),
             },
             ChatMessage {
                 role: "user".into(),
@@ -1644,19 +2351,7 @@ Respond with JSON: {"language": "javascript"} or {"language": "python"}"#;
             .content
             .clone();
 
-        #[derive(Deserialize)]
-        struct LanguageChoice {
-            language: String,
-        }
-
-        let choice: LanguageChoice = serde_json::from_str(&content)
-            .unwrap_or(LanguageChoice { language: "javascript".into() });
-
-        Ok(if choice.language.to_lowercase() == "python" {
-            crate::agent::SandboxLanguage::Python
-        } else {
-            crate::agent::SandboxLanguage::JavaScript
-        })
+        Ok(parse_language(&content))
     }
 
     fn get_prompt_tokens(&self) -> u64 {
@@ -1668,6 +2363,19 @@ Respond with JSON: {"language": "javascript"} or {"language": "python"}"#;
         self.total_completion_tokens
             .load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    fn get_embedding_tokens(&self) -> u64 {
+        self.total_embedding_tokens
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn model_name(&self) -> Option<&str> {
+        Some(&self.model)
+    }
+
+    fn embedding_model_name(&self) -> Option<&str> {
+        Some(&self.embedding_model)
+    }
 }
 
 #[cfg(test)]
@@ -1687,4 +2395,83 @@ mod tests {
         let action = client.decide_action(&prompt, &permissions).await.unwrap();
         assert!(action.is_search());
     }
+
+    #[test]
+    fn test_parse_parallel_action() {
+        let raw = r#"{"action": "parallel", "think": "plano", "actions": [
+            {"action": "search", "queries": [{"q": "a"}, {"q": "b"}]},
+            {"action": "read", "urls": ["https://x.com"], "think": "ler"},
+            {"action": "answer", "answer": "cedo demais"}
+        ]}"#;
+        let action = serde_json::from_str::<ActionJson>(raw)
+            .unwrap()
+            .into_action()
+            .unwrap();
+        let AgentAction::Parallel { actions, think } = action else {
+            panic!("esperava parallel");
+        };
+        assert_eq!(think, "plano");
+        // answer não é paralelizável; sub-ações sem think herdam o do plano
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].think(), "plano");
+        assert_eq!(actions[1].think(), "ler");
+
+        // Plano com uma única sub-ação vira a própria ação
+        let raw = r#"{"action": "parallel", "actions": [{"action": "read", "urls": ["u"]}]}"#;
+        let action = serde_json::from_str::<ActionJson>(raw)
+            .unwrap()
+            .into_action()
+            .unwrap();
+        assert_eq!(action.name(), "read");
+    }
+
+    #[test]
+    fn test_anthropic_response_mapping() {
+        let raw = r#"{
+            "id": "msg_01",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "```json\n{\"action\": \"read\", "},
+                {"type": "text", "text": "\"urls\": [\"https://x.com\"], \"think\": \"ler\"}\n```"}
+            ],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 120, "output_tokens": 30,
+                      "cache_creation_input_tokens": null, "cache_read_input_tokens": 80}
+        }"#;
+        let response: AnthropicResponse = serde_json::from_str(raw).unwrap();
+        assert_eq!(response.usage.prompt_tokens(), 200);
+        assert_eq!(response.usage.output_tokens, 30);
+
+        // Modelos sem modo JSON: o objeto vem cercado de ```json
+        let text = response.text();
+        let action = serde_json::from_str::<ActionJson>(extract_json_object(&text))
+            .unwrap()
+            .into_action()
+            .unwrap();
+        assert_eq!(action.name(), "read");
+        assert_eq!(action.think(), "ler");
+    }
+
+    #[test]
+    fn test_llm_client_for_requires_provider_key() {
+        let config = crate::config::LlmConfig {
+            provider: crate::config::LlmProvider::Anthropic,
+            ..Default::default()
+        };
+        let keys = LlmApiKeys {
+            openai: Some("sk-openai".into()),
+            ..Default::default()
+        };
+        let err = llm_client_for(&config, &keys).err().unwrap();
+        assert!(err.to_string().contains("ANTHROPIC_API_KEY"));
+
+        let keys = LlmApiKeys {
+            anthropic: Some("sk-ant".into()),
+            ..keys
+        };
+        let client = llm_client_for(&config, &keys).unwrap();
+        // Embeddings delegados ao EmbeddingProvider padrão (OpenAI)
+        assert_eq!(client.embedding_model_name(), Some("text-embedding-3-small"));
+    }
 }
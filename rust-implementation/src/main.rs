@@ -8,17 +8,26 @@
 //   deep-research-cli "Qual é a população do Brasil?"
 //   deep-research-cli --tui "pergunta"  (modo TUI interativo)
 //   deep-research-cli --budget 500000 "pergunta complexa"
+//   deep-research-cli --list-runs  (execuções interrompidas)
+//   deep-research-cli --resume <run_id>
+//   deep-research-cli --corpus-build <dir> <índice>  (corpus offline)
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use deep_research::config::{
     create_tokio_runtime, install_panic_hook, load_runtime_config, RuntimeConfig,
     load_llm_config, load_agent_config, LlmConfig, AgentConfig,
 };
-use deep_research::llm::OpenAiClient;
+use deep_research::corpus::{CorpusIndex, IndexStats, LocalCorpusClient};
+use deep_research::credibility::{CredibilityConfig, SourceCredibility};
+use deep_research::llm::{llm_client_for, LlmApiKeys};
 use deep_research::prelude::*;
 use deep_research::reader_comparison::ReaderComparison;
-use deep_research::search::JinaClient;
+use deep_research::search::{JinaClient, SearchClient};
+use deep_research::meta_search::meta_search_client_for;
+use deep_research::search_metrics::MetricsCollector;
+use deep_research::search_providers::WebSearchSettings;
 use deep_research::tui::create_event_channel;
+use deep_research::utils::PricingTable;
 use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 
@@ -45,6 +54,18 @@ fn get_agent_config() -> &'static AgentConfig {
     AGENT_CONFIG.get().expect("Agent config not initialized")
 }
 
+/// Credibilidade das fontes: AGENT_CREDIBILITY_FILE + sessões aprovadas do histórico local
+fn load_credibility() -> SourceCredibility {
+    let config = CredibilityConfig::load(get_agent_config().credibility_file.as_deref());
+    let credibility = SourceCredibility::new(config);
+    let sessions = deep_research::agent::history::LocalBackend::default_path().load_all_sessions();
+    let learned = credibility.learn_from_sessions(&sessions);
+    if learned > 0 {
+        log::info!("🏅 Credibilidade: histórico de {} sessões aprovadas", learned);
+    }
+    credibility
+}
+
 /// Tenta carregar o arquivo .env de múltiplos locais possíveis
 fn load_dotenv() {
     // Lista de possíveis locais para o .env
@@ -659,6 +680,30 @@ fn show_help(program_name: &str) {
     println!("  --budget <tokens>     Budget máximo de tokens (padrão: 1000000)");
     println!("  --compare <urls>      Comparar Jina Reader vs Rust+OpenAI (URLs separadas por vírgula)");
     println!("  --compare-live        Habilita comparação Jina vs Rust durante pesquisa");
+    println!("  --report              Relatório longo com esboço, seções e sumário executivo");
+    println!("  --list-runs           Lista execuções interrompidas (checkpoints)");
+    println!("  --resume <run_id>     Retoma uma execução interrompida");
+    println!("  --timeline <run_id>   Imprime a linha do tempo de um transcript");
+    println!("  --replay <run_id>     Reproduz um transcript na TUI");
+    println!("  --speed=<fator>       Velocidade do replay (padrão: 1.0, requer --replay)");
+    println!("  --corpus-build <dir> <índice>  Indexa um diretório para busca offline (BM25)");
+    println!("  --corpus-update <índice>       Atualiza o índice com arquivos novos/alterados");
+    println!();
+    println!("Cassettes (variáveis de ambiente):");
+    println!("  CASSETTE_RECORD=<arquivo>   Grava chamadas LLM/busca em um cassette JSONL");
+    println!("  CASSETTE_REPLAY=<arquivo>   Reexecuta a pesquisa offline a partir do cassette");
+    println!();
+    println!("LLM (variáveis de ambiente):");
+    println!("  LLM_PROVIDER=<nome>         openai (padrão, OPENAI_API_KEY), anthropic (ANTHROPIC_API_KEY), local");
+    println!("  EMBEDDING_PROVIDER=<nome>   openai ou jina (com anthropic, usado para embeddings)");
+    println!();
+    println!("Busca web (variáveis de ambiente):");
+    println!("  SEARCH_PROVIDER=<nome>      jina (padrão), searxng, brave, serper, serpapi, duckduckgo");
+    println!("                              Vários separados por vírgula ativam a meta-busca (RRF)");
+    println!("  SEARXNG_URL / BRAVE_API_KEY / SERPER_API_KEY / SERPAPI_API_KEY");
+    println!();
+    println!("Corpus local (variável de ambiente):");
+    println!("  CORPUS_INDEX=<índice>       Busca e leitura no corpus local, sem internet");
     println!();
     println!("Exemplos:");
     println!("  {} \"Qual é a população do Brasil?\"", program_name);
@@ -666,6 +711,7 @@ fn show_help(program_name: &str) {
     println!("  {} --tui \"Qual é a capital da França?\"", program_name);
     println!("  {} --compare \"https://example.com,https://rust-lang.org\"", program_name);
     println!("  {} --compare-live \"pergunta\"         # Pesquisa com comparação", program_name);
+    println!("  {} --report \"mercado de cafés especiais no Brasil\"", program_name);
     println!();
     println!("Features de compilação:");
     println!("  cargo build --release                           # Produção (sem clipboard)");
@@ -740,6 +786,41 @@ async fn async_main(args: Vec<String>, is_tui_mode: bool, is_server_mode: bool)
         return run_tui_mode(&question).await;
     }
 
+    // Listar execuções interrompidas
+    if args[1] == "--list-runs" {
+        return run_list_runs_mode();
+    }
+
+    // Retomar execução a partir de checkpoint
+    if args.len() >= 3 && args[1] == "--resume" {
+        return run_resume_mode(&args[2]).await;
+    }
+
+    // Linha do tempo de um transcript
+    if args.len() >= 3 && args[1] == "--timeline" {
+        return run_timeline_mode(&args[2]);
+    }
+
+    // Replay de um transcript na TUI
+    if args.len() >= 3 && args[1] == "--replay" {
+        let speed = args
+            .iter()
+            .find_map(|a| a.strip_prefix("--speed="))
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(1.0);
+        return run_replay_mode(&args[2], speed);
+    }
+
+    // Indexar um diretório para busca offline
+    if args.len() >= 4 && args[1] == "--corpus-build" {
+        return run_corpus_build_mode(&args[2], &args[3]);
+    }
+
+    // Atualizar incrementalmente um índice existente
+    if args.len() >= 3 && args[1] == "--corpus-update" {
+        return run_corpus_update_mode(&args[2]);
+    }
+
     // Modo comparação standalone
     if args.len() >= 3 && args[1] == "--compare" {
         return run_comparison_mode(&args[2]).await;
@@ -747,13 +828,15 @@ async fn async_main(args: Vec<String>, is_tui_mode: bool, is_server_mode: bool)
 
     // Verificar flag de comparação em tempo real
     let enable_compare_live = args.iter().any(|a| a == "--compare-live");
+    // Modo relatório (esboço + seções)
+    let enable_report = args.iter().any(|a| a == "--report");
 
     // Parse budget e question (considerando --compare-live)
     let (budget, question) = {
         let filtered_args: Vec<&str> = args
             .iter()
             .skip(1)
-            .filter(|a| *a != "--compare-live")
+            .filter(|a| *a != "--compare-live" && *a != "--report")
             .map(|s| s.as_str())
             .collect();
 
@@ -766,6 +849,10 @@ async fn async_main(args: Vec<String>, is_tui_mode: bool, is_server_mode: bool)
         }
     };
 
+    if enable_report {
+        return run_report_mode(&question, budget).await;
+    }
+
     run_direct_mode(&question, budget, enable_compare_live).await
 }
 
@@ -779,10 +866,11 @@ async fn run_server_mode(args: &[String]) -> anyhow::Result<()> {
     use std::net::SocketAddr;
     use std::sync::Arc;
 
-    let openai_key = std::env::var("OPENAI_API_KEY").unwrap_or_else(|_| {
-        eprintln!("Erro: OPENAI_API_KEY não encontrada no ambiente!");
+    let llm_keys = LlmApiKeys::from_env();
+    if let Err(e) = llm_client_for(get_llm_config(), &llm_keys) {
+        eprintln!("Erro: {}", e);
         std::process::exit(1);
-    });
+    }
     let jina_key = std::env::var("JINA_API_KEY").unwrap_or_else(|_| {
         eprintln!("Erro: JINA_API_KEY não encontrada no ambiente!");
         std::process::exit(1);
@@ -807,21 +895,34 @@ async fn run_server_mode(args: &[String]) -> anyhow::Result<()> {
     println!();
     println!("  Port: {}", port);
     println!("  Auth: {}", if secret.is_some() { "Bearer token" } else { "disabled" });
+    println!(
+        "  LLM: {} ({})",
+        get_llm_config().provider,
+        get_llm_config().model
+    );
+    println!("  Search: {}", get_runtime_config().search_providers_label());
     println!();
     println!("Endpoints:");
     println!("  GET  /health");
     println!("  GET  /v1/models");
     println!("  GET  /v1/models/{{model}}");
     println!("  POST /v1/chat/completions");
+    println!("  GET  /v1/runs");
+    println!("  POST /v1/runs/:run_id/resume");
     println!();
 
     let state = Arc::new(deep_research::server::AppState {
         llm_config: get_llm_config().clone(),
         runtime_config: get_runtime_config().clone(),
         agent_config: get_agent_config().clone(),
-        openai_key,
+        pricing: PricingTable::load(get_agent_config().pricing_file.as_deref()),
+        credibility: load_credibility(),
+        llm_keys,
         jina_key,
+        search_settings: WebSearchSettings::from_env(),
+        search_metrics: MetricsCollector::new(),
         secret,
+        conversations: Default::default(),
     });
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
@@ -847,49 +948,188 @@ async fn run_direct_mode(question: &str, budget: Option<u64>, enable_compare_liv
     }
     println!();
 
-    // Criar clientes reais com API keys de variáveis de ambiente
-    let openai_key = std::env::var("OPENAI_API_KEY").unwrap_or_else(|_| {
-        eprintln!("✗ Erro: OPENAI_API_KEY não encontrada!");
-        eprintln!();
-        eprintln!("Certifique-se de que:");
-        eprintln!("  1. O arquivo .env existe no diretório raiz do projeto");
-        eprintln!("  2. O arquivo contém: OPENAI_API_KEY=sua-chave-aqui");
-        eprintln!();
-        eprintln!("Ou defina a variável de ambiente diretamente:");
-        eprintln!("  export OPENAI_API_KEY=sua-chave-aqui");
+    let (llm_client, search_client) = create_research_clients();
+
+    // Criar e executar agente
+    let mut agent = DeepResearchAgent::new(llm_client, search_client, budget)
+        .with_comparative_read(enable_compare_live)
+        .with_answer_finalization(get_agent_config().finalize_answer)
+        .with_claim_verification(get_agent_config().verify_claims)
+        .with_contradiction_detection(get_agent_config().detect_contradictions)
+        .with_time_beast_mode_fraction(get_agent_config().time_beast_mode_fraction)
+        .with_pricing(&PricingTable::load(get_agent_config().pricing_file.as_deref()))
+        .with_credibility(load_credibility())
+        .with_approval_policy(get_agent_config().approval_policy.clone());
+    print_approval_notice();
+    if let Some(secs) = get_agent_config().time_budget_secs {
+        agent = agent.with_time_budget(std::time::Duration::from_secs(secs));
+        println!("⏱️ Prazo: {}s", secs);
+    }
+    if let Some(usd) = get_agent_config().cost_budget_usd {
+        agent = agent.with_cost_budget(usd);
+        println!("💰 Budget de custo: ${:.2}", usd);
+    }
+    if let Some(dir) = &get_agent_config().checkpoint_dir {
+        agent = agent.with_checkpoint_dir(dir);
+        println!("💾 Checkpoint: {}/{}.json", dir, agent.run_id());
+    }
+    if let Some(dir) = &get_agent_config().transcript_dir {
+        agent = agent.with_transcript_dir(dir);
+        println!("📼 Transcript: {}/{}.jsonl", dir, agent.run_id());
+    }
+
+    println!("Iniciando pesquisa...");
+    println!();
+
+    let result = agent.run(question.to_string()).await;
+
+    print_research_result(&result);
+
+    Ok(())
+}
+
+/// Executa a pesquisa em modo relatório no terminal
+async fn run_report_mode(question: &str, budget: Option<u64>) -> anyhow::Result<()> {
+    use deep_research::agent::{AgentProgress, ReportResearch};
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(" DEEP RESEARCH v{} - RELATÓRIO", deep_research::VERSION);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+    println!("Tema: {}", question);
+    if let Some(b) = budget {
+        println!("Budget: {} tokens", b);
+    }
+    println!();
+
+    let (llm_client, search_client) = create_research_clients();
+
+    print_approval_notice();
+    let mut report = ReportResearch::new(llm_client, search_client, budget)
+        .with_time_beast_mode_fraction(get_agent_config().time_beast_mode_fraction)
+        .with_pricing(&PricingTable::load(get_agent_config().pricing_file.as_deref()))
+        .with_credibility(load_credibility())
+        .with_approval_policy(get_agent_config().approval_policy.clone())
+        .with_progress_callback(Arc::new(|event| match event {
+            AgentProgress::ReportOutline { title, sections } => {
+                println!("📑 {}", title);
+                for (i, heading) in sections.iter().enumerate() {
+                    println!("   {}. {}", i + 1, heading);
+                }
+                println!();
+            }
+            AgentProgress::Info(msg) if msg.starts_with("📑") => println!("{}", msg),
+            _ => {}
+        }));
+    if let Some(secs) = get_agent_config().time_budget_secs {
+        report = report.with_time_budget(std::time::Duration::from_secs(secs));
+        println!("⏱️ Prazo: {}s", secs);
+    }
+    if let Some(usd) = get_agent_config().cost_budget_usd {
+        report = report.with_cost_budget(usd);
+        println!("💰 Budget de custo: ${:.2}", usd);
+    }
+
+    println!("Gerando esboço...");
+    println!();
+
+    let result = report.run(question.to_string()).await;
+
+    print_research_result(&result);
+
+    Ok(())
+}
+
+/// Cliente de busca do(s) `SEARCH_PROVIDER`; leitura e rerank pelo Jina/Rust local
+fn web_search_client(jina_key: String) -> Arc<dyn SearchClient> {
+    let credibility = CredibilityConfig::load(get_agent_config().credibility_file.as_deref());
+
+    // Usar preferência de WebReader da configuração global
+    let webreader_pref = get_runtime_config().webreader;
+    let reader: Arc<dyn SearchClient> = Arc::new(
+        JinaClient::with_preference(jina_key, webreader_pref).with_credibility(credibility.clone()),
+    );
+
+    meta_search_client_for(
+        &get_runtime_config().search_providers,
+        &WebSearchSettings::from_env(),
+        reader,
+        credibility,
+        MetricsCollector::new(),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("✗ Erro: {}", e);
         std::process::exit(1);
-    });
+    })
+}
 
-    let jina_key = std::env::var("JINA_API_KEY").unwrap_or_else(|_| {
-        eprintln!("✗ Erro: JINA_API_KEY não encontrada!");
+/// Cria os clientes LLM e de busca a partir das variáveis de ambiente
+fn create_research_clients() -> (
+    Arc<dyn deep_research::llm::LlmClient>,
+    Arc<dyn deep_research::search::SearchClient>,
+) {
+    // CASSETTE_REPLAY: reexecuta offline a partir de um cassette gravado
+    if let Ok(path) = std::env::var("CASSETTE_REPLAY") {
+        println!("📼 Replay do cassette: {}", path);
+        return deep_research::cassette::replay_clients(&path).unwrap_or_else(|e| {
+            eprintln!("✗ Erro ao carregar cassette {}: {}", path, e);
+            std::process::exit(1);
+        });
+    }
+
+    // Criar cliente LLM do LLM_PROVIDER com API keys de variáveis de ambiente
+    let llm_client = llm_client_for(get_llm_config(), &LlmApiKeys::from_env()).unwrap_or_else(|e| {
+        let variable = get_llm_config().provider.api_key_variable().unwrap_or_default();
+        eprintln!("✗ Erro: {}", e);
         eprintln!();
         eprintln!("Certifique-se de que:");
         eprintln!("  1. O arquivo .env existe no diretório raiz do projeto");
-        eprintln!("  2. O arquivo contém: JINA_API_KEY=sua-chave-aqui");
+        eprintln!("  2. O arquivo contém: {}=sua-chave-aqui", variable);
         eprintln!();
         eprintln!("Ou defina a variável de ambiente diretamente:");
-        eprintln!("  export JINA_API_KEY=sua-chave-aqui");
+        eprintln!("  export {}=sua-chave-aqui", variable);
         std::process::exit(1);
     });
 
-    // Criar cliente LLM com configuração do .env
-    let llm_client: Arc<dyn deep_research::llm::LlmClient> =
-        Arc::new(OpenAiClient::from_config(openai_key, get_llm_config()));
-
-    // Usar preferência de WebReader da configuração global
-    let webreader_pref = get_runtime_config().webreader;
+    // CORPUS_INDEX: busca offline em um corpus indexado com --corpus-build
     let search_client: Arc<dyn deep_research::search::SearchClient> =
-        Arc::new(JinaClient::with_preference(jina_key, webreader_pref));
-
-    // Criar e executar agente
-    let agent = DeepResearchAgent::new(llm_client, search_client, budget)
-        .with_comparative_read(enable_compare_live);
+        if let Ok(path) = std::env::var("CORPUS_INDEX") {
+            println!("📚 Corpus local: {}", path);
+            Arc::new(LocalCorpusClient::open(&path).unwrap_or_else(|e| {
+                eprintln!("✗ Erro ao carregar corpus {}: {}", path, e);
+                std::process::exit(1);
+            }))
+        } else {
+            let jina_key = std::env::var("JINA_API_KEY").unwrap_or_else(|_| {
+                eprintln!("✗ Erro: JINA_API_KEY não encontrada!");
+                eprintln!();
+                eprintln!("Certifique-se de que:");
+                eprintln!("  1. O arquivo .env existe no diretório raiz do projeto");
+                eprintln!("  2. O arquivo contém: JINA_API_KEY=sua-chave-aqui");
+                eprintln!();
+                eprintln!("Ou defina a variável de ambiente diretamente:");
+                eprintln!("  export JINA_API_KEY=sua-chave-aqui");
+                std::process::exit(1);
+            });
+
+            web_search_client(jina_key)
+        };
 
-    println!("Iniciando pesquisa...");
-    println!();
+    // CASSETTE_RECORD: grava cada chamada LLM/busca para replay posterior
+    if let Ok(path) = std::env::var("CASSETTE_RECORD") {
+        println!("📼 Gravando cassette: {}", path);
+        return deep_research::cassette::recording_clients(llm_client, search_client, &path)
+            .unwrap_or_else(|e| {
+                eprintln!("✗ Erro ao criar cassette {}: {}", path, e);
+                std::process::exit(1);
+            });
+    }
 
-    let result = agent.run(question.to_string()).await;
+    (llm_client, search_client)
+}
 
+/// Exibe resultado e estatísticas de uma pesquisa no terminal
+fn print_research_result(result: &deep_research::agent::ResearchResult) {
     // Exibir resultado
     println!();
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -919,6 +1159,55 @@ async fn run_direct_mode(question: &str, budget: Option<u64>, enable_compare_liv
             }
             println!();
         }
+
+        if let Some(vote) = &result.beast_mode_vote {
+            println!("🗳️ Votação do Beast Mode ({:?}):", vote.strategy);
+            for (i, candidate) in vote.candidates.iter().enumerate() {
+                println!(
+                    "  {} #{} temp {:.2} | {}/{} avaliações | concordância {:.0}% | score {:.2}",
+                    if candidate.selected { "✓" } else { " " },
+                    i + 1,
+                    candidate.temperature,
+                    candidate.passed_evals,
+                    candidate.required_evals,
+                    candidate.agreement * 100.0,
+                    candidate.score
+                );
+            }
+            println!();
+        }
+
+        if !result.claim_checks.is_empty() {
+            println!("🔎 Verificação por afirmação:");
+            for check in &result.claim_checks {
+                let icon = match check.verdict {
+                    deep_research::tools::ClaimVerdict::Supported => "✓",
+                    deep_research::tools::ClaimVerdict::Contradicted => "✗",
+                    deep_research::tools::ClaimVerdict::Unverified => "?",
+                };
+                match &check.source {
+                    Some(source) => println!("  {} {} ({})", icon, check.claim, source),
+                    None => println!("  {} {}", icon, check.claim),
+                }
+            }
+            println!();
+        }
+
+        if !result.disputed_facts.is_empty() {
+            println!("⚖️ Fontes discordam:");
+            for conflict in &result.disputed_facts {
+                println!("  • {}", conflict.describe());
+            }
+            println!();
+        }
+
+        if !result.source_credibility.is_empty() {
+            println!("🏅 Credibilidade das fontes:");
+            for score in result.source_credibility.iter().take(5) {
+                println!("  {:.2} {} ({})", score.score, score.host, score.explain());
+            }
+            println!();
+        }
     } else {
         println!("✗ Pesquisa falhou");
         if let Some(error) = &result.error {
@@ -942,15 +1231,167 @@ async fn run_direct_mode(question: &str, budget: Option<u64>, enable_compare_liv
     println!("    - Completion: {}", result.token_usage.completion_tokens);
     println!("    - Total:      {}", result.token_usage.total_tokens);
     println!();
+    println!("💰 Custo estimado: ${:.4}", result.cost.total_usd);
+    println!("    - Prompt:     ${:.4}", result.cost.prompt_usd);
+    println!("    - Completion: ${:.4}", result.cost.completion_usd);
+    println!("    - Embedding:  ${:.4}", result.cost.embedding_usd);
+    println!("    - Busca:      ${:.4}", result.cost.search_usd);
+    println!();
     println!("🔗 URLs visitadas: {}", result.visited_urls.len());
     for url in &result.visited_urls {
         println!("    - {}", url);
     }
     println!();
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CHECKPOINTS (LISTAR / RETOMAR)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Diretório de checkpoints configurado (ou o padrão)
+fn checkpoint_dir() -> PathBuf {
+    PathBuf::from(
+        get_agent_config()
+            .checkpoint_dir
+            .clone()
+            .unwrap_or_else(|| deep_research::agent::DEFAULT_CHECKPOINT_DIR.to_string()),
+    )
+}
+
+/// Lista execuções interrompidas que podem ser retomadas
+fn run_list_runs_mode() -> anyhow::Result<()> {
+    let dir = checkpoint_dir();
+    let runs = deep_research::agent::checkpoint::list_interrupted_runs(&dir);
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(" EXECUÇÕES INTERROMPIDAS ({})", dir.display());
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+
+    if runs.is_empty() {
+        println!("Nenhuma execução interrompida encontrada.");
+        return Ok(());
+    }
+
+    for run in &runs {
+        println!("▶ {} [{}]", run.run_id, run.state);
+        println!("    Pergunta:    {}", run.question);
+        println!(
+            "    Progresso:   step {} | {} itens | {} URLs | {} tokens",
+            run.total_step, run.knowledge_items, run.visited_urls, run.tokens_used
+        );
+        println!("    Atualizado:  {}", run.updated_at);
+        println!();
+    }
+    println!("Para retomar: deep-research-cli --resume <run_id>");
+
+    Ok(())
+}
+
+/// Avisa que ações sujeitas a aprovação serão recusadas (sem TUI não há quem aprove)
+fn print_approval_notice() {
+    let policy = &get_agent_config().approval_policy;
+    if !policy.is_empty() {
+        println!(
+            "🛂 {} regra(s) de aprovação: sem interface interativa, essas ações serão recusadas",
+            policy.rules.len()
+        );
+    }
+}
+
+/// Retoma uma execução interrompida a partir do checkpoint
+async fn run_resume_mode(target: &str) -> anyhow::Result<()> {
+    let path = deep_research::agent::checkpoint::find_checkpoint(&checkpoint_dir(), target)?;
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(" DEEP RESEARCH v{} - RETOMANDO", deep_research::VERSION);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+    println!("💾 Checkpoint: {}", path.display());
+
+    let (llm_client, search_client) = create_research_clients();
+    let mut agent = DeepResearchAgent::resume(&path, llm_client, search_client)?
+        .with_approval_policy(get_agent_config().approval_policy.clone());
+    print_approval_notice();
+    if let Some(dir) = &get_agent_config().transcript_dir {
+        agent = agent.with_transcript_dir(dir);
+    }
+    let question = agent.original_question().to_string();
+
+    println!("Pergunta: {}", question);
+    println!();
+
+    let result = agent.run(question).await;
+
+    print_research_result(&result);
+
+    Ok(())
+}
+
+/// Diretório dos transcripts (configuração ou padrão)
+fn transcript_dir() -> PathBuf {
+    PathBuf::from(
+        get_agent_config()
+            .transcript_dir
+            .clone()
+            .unwrap_or_else(|| deep_research::agent::DEFAULT_TRANSCRIPT_DIR.to_string()),
+    )
+}
+
+/// Imprime a linha do tempo compacta de um transcript
+fn run_timeline_mode(target: &str) -> anyhow::Result<()> {
+    use deep_research::agent::{find_transcript, format_timeline, read_transcript};
+
+    let path = find_transcript(&transcript_dir(), target)?;
+    let records = read_transcript(&path)?;
+
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(" LINHA DO TEMPO ({})", path.display());
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+    println!("{}", format_timeline(&records));
 
     Ok(())
 }
 
+/// Indexa um diretório e salva o índice do corpus
+fn run_corpus_build_mode(dir: &str, index_path: &str) -> anyhow::Result<()> {
+    println!("📚 Indexando {}...", dir);
+    let (index, stats) = CorpusIndex::build(dir)?;
+    index.save(index_path)?;
+    print_corpus_stats(&index, &stats, index_path);
+    Ok(())
+}
+
+/// Atualiza um índice existente com as mudanças do diretório
+fn run_corpus_update_mode(index_path: &str) -> anyhow::Result<()> {
+    let mut index = CorpusIndex::load(index_path)?;
+    println!("📚 Atualizando {}...", index.root().display());
+    let stats = index.update()?;
+    index.save(index_path)?;
+    print_corpus_stats(&index, &stats, index_path);
+    Ok(())
+}
+
+fn print_corpus_stats(index: &CorpusIndex, stats: &IndexStats, index_path: &str) {
+    println!();
+    println!("  Novos:       {}", stats.added);
+    println!("  Alterados:   {}", stats.updated);
+    println!("  Removidos:   {}", stats.removed);
+    println!("  Inalterados: {}", stats.unchanged);
+    println!("  Falhas:      {}", stats.failed);
+    println!();
+    println!("✓ {} documentos em {}", index.len(), index_path);
+    println!("  Use: CORPUS_INDEX={} deep-research-cli \"pergunta\"", index_path);
+}
+
+/// Reproduz um transcript na TUI
+fn run_replay_mode(target: &str, speed: f64) -> anyhow::Result<()> {
+    let path = deep_research::agent::find_transcript(&transcript_dir(), target)?;
+    deep_research::tui::run_replay(&path, speed)?;
+    Ok(())
+}
+
 /// Executa o modo de comparação entre Jina Reader e Rust+OpenAI
 async fn run_comparison_mode(urls_arg: &str) -> anyhow::Result<()> {
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -1104,16 +1545,17 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     };
     use deep_research::tui::{App, AppScreen, ActiveTab, execute_benchmark};
-    use deep_research::agent::UserResponse;
+    use deep_research::agent::{CancellationToken, UserResponse};
     use ratatui::{backend::CrosstermBackend, Terminal};
     use std::io;
     use std::time::Duration;
 
-    // Criar clientes
-    let openai_key = std::env::var("OPENAI_API_KEY").unwrap_or_else(|_| {
-        eprintln!("✗ Erro: OPENAI_API_KEY não encontrada!");
+    // Criar clientes (valida as chaves do LLM_PROVIDER antes de abrir a TUI)
+    let llm_keys = LlmApiKeys::from_env();
+    if let Err(e) = llm_client_for(get_llm_config(), &llm_keys) {
+        eprintln!("✗ Erro: {}", e);
         std::process::exit(1);
-    });
+    }
 
     let jina_key = std::env::var("JINA_API_KEY").unwrap_or_else(|_| {
         eprintln!("✗ Erro: JINA_API_KEY não encontrada!");
@@ -1147,16 +1589,21 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
     // Handle de tarefa do agente (opcional)
     let mut agent_task: Option<tokio::task::JoinHandle<_>> = None;
 
+    // Token de cancelamento da pesquisa atual (Esc na tela de pesquisa)
+    let mut research_cancel = CancellationToken::new();
+
     // Se já tem pergunta, iniciar pesquisa
     if !question.is_empty() {
         let (new_tx, new_rx) = tokio::sync::mpsc::channel::<UserResponse>(16);
         user_response_tx = Some(new_tx);
+        research_cancel = CancellationToken::new();
         agent_task = Some(spawn_research_task(
             question.to_string(),
-            openai_key.clone(),
+            llm_keys.clone(),
             jina_key.clone(),
             tx.clone(),
             new_rx,
+            research_cancel.clone(),
         ));
     }
 
@@ -1189,12 +1636,15 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
                                         let (new_tx, new_rx) = tokio::sync::mpsc::channel::<UserResponse>(16);
                                         user_response_tx = Some(new_tx);
 
+                                        research_cancel = CancellationToken::new();
+
                                         agent_task = Some(spawn_research_task(
                                             q,
-                                            openai_key.clone(),
+                                            llm_keys.clone(),
                                             jina_key.clone(),
                                             tx.clone(),
                                             new_rx,
+                                            research_cancel.clone(),
                                         ));
                                     }
                                 }
@@ -1260,6 +1710,16 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
                                         app.should_quit = true;
                                         break;
                                     }
+                                    KeyCode::Esc if !app.is_complete && app.error.is_none() && !research_cancel.is_cancelled() => {
+                                        // Esc com pesquisa em andamento = cancelar (novo Esc sai)
+                                        research_cancel.cancel();
+                                        app.handle_event(deep_research::tui::AppEvent::Log(
+                                            deep_research::tui::LogEntry::new(
+                                                deep_research::tui::LogLevel::Warning,
+                                                "⏹️ Cancelando pesquisa... (Esc novamente para sair)".to_string(),
+                                            ),
+                                        ));
+                                    }
                                     KeyCode::Esc => {
                                         // Esc sem input focado = sair
                                         app.should_quit = true;
@@ -1312,12 +1772,15 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
                                             let (new_tx, new_rx) = tokio::sync::mpsc::channel::<UserResponse>(16);
                                             user_response_tx = Some(new_tx);
 
+                                            research_cancel = CancellationToken::new();
+
                                             agent_task = Some(spawn_research_task(
                                                 q,
-                                                openai_key.clone(),
+                                                llm_keys.clone(),
                                                 jina_key.clone(),
                                                 tx.clone(),
                                                 new_rx,
+                                                research_cancel.clone(),
                                             ));
                                         }
                                     }
@@ -1548,9 +2011,10 @@ async fn run_tui_mode(question: &str) -> anyhow::Result<()> {
 
             println!();
             println!(
-                "⏱️  {:.2}s │ 🎫 {} tokens │ 🔗 {} URLs",
+                "⏱️  {:.2}s │ 🎫 {} tokens │ 💰 ${:.4} │ 🔗 {} URLs",
                 result.total_time_ms as f64 / 1000.0,
                 result.token_usage.total_tokens,
+                result.cost.total_usd,
                 result.visited_urls.len()
             );
             println!();
@@ -1734,26 +2198,23 @@ async fn run_all_benchmarks() -> anyhow::Result<()> {
 /// Spawna tarefa de pesquisa
 fn spawn_research_task(
     question: String,
-    openai_key: String,
+    llm_keys: LlmApiKeys,
     jina_key: String,
     tx: std::sync::mpsc::Sender<deep_research::tui::AppEvent>,
     mut user_response_rx: tokio::sync::mpsc::Receiver<deep_research::agent::UserResponse>,
+    cancel_token: deep_research::agent::CancellationToken,
 ) -> tokio::task::JoinHandle<deep_research::agent::ResearchResult> {
     use deep_research::agent::AgentProgress;
     use deep_research::tui::{AppEvent, LogEntry, LogLevel};
 
-    // Obter preferência de WebReader da configuração global
-    let webreader_pref = get_runtime_config().webreader;
-
     // Clonar configuração do LLM para mover para a task
     let llm_config = get_llm_config().clone();
 
     tokio::spawn(async move {
-        // Criar cliente LLM com configuração do .env
-        let llm_client: Arc<dyn deep_research::llm::LlmClient> =
-            Arc::new(OpenAiClient::from_config(openai_key, &llm_config));
-        let search_client: Arc<dyn deep_research::search::SearchClient> =
-            Arc::new(JinaClient::with_preference(jina_key, webreader_pref));
+        // Criar cliente LLM do LLM_PROVIDER (chaves validadas ao abrir a TUI)
+        let llm_client = llm_client_for(&llm_config, &llm_keys)
+            .expect("chaves do LLM validadas ao iniciar a TUI");
+        let search_client = web_search_client(jina_key);
 
         // Criar callback para enviar eventos em tempo real para a TUI
         let tx_clone = tx.clone();
@@ -2004,14 +2465,61 @@ fn spawn_research_task(
                         ))
                     }
                 }
+                AgentProgress::FinalizeStart { chars } => AppEvent::Log(LogEntry::new(
+                    LogLevel::Info,
+                    format!("✨ Polindo resposta aprovada ({} chars)...", chars),
+                )),
+                AgentProgress::FinalizeEnd { original_chars, final_chars, preserved_original, tokens, duration_ms } => {
+                    let msg = if preserved_original {
+                        format!("✨ Resposta aprovada mantida ({} chars, {} tokens, {}ms)", original_chars, tokens, duration_ms)
+                    } else {
+                        format!("✨ Resposta polida: {} → {} chars ({} tokens, {}ms)", original_chars, final_chars, tokens, duration_ms)
+                    };
+                    AppEvent::Log(LogEntry::new(LogLevel::Success, msg))
+                }
+                AgentProgress::TeamPlan { subproblems } => AppEvent::Log(LogEntry::new(
+                    LogLevel::Info,
+                    format!("👥 Equipe com {} subproblemas", subproblems.len()),
+                )),
+                AgentProgress::GapTree(rows) => AppEvent::SetGapTree(rows),
+                AgentProgress::ReportOutline { title, sections } => AppEvent::Log(LogEntry::new(
+                    LogLevel::Info,
+                    format!("📑 Relatório \"{}\" com {} seções", title, sections.len()),
+                )),
+                AgentProgress::SubAgent { agent_index, event } => match *event {
+                    AgentProgress::Info(msg) | AgentProgress::Success(msg) => AppEvent::Log(
+                        LogEntry::new(LogLevel::Info, format!("[agente {}] {}", agent_index + 1, msg)),
+                    ),
+                    AgentProgress::Warning(msg) | AgentProgress::Error(msg) => AppEvent::Log(
+                        LogEntry::new(LogLevel::Warning, format!("[agente {}] {}", agent_index + 1, msg)),
+                    ),
+                    _ => return,
+                },
             };
             let _ = tx_clone.send(app_event);
         });
 
         // Criar agente com callback de progresso e canais de interação
-        let (agent, response_tx, _question_rx) = DeepResearchAgent::new(llm_client, search_client, None)
-            .with_progress_callback(progress_callback)
-            .with_interaction_channels(16);
+        let mut agent = DeepResearchAgent::new(llm_client, search_client, None)
+            .with_answer_finalization(get_agent_config().finalize_answer)
+            .with_claim_verification(get_agent_config().verify_claims)
+            .with_contradiction_detection(get_agent_config().detect_contradictions)
+            .with_cancellation_token(cancel_token)
+            .with_time_beast_mode_fraction(get_agent_config().time_beast_mode_fraction)
+            .with_pricing(&PricingTable::load(get_agent_config().pricing_file.as_deref()))
+            .with_credibility(load_credibility())
+            .with_approval_policy(get_agent_config().approval_policy.clone())
+            .with_progress_callback(progress_callback);
+        if let Some(secs) = get_agent_config().time_budget_secs {
+            agent = agent.with_time_budget(std::time::Duration::from_secs(secs));
+        }
+        if let Some(usd) = get_agent_config().cost_budget_usd {
+            agent = agent.with_cost_budget(usd);
+        }
+        if let Some(dir) = &get_agent_config().transcript_dir {
+            agent = agent.with_transcript_dir(dir);
+        }
+        let (agent, response_tx, _question_rx) = agent.with_interaction_channels(16);
 
         // Spawn task para receber respostas do usuário da TUI e enviar para o agente
         let tx_for_bridge = tx.clone();
@@ -2061,6 +2569,10 @@ fn spawn_research_task(
                 result.token_usage.total_tokens
             ),
         )));
+        let _ = tx.send(AppEvent::Log(LogEntry::new(
+            LogLevel::Info,
+            format!("💰 Custo: {}", result.cost),
+        )));
 
         let _ = tx.send(AppEvent::SetVisitedCount(result.visited_urls.len()));
         let _ = tx.send(AppEvent::SetTokens(result.token_usage.total_tokens));
@@ -2073,6 +2585,16 @@ fn spawn_research_task(
             llm_ms: result.llm_time_ms,
         });
 
+        if result.cancelled {
+            let _ = tx.send(AppEvent::Log(LogEntry::new(
+                LogLevel::Warning,
+                format!(
+                    "⏹️ Pesquisa cancelada: {} itens de conhecimento coletados",
+                    result.partial_knowledge.len()
+                ),
+            )));
+        }
+
         // Enviar resultado
         if result.success {
             if let Some(ref answer) = result.answer {
@@ -0,0 +1,504 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// META-BUSCA - fan-out em vários provedores com Reciprocal Rank Fusion
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// `MetaSearchClient` envia cada `SerpQuery` a N clientes em paralelo,
+// cada um com seu próprio timeout. Provedores que falham ou estouram o
+// prazo são ignorados; a busca só falha se nenhum responder.
+//
+// Fusão:
+// - URLs são agrupadas pela forma normalizada (`normalize_url`)
+// - `weight` = Σ 1/(k + posição), normalizado para o melhor resultado = 1.0
+// - `freq_boost` = 1 + (provedores que retornaram a URL - 1) / respostas
+// - Boosts de domínio e path vêm do primeiro provedor que trouxe a URL
+//
+// Latência, resultados e resultados exclusivos de cada provedor vão para
+// `SearchMetrics` (`record_provider` / `record_provider_failure`).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicU8;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::config::SearchProvider;
+use crate::credibility::CredibilityConfig;
+use crate::search::{ComparativeReadResult, SearchClient, SearchError, SearchResult, UrlContent};
+use crate::search_metrics::MetricsCollector;
+use crate::search_providers::{search_client_for, SearchProviderError, WebSearchSettings};
+use crate::types::{BoostedSearchSnippet, SerpQuery, Url};
+
+/// Constante `k` do RRF (valor usual da literatura)
+pub const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Timeout padrão por provedor
+pub const DEFAULT_PROVIDER_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// Máximo de URLs no resultado fundido
+const MAX_MERGED_RESULTS: usize = 20;
+
+/// Parâmetros de rastreamento removidos na normalização
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "msclkid", "ref", "ref_src"];
+
+/// Normaliza uma URL para deduplicação entre provedores
+///
+/// Ignora esquema, `www.`, fragmento, barra final e parâmetros de
+/// rastreamento (`utm_*`, `fbclid`, ...). URLs inválidas voltam em minúsculas.
+pub fn normalize_url(raw: &str) -> String {
+    let Ok(parsed) = url::Url::parse(raw.trim()) else {
+        return raw.trim().to_lowercase();
+    };
+
+    let host = parsed.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let path = parsed.path().trim_end_matches('/');
+
+    let query: Vec<String> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    let mut normalized = host.to_string();
+    if let Some(port) = parsed.port() {
+        normalized.push_str(&format!(":{}", port));
+    }
+    normalized.push_str(path);
+    if !query.is_empty() {
+        normalized.push('?');
+        normalized.push_str(&query.join("&"));
+    }
+    normalized
+}
+
+/// Resposta de um provedor na rodada de fan-out
+struct ProviderOutcome {
+    name: String,
+    result: Result<SearchResult, SearchError>,
+    latency_ms: u64,
+    timed_out: bool,
+}
+
+/// URL agrupada durante a fusão
+struct FusedEntry {
+    snippet: BoostedSearchSnippet,
+    rrf: f32,
+    providers: HashSet<usize>,
+    descriptions: Vec<String>,
+}
+
+/// `SearchClient` que combina vários provedores de busca
+///
+/// Leitura de URLs, rerank e comparação são delegados a `reader`.
+pub struct MetaSearchClient {
+    providers: Vec<(String, Arc<dyn SearchClient>)>,
+    reader: Arc<dyn SearchClient>,
+    timeout: Duration,
+    rrf_k: f32,
+    metrics: MetricsCollector,
+}
+
+impl MetaSearchClient {
+    /// Cria o cliente sem provedores; `reader` atende leitura e rerank
+    pub fn new(reader: Arc<dyn SearchClient>) -> Self {
+        Self {
+            providers: Vec::new(),
+            reader,
+            timeout: DEFAULT_PROVIDER_TIMEOUT,
+            rrf_k: DEFAULT_RRF_K,
+            metrics: MetricsCollector::new(),
+        }
+    }
+
+    /// Adiciona um provedor de busca
+    pub fn with_provider(mut self, name: impl Into<String>, client: Arc<dyn SearchClient>) -> Self {
+        self.providers.push((name.into(), client));
+        self
+    }
+
+    /// Define o timeout por provedor
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Define a constante `k` do RRF
+    pub fn with_rrf_k(mut self, k: f32) -> Self {
+        self.rrf_k = k.max(1.0);
+        self
+    }
+
+    /// Compartilha um coletor de métricas
+    pub fn with_metrics(mut self, metrics: MetricsCollector) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Coletor com as estatísticas por provedor
+    pub fn metrics(&self) -> &MetricsCollector {
+        &self.metrics
+    }
+
+    /// Nomes dos provedores, na ordem de cadastro
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Consulta todos os provedores em paralelo
+    async fn fan_out(&self, query: &SerpQuery) -> Vec<ProviderOutcome> {
+        let calls = self.providers.iter().map(|(name, client)| async move {
+            let start = Instant::now();
+            let (result, timed_out) =
+                match tokio::time::timeout(self.timeout, client.search(query)).await {
+                    Ok(result) => (result, false),
+                    Err(_) => (
+                        Err(SearchError::NetworkError(format!(
+                            "{} timed out after {}s",
+                            name,
+                            self.timeout.as_secs()
+                        ))),
+                        true,
+                    ),
+                };
+            ProviderOutcome {
+                name: name.clone(),
+                result,
+                latency_ms: start.elapsed().as_millis() as u64,
+                timed_out,
+            }
+        });
+        futures::future::join_all(calls).await
+    }
+
+    /// Funde as respostas com RRF e registra as métricas por provedor
+    fn fuse(&self, outcomes: Vec<ProviderOutcome>) -> Result<SearchResult, SearchError> {
+        let mut entries: Vec<FusedEntry> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut provider_keys: Vec<Vec<usize>> = vec![Vec::new(); outcomes.len()];
+        let mut total_results = 0u64;
+        let mut responded = 0usize;
+        let mut last_error = None;
+
+        for (slot, outcome) in outcomes.iter().enumerate() {
+            let result = match &outcome.result {
+                Ok(result) => result,
+                Err(e) => {
+                    log::warn!("⚠️ Meta-busca: {} falhou: {}", outcome.name, e);
+                    self.metrics.record_provider_failure(
+                        &outcome.name,
+                        outcome.latency_ms,
+                        outcome.timed_out,
+                    );
+                    last_error = Some(e.to_string());
+                    continue;
+                }
+            };
+            responded += 1;
+            total_results = total_results.max(result.total_results);
+
+            for (rank, snippet) in result.urls.iter().enumerate() {
+                let key = normalize_url(&snippet.url);
+                let contribution = 1.0 / (self.rrf_k + rank as f32 + 1.0);
+                let position = *index.entry(key).or_insert_with(|| {
+                    entries.push(FusedEntry {
+                        snippet: snippet.clone(),
+                        rrf: 0.0,
+                        providers: HashSet::new(),
+                        descriptions: Vec::new(),
+                    });
+                    entries.len() - 1
+                });
+
+                let entry = &mut entries[position];
+                // A mesma URL repetida num provedor conta só a melhor posição
+                if !entry.providers.insert(slot) {
+                    continue;
+                }
+                entry.rrf += contribution;
+                if entry.snippet.title.is_empty() {
+                    entry.snippet.title = snippet.title.clone();
+                }
+                if !snippet.description.is_empty()
+                    && !entry.descriptions.contains(&snippet.description)
+                {
+                    entry.descriptions.push(snippet.description.clone());
+                }
+                provider_keys[slot].push(position);
+            }
+        }
+
+        if responded == 0 {
+            return Err(SearchError::ApiError(format!(
+                "all {} search providers failed: {}",
+                outcomes.len(),
+                last_error.unwrap_or_default()
+            )));
+        }
+
+        for (slot, outcome) in outcomes.iter().enumerate() {
+            if outcome.result.is_err() {
+                continue;
+            }
+            let unique = provider_keys[slot]
+                .iter()
+                .filter(|&&position| entries[position].providers.len() == 1)
+                .count();
+            self.metrics.record_provider(
+                &outcome.name,
+                outcome.latency_ms,
+                provider_keys[slot].len(),
+                unique,
+            );
+        }
+
+        let max_rrf = entries.iter().map(|e| e.rrf).fold(0.0f32, f32::max);
+        let mut urls: Vec<BoostedSearchSnippet> = entries
+            .into_iter()
+            .map(|entry| {
+                let mut snippet = entry.snippet;
+                snippet.weight = if max_rrf > 0.0 {
+                    entry.rrf / max_rrf
+                } else {
+                    1.0
+                };
+                snippet.freq_boost = 1.0 + (entry.providers.len() - 1) as f32 / responded as f32;
+                if let Some(first) = entry.descriptions.first() {
+                    snippet.description = first.clone();
+                }
+                snippet.merged = entry.descriptions.join(" ");
+                snippet.final_score = snippet.weight
+                    * snippet.freq_boost
+                    * snippet.hostname_boost
+                    * snippet.path_boost
+                    * snippet.jina_rerank_boost;
+                snippet.score = snippet.final_score;
+                snippet
+            })
+            .collect();
+
+        urls.sort_by(|a, b| b.final_score.total_cmp(&a.final_score));
+        urls.truncate(MAX_MERGED_RESULTS);
+
+        Ok(SearchResult {
+            snippets: urls.iter().map(|u| u.description.clone()).collect(),
+            total_results: total_results.max(urls.len() as u64),
+            urls,
+        })
+    }
+}
+
+#[async_trait]
+impl SearchClient for MetaSearchClient {
+    async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
+        log::info!(
+            "🔍 Meta-busca [{}]: q={}",
+            self.provider_names().join(", "),
+            query.q
+        );
+        let result = self.fuse(self.fan_out(query).await)?;
+        log::info!(
+            "✅ Meta-busca: query='{}' | {} resultados fundidos",
+            query.q,
+            result.urls.len()
+        );
+        Ok(result)
+    }
+
+    async fn search_batch(&self, queries: &[SerpQuery]) -> Vec<Result<SearchResult, SearchError>> {
+        futures::future::join_all(queries.iter().map(|q| self.search(q))).await
+    }
+
+    async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+        self.reader.read_url(url).await
+    }
+
+    async fn read_urls_batch(&self, urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+        self.reader.read_urls_batch(urls).await
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        urls: &[BoostedSearchSnippet],
+    ) -> Vec<BoostedSearchSnippet> {
+        self.reader.rerank(query, urls).await
+    }
+
+    async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+        self.reader.read_url_comparative(url).await
+    }
+
+    async fn read_urls_comparative_batch(&self, urls: &[Url]) -> Vec<ComparativeReadResult> {
+        self.reader.read_urls_comparative_batch(urls).await
+    }
+
+    async fn read_url_with_fallback_progress(
+        &self,
+        url: &Url,
+        progress: Arc<AtomicU8>,
+    ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+        self.reader
+            .read_url_with_fallback_progress(url, progress)
+            .await
+    }
+
+    fn get_credits(&self) -> u64 {
+        self.reader.get_credits()
+    }
+}
+
+/// Cria o cliente de busca para uma lista de provedores
+///
+/// Um provedor só usa `search_client_for` diretamente; dois ou mais viram
+/// um `MetaSearchClient` que registra as estatísticas em `metrics`.
+pub fn meta_search_client_for(
+    providers: &[SearchProvider],
+    settings: &WebSearchSettings,
+    reader: Arc<dyn SearchClient>,
+    credibility: CredibilityConfig,
+    metrics: MetricsCollector,
+) -> Result<Arc<dyn SearchClient>, SearchProviderError> {
+    match providers {
+        [] => search_client_for(SearchProvider::default(), settings, reader, credibility),
+        [provider] => search_client_for(*provider, settings, reader, credibility),
+        _ => {
+            let mut client = MetaSearchClient::new(reader.clone()).with_metrics(metrics);
+            for provider in providers {
+                let backend =
+                    search_client_for(*provider, settings, reader.clone(), credibility.clone())?;
+                client = client.with_provider(provider.as_str(), backend);
+            }
+            log::info!("🔧 Meta-busca: {}", client.provider_names().join(", "));
+            Ok(Arc::new(client))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::MockSearchClient;
+
+    fn snippet(url: &str, description: &str) -> BoostedSearchSnippet {
+        BoostedSearchSnippet {
+            url: url.into(),
+            title: url.into(),
+            description: description.into(),
+            ..Default::default()
+        }
+    }
+
+    fn provider(urls: &[&str]) -> Arc<dyn SearchClient> {
+        Arc::new(MockSearchClient::with_results(SearchResult {
+            urls: urls
+                .iter()
+                .map(|u| snippet(u, &format!("from {}", u)))
+                .collect(),
+            snippets: vec![],
+            total_results: urls.len() as u64,
+        }))
+    }
+
+    /// Provedor que nunca responde dentro do timeout
+    struct SlowClient;
+
+    #[async_trait]
+    impl SearchClient for SlowClient {
+        async fn search(&self, _query: &SerpQuery) -> Result<SearchResult, SearchError> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(SearchResult {
+                urls: vec![],
+                snippets: vec![],
+                total_results: 0,
+            })
+        }
+        async fn search_batch(
+            &self,
+            _queries: &[SerpQuery],
+        ) -> Vec<Result<SearchResult, SearchError>> {
+            vec![]
+        }
+        async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+            Err(SearchError::FetchError(url.clone()))
+        }
+        async fn read_urls_batch(&self, _urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+            vec![]
+        }
+        async fn rerank(
+            &self,
+            _query: &str,
+            urls: &[BoostedSearchSnippet],
+        ) -> Vec<BoostedSearchSnippet> {
+            urls.to_vec()
+        }
+        async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+            MockSearchClient::new().read_url_comparative(url).await
+        }
+        async fn read_urls_comparative_batch(&self, _urls: &[Url]) -> Vec<ComparativeReadResult> {
+            vec![]
+        }
+        async fn read_url_with_fallback_progress(
+            &self,
+            url: &Url,
+            _progress: Arc<AtomicU8>,
+        ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+            (Err(SearchError::FetchError(url.clone())), "slow", 0, 0)
+        }
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("https://www.Example.com/docs/?utm_source=x&id=3#intro"),
+            "example.com/docs?id=3"
+        );
+        assert_eq!(normalize_url("http://example.com/docs"), "example.com/docs");
+        assert_eq!(
+            normalize_url("https://example.com:8080/"),
+            "example.com:8080"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fusion_tolerates_slow_provider() {
+        let client = MetaSearchClient::new(Arc::new(MockSearchClient::new()))
+            .with_provider(
+                "a",
+                provider(&[
+                    "https://a.example/only-a",
+                    "https://www.shared.example/page/",
+                ]),
+            )
+            .with_provider(
+                "b",
+                provider(&[
+                    "https://shared.example/page?utm_medium=feed",
+                    "https://b.example/only-b",
+                ]),
+            )
+            .with_provider("slow", Arc::new(SlowClient))
+            .with_timeout(Duration::from_millis(50));
+
+        let result = client.search(&SerpQuery::default()).await.unwrap();
+
+        // A URL compartilhada vence: duas posições no RRF e freq_boost
+        assert_eq!(result.urls.len(), 3);
+        let top = &result.urls[0];
+        assert_eq!(normalize_url(&top.url), "shared.example/page");
+        assert_eq!(top.weight, 1.0);
+        assert_eq!(top.freq_boost, 1.5);
+        assert!(top.merged.contains("from https://www.shared.example/page/"));
+        assert!(result.urls[1].freq_boost == 1.0 && result.urls[1].weight < 1.0);
+
+        let stats = client.metrics().metrics().provider_stats();
+        assert_eq!(stats["a"].results, 2);
+        assert_eq!(stats["a"].unique_results, 1);
+        assert_eq!(stats["slow"].timeouts, 1);
+        assert_eq!(stats["slow"].failures, 1);
+    }
+}
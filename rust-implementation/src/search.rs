@@ -6,6 +6,7 @@
 // Suporta múltiplos provedores: Jina, SerpAPI, Brave, etc.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
+use crate::credibility::CredibilityConfig;
 use crate::types::{BoostedSearchSnippet, SerpQuery, Url};
 use crate::utils::ActionTimer;
 use async_trait::async_trait;
@@ -15,7 +16,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Cobre tanto erros de busca na API quanto erros
 /// ao ler e extrair conteúdo de URLs.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, thiserror::Error, Serialize, Deserialize)]
 pub enum SearchError {
     /// Erro retornado pela API de busca (Jina, SerpAPI, etc).
     ///
@@ -63,7 +64,7 @@ pub enum SearchError {
 ///
 /// Contém os URLs encontrados (com scores de relevância),
 /// snippets de texto para preview, e o total de resultados.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     /// URLs encontradas com metadados e scores de ranking.
     ///
@@ -84,7 +85,7 @@ pub struct SearchResult {
 ///
 /// Após baixar e processar uma URL, o texto limpo
 /// (sem HTML, scripts, ads) é armazenado aqui.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UrlContent {
     /// Título da página (tag `<title>`).
     pub title: String,
@@ -106,7 +107,7 @@ pub struct UrlContent {
 }
 
 /// Resultado de leitura comparativa entre Jina e Rust local
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComparativeReadResult {
     /// URL que foi lida
     pub url: String,
@@ -125,7 +126,7 @@ pub struct ComparativeReadResult {
 }
 
 /// Método de leitura utilizado para extração de conteúdo de URLs.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ReadMethod {
     /// Jina Reader API - extração especializada via API externa
     Jina,
@@ -169,6 +170,13 @@ pub trait SearchClient: Send + Sync {
         url: &Url,
         progress: std::sync::Arc<std::sync::atomic::AtomicU8>,
     ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize);
+
+    /// Retorna créditos de API acumulados (busca, leitura, embeddings)
+    ///
+    /// Usado para precificação; implementação opcional.
+    fn get_credits(&self) -> u64 {
+        0
+    }
 }
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
@@ -347,62 +355,6 @@ impl SearchClient for MockSearchClient {
 /// Re-exportado de `crate::config::WebReaderPreference` para conveniência.
 pub use crate::config::WebReaderPreference;
 
-/// Verifica se uma URL requer Jina Reader (JS-heavy, paywalls, redes sociais)
-///
-/// Essas URLs não funcionam bem com leitura Rust local porque:
-/// - Requerem JavaScript para renderizar conteúdo
-/// - Têm paywalls que Jina consegue contornar
-/// - Bloqueiam crawlers tradicionais
-fn url_requires_jina(url: &str) -> bool {
-    const JINA_REQUIRED_DOMAINS: &[&str] = &[
-        // Redes sociais que requerem JS
-        "tiktok.com",
-        "instagram.com",
-        "twitter.com",
-        "x.com",
-        "facebook.com",
-        "threads.net",
-        "snapchat.com",
-        "pinterest.com",
-        // Plataformas de vídeo (requerem JS)
-        "youtube.com",
-        "youtu.be",
-        "vimeo.com",
-        "twitch.tv",
-        "dailymotion.com",
-        // Apps e SPAs
-        "linkedin.com",
-        "discord.com",
-        "slack.com",
-        "notion.so",
-        "figma.com",
-        "canva.com",
-        "medium.com",
-        // Paywalls que Jina consegue ler
-        "wsj.com",
-        "ft.com",
-        "nytimes.com",
-        "washingtonpost.com",
-        "bloomberg.com",
-        "economist.com",
-        "reuters.com",
-        // URL shorteners (precisam resolver redirect)
-        "t.co",
-        "bit.ly",
-        "goo.gl",
-        "tinyurl.com",
-        "ow.ly",
-        // Google Apps (requerem JS)
-        "maps.google.com",
-        "drive.google.com",
-        "docs.google.com",
-        "sheets.google.com",
-    ];
-
-    let url_lower = url.to_lowercase();
-    JINA_REQUIRED_DOMAINS.iter().any(|domain| url_lower.contains(domain))
-}
-
 /// Cliente para Jina AI APIs
 pub struct JinaClient {
     /// Chave da API Jina
@@ -421,6 +373,10 @@ pub struct JinaClient {
     client: reqwest::Client,
     /// Preferência de método de leitura de URLs
     webreader_preference: WebReaderPreference,
+    /// Créditos Jina consumidos (thread-safe)
+    credits_used: std::sync::atomic::AtomicU64,
+    /// Camadas de domínio e domínios que requerem Jina Reader
+    credibility: CredibilityConfig,
 }
 
 /// Resultado de embedding Jina
@@ -486,6 +442,8 @@ impl JinaClient {
             embeddings_model,
             client: reqwest::Client::new(),
             webreader_preference,
+            credits_used: std::sync::atomic::AtomicU64::new(0),
+            credibility: CredibilityConfig::builtin(),
         }
     }
 
@@ -515,9 +473,23 @@ impl JinaClient {
             embeddings_model: embedding_model,
             client: reqwest::Client::new(),
             webreader_preference,
+            credits_used: std::sync::atomic::AtomicU64::new(0),
+            credibility: CredibilityConfig::builtin(),
         }
     }
 
+    /// Define as camadas de domínio e os domínios que requerem Jina Reader
+    pub fn with_credibility(mut self, credibility: CredibilityConfig) -> Self {
+        self.credibility = credibility;
+        self
+    }
+
+    /// Soma créditos consumidos por uma chamada à API Jina
+    fn add_credits(&self, credits: u64) {
+        self.credits_used
+            .fetch_add(credits, std::sync::atomic::Ordering::Relaxed);
+    }
+
     /// Retorna o modelo de embedding configurado
     pub fn embedding_model(&self) -> &str {
         &self.embeddings_model
@@ -591,6 +563,7 @@ impl JinaClient {
 
         let total_tokens = embedding_response.usage.total_tokens;
         let prompt_tokens = embedding_response.usage.prompt_tokens;
+        self.add_credits(total_tokens);
         let tokens_per_embedding = prompt_tokens / texts.len().max(1) as u64;
 
         // Ordenar por index para garantir ordem correta
@@ -668,6 +641,7 @@ impl JinaClient {
         let mut content = String::new();
         let mut title = String::new();
         let mut total_bytes: usize = 0;
+        let mut usage_tokens: u64 = 0;
 
         // Estimar tamanho total (páginas típicas ~50-200KB de markdown)
         let estimated_total: usize = 100_000; // 100KB como estimativa
@@ -693,6 +667,9 @@ impl JinaClient {
                             let data = &line[6..];
                             if data.starts_with('{') {
                                 if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                                    if let Some(tokens) = reader_usage_tokens(&json) {
+                                        usage_tokens = tokens;
+                                    }
                                     if let Some(t) = json.get("title").and_then(|v| v.as_str()) {
                                         title = t.to_string();
                                     }
@@ -723,6 +700,7 @@ impl JinaClient {
 
         // Progresso final 100%
         progress_callback(total_bytes, 100);
+        self.add_credits(usage_tokens);
 
         // Fallback se não parseou SSE
         if content.is_empty() {
@@ -987,6 +965,14 @@ struct JinaRerankResult {
     relevance_score: f32,
 }
 
+/// Extrai `usage.tokens` de um evento do Jina Reader (créditos cobrados)
+fn reader_usage_tokens(json: &serde_json::Value) -> Option<u64> {
+    json.get("usage")
+        .or_else(|| json.get("data").and_then(|d| d.get("usage")))
+        .and_then(|usage| usage.get("tokens"))
+        .and_then(|tokens| tokens.as_u64())
+}
+
 #[async_trait]
 impl SearchClient for JinaClient {
     async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
@@ -1044,13 +1030,17 @@ impl SearchClient for JinaClient {
             search_response.meta.latency,
             search_response.meta.credits
         );
+        self.add_credits(search_response.meta.credits);
 
         let mut snippets: Vec<BoostedSearchSnippet> = Vec::new();
         let mut snippet_strings: Vec<String> = Vec::new();
 
         for r in search_response.results {
             let hostname = extract_hostname(&r.url);
-            let hostname_boost_val = hostname.as_ref().map(|h| hostname_boost(h)).unwrap_or(1.0);
+            let hostname_boost_val = hostname
+                .as_ref()
+                .map(|h| self.credibility.host_weight(h))
+                .unwrap_or(1.0);
             let path_boost_val = path_boost(&r.url);
 
             snippets.push(BoostedSearchSnippet {
@@ -1131,6 +1121,7 @@ impl SearchClient for JinaClient {
         // Processar streaming SSE - cada chunk contém mais conteúdo
         let mut content = String::new();
         let mut title = String::new();
+        let mut usage_tokens: u64 = 0;
 
         // Ler bytes como stream
         let bytes = response.bytes().await.map_err(|e| {
@@ -1149,6 +1140,9 @@ impl SearchClient for JinaClient {
                 // Tentar parsear como JSON (último evento contém JSON completo)
                 if data.starts_with('{') {
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                        if let Some(tokens) = reader_usage_tokens(&json) {
+                            usage_tokens = tokens;
+                        }
                         if let Some(t) = json.get("title").and_then(|v| v.as_str()) {
                             title = t.to_string();
                         }
@@ -1173,6 +1167,8 @@ impl SearchClient for JinaClient {
             }
         }
 
+        self.add_credits(usage_tokens);
+
         // Se não conseguiu parsear SSE, usar conteúdo raw como markdown
         if content.is_empty() {
             content = raw_text.to_string();
@@ -1447,7 +1443,7 @@ impl SearchClient for JinaClient {
         const MIN_CONTENT_LENGTH: usize = 100;
 
         // 🔥 FORÇAR JINA para URLs que precisam de JS/paywall bypass
-        if url_requires_jina(url) {
+        if self.credibility.requires_reader(url) {
             progress.store(10, Ordering::Relaxed);
             log::info!("🔄 [JINA-FORCED] URL requer Jina: {}", url);
 
@@ -1672,6 +1668,11 @@ impl SearchClient for JinaClient {
             }
         }
     }
+
+    fn get_credits(&self) -> u64 {
+        self.credits_used
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
@@ -1685,23 +1686,12 @@ pub fn extract_hostname(url: &str) -> Option<String> {
         .and_then(|u| u.host_str().map(String::from))
 }
 
-/// Calcula boost baseado no hostname
+/// Calcula boost baseado no hostname (tabela de credibilidade embutida)
+///
+/// Para camadas configuradas, histórico e sinais da página, use
+/// `credibility::SourceCredibility`.
 pub fn hostname_boost(hostname: &str) -> f32 {
-    // Domínios confiáveis recebem boost
-    let trusted = [
-        "wikipedia.org",
-        "arxiv.org",
-        "github.com",
-        "stackoverflow.com",
-        "docs.rs",
-        "rust-lang.org",
-    ];
-
-    if trusted.iter().any(|t| hostname.contains(t)) {
-        1.5
-    } else {
-        1.0
-    }
+    CredibilityConfig::builtin().host_weight(hostname)
 }
 
 /// Calcula boost baseado no path da URL
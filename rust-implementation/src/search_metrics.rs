@@ -8,7 +8,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
@@ -16,6 +16,50 @@ use uuid::Uuid;
 /// Constante para tamanho do buffer de latências (para percentis)
 const LATENCY_BUFFER_SIZE: usize = 1000;
 
+/// Estatísticas de um provedor de busca (meta-busca)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderStats {
+    /// Buscas enviadas ao provedor
+    pub searches: u64,
+    /// Buscas que falharam (inclui timeouts)
+    pub failures: u64,
+    /// Buscas que estouraram o timeout
+    pub timeouts: u64,
+    /// Soma das latências em ms
+    pub total_latency_ms: u64,
+    /// Resultados retornados
+    pub results: u64,
+    /// Resultados que nenhum outro provedor retornou
+    pub unique_results: u64,
+}
+
+impl ProviderStats {
+    /// Latência média em ms
+    pub fn avg_latency(&self) -> f64 {
+        if self.searches == 0 {
+            return 0.0;
+        }
+        self.total_latency_ms as f64 / self.searches as f64
+    }
+
+    /// Taxa de sucesso (0.0 - 1.0)
+    pub fn success_rate(&self) -> f64 {
+        if self.searches == 0 {
+            return 0.0;
+        }
+        (self.searches - self.failures) as f64 / self.searches as f64
+    }
+
+    /// Resultados por busca bem sucedida
+    pub fn avg_yield(&self) -> f64 {
+        let successful = self.searches - self.failures;
+        if successful == 0 {
+            return 0.0;
+        }
+        self.results as f64 / successful as f64
+    }
+}
+
 /// Métricas agregadas de busca
 ///
 /// Coleta estatísticas de performance para comparação com TypeScript:
@@ -63,6 +107,8 @@ pub struct SearchMetrics {
     total_latency_ms: AtomicU64,
     /// Tempo total de execução em ms
     total_execution_time_ms: AtomicU64,
+    /// Estatísticas por provedor (meta-busca)
+    providers: RwLock<BTreeMap<String, ProviderStats>>,
 }
 
 impl SearchMetrics {
@@ -81,6 +127,7 @@ impl SearchMetrics {
             cache_misses: AtomicU64::new(0),
             total_latency_ms: AtomicU64::new(0),
             total_execution_time_ms: AtomicU64::new(0),
+            providers: RwLock::new(BTreeMap::new()),
         }
     }
 
@@ -106,6 +153,44 @@ impl SearchMetrics {
         }
     }
 
+    /// Registra a resposta de um provedor na meta-busca
+    pub fn record_provider(
+        &self,
+        provider: &str,
+        latency_ms: u64,
+        results_count: usize,
+        unique_results: usize,
+    ) {
+        if let Ok(mut providers) = self.providers.write() {
+            let stats = providers.entry(provider.to_string()).or_default();
+            stats.searches += 1;
+            stats.total_latency_ms += latency_ms;
+            stats.results += results_count as u64;
+            stats.unique_results += unique_results as u64;
+        }
+    }
+
+    /// Registra uma falha (ou timeout) de um provedor na meta-busca
+    pub fn record_provider_failure(&self, provider: &str, latency_ms: u64, timed_out: bool) {
+        if let Ok(mut providers) = self.providers.write() {
+            let stats = providers.entry(provider.to_string()).or_default();
+            stats.searches += 1;
+            stats.failures += 1;
+            stats.total_latency_ms += latency_ms;
+            if timed_out {
+                stats.timeouts += 1;
+            }
+        }
+    }
+
+    /// Estatísticas por provedor
+    pub fn provider_stats(&self) -> BTreeMap<String, ProviderStats> {
+        self.providers
+            .read()
+            .map(|p| p.clone())
+            .unwrap_or_default()
+    }
+
     /// Registra um cache hit
     pub fn record_cache_hit(&self) {
         self.cache_hits.fetch_add(1, Ordering::Relaxed);
@@ -266,6 +351,7 @@ impl SearchMetrics {
             latency_p99: self.latency_p99(),
             latency_min: self.latency_min(),
             latency_max: self.latency_max(),
+            providers: self.provider_stats(),
         }
     }
 
@@ -284,11 +370,14 @@ impl SearchMetrics {
         if let Ok(mut latencies) = self.latencies.write() {
             latencies.clear();
         }
+        if let Ok(mut providers) = self.providers.write() {
+            providers.clear();
+        }
     }
 
     /// Retorna resumo formatado
     pub fn summary(&self) -> String {
-        format!(
+        let mut summary = format!(
             "SearchMetrics [{}]\n\
              Total: {} searches ({} success, {} failed)\n\
              Success rate: {:.1}%\n\
@@ -310,7 +399,22 @@ impl SearchMetrics {
             self.latency_p95(),
             self.latency_p99(),
             self.bytes_per_second() / 1024.0
-        )
+        );
+
+        for (name, stats) in self.provider_stats() {
+            summary.push_str(&format!(
+                "\nProvider {}: {} searches ({} failed, {} timeouts), avg={:.0}ms, yield={:.1}, unique={}",
+                name,
+                stats.searches,
+                stats.failures,
+                stats.timeouts,
+                stats.avg_latency(),
+                stats.avg_yield(),
+                stats.unique_results
+            ));
+        }
+
+        summary
     }
 }
 
@@ -340,6 +444,11 @@ impl Clone for SearchMetrics {
             *dst = src.clone();
         }
 
+        // Copiar estatísticas por provedor
+        if let (Ok(src), Ok(mut dst)) = (self.providers.read(), new.providers.write()) {
+            *dst = src.clone();
+        }
+
         new
     }
 }
@@ -383,6 +492,9 @@ pub struct MetricsSnapshot {
     pub latency_min: u64,
     /// Latência máxima em ms
     pub latency_max: u64,
+    /// Estatísticas por provedor (meta-busca)
+    #[serde(default)]
+    pub providers: BTreeMap<String, ProviderStats>,
 }
 
 impl MetricsSnapshot {
@@ -493,6 +605,24 @@ impl MetricsCollector {
         self.metrics.record_search(latency_ms, success, results_count, bytes);
     }
 
+    /// Registra a resposta de um provedor na meta-busca
+    pub fn record_provider(
+        &self,
+        provider: &str,
+        latency_ms: u64,
+        results_count: usize,
+        unique_results: usize,
+    ) {
+        self.metrics
+            .record_provider(provider, latency_ms, results_count, unique_results);
+    }
+
+    /// Registra uma falha (ou timeout) de um provedor na meta-busca
+    pub fn record_provider_failure(&self, provider: &str, latency_ms: u64, timed_out: bool) {
+        self.metrics
+            .record_provider_failure(provider, latency_ms, timed_out);
+    }
+
     /// Registra cache hit
     pub fn record_cache_hit(&self) {
         self.metrics.record_cache_hit();
@@ -717,6 +847,31 @@ mod tests {
         assert_eq!(metrics.cache_hit_rate(), 0.0);
     }
 
+    #[test]
+    fn test_provider_stats() {
+        let metrics = SearchMetrics::new();
+
+        metrics.record_provider("brave", 200, 10, 4);
+        metrics.record_provider("brave", 400, 6, 2);
+        metrics.record_provider_failure("brave", 12000, true);
+        metrics.record_provider_failure("searxng", 50, false);
+
+        let stats = metrics.provider_stats();
+        let brave = &stats["brave"];
+        assert_eq!(brave.searches, 3);
+        assert_eq!(brave.timeouts, 1);
+        assert_eq!(brave.unique_results, 6);
+        assert!((brave.avg_yield() - 8.0).abs() < 0.01);
+        assert!((brave.success_rate() - 2.0 / 3.0).abs() < 0.01);
+        assert_eq!(stats["searxng"].success_rate(), 0.0);
+
+        assert_eq!(metrics.snapshot().providers, stats);
+        assert!(metrics.summary().contains("Provider brave"));
+
+        metrics.reset();
+        assert!(metrics.provider_stats().is_empty());
+    }
+
     #[test]
     fn test_collector() {
         let collector = MetricsCollector::new();
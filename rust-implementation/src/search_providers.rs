@@ -0,0 +1,1044 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// PROVEDORES DE BUSCA - SearXNG, Brave, Serper, SerpAPI e DuckDuckGo
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Cada provedor implementa `SearchBackend` em duas partes puras:
+// - `request`: mapeia `SerpQuery` (q, tbs, location) para a requisição HTTP
+// - `parse`: normaliza a resposta (JSON ou HTML) em `ProviderHit`
+//
+// `WebSearchClient` envia a requisição e converte os hits em
+// `BoostedSearchSnippet` com os mesmos boosts do Jina (domínio e path).
+// Leitura de URLs, rerank e comparação ficam com o cliente de leitura
+// (normalmente o `JinaClient`), então só a busca muda de provedor.
+//
+// Mapeamento de `tbs` (formato Google `qdr:h|d|w|m|y`):
+// - SearXNG: `time_range` (day/week/month/year)
+// - Brave: `freshness` (pd/pw/pm/py)
+// - Serper e SerpAPI: repassado como está
+// - DuckDuckGo: `df` (d/w/m/y)
+//
+// `location` vira país/idioma via tabela (SearXNG `language`, Brave
+// `country`, DuckDuckGo `kl`); Serper e SerpAPI aceitam o texto original.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::HashSet;
+use std::sync::atomic::AtomicU8;
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::config::SearchProvider;
+use crate::credibility::CredibilityConfig;
+use crate::search::{
+    extract_hostname, path_boost, ComparativeReadResult, SearchClient, SearchError, SearchResult,
+    UrlContent,
+};
+use crate::types::{BoostedSearchSnippet, SerpQuery, Url};
+
+/// Resultados pedidos a cada provedor
+pub const RESULTS_PER_QUERY: usize = 10;
+
+/// Timeout de uma busca
+const SEARCH_TIMEOUT_SECS: u64 = 10;
+
+/// User-Agent para provedores sem API (DuckDuckGo HTML)
+const BROWSER_USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36";
+
+/// Erros de configuração de provedores
+#[derive(Debug, thiserror::Error)]
+pub enum SearchProviderError {
+    /// Variável de ambiente obrigatória ausente
+    #[error("Search provider {provider} requires {variable}")]
+    MissingSetting {
+        /// Provedor escolhido
+        provider: SearchProvider,
+        /// Variável que faltou
+        variable: &'static str,
+    },
+
+    /// Nome de provedor desconhecido (ex.: `search_provider` da requisição)
+    #[error("Unknown search provider: {0}")]
+    UnknownProvider(String),
+}
+
+/// Endpoints e chaves dos provedores (variáveis de ambiente)
+#[derive(Debug, Clone, Default)]
+pub struct WebSearchSettings {
+    /// `SEARXNG_URL`: URL base da instância SearXNG
+    pub searxng_url: Option<String>,
+    /// `BRAVE_API_KEY`
+    pub brave_api_key: Option<String>,
+    /// `SERPER_API_KEY`
+    pub serper_api_key: Option<String>,
+    /// `SERPAPI_API_KEY`
+    pub serpapi_api_key: Option<String>,
+}
+
+impl WebSearchSettings {
+    /// Lê `SEARXNG_URL`, `BRAVE_API_KEY`, `SERPER_API_KEY` e `SERPAPI_API_KEY`
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self {
+            searxng_url: var("SEARXNG_URL"),
+            brave_api_key: var("BRAVE_API_KEY"),
+            serper_api_key: var("SERPER_API_KEY"),
+            serpapi_api_key: var("SERPAPI_API_KEY"),
+        }
+    }
+
+    /// Backend de busca do provedor (`None` para Jina, que já é um `SearchClient`)
+    pub fn backend(
+        &self,
+        provider: SearchProvider,
+    ) -> Result<Option<Box<dyn SearchBackend>>, SearchProviderError> {
+        let require = |value: &Option<String>, variable: &'static str| {
+            value
+                .clone()
+                .ok_or(SearchProviderError::MissingSetting { provider, variable })
+        };
+
+        Ok(match provider {
+            SearchProvider::Jina => None,
+            SearchProvider::Searxng => Some(Box::new(SearxngBackend::new(require(
+                &self.searxng_url,
+                "SEARXNG_URL",
+            )?))),
+            SearchProvider::Brave => Some(Box::new(BraveBackend::new(require(
+                &self.brave_api_key,
+                "BRAVE_API_KEY",
+            )?))),
+            SearchProvider::Serper => Some(Box::new(SerperBackend::new(require(
+                &self.serper_api_key,
+                "SERPER_API_KEY",
+            )?))),
+            SearchProvider::SerpApi => Some(Box::new(SerpApiBackend::new(require(
+                &self.serpapi_api_key,
+                "SERPAPI_API_KEY",
+            )?))),
+            SearchProvider::DuckDuckGo => Some(Box::new(DuckDuckGoBackend::new())),
+        })
+    }
+}
+
+/// Cria o cliente de busca do provedor
+///
+/// `reader` atende leitura, rerank e comparação; com `SearchProvider::Jina`
+/// ele próprio é devolvido.
+pub fn search_client_for(
+    provider: SearchProvider,
+    settings: &WebSearchSettings,
+    reader: Arc<dyn SearchClient>,
+    credibility: CredibilityConfig,
+) -> Result<Arc<dyn SearchClient>, SearchProviderError> {
+    match settings.backend(provider)? {
+        None => Ok(reader),
+        Some(backend) => {
+            log::info!(
+                "🔧 Busca: {} (leitura e rerank pelo cliente Jina/Rust)",
+                provider
+            );
+            Ok(Arc::new(
+                WebSearchClient::new(backend, reader).with_credibility(credibility),
+            ))
+        }
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// MAPEAMENTO DE FILTROS
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Janela de tempo de um `tbs` no formato Google (`qdr:h`, `qdr:d`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeRange {
+    /// Última hora
+    Hour,
+    /// Último dia
+    Day,
+    /// Última semana
+    Week,
+    /// Último mês
+    Month,
+    /// Último ano
+    Year,
+}
+
+impl TimeRange {
+    /// Extrai a janela de um `tbs` (ignora outros filtros, ex.: `sbd:1`)
+    pub fn from_tbs(tbs: &str) -> Option<Self> {
+        tbs.split(',').find_map(|part| match part.trim() {
+            "qdr:h" => Some(Self::Hour),
+            "qdr:d" => Some(Self::Day),
+            "qdr:w" => Some(Self::Week),
+            "qdr:m" => Some(Self::Month),
+            "qdr:y" => Some(Self::Year),
+            _ => None,
+        })
+    }
+}
+
+fn query_time_range(query: &SerpQuery) -> Option<TimeRange> {
+    query.tbs.as_deref().and_then(TimeRange::from_tbs)
+}
+
+/// País de uma `location`: código ISO, idioma e região do DuckDuckGo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Country {
+    /// Código ISO 3166 (ex.: "BR")
+    pub code: &'static str,
+    /// Idioma principal (ex.: "pt")
+    pub language: &'static str,
+    /// Região do DuckDuckGo (ex.: "br-pt")
+    pub ddg_region: &'static str,
+}
+
+/// Nomes (e cidades frequentes) → (código ISO, idioma, região DuckDuckGo)
+const COUNTRIES: &[(&[&str], &str, &str, &str)] = &[
+    (
+        &[
+            "brazil",
+            "brasil",
+            "br",
+            "são paulo",
+            "sao paulo",
+            "rio de janeiro",
+        ],
+        "BR",
+        "pt",
+        "br-pt",
+    ),
+    (&["portugal", "pt", "lisbon", "lisboa"], "PT", "pt", "pt-pt"),
+    (
+        &["united states", "usa", "us", "new york", "san francisco"],
+        "US",
+        "en",
+        "us-en",
+    ),
+    (
+        &["united kingdom", "uk", "gb", "england", "london"],
+        "GB",
+        "en",
+        "uk-en",
+    ),
+    (&["canada", "ca"], "CA", "en", "ca-en"),
+    (&["mexico", "méxico", "mx"], "MX", "es", "mx-es"),
+    (
+        &["spain", "españa", "espanha", "es", "madrid"],
+        "ES",
+        "es",
+        "es-es",
+    ),
+    (&["argentina", "ar"], "AR", "es", "ar-es"),
+    (&["france", "frança", "fr", "paris"], "FR", "fr", "fr-fr"),
+    (
+        &["germany", "deutschland", "alemanha", "de", "berlin"],
+        "DE",
+        "de",
+        "de-de",
+    ),
+    (
+        &["italy", "italia", "itália", "it", "rome", "roma"],
+        "IT",
+        "it",
+        "it-it",
+    ),
+    (&["japan", "japão", "jp", "tokyo"], "JP", "ja", "jp-jp"),
+    (&["china", "cn", "beijing"], "CN", "zh", "cn-zh"),
+    (&["india", "índia", "in"], "IN", "en", "in-en"),
+];
+
+impl Country {
+    /// País de uma `location` livre ("Italy", "São Paulo, Brazil", "BR")
+    ///
+    /// Tenta o texto inteiro e depois cada parte separada por vírgula, da
+    /// última para a primeira.
+    pub fn from_location(location: &str) -> Option<Self> {
+        let lower = location.to_lowercase();
+        std::iter::once(lower.trim())
+            .chain(lower.rsplit(',').map(str::trim))
+            .find_map(|name| {
+                COUNTRIES
+                    .iter()
+                    .find(|(names, ..)| names.contains(&name))
+                    .map(|&(_, code, language, ddg_region)| Country {
+                        code,
+                        language,
+                        ddg_region,
+                    })
+            })
+    }
+}
+
+fn query_country(query: &SerpQuery) -> Option<Country> {
+    query.location.as_deref().and_then(Country::from_location)
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// BACKENDS
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Resultado normalizado de um provedor
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHit {
+    /// Título (texto puro)
+    pub title: String,
+    /// URL de destino
+    pub url: String,
+    /// Snippet (texto puro)
+    pub snippet: String,
+}
+
+/// Resposta normalizada de um provedor
+#[derive(Debug, Clone, Default)]
+pub struct ProviderResponse {
+    /// Resultados, na ordem do provedor
+    pub hits: Vec<ProviderHit>,
+    /// Total estimado, quando o provedor informa
+    pub total_results: Option<u64>,
+}
+
+/// Mapeamento de requisição e resposta de um provedor de busca
+pub trait SearchBackend: Send + Sync {
+    /// Provedor implementado
+    fn provider(&self) -> SearchProvider;
+
+    /// Monta a requisição HTTP para a query
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder;
+
+    /// Normaliza o corpo da resposta
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError>;
+}
+
+fn parse_json<'a, T: Deserialize<'a>>(
+    provider: SearchProvider,
+    body: &'a str,
+) -> Result<T, SearchError> {
+    serde_json::from_str(body)
+        .map_err(|e| SearchError::ParseError(format!("{} response: {}", provider, e)))
+}
+
+/// Texto puro de um trecho HTML (tags removidas, entidades decodificadas)
+fn inline_text(html: &str) -> String {
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+    let tags = TAGS.get_or_init(|| Regex::new(r"<[^>]*>").expect("regex válida"));
+    let text = tags
+        .replace_all(html, "")
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn hit(title: &str, url: &str, snippet: &str) -> ProviderHit {
+    ProviderHit {
+        title: inline_text(title),
+        url: url.trim().to_string(),
+        snippet: inline_text(snippet),
+    }
+}
+
+/// SearXNG (API JSON, `format=json` precisa estar habilitado na instância)
+#[derive(Debug, Clone)]
+pub struct SearxngBackend {
+    base_url: String,
+}
+
+impl SearxngBackend {
+    /// Cria o backend para a instância em `base_url`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearxngResponse {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+    #[serde(default)]
+    number_of_results: f64,
+}
+
+#[derive(Deserialize)]
+struct SearxngResult {
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    content: String,
+}
+
+impl SearchBackend for SearxngBackend {
+    fn provider(&self) -> SearchProvider {
+        SearchProvider::Searxng
+    }
+
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder {
+        let mut params = vec![("q", query.q.clone()), ("format", "json".to_string())];
+        if let Some(range) = query_time_range(query) {
+            let value = match range {
+                TimeRange::Hour | TimeRange::Day => "day",
+                TimeRange::Week => "week",
+                TimeRange::Month => "month",
+                TimeRange::Year => "year",
+            };
+            params.push(("time_range", value.to_string()));
+        }
+        if let Some(country) = query_country(query) {
+            params.push(("language", format!("{}-{}", country.language, country.code)));
+        }
+        http.get(format!("{}/search", self.base_url)).query(&params)
+    }
+
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError> {
+        let response: SearxngResponse = parse_json(self.provider(), body)?;
+        Ok(ProviderResponse {
+            hits: response
+                .results
+                .iter()
+                .map(|r| hit(&r.title, &r.url, &r.content))
+                .collect(),
+            // SearXNG costuma devolver 0 quando os motores não informam o total
+            total_results: Some(response.number_of_results as u64).filter(|n| *n > 0),
+        })
+    }
+}
+
+/// Brave Search API
+#[derive(Debug, Clone)]
+pub struct BraveBackend {
+    api_key: String,
+    endpoint: String,
+}
+
+impl BraveBackend {
+    /// Cria o backend com a chave `X-Subscription-Token`
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            endpoint: "https://api.search.brave.com/res/v1/web/search".into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BraveResponse {
+    #[serde(default)]
+    web: Option<BraveWeb>,
+}
+
+#[derive(Deserialize)]
+struct BraveWeb {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+#[derive(Deserialize)]
+struct BraveResult {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl SearchBackend for BraveBackend {
+    fn provider(&self) -> SearchProvider {
+        SearchProvider::Brave
+    }
+
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder {
+        let mut params = vec![
+            ("q", query.q.clone()),
+            ("count", RESULTS_PER_QUERY.to_string()),
+        ];
+        if let Some(range) = query_time_range(query) {
+            let value = match range {
+                TimeRange::Hour | TimeRange::Day => "pd",
+                TimeRange::Week => "pw",
+                TimeRange::Month => "pm",
+                TimeRange::Year => "py",
+            };
+            params.push(("freshness", value.to_string()));
+        }
+        if let Some(country) = query_country(query) {
+            params.push(("country", country.code.to_string()));
+        }
+        http.get(&self.endpoint)
+            .query(&params)
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", &self.api_key)
+    }
+
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError> {
+        let response: BraveResponse = parse_json(self.provider(), body)?;
+        Ok(ProviderResponse {
+            hits: response
+                .web
+                .map(|web| web.results)
+                .unwrap_or_default()
+                .iter()
+                .map(|r| hit(&r.title, &r.url, &r.description))
+                .collect(),
+            total_results: None,
+        })
+    }
+}
+
+/// Resultado orgânico no formato Google (Serper e SerpAPI)
+#[derive(Deserialize)]
+struct OrganicResult {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    snippet: String,
+}
+
+/// Serper.dev (Google via POST JSON)
+#[derive(Debug, Clone)]
+pub struct SerperBackend {
+    api_key: String,
+    endpoint: String,
+}
+
+impl SerperBackend {
+    /// Cria o backend com a chave `X-API-KEY`
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            endpoint: "https://google.serper.dev/search".into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SerperResponse {
+    #[serde(default)]
+    organic: Vec<OrganicResult>,
+}
+
+impl SearchBackend for SerperBackend {
+    fn provider(&self) -> SearchProvider {
+        SearchProvider::Serper
+    }
+
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder {
+        let mut body = serde_json::json!({ "q": query.q, "num": RESULTS_PER_QUERY });
+        if let Some(tbs) = &query.tbs {
+            body["tbs"] = tbs.clone().into();
+        }
+        if let Some(location) = &query.location {
+            body["location"] = location.clone().into();
+        }
+        http.post(&self.endpoint)
+            .header("X-API-KEY", &self.api_key)
+            .json(&body)
+    }
+
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError> {
+        let response: SerperResponse = parse_json(self.provider(), body)?;
+        Ok(ProviderResponse {
+            hits: response
+                .organic
+                .iter()
+                .map(|r| hit(&r.title, &r.link, &r.snippet))
+                .collect(),
+            total_results: None,
+        })
+    }
+}
+
+/// SerpAPI (engine Google)
+#[derive(Debug, Clone)]
+pub struct SerpApiBackend {
+    api_key: String,
+    endpoint: String,
+}
+
+impl SerpApiBackend {
+    /// Cria o backend com a chave `api_key`
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            endpoint: "https://serpapi.com/search.json".into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SerpApiResponse {
+    #[serde(default)]
+    organic_results: Vec<OrganicResult>,
+    #[serde(default)]
+    search_information: Option<SerpApiInformation>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SerpApiInformation {
+    #[serde(default)]
+    total_results: Option<u64>,
+}
+
+impl SearchBackend for SerpApiBackend {
+    fn provider(&self) -> SearchProvider {
+        SearchProvider::SerpApi
+    }
+
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder {
+        let mut params = vec![
+            ("engine", "google".to_string()),
+            ("q", query.q.clone()),
+            ("num", RESULTS_PER_QUERY.to_string()),
+            ("api_key", self.api_key.clone()),
+        ];
+        if let Some(tbs) = &query.tbs {
+            params.push(("tbs", tbs.clone()));
+        }
+        if let Some(location) = &query.location {
+            params.push(("location", location.clone()));
+        }
+        http.get(&self.endpoint).query(&params)
+    }
+
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError> {
+        let response: SerpApiResponse = parse_json(self.provider(), body)?;
+        if let Some(error) = response.error {
+            return Err(SearchError::ApiError(format!("SerpAPI: {}", error)));
+        }
+        Ok(ProviderResponse {
+            hits: response
+                .organic_results
+                .iter()
+                .map(|r| hit(&r.title, &r.link, &r.snippet))
+                .collect(),
+            total_results: response.search_information.and_then(|i| i.total_results),
+        })
+    }
+}
+
+/// DuckDuckGo (versão HTML, sem chave)
+#[derive(Debug, Clone)]
+pub struct DuckDuckGoBackend {
+    endpoint: String,
+}
+
+impl DuckDuckGoBackend {
+    /// Cria o backend para `html.duckduckgo.com`
+    pub fn new() -> Self {
+        Self {
+            endpoint: "https://html.duckduckgo.com/html/".into(),
+        }
+    }
+}
+
+impl Default for DuckDuckGoBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// URL de destino de um link do DuckDuckGo (`//duckduckgo.com/l/?uddg=...`)
+///
+/// Links sem `uddg` (anúncios em `y.js`) retornam `None`.
+fn ddg_target(href: &str) -> Option<String> {
+    let href = href.replace("&amp;", "&");
+    let absolute = if href.starts_with("//") {
+        format!("https:{}", href)
+    } else {
+        href
+    };
+    let parsed = url::Url::parse(&absolute).ok()?;
+    if !parsed.host_str()?.ends_with("duckduckgo.com") {
+        return Some(absolute);
+    }
+    parsed
+        .query_pairs()
+        .find(|(key, _)| key == "uddg")
+        .map(|(_, value)| value.into_owned())
+}
+
+impl SearchBackend for DuckDuckGoBackend {
+    fn provider(&self) -> SearchProvider {
+        SearchProvider::DuckDuckGo
+    }
+
+    fn request(&self, http: &reqwest::Client, query: &SerpQuery) -> reqwest::RequestBuilder {
+        let mut params = vec![("q", query.q.clone())];
+        if let Some(range) = query_time_range(query) {
+            let value = match range {
+                TimeRange::Hour | TimeRange::Day => "d",
+                TimeRange::Week => "w",
+                TimeRange::Month => "m",
+                TimeRange::Year => "y",
+            };
+            params.push(("df", value.to_string()));
+        }
+        if let Some(country) = query_country(query) {
+            params.push(("kl", country.ddg_region.to_string()));
+        }
+        http.get(&self.endpoint)
+            .query(&params)
+            .header("User-Agent", BROWSER_USER_AGENT)
+    }
+
+    fn parse(&self, body: &str) -> Result<ProviderResponse, SearchError> {
+        let title_re =
+            Regex::new(r#"(?s)<a([^>]*class="result__a"[^>]*)>(.*?)</a>"#).expect("regex válida");
+        let snippet_re = Regex::new(r#"(?s)<a[^>]*class="result__snippet"[^>]*>(.*?)</a>"#)
+            .expect("regex válida");
+        let href_re = Regex::new(r#"href="([^"]*)""#).expect("regex válida");
+
+        let titles: Vec<_> = title_re.captures_iter(body).collect();
+        let mut hits = Vec::new();
+
+        for (i, caps) in titles.iter().enumerate() {
+            let whole = caps.get(0).expect("match completo");
+            let Some(url) = href_re.captures(&caps[1]).and_then(|h| ddg_target(&h[1])) else {
+                continue;
+            };
+
+            // Snippet entre este título e o próximo
+            let end = titles
+                .get(i + 1)
+                .and_then(|next| next.get(0))
+                .map(|m| m.start())
+                .unwrap_or(body.len());
+            let snippet = snippet_re
+                .captures(&body[whole.end()..end])
+                .map(|s| s[1].to_string())
+                .unwrap_or_default();
+
+            hits.push(hit(&caps[2], &url, &snippet));
+        }
+
+        if hits.is_empty() && body.contains("anomaly-modal") {
+            return Err(SearchError::RateLimitError);
+        }
+
+        Ok(ProviderResponse {
+            hits,
+            total_results: None,
+        })
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CLIENTE
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// `SearchClient` que busca em um provedor e delega o resto a `reader`
+pub struct WebSearchClient {
+    backend: Box<dyn SearchBackend>,
+    reader: Arc<dyn SearchClient>,
+    http: reqwest::Client,
+    credibility: CredibilityConfig,
+}
+
+impl WebSearchClient {
+    /// Cria o cliente com um backend de busca e um cliente de leitura
+    pub fn new(backend: Box<dyn SearchBackend>, reader: Arc<dyn SearchClient>) -> Self {
+        Self {
+            backend,
+            reader,
+            http: reqwest::Client::new(),
+            credibility: CredibilityConfig::builtin(),
+        }
+    }
+
+    /// Define as camadas de domínio usadas no `hostname_boost`
+    pub fn with_credibility(mut self, credibility: CredibilityConfig) -> Self {
+        self.credibility = credibility;
+        self
+    }
+
+    /// Provedor de busca
+    pub fn provider(&self) -> SearchProvider {
+        self.backend.provider()
+    }
+
+    /// Converte a resposta do provedor em `SearchResult`
+    ///
+    /// Descarta URLs não-HTTP e repetidas; limita a `RESULTS_PER_QUERY`.
+    pub fn normalize(&self, response: ProviderResponse) -> SearchResult {
+        let mut seen = HashSet::new();
+        let mut urls = Vec::new();
+        let mut snippets = Vec::new();
+
+        for hit in response.hits {
+            if !hit.url.starts_with("http") || !seen.insert(hit.url.clone()) {
+                continue;
+            }
+            let hostname_boost = extract_hostname(&hit.url)
+                .map(|h| self.credibility.host_weight(&h))
+                .unwrap_or(1.0);
+
+            urls.push(BoostedSearchSnippet {
+                path_boost: path_boost(&hit.url),
+                hostname_boost,
+                url: hit.url,
+                title: hit.title,
+                description: hit.snippet.clone(),
+                merged: hit.snippet.clone(),
+                ..Default::default()
+            });
+            snippets.push(hit.snippet);
+
+            if urls.len() >= RESULTS_PER_QUERY {
+                break;
+            }
+        }
+
+        SearchResult {
+            total_results: response.total_results.unwrap_or(urls.len() as u64),
+            urls,
+            snippets,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchClient for WebSearchClient {
+    async fn search(&self, query: &SerpQuery) -> Result<SearchResult, SearchError> {
+        let provider = self.provider();
+        log::info!("🔍 {} Search: q={}", provider, query.q);
+
+        let response = self
+            .backend
+            .request(&self.http, query)
+            .timeout(std::time::Duration::from_secs(SEARCH_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|e| {
+                log::error!("❌ {} network error: {}", provider, e);
+                SearchError::NetworkError(e.to_string())
+            })?;
+
+        if response.status() == 429 {
+            log::warn!("⚠️ {} rate limit exceeded", provider);
+            return Err(SearchError::RateLimitError);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            log::error!("❌ {} API error {}: {}", provider, status, error_text);
+            return Err(SearchError::ApiError(format!(
+                "{} search error {}: {}",
+                provider, status, error_text
+            )));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| SearchError::NetworkError(e.to_string()))?;
+        let result = self.normalize(self.backend.parse(&body)?);
+
+        log::info!(
+            "✅ {} Search: query='{}' | {} resultados",
+            provider,
+            query.q,
+            result.urls.len()
+        );
+        Ok(result)
+    }
+
+    async fn search_batch(&self, queries: &[SerpQuery]) -> Vec<Result<SearchResult, SearchError>> {
+        futures::future::join_all(queries.iter().map(|q| self.search(q))).await
+    }
+
+    async fn read_url(&self, url: &Url) -> Result<UrlContent, SearchError> {
+        self.reader.read_url(url).await
+    }
+
+    async fn read_urls_batch(&self, urls: &[Url]) -> Vec<Result<UrlContent, SearchError>> {
+        self.reader.read_urls_batch(urls).await
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        urls: &[BoostedSearchSnippet],
+    ) -> Vec<BoostedSearchSnippet> {
+        self.reader.rerank(query, urls).await
+    }
+
+    async fn read_url_comparative(&self, url: &Url) -> ComparativeReadResult {
+        self.reader.read_url_comparative(url).await
+    }
+
+    async fn read_urls_comparative_batch(&self, urls: &[Url]) -> Vec<ComparativeReadResult> {
+        self.reader.read_urls_comparative_batch(urls).await
+    }
+
+    async fn read_url_with_fallback_progress(
+        &self,
+        url: &Url,
+        progress: Arc<AtomicU8>,
+    ) -> (Result<UrlContent, SearchError>, &'static str, u8, usize) {
+        self.reader
+            .read_url_with_fallback_progress(url, progress)
+            .await
+    }
+
+    fn get_credits(&self) -> u64 {
+        self.reader.get_credits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::MockSearchClient;
+
+    fn backends() -> Vec<(Box<dyn SearchBackend>, &'static str)> {
+        vec![
+            (
+                Box::new(SearxngBackend::new("https://searx.example.org/")),
+                include_str!("../tests/fixtures/search/searxng.json"),
+            ),
+            (
+                Box::new(BraveBackend::new("brave-key")),
+                include_str!("../tests/fixtures/search/brave.json"),
+            ),
+            (
+                Box::new(SerperBackend::new("serper-key")),
+                include_str!("../tests/fixtures/search/serper.json"),
+            ),
+            (
+                Box::new(SerpApiBackend::new("serpapi-key")),
+                include_str!("../tests/fixtures/search/serpapi.json"),
+            ),
+            (
+                Box::new(DuckDuckGoBackend::new()),
+                include_str!("../tests/fixtures/search/duckduckgo.html"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_backends_parse_recorded_fixtures() {
+        for (backend, fixture) in backends() {
+            let provider = backend.provider();
+            let client = WebSearchClient::new(backend, Arc::new(MockSearchClient::new()));
+            let response = client.backend.parse(fixture).unwrap();
+            let result = client.normalize(response);
+
+            assert_eq!(result.urls.len(), 2, "{}", provider);
+            let first = &result.urls[0];
+            assert_eq!(
+                first.url, "https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html",
+                "{}",
+                provider
+            );
+            assert_eq!(
+                first.title,
+                "What is Ownership? - The Rust Programming Language"
+            );
+            assert_eq!(
+                first.description,
+                "Ownership is a set of rules that govern how a Rust program manages memory."
+            );
+            // Mesmos boosts do Jina: doc.rust-lang.org é domínio confiável
+            assert!(first.hostname_boost > 1.0, "{}", provider);
+            assert_eq!(result.snippets.len(), 2);
+        }
+
+        let serpapi = SerpApiBackend::new("k");
+        let parsed = serpapi
+            .parse(include_str!("../tests/fixtures/search/serpapi.json"))
+            .unwrap();
+        assert_eq!(parsed.total_results, Some(48_200_000));
+        assert!(serpapi.parse(r#"{"error": "Invalid API key."}"#).is_err());
+
+        let ddg = DuckDuckGoBackend::new()
+            .parse(include_str!("../tests/fixtures/search/duckduckgo.html"))
+            .unwrap();
+        assert_eq!(
+            ddg.hits[1].title,
+            "Ownership & borrowing explained : r/rust"
+        );
+    }
+
+    #[test]
+    fn test_backends_map_tbs_and_location() {
+        let http = reqwest::Client::new();
+        let query = SerpQuery {
+            q: "pizza napoletana".into(),
+            tbs: Some("qdr:w".into()),
+            location: Some("Naples, Italy".into()),
+        };
+        let params = |backend: &dyn SearchBackend| {
+            let request = backend.request(&http, &query).build().unwrap();
+            let pairs: Vec<(String, String)> = request
+                .url()
+                .query_pairs()
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect();
+            (request, pairs)
+        };
+        let has = |pairs: &[(String, String)], key: &str, value: &str| {
+            pairs.iter().any(|(k, v)| k == key && v == value)
+        };
+
+        let (_, pairs) = params(&SearxngBackend::new("https://searx.example.org"));
+        assert!(has(&pairs, "time_range", "week"));
+        assert!(has(&pairs, "language", "it-IT"));
+        assert!(has(&pairs, "format", "json"));
+
+        let (request, pairs) = params(&BraveBackend::new("brave-key"));
+        assert!(has(&pairs, "freshness", "pw"));
+        assert!(has(&pairs, "country", "IT"));
+        assert_eq!(request.headers()["X-Subscription-Token"], "brave-key");
+
+        let (request, _) = params(&SerperBackend::new("serper-key"));
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(body["tbs"], "qdr:w");
+        assert_eq!(body["location"], "Naples, Italy");
+        assert_eq!(request.headers()["X-API-KEY"], "serper-key");
+
+        let (_, pairs) = params(&SerpApiBackend::new("serpapi-key"));
+        assert!(has(&pairs, "tbs", "qdr:w"));
+        assert!(has(&pairs, "location", "Naples, Italy"));
+        assert!(has(&pairs, "api_key", "serpapi-key"));
+
+        let (_, pairs) = params(&DuckDuckGoBackend::new());
+        assert!(has(&pairs, "df", "w"));
+        assert!(has(&pairs, "kl", "it-it"));
+
+        // Sem filtros, nenhum parâmetro extra
+        let plain = SerpQuery {
+            q: "rust".into(),
+            ..Default::default()
+        };
+        let request = BraveBackend::new("k")
+            .request(&http, &plain)
+            .build()
+            .unwrap();
+        assert_eq!(request.url().query_pairs().count(), 2);
+
+        assert!(WebSearchSettings::default()
+            .backend(SearchProvider::Brave)
+            .is_err());
+        assert!(WebSearchSettings::default()
+            .backend(SearchProvider::DuckDuckGo)
+            .unwrap()
+            .is_some());
+    }
+}
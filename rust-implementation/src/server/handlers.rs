@@ -1,5 +1,5 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-// ENDPOINT HANDLERS - 4 endpoints compatíveis com OpenAI API
+// ENDPOINT HANDLERS - endpoints compatíveis com OpenAI API + checkpoints
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use axum::{
@@ -10,18 +10,28 @@ use axum::{
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use super::sse;
+use super::sse::{self, ConversationTarget};
 use super::types::*;
 use super::AppState;
-use crate::agent::DeepResearchAgent;
-use crate::llm::OpenAiClient;
-use crate::search::JinaClient;
+use crate::agent::checkpoint::{find_run_checkpoint, list_interrupted_runs, CheckpointError};
+use crate::agent::{
+    CancellationToken, DeepResearchAgent, LlmClientFactory, ReportResearch, ResearchRun,
+    TeamResearch,
+};
+use crate::config::SearchProvider;
+use crate::llm::{llm_client_for, LlmClient, LlmError};
+use crate::meta_search::meta_search_client_for;
+use crate::search::{JinaClient, SearchClient};
+use crate::search_providers::SearchProviderError;
 
 // ── GET /health ─────────────────────────────────
 
 /// Health check endpoint
-pub async fn health() -> Json<serde_json::Value> {
-    Json(serde_json::json!({ "status": "ok" }))
+pub async fn health(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "ok",
+        "search_providers": state.search_metrics.metrics().provider_stats(),
+    }))
 }
 
 // ── GET /v1/models ──────────────────────────────
@@ -87,6 +97,52 @@ pub async fn chat_completions(
         );
     }
 
+    // Resposta estruturada (response_format json_object / json_schema)
+    let response_schema = match body.response_format.as_ref().map(ResponseFormat::schema) {
+        Some(Ok(schema)) => schema,
+        Some(Err(e)) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid response_format: {}", e),
+            )
+        }
+        None => None,
+    };
+    // Um cliente LLM é criado por agente: limitar antes de criá-los
+    let team_size = match body.team_size() {
+        Ok(team_size) => team_size,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+    };
+
+    // Equipe e relatório não aplicam as etapas pós-resposta do agente único
+    if body.model == REPORT_MODEL_ID || team_size.is_some() {
+        if let Some(option) = single_agent_option(&body) {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("{} is only supported by single-agent research.", option),
+            );
+        }
+    }
+
+    if response_schema.is_some() && body.model == REPORT_MODEL_ID {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "response_format is not supported by the report model.",
+        );
+    }
+
+    // Conversa: histórico enviado nas mensagens ou guardado no servidor
+    let conversation_id = body
+        .conversation_id
+        .clone()
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| format!("conv_{}", uuid::Uuid::new_v4().simple()));
+    let mut conversation = state.conversations.get(&conversation_id).unwrap_or_default();
+    let request_turns = extract_chat_turns(&body.messages[..body.messages.len() - 1]);
+    if !request_turns.is_empty() {
+        conversation.turns = request_turns;
+    }
+
     // Calcular token budget
     let token_budget = resolve_token_budget(
         body.reasoning_effort.as_deref(),
@@ -99,61 +155,283 @@ pub async fn chat_completions(
     let model = body.model.clone();
 
     // Criar clientes (mesmo padrão de spawn_research_task no main.rs)
-    let llm_client: Arc<dyn crate::llm::LlmClient> =
-        Arc::new(OpenAiClient::from_config(state.openai_key.clone(), &state.llm_config));
-    let search_client: Arc<dyn crate::search::SearchClient> =
-        Arc::new(JinaClient::with_preference(
-            state.jina_key.clone(),
-            state.runtime_config.webreader,
-        ));
-
-    if body.stream {
+    let llm_client = match llm_client(&state) {
+        Ok(client) => client,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string()),
+    };
+    let search_client = match search_client(&state, body.search_provider.as_deref()) {
+        Ok(client) => client,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    // Prazo de parede (requisição > AGENT_TIME_BUDGET_SECS)
+    let time_budget = body
+        .time_budget_secs
+        .or(state.agent_config.time_budget_secs)
+        .map(std::time::Duration::from_secs);
+    let time_beast_mode_fraction = state.agent_config.time_beast_mode_fraction;
+
+    // Budget de custo (requisição > AGENT_COST_BUDGET_USD)
+    let cost_budget = body
+        .cost_budget_usd
+        .or(state.agent_config.cost_budget_usd)
+        .filter(|budget| *budget > 0.0);
+
+    // Modelo de relatório: esboço, uma pesquisa por seção e documento final
+    // team_size > 1: planeja subproblemas e roda uma equipe de agentes
+    let research: ResearchRun = match team_size {
+        _ if body.model == REPORT_MODEL_ID => {
+            log::info!("[report] for: {}", question);
+            let mut report = ReportResearch::new(llm_client, search_client, Some(token_budget))
+                .with_time_beast_mode_fraction(time_beast_mode_fraction)
+                .with_pricing(&state.pricing)
+                .with_credibility(state.credibility.clone())
+                .with_approval_policy(state.agent_config.approval_policy.clone());
+            if let Some(budget) = time_budget {
+                report = report.with_time_budget(budget);
+            }
+            if let Some(budget) = cost_budget {
+                report = report.with_cost_budget(budget);
+            }
+            report.into()
+        }
+        Some(team_size) if team_size > 1 => {
+            log::info!("[team] team_size={} for: {}", team_size, question);
+            // Um cliente isolado por sub-agente, criado antes de responder:
+            // contadores de tokens separados para a contabilidade por agente
+            let agent_clients = match (0..team_size)
+                .map(|_| llm_client_for(&state.llm_config, &state.llm_keys))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(clients) => std::sync::Mutex::new(clients),
+                Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string()),
+            };
+            let lead_client = llm_client.clone();
+            let llm_factory: LlmClientFactory = Arc::new(move || {
+                agent_clients
+                    .lock()
+                    .ok()
+                    .and_then(|mut clients| clients.pop())
+                    .unwrap_or_else(|| {
+                        log::warn!("⚠️ [team] No isolated LLM client left, sharing the lead client");
+                        lead_client.clone()
+                    })
+            });
+            let mut team =
+                TeamResearch::new(llm_client, search_client, team_size, Some(token_budget))
+                    .with_llm_factory(llm_factory)
+                    .with_time_beast_mode_fraction(time_beast_mode_fraction)
+                    .with_pricing(&state.pricing)
+                    .with_credibility(state.credibility.clone())
+                    .with_approval_policy(state.agent_config.approval_policy.clone());
+            if let Some(budget) = time_budget {
+                team = team.with_time_budget(budget);
+            }
+            if let Some(budget) = cost_budget {
+                team = team.with_cost_budget(budget);
+            }
+            if let Some(schema) = response_schema {
+                team = team.with_response_schema(schema);
+            }
+            team.into()
+        }
+        _ => {
+            let finalize = body
+                .finalize_answer
+                .unwrap_or(state.agent_config.finalize_answer);
+            let mut agent = DeepResearchAgent::new(llm_client, search_client, Some(token_budget))
+                .with_answer_finalization(finalize)
+                .with_claim_verification(
+                    body.verify_claims
+                        .unwrap_or(state.agent_config.verify_claims),
+                )
+                .with_contradiction_detection(
+                    body.detect_contradictions
+                        .unwrap_or(state.agent_config.detect_contradictions),
+                )
+                .with_time_beast_mode_fraction(time_beast_mode_fraction)
+                .with_pricing(&state.pricing)
+                .with_credibility(state.credibility.clone())
+                .with_approval_policy(state.agent_config.approval_policy.clone());
+            if let Some(budget) = time_budget {
+                agent = agent.with_time_budget(budget);
+            }
+            if let Some(budget) = cost_budget {
+                agent = agent.with_cost_budget(budget);
+            }
+            if let Some(schema) = response_schema {
+                agent = agent.with_response_schema(schema);
+            }
+            if let Some(dir) = &state.agent_config.checkpoint_dir {
+                agent = agent.with_checkpoint_dir(dir);
+            }
+            if let Some(dir) = &state.agent_config.transcript_dir {
+                agent = agent.with_transcript_dir(dir);
+            }
+            agent.into()
+        }
+    };
+    let research = research.with_conversation(&conversation);
+
+    let conversation = ConversationTarget {
+        store: state.conversations.clone(),
+        id: conversation_id,
+    };
+    dispatch_research(
+        research,
+        question,
+        body.stream,
+        request_id,
+        created,
+        model,
+        Some(conversation),
+    )
+    .await
+}
+
+// ── GET /v1/runs ────────────────────────────────
+
+/// Lista execuções interrompidas (checkpoints não terminais)
+pub async fn list_runs(State(state): State<Arc<AppState>>) -> Json<RunList> {
+    let dir = checkpoint_dir(&state);
+    Json(RunList {
+        object: "list".into(),
+        data: list_interrupted_runs(&dir),
+    })
+}
+
+// ── POST /v1/runs/{run_id}/resume ───────────────
+
+/// Retoma uma execução interrompida a partir do checkpoint
+pub async fn resume_run(
+    State(state): State<Arc<AppState>>,
+    Path(run_id): Path<String>,
+    body: Option<Json<ResumeRunRequest>>,
+) -> Response {
+    let body = body.map(|Json(b)| b).unwrap_or_default();
+
+    // run_id vem da URL: só ids dentro do diretório de checkpoints
+    let path = match find_run_checkpoint(&checkpoint_dir(&state), &run_id) {
+        Ok(path) => path,
+        Err(e @ CheckpointError::InvalidRunId(_)) => {
+            return error_response(StatusCode::BAD_REQUEST, &e.to_string())
+        }
+        Err(e) => return error_response(StatusCode::NOT_FOUND, &e.to_string()),
+    };
+
+    let llm_client = match llm_client(&state) {
+        Ok(client) => client,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string()),
+    };
+    let search_client = match search_client(&state, None) {
+        Ok(client) => client,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, &e.to_string()),
+    };
+
+    // Sem canal de interação: ações sujeitas a aprovação são recusadas
+    let mut agent = match DeepResearchAgent::resume(&path, llm_client, search_client) {
+        Ok(agent) => agent
+            .with_pricing(&state.pricing)
+            .with_credibility(state.credibility.clone())
+            .with_approval_policy(state.agent_config.approval_policy.clone()),
+        Err(e) => return error_response(StatusCode::UNPROCESSABLE_ENTITY, &e.to_string()),
+    };
+    // Mesmo run_id: o transcript continua no arquivo da execução original
+    if let Some(dir) = &state.agent_config.transcript_dir {
+        agent = agent.with_transcript_dir(dir);
+    }
+    let question = agent.original_question().to_string();
+
+    let request_id = format!("req_{}", now_millis());
+    let created = now_secs();
+    let model = body.model.unwrap_or_else(|| "jina-deepsearch-v1".into());
+
+    log::info!("[resume] Resuming run {}: {}", agent.run_id(), question);
+    dispatch_research(agent.into(), question, body.stream, request_id, created, model, None).await
+}
+
+/// Cliente LLM do `LLM_PROVIDER` (OpenAI, Anthropic ou local)
+fn llm_client(state: &AppState) -> Result<Arc<dyn LlmClient>, LlmError> {
+    llm_client_for(&state.llm_config, &state.llm_keys)
+}
+
+/// Cliente de busca do(s) provedor(es) (requisição > `SEARCH_PROVIDER`)
+///
+/// Dois ou mais provedores ativam a meta-busca. Leitura, rerank e
+/// embeddings continuam no Jina/Rust local.
+fn search_client(
+    state: &AppState,
+    provider: Option<&str>,
+) -> Result<Arc<dyn SearchClient>, SearchProviderError> {
+    let providers = match provider {
+        Some(names) => SearchProvider::parse_list(names)
+            .ok_or_else(|| SearchProviderError::UnknownProvider(names.to_string()))?,
+        None => state.runtime_config.search_providers.clone(),
+    };
+    let reader: Arc<dyn SearchClient> = Arc::new(
+        JinaClient::with_preference(state.jina_key.clone(), state.runtime_config.webreader)
+            .with_credibility(state.credibility.config().clone()),
+    );
+    meta_search_client_for(
+        &providers,
+        &state.search_settings,
+        reader,
+        state.credibility.config().clone(),
+        state.search_metrics.clone(),
+    )
+}
+
+// ── Dispatch (SSE ou JSON) ──────────────────────
+
+async fn dispatch_research(
+    research: ResearchRun,
+    question: String,
+    stream: bool,
+    request_id: String,
+    created: i64,
+    model: String,
+    conversation: Option<ConversationTarget>,
+) -> Response {
+    if stream {
         // SSE streaming
         log::info!("[SSE] Starting streaming research: {}", question);
-        sse::handle_streaming(
-            llm_client,
-            search_client,
-            question,
-            token_budget,
-            request_id,
-            created,
-            model,
-        )
-        .await
+        sse::handle_streaming(research, question, request_id, created, model, conversation).await
     } else {
         // Resposta JSON completa
         log::info!("[JSON] Starting research: {}", question);
-        handle_non_streaming(
-            llm_client,
-            search_client,
-            question,
-            token_budget,
-            request_id,
-            created,
-            model,
-        )
-        .await
+        handle_non_streaming(research, question, request_id, created, model, conversation).await
     }
 }
 
 // ── Non-streaming handler ───────────────────────
 
 async fn handle_non_streaming(
-    llm_client: Arc<dyn crate::llm::LlmClient>,
-    search_client: Arc<dyn crate::search::SearchClient>,
+    research: ResearchRun,
     question: String,
-    token_budget: u64,
     request_id: String,
     created: i64,
     model: String,
+    conversation: Option<ConversationTarget>,
 ) -> Response {
-    let agent = DeepResearchAgent::new(llm_client, search_client, Some(token_budget));
+    // Se o cliente desconectar, o handler é descartado e o guard cancela a pesquisa
+    let cancel_token = CancellationToken::new();
+    let _cancel_guard = cancel_token.clone().drop_guard();
+    let research = research.with_cancellation_token(cancel_token);
+
+    let task = async move {
+        let result = research.run(question.clone()).await;
+        if let Some(conversation) = &conversation {
+            conversation.record(&question, &result);
+        }
+        (result, conversation.map(|c| c.id))
+    };
 
-    match tokio::spawn(async move { agent.run(question).await }).await {
-        Ok(result) => {
+    match tokio::spawn(task).await {
+        Ok((result, conversation_id)) => {
             let (content, content_type, finish_reason) = if result.success {
                 let answer = result.answer.unwrap_or_default();
-                (answer, "text", "stop")
+                let content_type = if result.structured.is_some() { "json" } else { "text" };
+                (answer, content_type, "stop")
             } else {
                 let err = result.error.unwrap_or_else(|| "Unknown error".into());
                 (format!("Error: {}", err), "error", "error")
@@ -183,10 +461,13 @@ async fn handle_non_streaming(
                     prompt_tokens: result.token_usage.prompt_tokens,
                     completion_tokens: result.token_usage.completion_tokens,
                     total_tokens: result.token_usage.total_tokens,
+                    cost_usd: Some(result.cost.total_usd),
                 },
                 visited_urls: Some(visited.clone()),
                 read_urls: None,
                 num_urls: Some(visited.len()),
+                structured_output: result.structured.as_ref().map(build_structured_output),
+                conversation_id,
             };
 
             Json(response).into_response()
@@ -218,6 +499,28 @@ fn error_response(status: StatusCode, message: &str) -> Response {
         .into_response()
 }
 
+/// Primeira opção ativada que só o agente único aplica
+fn single_agent_option(body: &ChatCompletionRequest) -> Option<&'static str> {
+    [
+        ("finalize_answer", body.finalize_answer),
+        ("verify_claims", body.verify_claims),
+        ("detect_contradictions", body.detect_contradictions),
+    ]
+    .into_iter()
+    .find(|(_, enabled)| *enabled == Some(true))
+    .map(|(name, _)| name)
+}
+
+fn checkpoint_dir(state: &AppState) -> std::path::PathBuf {
+    std::path::PathBuf::from(
+        state
+            .agent_config
+            .checkpoint_dir
+            .clone()
+            .unwrap_or_else(|| crate::agent::DEFAULT_CHECKPOINT_DIR.to_string()),
+    )
+}
+
 fn now_millis() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -231,3 +534,105 @@ fn now_secs() -> i64 {
         .unwrap_or_default()
         .as_secs() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::Service;
+
+    fn state_with_checkpoints(dir: &std::path::Path) -> Arc<AppState> {
+        Arc::new(AppState {
+            llm_config: Default::default(),
+            runtime_config: Default::default(),
+            agent_config: crate::config::AgentConfig {
+                checkpoint_dir: Some(dir.to_string_lossy().into_owned()),
+                ..Default::default()
+            },
+            pricing: Default::default(),
+            credibility: Default::default(),
+            llm_keys: Default::default(),
+            jina_key: String::new(),
+            search_settings: Default::default(),
+            search_metrics: Default::default(),
+            secret: None,
+            conversations: Default::default(),
+        })
+    }
+
+    async fn resume_status(state: Arc<AppState>, run_id: &str) -> StatusCode {
+        let request = Request::post(format!("/v1/runs/{}/resume", run_id))
+            .body(Body::empty())
+            .unwrap();
+        super::super::router(state).call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_resume_rejects_path_traversal() {
+        let root = std::env::temp_dir().join(format!("deep-research-srv-{}", uuid::Uuid::new_v4()));
+        let dir = root.join("checkpoints");
+        std::fs::create_dir_all(&dir).unwrap();
+        let outside = root.join("secret.json");
+        std::fs::write(&outside, "{}").unwrap();
+        let state = state_with_checkpoints(&dir);
+
+        assert_eq!(resume_status(state.clone(), "..%2Fsecret").await, StatusCode::BAD_REQUEST);
+        let absolute = outside.to_string_lossy().replace('/', "%2F");
+        assert_eq!(resume_status(state.clone(), &absolute).await, StatusCode::BAD_REQUEST);
+        assert_eq!(resume_status(state, "missing-run").await, StatusCode::NOT_FOUND);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    async fn chat_status(state: Arc<AppState>, body: serde_json::Value) -> StatusCode {
+        let request = Request::post("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        super::super::router(state).call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_chat_rejects_out_of_range_team_size() {
+        let state = state_with_checkpoints(&std::env::temp_dir());
+        for team_size in [0, 11, 1_000_000] {
+            let body = serde_json::json!({
+                "model": "jina-deepsearch-v1",
+                "messages": [{"role": "user", "content": "Who created Rust?"}],
+                "team_size": team_size
+            });
+            // 400 antes de criar clientes (sem chaves seria 503)
+            assert_eq!(chat_status(state.clone(), body).await, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_rejects_single_agent_options_in_team_and_report() {
+        let state = state_with_checkpoints(&std::env::temp_dir());
+        for option in ["finalize_answer", "verify_claims", "detect_contradictions"] {
+            let team = serde_json::json!({
+                "model": "jina-deepsearch-v1",
+                "messages": [{"role": "user", "content": "Who created Rust?"}],
+                "team_size": 3,
+                option: true
+            });
+            assert_eq!(chat_status(state.clone(), team).await, StatusCode::BAD_REQUEST);
+
+            let report = serde_json::json!({
+                "model": REPORT_MODEL_ID,
+                "messages": [{"role": "user", "content": "Who created Rust?"}],
+                option: false
+            });
+            // Desativar não pede nada que o modo ignore: segue até o cliente (sem chave)
+            assert_eq!(
+                chat_status(state.clone(), report.clone()).await,
+                StatusCode::SERVICE_UNAVAILABLE
+            );
+
+            let mut report = report;
+            report[option] = true.into();
+            assert_eq!(chat_status(state.clone(), report).await, StatusCode::BAD_REQUEST);
+        }
+    }
+}
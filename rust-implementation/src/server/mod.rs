@@ -10,6 +10,8 @@
 //! - `GET /v1/models` - Lista modelos disponíveis
 //! - `GET /v1/models/{model}` - Detalhes de um modelo
 //! - `POST /v1/chat/completions` - Pesquisa com SSE streaming ou JSON
+//! - `GET /v1/runs` - Lista execuções interrompidas (checkpoints)
+//! - `POST /v1/runs/:run_id/resume` - Retoma uma execução interrompida
 //!
 //! ## Uso
 //!
@@ -39,18 +41,26 @@ pub struct AppState {
     pub runtime_config: crate::config::RuntimeConfig,
     /// Configuração do agente
     pub agent_config: crate::config::AgentConfig,
-    /// Chave da API OpenAI
-    pub openai_key: String,
+    /// Tabela de preços (embutida ou AGENT_PRICING_FILE)
+    pub pricing: crate::utils::PricingTable,
+    /// Credibilidade das fontes (AGENT_CREDIBILITY_FILE + histórico local)
+    pub credibility: crate::credibility::SourceCredibility,
+    /// Chaves de API do LLM e dos embeddings (OpenAI, Anthropic, Jina)
+    pub llm_keys: crate::llm::LlmApiKeys,
     /// Chave da API Jina
     pub jina_key: String,
+    /// Endpoints e chaves dos provedores de busca (`SEARCH_PROVIDER`)
+    pub search_settings: crate::search_providers::WebSearchSettings,
+    /// Latência e rendimento por provedor na meta-busca (exposto em `/health`)
+    pub search_metrics: crate::search_metrics::MetricsCollector,
     /// Token de autenticação opcional (Bearer)
     pub secret: Option<String>,
+    /// Conversas em andamento (`conversation_id` → histórico e conhecimento)
+    pub conversations: Arc<crate::agent::ConversationStore>,
 }
 
-/// Inicia o servidor HTTP no endereço especificado.
-///
-/// Entry point chamado de main.rs quando `--server` é passado.
-pub async fn start_server(addr: SocketAddr, state: Arc<AppState>) -> anyhow::Result<()> {
+/// Monta o router com todas as rotas, autenticação e CORS
+pub fn router(state: Arc<AppState>) -> axum::Router {
     use axum::{middleware, routing::{get, post}, Router};
     use tower_http::cors::CorsLayer;
 
@@ -58,7 +68,9 @@ pub async fn start_server(addr: SocketAddr, state: Arc<AppState>) -> anyhow::Res
         .route("/health", get(handlers::health))
         .route("/v1/models", get(handlers::list_models))
         .route("/v1/models/{model}", get(handlers::get_model))
-        .route("/v1/chat/completions", post(handlers::chat_completions));
+        .route("/v1/chat/completions", post(handlers::chat_completions))
+        .route("/v1/runs", get(handlers::list_runs))
+        .route("/v1/runs/:run_id/resume", post(handlers::resume_run));
 
     // Auth middleware condicional
     let routes = if state.secret.is_some() {
@@ -71,10 +83,16 @@ pub async fn start_server(addr: SocketAddr, state: Arc<AppState>) -> anyhow::Res
     };
 
     // CORS + state → Router<()> (pronto para serve)
-    let app = routes
+    routes
         .layer(CorsLayer::permissive())
-        .with_state(state);
+        .with_state(state)
+}
 
+/// Inicia o servidor HTTP no endereço especificado.
+///
+/// Entry point chamado de main.rs quando `--server` é passado.
+pub async fn start_server(addr: SocketAddr, state: Arc<AppState>) -> anyhow::Result<()> {
+    let app = router(state);
     let listener = tokio::net::TcpListener::bind(addr).await?;
     log::info!("DeepResearch server listening on http://{}", addr);
     axum::serve(listener, app).await?;
@@ -12,7 +12,9 @@ use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::BroadcastStream;
 
-use crate::agent::{AgentProgress, DeepResearchAgent};
+use crate::agent::{
+    AgentProgress, CancellationToken, ConversationStore, ResearchResult, ResearchRun,
+};
 use super::types::*;
 
 /// Payload interno enviado pelo broadcast channel
@@ -33,24 +35,42 @@ pub struct CompletedPayload {
     pub prompt_tokens: u64,
     pub completion_tokens: u64,
     pub total_tokens: u64,
+    pub cost_usd: f64,
+    pub structured: Option<crate::agent::StructuredAnswer>,
     pub visited_urls: Vec<String>,
     pub error: Option<String>,
+    pub conversation_id: Option<String>,
+}
+
+/// Conversa atualizada com o resultado ao fim da pesquisa
+#[derive(Clone)]
+pub struct ConversationTarget {
+    pub store: Arc<ConversationStore>,
+    pub id: String,
+}
+
+impl ConversationTarget {
+    /// Registra a pergunta e o resultado na conversa
+    pub fn record(&self, question: &str, result: &ResearchResult) {
+        self.store.record(&self.id, question, result);
+    }
 }
 
 /// Cria e retorna uma resposta SSE para streaming do agente.
 ///
 /// 1. Cria um broadcast channel
 /// 2. Wires o ProgressCallback para enviar AgentProgress no channel
-/// 3. Spawna o agente em uma task tokio
+/// 3. Spawna a pesquisa (agente ou equipe, já configurada pelo handler) em uma task tokio
 /// 4. Retorna Sse<Stream> que consome o channel e emite chunks JSON
+///
+/// Se o cliente desconectar, o stream é descartado e a pesquisa é cancelada.
 pub async fn handle_streaming(
-    llm_client: Arc<dyn crate::llm::LlmClient>,
-    search_client: Arc<dyn crate::search::SearchClient>,
+    research: ResearchRun,
     question: String,
-    token_budget: u64,
     request_id: String,
     created: i64,
     model: String,
+    conversation: Option<ConversationTarget>,
 ) -> Response {
     let (tx, _) = broadcast::channel::<SsePayload>(512);
     let tx_callback = tx.clone();
@@ -62,12 +82,20 @@ pub async fn handle_streaming(
             let _ = tx_callback.send(SsePayload::Progress(event));
         });
 
+    // Cancelada quando o stream SSE for descartado (cliente desconectou)
+    let cancel_token = CancellationToken::new();
+    let cancel_guard = cancel_token.clone().drop_guard();
+
     // Spawnar agente em background task
     tokio::spawn(async move {
-        let agent = DeepResearchAgent::new(llm_client, search_client, Some(token_budget))
-            .with_progress_callback(progress_callback);
+        let research = research
+            .with_progress_callback(progress_callback)
+            .with_cancellation_token(cancel_token);
 
-        let result = agent.run(question).await;
+        let result = research.run(question.clone()).await;
+        if let Some(conversation) = &conversation {
+            conversation.record(&question, &result);
+        }
 
         let _ = tx_completion.send(SsePayload::Completed(CompletedPayload {
             success: result.success,
@@ -76,14 +104,20 @@ pub async fn handle_streaming(
             prompt_tokens: result.token_usage.prompt_tokens,
             completion_tokens: result.token_usage.completion_tokens,
             total_tokens: result.token_usage.total_tokens,
+            cost_usd: result.cost.total_usd,
+            structured: result.structured,
             visited_urls: result.visited_urls,
             error: result.error,
+            conversation_id: conversation.map(|c| c.id),
         }));
     });
 
     // Criar stream SSE a partir do broadcast receiver
     let rx = tx.subscribe();
-    let stream = build_sse_stream(rx, request_id, created, model);
+    let stream = build_sse_stream(rx, request_id, created, model).map(move |event| {
+        let _ = &cancel_guard;
+        event
+    });
 
     Sse::new(stream)
         .keep_alive(KeepAlive::default())
@@ -118,6 +152,7 @@ fn build_sse_stream(
             url: None,
             query: None,
             annotations: None,
+            sub_agent: None,
         },
         None,
     );
@@ -168,6 +203,20 @@ fn progress_to_events(
     model: &str,
 ) -> Vec<String> {
     let mut events = Vec::new();
+    for chunk in progress_to_chunks(progress, request_id, created, model) {
+        push_json(&mut events, &chunk);
+    }
+    events
+}
+
+/// Converte AgentProgress em 0+ chunks SSE
+fn progress_to_chunks(
+    progress: &AgentProgress,
+    request_id: &str,
+    created: i64,
+    model: &str,
+) -> Vec<ChatCompletionChunk> {
+    let mut chunks = Vec::new();
 
     match progress {
         AgentProgress::Think(content) => {
@@ -182,10 +231,11 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::VisitedUrl(url) => {
             let chunk = make_chunk(
@@ -199,10 +249,11 @@ fn progress_to_events(
                     url: Some(url.clone()),
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::PersonaQuery { expanded, .. } => {
             let chunk = make_chunk(
@@ -216,10 +267,11 @@ fn progress_to_events(
                     url: None,
                     query: Some(expanded.clone()),
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::Action(action) => {
             let chunk = make_chunk(
@@ -233,10 +285,11 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::Info(msg) | AgentProgress::Success(msg) => {
             let chunk = make_chunk(
@@ -250,10 +303,11 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::Warning(msg) => {
             let chunk = make_chunk(
@@ -267,10 +321,11 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::Error(msg) => {
             let chunk = make_chunk(
@@ -284,10 +339,11 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
         }
         AgentProgress::ValidationStep {
             eval_type,
@@ -310,16 +366,63 @@ fn progress_to_events(
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
+                },
+                None,
+            );
+            chunks.push(chunk);
+        }
+        AgentProgress::TeamPlan { subproblems } => {
+            let chunk = make_chunk(
+                request_id,
+                created,
+                model,
+                ChunkDelta {
+                    role: None,
+                    content: Some(format!("[team] {} ", subproblems.join(" | "))),
+                    delta_type: Some("think".into()),
+                    url: None,
+                    query: None,
+                    annotations: None,
+                    sub_agent: None,
+                },
+                None,
+            );
+            chunks.push(chunk);
+        }
+        AgentProgress::ReportOutline { title, sections } => {
+            let chunk = make_chunk(
+                request_id,
+                created,
+                model,
+                ChunkDelta {
+                    role: None,
+                    content: Some(format!("[report] {}: {} ", title, sections.join(" | "))),
+                    delta_type: Some("think".into()),
+                    url: None,
+                    query: None,
+                    annotations: None,
+                    sub_agent: None,
                 },
                 None,
             );
-            push_json(&mut events, &chunk);
+            chunks.push(chunk);
+        }
+        // Eventos de sub-agentes seguem o mesmo mapeamento do agente único,
+        // marcados com o índice do sub-agente
+        AgentProgress::SubAgent { agent_index, event } => {
+            for mut chunk in progress_to_chunks(event, request_id, created, model) {
+                for choice in &mut chunk.choices {
+                    choice.delta.sub_agent = Some(*agent_index);
+                }
+                chunks.push(chunk);
+            }
         }
         // Outros eventos são silenciados no SSE
         _ => {}
     }
 
-    events
+    chunks
 }
 
 /// Converte resultado final em chunks SSE (thinking_end + stop)
@@ -343,6 +446,7 @@ fn completion_to_events(
             url: None,
             query: None,
             annotations: None,
+            sub_agent: None,
         },
         Some("thinking_end"),
     );
@@ -350,9 +454,10 @@ fn completion_to_events(
 
     // 2. Chunk final com resposta
     let (content, content_type, finish_reason) = if result.success {
+        let content_type = if result.structured.is_some() { "json" } else { "text" };
         (
             result.answer.clone().unwrap_or_default(),
-            "text",
+            content_type,
             "stop",
         )
     } else {
@@ -365,6 +470,7 @@ fn completion_to_events(
         prompt_tokens: result.prompt_tokens,
         completion_tokens: result.completion_tokens,
         total_tokens: result.total_tokens,
+        cost_usd: Some(result.cost_usd),
     };
 
     let final_chunk = ChatCompletionChunk {
@@ -382,6 +488,7 @@ fn completion_to_events(
                 url: None,
                 query: None,
                 annotations,
+                sub_agent: None,
             },
             logprobs: None,
             finish_reason: Some(finish_reason.into()),
@@ -390,6 +497,8 @@ fn completion_to_events(
         visited_urls: Some(result.visited_urls.clone()),
         read_urls: None,
         num_urls: Some(result.visited_urls.len()),
+        structured_output: result.structured.as_ref().map(build_structured_output),
+        conversation_id: result.conversation_id.clone(),
     };
     push_json(&mut events, &final_chunk);
 
@@ -423,6 +532,8 @@ fn make_chunk(
         visited_urls: None,
         read_urls: None,
         num_urls: None,
+        structured_output: None,
+        conversation_id: None,
     }
 }
 
@@ -431,3 +542,42 @@ fn push_json(events: &mut Vec<String>, chunk: &ChatCompletionChunk) {
         events.push(json);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(events: &[String]) -> Vec<serde_json::Value> {
+        events
+            .iter()
+            .map(|json| serde_json::from_str(json).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sub_agent_events_keep_agent_index() {
+        let progress = AgentProgress::SubAgent {
+            agent_index: 2,
+            event: Box::new(AgentProgress::VisitedUrl("https://example.com".into())),
+        };
+
+        let events = parse(&progress_to_events(&progress, "req_1", 0, "model"));
+
+        assert_eq!(events.len(), 1);
+        let delta = &events[0]["choices"][0]["delta"];
+        assert_eq!(delta["subAgent"], 2);
+        assert_eq!(delta["url"], "https://example.com");
+    }
+
+    #[test]
+    fn test_single_agent_events_omit_sub_agent() {
+        let progress = AgentProgress::Think("pensando".into());
+
+        let events = parse(&progress_to_events(&progress, "req_1", 0, "model"));
+
+        assert_eq!(events.len(), 1);
+        let delta = &events[0]["choices"][0]["delta"];
+        assert!(delta.get("subAgent").is_none());
+        assert_eq!(delta["content"], "pensando ");
+    }
+}
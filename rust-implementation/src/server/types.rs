@@ -5,6 +5,11 @@
 #![allow(missing_docs)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::agent::ChatTurn;
+use crate::tools::research_planner::PlannerConfig;
+use crate::utils::{JsonSchema, SchemaError};
 
 // ─────────────────────────────────────────────────
 // Model
@@ -62,8 +67,92 @@ pub struct ChatCompletionRequest {
     pub with_images: Option<bool>,
     pub language_code: Option<String>,
     pub search_language_code: Option<String>,
+    /// Provedor(es) de busca separados por vírgula (sobrescreve `SEARCH_PROVIDER`)
     pub search_provider: Option<String>,
+    /// Agentes no modo equipe (2..=10); ausente = agente único
     pub team_size: Option<usize>,
+    /// Polir a resposta aprovada (sobrescreve `AGENT_FINALIZE_ANSWER`)
+    pub finalize_answer: Option<bool>,
+    /// Verificar afirmações da resposta aprovada (sobrescreve `AGENT_VERIFY_CLAIMS`)
+    pub verify_claims: Option<bool>,
+    /// Detectar fontes que discordam (sobrescreve `AGENT_DETECT_CONTRADICTIONS`)
+    pub detect_contradictions: Option<bool>,
+    /// Prazo da pesquisa em segundos (sobrescreve `AGENT_TIME_BUDGET_SECS`)
+    pub time_budget_secs: Option<u64>,
+    /// Budget de custo em USD (sobrescreve `AGENT_COST_BUDGET_USD`)
+    pub cost_budget_usd: Option<f64>,
+    /// Formato da resposta: texto livre ou JSON validado contra um schema
+    pub response_format: Option<ResponseFormat>,
+    /// Conversa a continuar (conhecimento e URLs da resposta anterior são reaproveitados)
+    pub conversation_id: Option<String>,
+}
+
+impl ChatCompletionRequest {
+    /// `team_size` validado contra os limites do `ResearchPlanner`
+    pub fn team_size(&self) -> Result<Option<usize>, String> {
+        let limits = PlannerConfig::default();
+        match self.team_size {
+            Some(size) if !(limits.min_team_size..=limits.max_team_size).contains(&size) => {
+                Err(format!(
+                    "team_size must be between {} and {}.",
+                    limits.min_team_size, limits.max_team_size
+                ))
+            }
+            size => Ok(size),
+        }
+    }
+}
+
+/// `response_format` do request (mesmo formato da API OpenAI)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Texto livre (padrão)
+    Text,
+    /// Qualquer objeto JSON
+    JsonObject,
+    /// JSON validado contra `json_schema.schema`
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+/// Schema nomeado de `response_format: {"type": "json_schema"}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonSchemaFormat {
+    pub name: Option<String>,
+    pub schema: serde_json::Value,
+    /// Aceito por compatibilidade; a validação é sempre estrita
+    pub strict: Option<bool>,
+}
+
+impl ResponseFormat {
+    /// Schema exigido da resposta (None = texto livre)
+    pub fn schema(&self) -> Result<Option<JsonSchema>, SchemaError> {
+        match self {
+            Self::Text => Ok(None),
+            Self::JsonObject => JsonSchema::new(serde_json::json!({ "type": "object" })).map(Some),
+            Self::JsonSchema { json_schema } => JsonSchema::new(json_schema.schema.clone()).map(Some),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────
+// Runs (checkpoints)
+// ─────────────────────────────────────────────────
+
+/// Lista de execuções interrompidas
+#[derive(Debug, Serialize)]
+pub struct RunList {
+    /// Sempre "list"
+    pub object: String,
+    pub data: Vec<crate::agent::CheckpointSummary>,
+}
+
+/// Request opcional para POST /v1/runs/{run_id}/resume
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResumeRunRequest {
+    #[serde(default)]
+    pub stream: bool,
+    pub model: Option<String>,
 }
 
 // ─────────────────────────────────────────────────
@@ -119,6 +208,9 @@ pub struct UsageInfo {
     pub prompt_tokens: u64,
     pub completion_tokens: u64,
     pub total_tokens: u64,
+    /// Custo da pesquisa em USD (extensão; ausente na API OpenAI)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
 }
 
 /// Resposta completa (non-streaming)
@@ -138,6 +230,20 @@ pub struct ChatCompletionResponse {
     pub read_urls: Option<Vec<String>>,
     #[serde(rename = "numURLs", skip_serializing_if = "Option::is_none")]
     pub num_urls: Option<usize>,
+    #[serde(rename = "structuredOutput", skip_serializing_if = "Option::is_none")]
+    pub structured_output: Option<StructuredOutput>,
+    /// Id da conversa (envie como `conversation_id` na próxima pergunta)
+    #[serde(rename = "conversationId", skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+}
+
+/// Resposta estruturada parseada (extensão; presente com `response_format` JSON)
+#[derive(Debug, Clone, Serialize)]
+pub struct StructuredOutput {
+    pub parsed: serde_json::Value,
+    /// Citações que sustentam cada campo, por JSON Pointer
+    #[serde(rename = "fieldReferences")]
+    pub field_references: BTreeMap<String, Vec<URLCitation>>,
 }
 
 // ─────────────────────────────────────────────────
@@ -159,6 +265,9 @@ pub struct ChunkDelta {
     pub query: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Vec<URLAnnotation>>,
+    /// Índice do sub-agente que gerou o evento (extensão; modos equipe e relatório)
+    #[serde(rename = "subAgent", skip_serializing_if = "Option::is_none")]
+    pub sub_agent: Option<usize>,
 }
 
 /// Choice no chunk SSE
@@ -188,6 +297,11 @@ pub struct ChatCompletionChunk {
     pub read_urls: Option<Vec<String>>,
     #[serde(rename = "numURLs", skip_serializing_if = "Option::is_none")]
     pub num_urls: Option<usize>,
+    #[serde(rename = "structuredOutput", skip_serializing_if = "Option::is_none")]
+    pub structured_output: Option<StructuredOutput>,
+    /// Id da conversa (envie como `conversation_id` na próxima pergunta)
+    #[serde(rename = "conversationId", skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
 }
 
 // ─────────────────────────────────────────────────
@@ -214,6 +328,9 @@ pub struct ApiErrorDetail {
 // Helpers
 // ─────────────────────────────────────────────────
 
+/// Modelo que roda a pesquisa em modo relatório (esboço + seções)
+pub const REPORT_MODEL_ID: &str = "jina-deepsearch-report";
+
 /// Retorna a lista de modelos disponíveis
 pub fn available_models() -> Vec<Model> {
     vec![
@@ -229,6 +346,12 @@ pub fn available_models() -> Vec<Model> {
             created: 1717987200,
             owned_by: "jina-ai".into(),
         },
+        Model {
+            id: REPORT_MODEL_ID.into(),
+            object: "model".into(),
+            created: 1717987200,
+            owned_by: "jina-ai".into(),
+        },
     ]
 }
 
@@ -251,6 +374,38 @@ pub fn extract_question(content: &serde_json::Value) -> String {
     }
 }
 
+/// Converte as mensagens anteriores à pergunta em turnos da conversa
+///
+/// Cada mensagem `user` abre um turno e a `assistant` seguinte o responde;
+/// mensagens `system`/`tool` são ignoradas. O raciocínio (`<think>...</think>`)
+/// que o streaming devolve junto da resposta é descartado.
+pub fn extract_chat_turns(messages: &[ChatMessage]) -> Vec<ChatTurn> {
+    let mut turns = Vec::new();
+    let mut pending: Option<String> = None;
+
+    for msg in messages {
+        let content = extract_question(&msg.content);
+        match msg.role.as_str() {
+            "user" => {
+                if let Some(question) = pending.replace(content) {
+                    turns.push(ChatTurn::new(question, ""));
+                }
+            }
+            "assistant" => {
+                if let Some(question) = pending.take() {
+                    let answer = content.rsplit("</think>").next().unwrap_or_default();
+                    turns.push(ChatTurn::new(question, answer.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(question) = pending {
+        turns.push(ChatTurn::new(question, ""));
+    }
+    turns
+}
+
 /// Calcula o budget de tokens a partir dos parâmetros do request
 pub fn resolve_token_budget(
     reasoning_effort: Option<&str>,
@@ -292,6 +447,29 @@ pub fn build_annotations(references: &[crate::types::Reference]) -> Option<Vec<U
     }
 }
 
+/// Converte a resposta estruturada do agente para o formato da API
+pub fn build_structured_output(structured: &crate::agent::StructuredAnswer) -> StructuredOutput {
+    StructuredOutput {
+        parsed: structured.value.clone(),
+        field_references: structured
+            .field_references
+            .iter()
+            .map(|(pointer, references)| {
+                let citations = references
+                    .iter()
+                    .map(|r| URLCitation {
+                        title: r.title.clone(),
+                        exact_quote: r.exact_quote.clone().unwrap_or_default(),
+                        url: r.url.clone(),
+                        date_time: None,
+                    })
+                    .collect();
+                (pointer.clone(), citations)
+            })
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +490,27 @@ mod tests {
         assert_eq!(extract_question(&content), "Hello World");
     }
 
+    #[test]
+    fn test_extract_chat_turns() {
+        let messages: Vec<ChatMessage> = serde_json::from_value(serde_json::json!([
+            {"role": "system", "content": "Be concise"},
+            {"role": "user", "content": "Who founded Jina AI?"},
+            {"role": "assistant", "content": "<think>searching...</think>\n\nHan Xiao"},
+            {"role": "user", "content": [{"type": "text", "text": "Where?"}]},
+            {"role": "user", "content": "In Berlin?"}
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            extract_chat_turns(&messages),
+            vec![
+                ChatTurn::new("Who founded Jina AI?", "Han Xiao"),
+                ChatTurn::new("Where?", ""),
+                ChatTurn::new("In Berlin?", ""),
+            ]
+        );
+    }
+
     #[test]
     fn test_resolve_token_budget() {
         assert_eq!(resolve_token_budget(None, None, Some(42)), 42);
@@ -325,9 +524,10 @@ mod tests {
     #[test]
     fn test_available_models() {
         let models = available_models();
-        assert_eq!(models.len(), 2);
+        assert_eq!(models.len(), 3);
         assert_eq!(models[0].id, "jina-deepsearch-v1");
         assert_eq!(models[1].id, "jina-deepsearch-v2");
+        assert_eq!(models[2].id, REPORT_MODEL_ID);
     }
 
     #[test]
@@ -347,6 +547,7 @@ mod tests {
                     url: None,
                     query: None,
                     annotations: None,
+                    sub_agent: None,
                 },
                 logprobs: None,
                 finish_reason: None,
@@ -355,6 +556,8 @@ mod tests {
             visited_urls: None,
             read_urls: None,
             num_urls: None,
+            structured_output: None,
+            conversation_id: None,
         };
         let json = serde_json::to_string(&chunk).unwrap();
         assert!(json.contains("chat.completion.chunk"));
@@ -362,4 +565,55 @@ mod tests {
         // skip_serializing_if = None fields should not appear
         assert!(!json.contains("visitedURLs"));
     }
+
+    #[test]
+    fn test_team_size_bounds() {
+        let request = |team_size: serde_json::Value| -> ChatCompletionRequest {
+            serde_json::from_value(serde_json::json!({
+                "model": "jina-deepsearch-v1",
+                "messages": [{"role": "user", "content": "Who created Rust?"}],
+                "team_size": team_size
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(request(serde_json::Value::Null).team_size(), Ok(None));
+        assert_eq!(request(2.into()).team_size(), Ok(Some(2)));
+        assert_eq!(request(10.into()).team_size(), Ok(Some(10)));
+        for invalid in [0, 1, 11, 1_000_000] {
+            assert!(request(invalid.into()).team_size().is_err());
+        }
+    }
+
+    #[test]
+    fn test_response_format_schema() {
+        let request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "jina-deepsearch-v1",
+            "messages": [{"role": "user", "content": "Who created Rust?"}],
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "creator",
+                    "strict": true,
+                    "schema": {"type": "object", "properties": {"name": {"type": "string"}}}
+                }
+            }
+        }))
+        .unwrap();
+
+        let schema = request.response_format.unwrap().schema().unwrap().unwrap();
+        assert!(schema.validate(&serde_json::json!({"name": "Graydon"})).is_ok());
+
+        let text: ResponseFormat = serde_json::from_value(serde_json::json!({"type": "text"})).unwrap();
+        assert!(text.schema().unwrap().is_none());
+
+        let invalid = ResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: None,
+                schema: serde_json::json!({"type": "text"}),
+                strict: None,
+            },
+        };
+        assert!(invalid.schema().is_err());
+    }
 }
@@ -0,0 +1,449 @@
+//! # Claim Verifier
+//!
+//! Verifica uma resposta afirmação por afirmação. Respostas passam em
+//! `Definitive`/`Completeness` mesmo com um número errado; aqui cada
+//! afirmação atômica é confrontada com trechos do conteúdo já lido.
+//!
+//! ## Fluxo
+//! 1. `extract_claims`: o LLM divide a resposta em afirmações atômicas
+//! 2. `retrieve_passages`: trechos do conteúdo visitado por sobreposição de
+//!    termos (números pesam mais)
+//! 3. `judge`: o LLM rotula cada afirmação como suportada, contradita ou
+//!    não verificada, citando o trecho usado
+//!
+//! Falhas do LLM nunca reprovam a resposta: sem afirmações a verificação é
+//! pulada, e sem vereditos as afirmações ficam como não verificadas.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentPrompt;
+use crate::llm::LlmClient;
+use crate::utils::{extract_json, TokenTracker};
+
+/// Máximo de afirmações verificadas por resposta
+pub const MAX_CLAIMS: usize = 8;
+
+/// Trechos enviados ao verificador por afirmação
+pub const PASSAGES_PER_CLAIM: usize = 3;
+
+/// Tamanho alvo de cada trecho (em caracteres)
+const PASSAGE_CHARS: usize = 800;
+
+/// Fração mínima dos termos da afirmação presentes no trecho
+const MIN_PASSAGE_SCORE: f32 = 0.3;
+
+/// Instruções da extração de afirmações
+const EXTRACT_PROMPT: &str = "You split research answers into atomic factual claims.
+Each claim must be a single, self-contained, checkable statement (a fact, number, date, name or causal relation) taken from the answer.
+Skip opinions, hedges and generic statements. Keep numbers and units exactly as written. Use the language of the answer.
+Return at most {max} claims, most important first.
+Reply with JSON only: {\"claims\": [\"...\"]}";
+
+/// Instruções do julgamento das afirmações
+const JUDGE_PROMPT: &str = "You are a meticulous fact checker.
+For each numbered claim you get passages from sources the researcher has read.
+Label each claim:
+- supported: a passage states the same fact (numbers, dates and names must match)
+- contradicted: a passage states a different value or the opposite fact
+- unverified: the passages do not mention it
+Only use the given passages, never your own knowledge. Quote the decisive passage excerpt verbatim in \"evidence\".
+Reply with JSON only: {\"verdicts\": [{\"claim\": 1, \"verdict\": \"supported|contradicted|unverified\", \"evidence\": \"...\", \"source\": \"url\", \"reason\": \"...\"}]}";
+
+/// Trecho de uma fonte usado como evidência
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passage {
+    /// URL de origem
+    pub source: String,
+    /// Texto do trecho
+    pub text: String,
+}
+
+/// Afirmação e os trechos recuperados para ela
+#[derive(Debug, Clone)]
+pub struct ClaimEvidence {
+    /// Afirmação atômica
+    pub claim: String,
+    /// Trechos mais relevantes
+    pub passages: Vec<Passage>,
+}
+
+/// Veredito de uma afirmação
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimVerdict {
+    /// Confirmada por uma fonte
+    Supported,
+    /// Uma fonte afirma o contrário
+    Contradicted,
+    /// Nenhuma fonte menciona
+    Unverified,
+}
+
+impl ClaimVerdict {
+    /// Interpreta o rótulo devolvido pelo LLM
+    pub fn parse(label: &str) -> Option<Self> {
+        match label.trim().to_lowercase().as_str() {
+            "supported" => Some(Self::Supported),
+            "contradicted" => Some(Self::Contradicted),
+            "unverified" => Some(Self::Unverified),
+            _ => None,
+        }
+    }
+
+    /// Retorna o nome do veredito como string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Supported => "supported",
+            Self::Contradicted => "contradicted",
+            Self::Unverified => "unverified",
+        }
+    }
+}
+
+/// Resultado da verificação de uma afirmação, exposto no `ResearchResult`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimCheck {
+    /// Afirmação verificada
+    pub claim: String,
+    /// Veredito
+    pub verdict: ClaimVerdict,
+    /// Trecho decisivo citado pelo verificador
+    pub evidence: Option<String>,
+    /// URL do trecho
+    pub source: Option<String>,
+    /// Justificativa
+    pub reason: String,
+}
+
+/// Extrai e julga afirmações de uma resposta
+pub struct ClaimVerifier {
+    llm: Arc<dyn LlmClient>,
+}
+
+impl ClaimVerifier {
+    /// Cria um novo verificador
+    pub fn new(llm: Arc<dyn LlmClient>) -> Self {
+        Self { llm }
+    }
+
+    /// Divide a resposta em até `MAX_CLAIMS` afirmações atômicas
+    ///
+    /// Retorna vazio se o LLM falhar (a verificação é pulada).
+    pub async fn extract_claims(
+        &self,
+        question: &str,
+        answer: &str,
+        tracker: &mut TokenTracker,
+    ) -> Vec<String> {
+        let prompt = AgentPrompt {
+            system: EXTRACT_PROMPT.replace("{max}", &MAX_CLAIMS.to_string()),
+            user: format!("Question: {}\n\nAnswer:\n{}", question, answer),
+            diary: vec![],
+        };
+
+        match self.llm.generate_answer(&prompt, 0.0).await {
+            Ok(response) => {
+                tracker.add_tokens(
+                    "claim_verifier",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                parse_claims(&response.answer, MAX_CLAIMS)
+            }
+            Err(e) => {
+                log::warn!("⚠️ ClaimVerifier: Falha ao extrair afirmações ({})", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Julga cada afirmação contra seus trechos
+    ///
+    /// Sempre retorna um resultado por afirmação, na mesma ordem.
+    pub async fn judge(
+        &self,
+        evidence: &[ClaimEvidence],
+        tracker: &mut TokenTracker,
+    ) -> Vec<ClaimCheck> {
+        // Sem nenhum trecho não há o que julgar
+        if evidence.iter().all(|e| e.passages.is_empty()) {
+            return parse_verdicts("", evidence);
+        }
+
+        let body = evidence
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let passages = if e.passages.is_empty() {
+                    "(no passages found)".to_string()
+                } else {
+                    e.passages
+                        .iter()
+                        .map(|p| format!("<passage source=\"{}\">\n{}\n</passage>", p.source, p.text))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                format!("Claim {}: {}\n{}", i + 1, e.claim, passages)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let prompt = AgentPrompt {
+            system: JUDGE_PROMPT.to_string(),
+            user: body,
+            diary: vec![],
+        };
+
+        match self.llm.generate_answer(&prompt, 0.0).await {
+            Ok(response) => {
+                tracker.add_tokens(
+                    "claim_verifier",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                parse_verdicts(&response.answer, evidence)
+            }
+            Err(e) => {
+                log::warn!("⚠️ ClaimVerifier: Falha ao julgar afirmações ({})", e);
+                parse_verdicts("", evidence)
+            }
+        }
+    }
+}
+
+/// Divide o texto de uma fonte em trechos de ~`PASSAGE_CHARS` caracteres
+pub fn split_passages(source: &str, text: &str) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut current = String::new();
+
+    let mut flush = |current: &mut String| {
+        if !current.trim().is_empty() {
+            passages.push(Passage {
+                source: source.to_string(),
+                text: current.trim().to_string(),
+            });
+        }
+        current.clear();
+    };
+
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        // Linhas muito longas são quebradas por caracteres
+        let chars: Vec<char> = line.chars().collect();
+        for piece in chars.chunks(PASSAGE_CHARS) {
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.extend(piece);
+            if current.chars().count() >= PASSAGE_CHARS {
+                flush(&mut current);
+            }
+        }
+    }
+    flush(&mut current);
+    passages
+}
+
+/// Trechos mais relevantes para a afirmação, por sobreposição de termos
+///
+/// Números contam em dobro: o trecho que fala do mesmo assunto com outro
+/// número também é recuperado, e é ele que revela a contradição.
+pub fn retrieve_passages(claim: &str, passages: &[Passage], top_k: usize) -> Vec<Passage> {
+    let claim_terms = terms(claim);
+    let weight = |term: &str| if term.chars().any(|c| c.is_ascii_digit()) { 2.0 } else { 1.0 };
+    let total: f32 = claim_terms.iter().map(|t| weight(t)).sum();
+    if total == 0.0 {
+        return vec![];
+    }
+
+    let mut scored: Vec<(f32, &Passage)> = passages
+        .iter()
+        .map(|p| {
+            let passage_terms = terms(&p.text);
+            let matched: f32 = claim_terms
+                .iter()
+                .filter(|t| passage_terms.contains(*t))
+                .map(|t| weight(t))
+                .sum();
+            (matched / total, p)
+        })
+        .filter(|(score, _)| *score >= MIN_PASSAGE_SCORE)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(top_k)
+        .map(|(_, p)| p.clone())
+        .collect()
+}
+
+/// Termos normalizados: números e palavras com 3+ caracteres
+fn terms(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 3 || t.chars().any(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Interpreta a lista de afirmações do LLM
+fn parse_claims(raw: &str, max_claims: usize) -> Vec<String> {
+    #[derive(Deserialize)]
+    struct Claims {
+        claims: Vec<String>,
+    }
+
+    let Some(parsed) = extract_json(raw)
+        .ok()
+        .and_then(|value| serde_json::from_value::<Claims>(value).ok())
+    else {
+        log::warn!("⚠️ ClaimVerifier: Lista de afirmações inválida");
+        return vec![];
+    };
+    parsed
+        .claims
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .take(max_claims)
+        .collect()
+}
+
+/// Interpreta os vereditos do LLM; afirmações sem veredito ficam não verificadas
+fn parse_verdicts(raw: &str, evidence: &[ClaimEvidence]) -> Vec<ClaimCheck> {
+    #[derive(Deserialize)]
+    struct Verdict {
+        claim: usize,
+        verdict: String,
+        evidence: Option<String>,
+        source: Option<String>,
+        #[serde(default)]
+        reason: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Verdicts {
+        verdicts: Vec<Verdict>,
+    }
+
+    let verdicts = extract_json(raw)
+        .ok()
+        .and_then(|value| serde_json::from_value::<Verdicts>(value).ok())
+        .map(|v| v.verdicts)
+        .unwrap_or_default();
+
+    evidence
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let found = verdicts.iter().find(|v| v.claim == i + 1).and_then(|v| {
+                ClaimVerdict::parse(&v.verdict).map(|verdict| (verdict, v))
+            });
+            match found {
+                Some((verdict, v)) => ClaimCheck {
+                    claim: e.claim.clone(),
+                    verdict,
+                    evidence: v.evidence.clone().filter(|s| !s.trim().is_empty()),
+                    source: v.source.clone().filter(|s| !s.trim().is_empty()),
+                    reason: v.reason.clone(),
+                },
+                None => ClaimCheck {
+                    claim: e.claim.clone(),
+                    verdict: ClaimVerdict::Unverified,
+                    evidence: None,
+                    source: None,
+                    reason: if e.passages.is_empty() {
+                        "No passages found".to_string()
+                    } else {
+                        "No verdict from verifier".to_string()
+                    },
+                },
+            }
+        })
+        .collect()
+}
+
+/// Dica de melhoria com as afirmações contraditas, se houver
+pub fn improvement_hint(checks: &[ClaimCheck]) -> Option<String> {
+    let contradicted: Vec<String> = checks
+        .iter()
+        .filter(|c| c.verdict == ClaimVerdict::Contradicted)
+        .map(|c| {
+            let mut line = format!("- \"{}\"", c.claim);
+            if let Some(evidence) = &c.evidence {
+                line.push_str(&format!(" is contradicted by \"{}\"", evidence));
+            }
+            if let Some(source) = &c.source {
+                line.push_str(&format!(" ({})", source));
+            }
+            if !c.reason.is_empty() {
+                line.push_str(&format!(": {}", c.reason));
+            }
+            line
+        })
+        .collect();
+
+    if contradicted.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Fix these claims using the sources before answering again:\n{}",
+            contradicted.join("\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retrieve_passages_prefers_numbers() {
+        let text = "A empresa foi fundada em 1998 em São Paulo.\n\n\
+                    O faturamento de 2023 foi de R$ 4,2 bilhões, segundo o balanço.";
+        let passages = split_passages("https://a.com", text);
+        assert_eq!(passages.len(), 1);
+
+        let mut passages = passages;
+        passages.push(Passage {
+            source: "https://b.com".into(),
+            text: "Clima ameno e praias.".into(),
+        });
+        let found = retrieve_passages("O faturamento de 2023 foi de R$ 5 bilhões", &passages, 3);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].source, "https://a.com");
+
+        assert!(retrieve_passages("??", &passages, 3).is_empty());
+    }
+
+    #[test]
+    fn test_parse_verdicts_and_hint() {
+        let evidence = vec![
+            ClaimEvidence {
+                claim: "Receita de R$ 5 bilhões".into(),
+                passages: vec![Passage {
+                    source: "https://a.com".into(),
+                    text: "receita de R$ 4,2 bilhões".into(),
+                }],
+            },
+            ClaimEvidence {
+                claim: "Fundada em 1998".into(),
+                passages: vec![],
+            },
+        ];
+        let raw = r#"{"verdicts": [{"claim": 1, "verdict": "Contradicted",
+            "evidence": "receita de R$ 4,2 bilhões", "source": "https://a.com", "reason": "valor diferente"}]}"#;
+
+        let checks = parse_verdicts(raw, &evidence);
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].verdict, ClaimVerdict::Contradicted);
+        assert_eq!(checks[1].verdict, ClaimVerdict::Unverified);
+        assert_eq!(checks[1].reason, "No passages found");
+
+        let hint = improvement_hint(&checks).unwrap();
+        assert!(hint.contains("\"Receita de R$ 5 bilhões\" is contradicted by \"receita de R$ 4,2 bilhões\""));
+        assert!(improvement_hint(&checks[1..]).is_none());
+        assert_eq!(parse_claims("{\"claims\": [\" a \", \"\", \"b\"]}", 1), vec!["a"]);
+    }
+}
@@ -17,7 +17,7 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 use crate::llm::LlmClient;
-use crate::types::{KnowledgeItem, Language};
+use crate::types::{KnowledgeItem, Language, Reference};
 use crate::utils::TokenTracker;
 
 /// Erro que pode ocorrer durante a finalização de respostas.
@@ -136,9 +136,9 @@ impl ResponseFinalizer {
         language: &Language,
         tracker: &mut TokenTracker,
     ) -> Result<String, FinalizerError> {
-        // Se o conteúdo é muito curto, não vale a pena polir
+        // Se o conteúdo é muito curto, não vale a pena polir com LLM
         if md_content.len() < 100 {
-            return Ok(md_content.to_string());
+            return Ok(collapse_blank_lines(md_content));
         }
 
         let system_prompt = self.build_system_prompt(language);
@@ -201,7 +201,7 @@ impl ResponseFinalizer {
 2. **Structure**: Facts → Arguments → Conclusion
 3. **Natural Language**: Avoid excessive bullet points, use flowing prose
 4. **Clarity**: Make complex ideas accessible without dumbing them down
-5. **Citations**: Preserve all references and citations exactly
+5. **Citations**: Preserve all references and citations exactly. Every footnote marker such as [^1] must stay attached to the same claim
 
 ## What NOT to do
 - Don't add new information not in the original
@@ -261,12 +261,8 @@ Output the polished version directly, no explanations needed."#, lang_instructio
 
     /// Polimento básico do conteúdo (fallback quando LLM não disponível).
     fn polish_content(&self, content: &str) -> String {
-        let mut result = content.to_string();
-
         // Remove linhas em branco duplicadas
-        while result.contains("\n\n\n") {
-            result = result.replace("\n\n\n", "\n\n");
-        }
+        let mut result = collapse_blank_lines(content);
 
         // Remove espaços em branco no final das linhas
         result = result
@@ -284,6 +280,105 @@ Output the polished version directly, no explanations needed."#, lang_instructio
     }
 }
 
+/// Reduz sequências de linhas em branco a uma só
+fn collapse_blank_lines(content: &str) -> String {
+    let mut result = content.to_string();
+    while result.contains("\n\n\n") {
+        result = result.replace("\n\n\n", "\n\n");
+    }
+    result
+}
+
+/// Marcadores de citação `[^N]` presentes no texto, na ordem em que aparecem.
+///
+/// Retorna `(número, início, fim)` com offsets em bytes.
+pub fn citation_markers(text: &str) -> Vec<(usize, usize, usize)> {
+    let mut markers = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(rel) = text[search_from..].find("[^") {
+        let start = search_from + rel;
+        let digits_start = start + 2;
+        let digits_len = text[digits_start..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let close = digits_start + digits_len;
+
+        if digits_len > 0 && text[close..].starts_with(']') {
+            if let Ok(number) = text[digits_start..close].parse::<usize>() {
+                markers.push((number, start, close + 1));
+            }
+            search_from = close + 1;
+        } else {
+            search_from = digits_start;
+        }
+    }
+
+    markers
+}
+
+/// Verifica se `polished` mantém exatamente os mesmos marcadores de citação de `original`.
+pub fn preserves_citations(original: &str, polished: &str) -> bool {
+    let numbers = |text: &str| {
+        citation_markers(text)
+            .into_iter()
+            .map(|(n, _, _)| n)
+            .collect::<std::collections::BTreeSet<_>>()
+    };
+    numbers(original) == numbers(polished)
+}
+
+/// Recalcula `answer_chunk` e `answer_position` de cada referência a partir
+/// dos marcadores `[^N]` do texto final.
+///
+/// A numeração segue a mesma regra do `ReferenceBuilder`: o marcador `[^k]`
+/// pertence à k-ésima referência por ordem de posição na resposta (ou por
+/// ordem da lista quando nenhuma referência tem posição). As novas posições
+/// são relativas a `answer`; referências sem marcador ficam sem posição.
+pub fn realign_references(answer: &str, references: &mut [Reference]) {
+    let mut order: Vec<usize> = (0..references.len()).collect();
+    if references.iter().any(|r| r.answer_position.is_some()) {
+        order.sort_by_key(|&i| {
+            references[i]
+                .answer_position
+                .map(|(start, _)| start)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    let markers = citation_markers(answer);
+
+    for (rank, &index) in order.iter().enumerate() {
+        let number = rank + 1;
+        let reference = &mut references[index];
+
+        let Some(pos) = markers.iter().position(|(n, _, _)| *n == number) else {
+            reference.answer_position = None;
+            continue;
+        };
+        let (_, marker_start, _) = markers[pos];
+
+        // O trecho termina no marcador e começa no fim da frase/marcador anterior
+        let end = answer[..marker_start].trim_end().len();
+        let previous_marker_end = if pos > 0 { markers[pos - 1].2 } else { 0 };
+        // Recua um caractere (não um byte) para não cortar caracteres multibyte
+        let before_last_char = answer[..end]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+        let sentence_start = answer[..before_last_char]
+            .rfind(['.', '!', '?', '\n'])
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let start = sentence_start.max(previous_marker_end).min(end);
+        let start = end - answer[start..end].trim_start().len();
+
+        reference.answer_chunk = Some(answer[start..end].to_string());
+        reference.answer_position = Some((start, end));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -416,6 +511,65 @@ mod tests {
         assert!(!result.contains("\n\n\n"));
     }
 
+    #[test]
+    fn test_citation_markers_and_preservation() {
+        let text = "Rust is fast.[^1] It is also safe[^2]. See [^x] and [^10].";
+        let numbers: Vec<usize> = citation_markers(text).iter().map(|m| m.0).collect();
+        assert_eq!(numbers, vec![1, 2, 10]);
+
+        assert!(preserves_citations(text, "Safe[^2] and fast[^1], per [^10]."));
+        assert!(!preserves_citations(text, "Safe[^2] and fast, per [^10]."));
+        assert!(!preserves_citations(text, "Safe[^2], fast[^1], [^10] and [^3]."));
+    }
+
+    #[test]
+    fn test_realign_references_after_rewrite() {
+        let reference = |url: &str, start: usize| Reference {
+            url: url.into(),
+            title: url.into(),
+            answer_chunk: Some("old".into()),
+            answer_position: Some((start, start + 3)),
+            ..Default::default()
+        };
+        // Marcador [^1] é da referência com menor posição (b), [^2] da outra (a)
+        let mut refs = vec![reference("a", 50), reference("b", 10)];
+        let polished = "Intro line.\nMemory safety matters.[^2] Speed too.[^1]";
+
+        realign_references(polished, &mut refs);
+
+        let (start, end) = refs[0].answer_position.unwrap();
+        assert_eq!(&polished[start..end], "Memory safety matters.");
+        assert_eq!(refs[0].answer_chunk.as_deref(), Some("Memory safety matters."));
+        assert!(polished[end..].starts_with("[^2]"));
+
+        let (start, end) = refs[1].answer_position.unwrap();
+        assert_eq!(&polished[start..end], "Speed too.");
+        assert!(polished[end..].starts_with("[^1]"));
+    }
+
+    #[test]
+    fn test_realign_references_with_accented_text() {
+        let mut refs = vec![Reference {
+            url: "a".into(),
+            title: "a".into(),
+            ..Default::default()
+        }];
+        let polished = "Vale até[^1] o fim. Ação é necessária[^2]";
+
+        realign_references(polished, &mut refs);
+
+        let (start, end) = refs[0].answer_position.unwrap();
+        assert_eq!(&polished[start..end], "Vale até");
+        assert!(polished[end..].starts_with("[^1]"));
+
+        let mut refs = vec![refs[0].clone(), refs[0].clone()];
+        refs[0].answer_position = None;
+        refs[1].answer_position = None;
+        let polished = "Só é[^1] Ação.[^2]";
+        realign_references(polished, &mut refs);
+        assert_eq!(refs[1].answer_chunk.as_deref(), Some("Ação."));
+    }
+
     #[test]
     fn test_config_defaults() {
         let config = FinalizerConfig::default();
@@ -8,11 +8,22 @@
 //! - [`ResponseFinalizer`]: Polir respostas como um "editor sênior"
 //! - [`ResponseReducer`]: Mesclar respostas de múltiplos agentes
 //! - [`ResearchPlanner`]: Dividir problemas em subproblemas ortogonais
+//! - [`QuestionRewriter`]: Tornar perguntas de acompanhamento independentes
+//! - [`ReportWriter`]: Esboço e sumário executivo de relatórios longos
+//! - [`ClaimVerifier`]: Verificação da resposta afirmação por afirmação
 
+pub mod claim_verifier;
 pub mod finalizer;
+pub mod question_rewriter;
 pub mod reducer;
+pub mod report_writer;
 pub mod research_planner;
 
+pub use claim_verifier::{ClaimCheck, ClaimVerdict, ClaimVerifier};
 pub use finalizer::ResponseFinalizer;
+pub use question_rewriter::QuestionRewriter;
 pub use reducer::ResponseReducer;
+pub use report_writer::{
+    OutlineSection, ReportOutline, ReportWriter, DEFAULT_REPORT_SECTIONS, MAX_REPORT_SECTIONS,
+};
 pub use research_planner::ResearchPlanner;
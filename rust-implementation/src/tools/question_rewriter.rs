@@ -0,0 +1,139 @@
+//! # Question Rewriter
+//!
+//! Reescreve perguntas de acompanhamento ("e quando foi isso?") como
+//! perguntas independentes, usando os turnos anteriores da conversa.
+//!
+//! A pesquisa, as buscas e as avaliações passam a trabalhar com uma
+//! pergunta que faz sentido sozinha. Em caso de falha do LLM a pergunta
+//! original é mantida.
+
+use std::sync::Arc;
+
+use crate::agent::{AgentPrompt, ChatTurn, MAX_HISTORY_TURNS};
+use crate::llm::LlmClient;
+use crate::utils::TokenTracker;
+
+/// Tamanho máximo aceito para a pergunta reescrita (em caracteres)
+const MAX_REWRITE_CHARS: usize = 1000;
+
+/// Instruções do rewriter
+const SYSTEM_PROMPT: &str =
+    "You rewrite follow-up questions from a conversation into standalone questions.
+Resolve pronouns and implicit references (\"it\", \"that\", \"and when?\") using the conversation, \
+keep the user's language and intent, and do not answer the question.
+If the question is already standalone, return it unchanged.
+Reply with the standalone question only.";
+
+/// Reescreve perguntas de acompanhamento como perguntas independentes
+pub struct QuestionRewriter {
+    llm: Arc<dyn LlmClient>,
+}
+
+impl QuestionRewriter {
+    /// Cria um novo rewriter
+    pub fn new(llm: Arc<dyn LlmClient>) -> Self {
+        Self { llm }
+    }
+
+    /// Retorna `question` reescrita com o contexto de `turns`
+    ///
+    /// Sem turnos anteriores, ou se o LLM falhar, retorna a pergunta original.
+    pub async fn rewrite(
+        &self,
+        turns: &[ChatTurn],
+        question: &str,
+        tracker: &mut TokenTracker,
+    ) -> String {
+        if turns.is_empty() {
+            return question.to_string();
+        }
+
+        let prompt = Self::build_prompt(turns, question);
+        match self.llm.generate_answer(&prompt, 0.0).await {
+            Ok(response) => {
+                tracker.add_tokens(
+                    "question_rewriter",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                match clean_rewrite(&response.answer) {
+                    Some(rewritten) => {
+                        log::info!("💬 Pergunta reescrita: {} → {}", question, rewritten);
+                        rewritten
+                    }
+                    None => question.to_string(),
+                }
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ QuestionRewriter: Falha ao usar LLM ({}), mantendo a pergunta",
+                    e
+                );
+                question.to_string()
+            }
+        }
+    }
+
+    /// Monta o prompt com os últimos turnos e a pergunta atual
+    fn build_prompt(turns: &[ChatTurn], question: &str) -> AgentPrompt {
+        let history = turns[turns.len().saturating_sub(MAX_HISTORY_TURNS)..]
+            .iter()
+            .map(|turn| format!("User: {}\nAssistant: {}", turn.question, turn.answer))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        AgentPrompt {
+            system: SYSTEM_PROMPT.to_string(),
+            user: format!(
+                "<conversation>\n{}\n</conversation>\n\nFollow-up question: {}",
+                history, question
+            ),
+            diary: vec![],
+        }
+    }
+}
+
+/// Limpa a resposta do LLM (aspas, prefixos) e rejeita resultados inválidos
+fn clean_rewrite(raw: &str) -> Option<String> {
+    let mut text = raw.trim();
+    for prefix in ["Standalone question:", "Question:"] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = rest.trim();
+        }
+    }
+    let text = text
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .trim();
+
+    if text.is_empty() || text.chars().count() > MAX_REWRITE_CHARS {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_rewrite() {
+        assert_eq!(
+            clean_rewrite("Standalone question: \"When was Jina AI founded?\"\n"),
+            Some("When was Jina AI founded?".to_string())
+        );
+        assert_eq!(clean_rewrite("  \"\" "), None);
+        assert_eq!(clean_rewrite(&"a".repeat(MAX_REWRITE_CHARS + 1)), None);
+    }
+
+    #[test]
+    fn test_prompt_includes_recent_history() {
+        let turns = vec![ChatTurn::new("Who founded Jina AI?", "Han Xiao")];
+        let prompt = QuestionRewriter::build_prompt(&turns, "And when?");
+
+        assert!(prompt
+            .user
+            .contains("User: Who founded Jina AI?\nAssistant: Han Xiao"));
+        assert!(prompt.user.ends_with("Follow-up question: And when?"));
+    }
+}
@@ -0,0 +1,246 @@
+//! # Report Writer
+//!
+//! Gera o esboço de relatórios longos (título e seções, cada uma com a
+//! pergunta que a pesquisa da seção deve responder) e o sumário executivo
+//! a partir das seções já escritas.
+//!
+//! Em caso de falha do LLM o esboço cai em seções genéricas derivadas da
+//! pergunta, e o relatório segue sem sumário executivo.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentPrompt;
+use crate::llm::LlmClient;
+use crate::utils::{extract_json, TokenTracker};
+
+/// Número padrão de seções do relatório
+pub const DEFAULT_REPORT_SECTIONS: usize = 5;
+
+/// Número máximo de seções aceitas no esboço
+pub const MAX_REPORT_SECTIONS: usize = 10;
+
+/// Tamanho máximo de cada seção enviada ao sumário executivo (em caracteres)
+const MAX_SUMMARY_INPUT_CHARS: usize = 4000;
+
+/// Instruções do esboço
+const OUTLINE_PROMPT: &str = "You are a senior research analyst planning a long-form report.
+Given the user's request, produce a title and an outline of at most {max} sections that together cover the request.
+Each section needs a short heading and a self-contained research question that a researcher can answer without seeing the other sections.
+Order sections from context to conclusions, avoid overlapping sections, and do not include an introduction, executive summary or references section.
+Use the language of the user's request.
+Reply with JSON only: {\"title\": \"...\", \"sections\": [{\"heading\": \"...\", \"question\": \"...\"}]}";
+
+/// Instruções do sumário executivo
+const SUMMARY_PROMPT: &str = "You write executive summaries for long-form research reports.
+Summarize the key findings of the report sections below in 1-3 short paragraphs for a decision maker.
+Only use facts stated in the sections, keep numbers exact, do not add citations or headings, and use the language of the report.
+Reply with the summary only.";
+
+/// Seção planejada do relatório
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlineSection {
+    /// Título da seção
+    pub heading: String,
+    /// Pergunta pesquisada para escrever a seção
+    pub question: String,
+}
+
+/// Esboço do relatório
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportOutline {
+    /// Título do relatório
+    pub title: String,
+    /// Seções na ordem do documento
+    pub sections: Vec<OutlineSection>,
+}
+
+/// Planeja relatórios longos e escreve o sumário executivo
+pub struct ReportWriter {
+    llm: Arc<dyn LlmClient>,
+}
+
+impl ReportWriter {
+    /// Cria um novo writer
+    pub fn new(llm: Arc<dyn LlmClient>) -> Self {
+        Self { llm }
+    }
+
+    /// Gera o esboço com até `max_sections` seções
+    ///
+    /// Se o LLM falhar ou a resposta não for um esboço válido, usa
+    /// `fallback_outline`.
+    pub async fn outline(
+        &self,
+        question: &str,
+        max_sections: usize,
+        tracker: &mut TokenTracker,
+    ) -> ReportOutline {
+        let max_sections = max_sections.clamp(1, MAX_REPORT_SECTIONS);
+        let prompt = AgentPrompt {
+            system: OUTLINE_PROMPT.replace("{max}", &max_sections.to_string()),
+            user: question.to_string(),
+            diary: vec![],
+        };
+
+        match self.llm.generate_answer(&prompt, 0.3).await {
+            Ok(response) => {
+                tracker.add_tokens(
+                    "report_writer",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                match parse_outline(&response.answer, max_sections) {
+                    Some(outline) => {
+                        log::info!(
+                            "📑 ReportWriter: Esboço com {} seções",
+                            outline.sections.len()
+                        );
+                        return outline;
+                    }
+                    None => log::warn!("⚠️ ReportWriter: Esboço inválido, usando fallback"),
+                }
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ ReportWriter: Falha ao usar LLM ({}), usando esboço padrão",
+                    e
+                );
+            }
+        }
+
+        fallback_outline(question, max_sections)
+    }
+
+    /// Escreve o sumário executivo a partir de `(título, conteúdo)` das seções
+    ///
+    /// Retorna `None` sem seções ou se o LLM falhar.
+    pub async fn executive_summary(
+        &self,
+        title: &str,
+        sections: &[(&str, &str)],
+        tracker: &mut TokenTracker,
+    ) -> Option<String> {
+        if sections.is_empty() {
+            return None;
+        }
+
+        let body = sections
+            .iter()
+            .map(|(heading, content)| {
+                let content: String = content.chars().take(MAX_SUMMARY_INPUT_CHARS).collect();
+                format!("<section heading=\"{}\">\n{}\n</section>", heading, content)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let prompt = AgentPrompt {
+            system: SUMMARY_PROMPT.to_string(),
+            user: format!("Report: {}\n\n{}", title, body),
+            diary: vec![],
+        };
+
+        match self.llm.generate_answer(&prompt, 0.2).await {
+            Ok(response) => {
+                tracker.add_tokens(
+                    "report_writer",
+                    response.prompt_tokens,
+                    response.completion_tokens,
+                );
+                let summary = response.answer.trim();
+                (!summary.is_empty()).then(|| summary.to_string())
+            }
+            Err(e) => {
+                log::warn!("⚠️ ReportWriter: Falha ao gerar sumário executivo ({})", e);
+                None
+            }
+        }
+    }
+}
+
+/// Interpreta a resposta do LLM como esboço
+///
+/// Descarta seções sem título ou pergunta e corta em `max_sections`.
+fn parse_outline(raw: &str, max_sections: usize) -> Option<ReportOutline> {
+    let value = extract_json(raw).ok()?;
+    let mut outline: ReportOutline = serde_json::from_value(value).ok()?;
+
+    outline.title = outline.title.trim().to_string();
+    outline
+        .sections
+        .retain(|s| !s.heading.trim().is_empty() && !s.question.trim().is_empty());
+    outline.sections.truncate(max_sections);
+
+    if outline.title.is_empty() || outline.sections.is_empty() {
+        None
+    } else {
+        Some(outline)
+    }
+}
+
+/// Esboço genérico usado quando o LLM não produz um esboço válido
+pub fn fallback_outline(question: &str, max_sections: usize) -> ReportOutline {
+    let sections = [
+        ("Overview", "Give an overview and the background of"),
+        (
+            "Key facts and figures",
+            "What are the key facts, numbers and data about",
+        ),
+        (
+            "Main players",
+            "Who are the main players and how do they compare regarding",
+        ),
+        (
+            "Trends",
+            "What are the recent developments and trends regarding",
+        ),
+        (
+            "Outlook",
+            "What are the risks, opportunities and outlook for",
+        ),
+    ];
+
+    ReportOutline {
+        title: question.trim().to_string(),
+        sections: sections
+            .iter()
+            .take(max_sections.max(1))
+            .map(|(heading, prefix)| OutlineSection {
+                heading: heading.to_string(),
+                question: format!("{}: {}", prefix, question.trim()),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_outline() {
+        let raw = "```json\n{\"title\": \" Mercado de café \", \"sections\": [\
+            {\"heading\": \"Tamanho\", \"question\": \"Qual o tamanho do mercado de café no Brasil?\"},\
+            {\"heading\": \"\", \"question\": \"sem título\"},\
+            {\"heading\": \"Players\", \"question\": \"Quem lidera o mercado de café no Brasil?\"},\
+            {\"heading\": \"Tendências\", \"question\": \"Quais as tendências do mercado de café?\"}]}\n```";
+
+        let outline = parse_outline(raw, 2).unwrap();
+        assert_eq!(outline.title, "Mercado de café");
+        assert_eq!(outline.sections.len(), 2);
+        assert_eq!(outline.sections[1].heading, "Players");
+
+        assert!(parse_outline("{\"title\": \"x\", \"sections\": []}", 5).is_none());
+        assert!(parse_outline("not json", 5).is_none());
+    }
+
+    #[test]
+    fn test_fallback_outline() {
+        let outline = fallback_outline(" EV market in Brazil ", 3);
+        assert_eq!(outline.title, "EV market in Brazil");
+        assert_eq!(outline.sections.len(), 3);
+        assert!(outline.sections[0]
+            .question
+            .ends_with(": EV market in Brazil"));
+    }
+}
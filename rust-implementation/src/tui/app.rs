@@ -6,6 +6,8 @@ use std::path::PathBuf;
 use std::time::Instant;
 use uuid::Uuid;
 
+use crate::agent::GapTreeRow;
+
 /// Nível de severidade do log
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LogLevel {
@@ -1009,6 +1011,8 @@ pub enum AppEvent {
     SetAction(String),
     /// Atualiza think atual
     SetThink(String),
+    /// Atualiza a árvore de perguntas de gap
+    SetGapTree(Vec<GapTreeRow>),
     /// Atualiza contagem de URLs
     SetUrlCount(usize),
     /// Atualiza URLs visitadas
@@ -1288,6 +1292,8 @@ pub struct App {
     pub all_tasks: Vec<ParallelTask>,
     /// Histórico de steps completados
     pub completed_steps: Vec<CompletedStep>,
+    /// Árvore de perguntas de gap do agente
+    pub gap_tree: Vec<GapTreeRow>,
     /// Estado do AgentAnalyzer (análise de erros em background)
     pub agent_analyzer: AgentAnalyzerState,
     /// Estado do Sandbox de execução de código
@@ -1302,6 +1308,8 @@ pub struct App {
     pub user_message_queue: VecDeque<String>,
     /// Estado dos benchmarks
     pub benchmarks: BenchmarksState,
+    /// Reprodução de um transcript (não grava sessão ao concluir)
+    pub is_replay: bool,
 }
 
 /// Step completado para histórico
@@ -1369,6 +1377,7 @@ impl App {
             completed_batches: Vec::new(),
             all_tasks: Vec::new(),
             completed_steps: Vec::new(),
+            gap_tree: Vec::new(),
             agent_analyzer: AgentAnalyzerState::default(),
             sandbox: SandboxState::default(),
             clipboard_message: None,
@@ -1376,6 +1385,7 @@ impl App {
             pending_user_messages: 0,
             user_message_queue: VecDeque::new(),
             benchmarks: BenchmarksState::new(),
+            is_replay: false,
         };
         // Carregar sessões anteriores
         app.load_sessions();
@@ -1498,6 +1508,7 @@ impl App {
             self.start_time = Some(Instant::now());
             self.visited_urls.clear();
             self.completed_steps.clear();
+            self.gap_tree.clear();
             self.active_batches.clear();
             self.completed_batches.clear();
             self.all_tasks.clear();
@@ -1540,6 +1551,9 @@ impl App {
             AppEvent::SetThink(think) => {
                 self.current_think = think;
             }
+            AppEvent::SetGapTree(rows) => {
+                self.gap_tree = rows;
+            }
             AppEvent::SetUrlCount(count) => {
                 self.url_count = count;
             }
@@ -2033,6 +2047,7 @@ impl App {
         self.completed_batches.clear();
         self.all_tasks.clear();
         self.completed_steps.clear();
+        self.gap_tree.clear();
         self.agent_analyzer = AgentAnalyzerState::default();
         self.clipboard_message = None;
     }
@@ -2093,6 +2108,9 @@ impl App {
 
     /// Salva a sessão atual em arquivo JSON e logs em TXT
     pub fn save_session(&self) {
+        if self.is_replay {
+            return;
+        }
         let session = self.to_session();
         let sessions_dir = Self::sessions_dir();
         let logs_dir = Self::logs_dir();
@@ -23,6 +23,7 @@
 
 mod agent_adapter;
 mod app;
+mod replay;
 mod runner;
 /// Módulo de renderização UI.
 pub mod ui;
@@ -33,4 +34,5 @@ pub use app::{
     ParallelBatch, ParallelTask, PersonaStats, ReadMethod, ResearchSession, SandboxExecution,
     SandboxState, SystemMetrics, TaskStatus,
 };
-pub use runner::{create_event_channel, execute_benchmark, run_tui, TuiLogger};
+pub use replay::{run_replay, spawn_replay, transcript_events};
+pub use runner::{create_event_channel, execute_benchmark, run_tui, run_tui_with_app, TuiLogger};
@@ -0,0 +1,266 @@
+//! Replay de transcripts JSONL na TUI
+//!
+//! Converte os eventos de um transcript (`agent::transcript`) em `AppEvent`s
+//! e os reenvia à TUI respeitando os intervalos originais, acelerados ou
+//! desacelerados por um fator de velocidade.
+
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use super::app::{App, AppEvent, LogEntry, LogLevel};
+use super::runner::{create_event_channel, run_tui_with_app};
+use crate::agent::{
+    read_transcript, transcript::transcript_question, TranscriptError, TranscriptEvent,
+    TranscriptLevel, TranscriptRecord,
+};
+
+/// Maior pausa entre dois eventos durante o replay (após aplicar a velocidade)
+const MAX_REPLAY_GAP: Duration = Duration::from_secs(2);
+
+/// Converte um transcript em eventos da TUI, com o instante original (ms)
+pub fn transcript_events(records: &[TranscriptRecord]) -> Vec<(u64, AppEvent)> {
+    let started_at = records.iter().find_map(|r| match &r.event {
+        TranscriptEvent::RunStarted { started_at, .. } => {
+            chrono::DateTime::parse_from_rfc3339(started_at).ok()
+        }
+        _ => None,
+    });
+    let timestamp = |elapsed_ms: u64| match started_at {
+        Some(start) => (start + chrono::Duration::milliseconds(elapsed_ms as i64))
+            .with_timezone(&chrono::Local)
+            .format("%H:%M:%S")
+            .to_string(),
+        None => format!("+{:.1}s", elapsed_ms as f64 / 1000.0),
+    };
+    let log = |elapsed_ms: u64, level: LogLevel, message: String| {
+        AppEvent::Log(LogEntry {
+            timestamp: timestamp(elapsed_ms),
+            level,
+            message,
+        })
+    };
+
+    let mut events = Vec::new();
+    for record in records {
+        let at = record.elapsed_ms;
+        match &record.event {
+            TranscriptEvent::RunStarted {
+                run_id, resumed, ..
+            } => {
+                let verb = if *resumed { "Retomando" } else { "Iniciando" };
+                events.push((
+                    at,
+                    log(
+                        at,
+                        LogLevel::Info,
+                        format!("▶ {} execução {}", verb, run_id),
+                    ),
+                ));
+            }
+            TranscriptEvent::Decision {
+                step,
+                action,
+                think,
+                prompt_tokens,
+                ..
+            } => {
+                events.push((at, AppEvent::SetStep(*step)));
+                events.push((at, AppEvent::SetAction(action.clone())));
+                events.push((at, AppEvent::SetThink(think.clone())));
+                events.push((
+                    at,
+                    log(
+                        at,
+                        LogLevel::Debug,
+                        format!("📨 Prompt ~{} tokens → {}", prompt_tokens, action),
+                    ),
+                ));
+            }
+            TranscriptEvent::StepCompleted {
+                step,
+                entries,
+                tokens_used,
+                total_urls,
+                visited_urls,
+                ..
+            } => {
+                events.push((at, AppEvent::SetStep(*step)));
+                events.push((at, AppEvent::SetTokens(*tokens_used)));
+                events.push((at, AppEvent::SetUrlCount(*total_urls)));
+                events.push((at, AppEvent::SetVisitedCount(*visited_urls)));
+                for entry in entries {
+                    let summary = entry
+                        .format()
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                    events.push((at, log(at, LogLevel::Info, format!("📓 {}", summary))));
+                }
+            }
+            TranscriptEvent::Evaluation {
+                eval_type,
+                passed,
+                confidence,
+                duration_ms,
+                ..
+            } => {
+                let (icon, level) = if *passed {
+                    ("✅", LogLevel::Success)
+                } else {
+                    ("❌", LogLevel::Warning)
+                };
+                events.push((
+                    at,
+                    log(
+                        at,
+                        level,
+                        format!(
+                            "{} {}: {:.0}% conf | {}ms",
+                            icon,
+                            eval_type,
+                            confidence * 100.0,
+                            duration_ms
+                        ),
+                    ),
+                ));
+            }
+            TranscriptEvent::VisitedUrl { url } => {
+                events.push((at, AppEvent::AddVisitedUrl(url.clone())));
+            }
+            TranscriptEvent::Log { level, message } => {
+                let level = match level {
+                    TranscriptLevel::Info => LogLevel::Info,
+                    TranscriptLevel::Success => LogLevel::Success,
+                    TranscriptLevel::Warning => LogLevel::Warning,
+                    TranscriptLevel::Error => LogLevel::Error,
+                };
+                events.push((at, log(at, level, message.clone())));
+            }
+            TranscriptEvent::RunFinished {
+                answer,
+                references,
+                error,
+                tokens_used,
+                state,
+            } => {
+                events.push((at, AppEvent::SetTokens(*tokens_used)));
+                match (answer, error) {
+                    (Some(answer), _) => {
+                        events.push((at, AppEvent::SetAnswer(answer.clone())));
+                        events.push((at, AppEvent::SetReferences(references.clone())));
+                        events.push((at, AppEvent::Complete));
+                    }
+                    (None, Some(error)) => events.push((at, AppEvent::Error(error.clone()))),
+                    (None, None) => {
+                        events.push((at, AppEvent::Error(format!("Execução {}", state))))
+                    }
+                }
+            }
+        }
+    }
+    events
+}
+
+/// Envia os eventos para a TUI em uma thread, respeitando os intervalos
+///
+/// `speed` > 1.0 acelera o replay; pausas longas são limitadas a
+/// `MAX_REPLAY_GAP`. A thread termina quando os eventos acabam ou a TUI fecha.
+pub fn spawn_replay(
+    events: Vec<(u64, AppEvent)>,
+    tx: Sender<AppEvent>,
+    speed: f64,
+) -> JoinHandle<()> {
+    let speed = if speed.is_finite() && speed > 0.0 {
+        speed
+    } else {
+        1.0
+    };
+    std::thread::spawn(move || {
+        let mut previous_ms = events.first().map(|(at, _)| *at).unwrap_or(0);
+        for (at, event) in events {
+            let gap =
+                Duration::from_secs_f64(at.saturating_sub(previous_ms) as f64 / 1000.0 / speed);
+            std::thread::sleep(gap.min(MAX_REPLAY_GAP));
+            previous_ms = at;
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Reproduz um transcript na TUI
+pub fn run_replay(path: &Path, speed: f64) -> io::Result<App> {
+    let records = read_transcript(path).map_err(|e| match e {
+        TranscriptError::Io(e) => e,
+        other => io::Error::new(io::ErrorKind::NotFound, other.to_string()),
+    })?;
+
+    let question = transcript_question(&records)
+        .unwrap_or_default()
+        .to_string();
+    let mut app = App::with_question(question);
+    app.is_replay = true;
+
+    let (tx, rx) = create_event_channel();
+    let _replay = spawn_replay(transcript_events(&records), tx.clone(), speed);
+    run_tui_with_app(app, rx, tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_events_drive_app_to_completion() {
+        let records = vec![
+            TranscriptRecord {
+                elapsed_ms: 0,
+                event: TranscriptEvent::RunStarted {
+                    run_id: "run".into(),
+                    question: "Q".into(),
+                    resumed: false,
+                    started_at: "2024-01-01T00:00:00Z".into(),
+                },
+            },
+            TranscriptRecord {
+                elapsed_ms: 800,
+                event: TranscriptEvent::Decision {
+                    step: 0,
+                    question: "Q".into(),
+                    action: "answer".into(),
+                    think: "sei a resposta".into(),
+                    prompt_chars: 400,
+                    prompt_tokens: 100,
+                    prompt_tokens_used: 100,
+                    completion_tokens_used: 20,
+                    duration_ms: 800,
+                },
+            },
+            TranscriptRecord {
+                elapsed_ms: 1200,
+                event: TranscriptEvent::RunFinished {
+                    state: "completed".into(),
+                    answer: Some("A".into()),
+                    references: vec!["https://example.com".into()],
+                    error: None,
+                    tokens_used: 120,
+                },
+            },
+        ];
+
+        let mut app = App::with_question("Q".into());
+        app.is_replay = true;
+        for (_, event) in transcript_events(&records) {
+            app.handle_event(event);
+        }
+
+        assert!(app.is_complete);
+        assert_eq!(app.current_think, "sei a resposta");
+        assert_eq!(app.tokens_used, 120);
+    }
+}
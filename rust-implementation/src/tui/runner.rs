@@ -32,6 +32,15 @@ fn copy_to_clipboard(_text: &str) -> Result<(), String> {
 
 /// Executa a TUI com um receptor de eventos
 pub fn run_tui(question: String, event_rx: Receiver<AppEvent>, event_tx: Sender<AppEvent>) -> io::Result<App> {
+    run_tui_with_app(App::with_question(question), event_rx, event_tx)
+}
+
+/// Executa a TUI com um `App` já configurado (ex: replay de transcript)
+pub fn run_tui_with_app(
+    mut app: App,
+    event_rx: Receiver<AppEvent>,
+    event_tx: Sender<AppEvent>,
+) -> io::Result<App> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -39,9 +48,6 @@ pub fn run_tui(question: String, event_rx: Receiver<AppEvent>, event_tx: Sender<
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Criar app
-    let mut app = App::with_question(question);
-
     // Loop principal
     let result = run_app(&mut terminal, &mut app, event_rx, event_tx);
 
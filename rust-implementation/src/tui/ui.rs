@@ -9,6 +9,7 @@ use ratatui::{
 };
 
 use super::app::{App, AppScreen, LogLevel, ReadMethod, TaskStatus, AgentAnalyzerState};
+use crate::agent::GapStatus;
 
 /// Renderiza a interface completa
 pub fn render(frame: &mut Frame<'_>, app: &App) {
@@ -337,6 +338,8 @@ fn render_user_input_with_nav(frame: &mut Frame<'_>, app: &App, area: Rect) {
             Span::raw(" Tabs  "),
             Span::styled("↑↓", Style::default().fg(Color::Yellow)),
             Span::raw(" Scroll logs  "),
+            Span::styled("Esc", Style::default().fg(Color::Magenta)),
+            Span::raw(" Cancelar  "),
             Span::styled("q", Style::default().fg(Color::Red)),
             Span::raw(" Sair"),
         ])
@@ -382,13 +385,28 @@ fn render_header(frame: &mut Frame<'_>, app: &App, area: Rect) {
 
 /// Renderiza o painel de raciocínio e ação atual (em cima)
 fn render_thinking_panel(frame: &mut Frame<'_>, app: &App, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(70),  // Raciocínio
-            Constraint::Percentage(30),  // Ação atual
-        ])
-        .split(area);
+    // Com perguntas de gap, a árvore divide espaço com o raciocínio
+    let chunks = if app.gap_tree.len() > 1 {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(45),  // Raciocínio
+                Constraint::Percentage(30),  // Árvore de gaps
+                Constraint::Percentage(25),  // Ação atual
+            ])
+            .split(area);
+        render_gap_tree(frame, app, chunks[1]);
+        vec![chunks[0], chunks[2]]
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(70),  // Raciocínio
+                Constraint::Percentage(30),  // Ação atual
+            ])
+            .split(area)
+            .to_vec()
+    };
 
     // Painel de raciocínio
     let think_display = if app.current_think.is_empty() {
@@ -471,6 +489,52 @@ fn render_thinking_panel(frame: &mut Frame<'_>, app: &App, area: Rect) {
     frame.render_widget(action, chunks[1]);
 }
 
+/// Renderiza a árvore de perguntas de gap (○ aberta, ✓ respondida, ✗ abandonada)
+fn render_gap_tree(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let max_width = (area.width as usize).saturating_sub(4);
+    let lines: Vec<Line<'_>> = app
+        .gap_tree
+        .iter()
+        .map(|row| {
+            let color = match row.status {
+                GapStatus::Open => Color::White,
+                GapStatus::Answered => Color::Green,
+                GapStatus::Abandoned => Color::DarkGray,
+            };
+            let mut style = Style::default().fg(color);
+            if row.current {
+                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let prefix = format!("{}{} ", "  ".repeat(row.depth), row.status.icon());
+            let text = format!(
+                "{} ({:.0}%)",
+                row.question,
+                row.value * 100.0
+            );
+            Line::from(vec![
+                Span::styled(prefix.clone(), style),
+                Span::styled(
+                    truncate(&text, max_width.saturating_sub(prefix.chars().count())),
+                    style,
+                ),
+            ])
+        })
+        .collect();
+
+    let open = app
+        .gap_tree
+        .iter()
+        .filter(|row| row.status == GapStatus::Open)
+        .count();
+    let tree = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" 🌳 Perguntas ({} abertas) ", open))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(tree, area);
+}
+
 /// Renderiza o conteúdo principal (logs + analyzer + tasks + stats + personas)
 fn render_main_content(frame: &mut Frame<'_>, app: &App, area: Rect) {
     // Determinar quais painéis especiais estão ativos
@@ -18,7 +18,7 @@ pub type Url = String;
 /// let lang = Language::Portuguese;
 /// assert_ne!(lang, Language::English);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Language {
     /// Inglês - Idioma padrão, maior cobertura de fontes
     English,
@@ -145,7 +145,7 @@ pub struct SerpQuery {
 }
 
 /// Referência a uma fonte
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Reference {
     /// URL da fonte
     pub url: String,
@@ -175,7 +175,7 @@ impl Default for Reference {
 }
 
 /// Item de conhecimento acumulado
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct KnowledgeItem {
     /// Pergunta/contexto
     pub question: String,
@@ -188,7 +188,7 @@ pub struct KnowledgeItem {
 }
 
 /// Tipo de item de conhecimento
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum KnowledgeType {
     /// Pergunta e resposta
     Qa,
@@ -206,6 +206,8 @@ pub enum KnowledgeType {
     History,
     /// Informação fornecida diretamente pelo usuário
     UserProvided,
+    /// Resultado de uma ferramenta externa (ver `ToolRegistry`)
+    Tool,
 }
 
 impl KnowledgeType {
@@ -220,12 +222,13 @@ impl KnowledgeType {
             Self::Error => "error",
             Self::History => "history",
             Self::UserProvided => "user-provided",
+            Self::Tool => "tool",
         }
     }
 }
 
 /// Snippet de busca com boost
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BoostedSearchSnippet {
     /// URL do resultado
     pub url: String,
@@ -10,7 +10,7 @@
 // - Inserção de marcadores [^1], [^2] na resposta
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::llm::LlmClient;
@@ -52,6 +52,8 @@ pub struct ReferenceBuilderConfig {
     pub min_relevance_score: f32,
     /// Filtrar apenas por esses hostnames (vazio = todos)
     pub only_hostnames: Vec<String>,
+    /// Peso de credibilidade por URL (ausente = 1.0, 0.0 = descartar)
+    pub source_weights: HashMap<String, f32>,
 }
 
 impl Default for ReferenceBuilderConfig {
@@ -61,6 +63,7 @@ impl Default for ReferenceBuilderConfig {
             max_references: 10,
             min_relevance_score: 0.7,
             only_hostnames: Vec::new(),
+            source_weights: HashMap::new(),
         }
     }
 }
@@ -77,6 +80,7 @@ impl ReferenceBuilderConfig {
             max_references,
             min_relevance_score,
             only_hostnames: Vec::new(),
+            source_weights: HashMap::new(),
         }
     }
 
@@ -85,6 +89,12 @@ impl ReferenceBuilderConfig {
         self.only_hostnames = hostnames;
         self
     }
+
+    /// Define o peso de credibilidade de cada fonte
+    pub fn with_source_weights(mut self, weights: HashMap<String, f32>) -> Self {
+        self.source_weights = weights;
+        self
+    }
 }
 
 /// Chunk de conteúdo web com metadados
@@ -360,6 +370,7 @@ impl ReferenceBuilder {
 
     /// Filtra matches por score e remove duplicatas
     fn filter_matches(&self, matches: Vec<ChunkMatch>) -> Vec<ChunkMatch> {
+        let matches = self.rank_by_source(matches);
         let mut used_web_chunks = HashSet::new();
         let mut used_answer_chunks = HashSet::new();
         let mut filtered = Vec::new();
@@ -391,6 +402,27 @@ impl ReferenceBuilder {
         filtered
     }
 
+    /// Reordena os matches pela relevância ponderada pela credibilidade da fonte
+    ///
+    /// Fontes com peso zero são descartadas; sem pesos, a ordem não muda.
+    fn rank_by_source(&self, mut matches: Vec<ChunkMatch>) -> Vec<ChunkMatch> {
+        if self.config.source_weights.is_empty() {
+            return matches;
+        }
+        let weight = |m: &ChunkMatch| {
+            self.config
+                .source_weights
+                .get(&m.web_chunk.url)
+                .copied()
+                .unwrap_or(1.0)
+        };
+        matches.retain(|m| weight(m) > 0.0);
+        matches.sort_by(|a, b| {
+            (b.relevance_score * weight(b)).total_cmp(&(a.relevance_score * weight(a)))
+        });
+        matches
+    }
+
     /// Constrói resultado final com marcadores inseridos
     fn build_final_result(
         &self,
@@ -571,6 +603,38 @@ mod tests {
         assert_eq!(config.only_hostnames, vec!["example.com"]);
     }
 
+    #[test]
+    fn test_source_weights_rank_matches() {
+        let chunk_match = |url: &str, score: f32| ChunkMatch {
+            web_chunk: WebChunk {
+                url: url.into(),
+                title: String::new(),
+                text: String::new(),
+                index: 0,
+            },
+            answer_chunk_index: 0,
+            answer_position: (0, 10),
+            answer_chunk: String::new(),
+            relevance_score: score,
+        };
+        let config = ReferenceBuilderConfig::new(80, 1, 0.7).with_source_weights(HashMap::from([
+            ("https://farm.com".to_string(), 0.0),
+            ("https://trusted.org".to_string(), 1.5),
+        ]));
+        let builder = ReferenceBuilder::new(Arc::new(crate::llm::MockLlmClient::new()), config);
+
+        let matches = vec![
+            chunk_match("https://farm.com", 0.95),
+            chunk_match("https://blog.com", 0.9),
+            chunk_match("https://trusted.org", 0.8),
+        ];
+        let filtered = builder.filter_matches(matches);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].web_chunk.url, "https://trusted.org");
+        // O limiar vale para a relevância bruta
+        assert!((filtered[0].relevance_score - 0.8).abs() < 1e-6);
+    }
+
     #[test]
     fn test_reference_error_display() {
         let err1 = ReferenceError::EmbeddingFailed("test error".into());
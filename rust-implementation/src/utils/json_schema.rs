@@ -0,0 +1,794 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// JSON SCHEMA
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Validação de respostas estruturadas contra um JSON Schema fornecido pelo
+// chamador. Cobre o subconjunto usado em structured outputs:
+// - type, enum, const
+// - properties, required, additionalProperties
+// - items, minItems, maxItems
+// - minLength, maxLength, pattern
+// - minimum, maximum, exclusiveMinimum, exclusiveMaximum
+// - anyOf, oneOf, allOf, not
+// - $ref locais (#/$defs/..., #/definitions/...)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Profundidade máxima de `$ref` (evita recursão infinita)
+const MAX_REF_DEPTH: usize = 32;
+
+/// Máximo de nós de schema visitados numa validação
+///
+/// `anyOf`/`oneOf`/`allOf`/`not` revalidam o mesmo valor em cada ramo, então
+/// o custo pode crescer exponencialmente com o aninhamento do schema.
+const MAX_VALIDATION_NODES: usize = 100_000;
+
+/// Tipos aceitos na palavra-chave `type`
+const KNOWN_TYPES: [&str; 7] = [
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+/// Schema inválido (não dá para validar respostas com ele)
+#[derive(Debug, Clone, Error)]
+pub enum SchemaError {
+    /// O schema não é um objeto JSON
+    #[error("JSON Schema must be an object")]
+    NotAnObject,
+
+    /// Palavra-chave com valor inválido
+    #[error("Invalid JSON Schema at {path}: {message}")]
+    Invalid {
+        /// Caminho (JSON Pointer) dentro do schema
+        path: String,
+        /// Descrição do problema
+        message: String,
+    },
+}
+
+/// Violação encontrada ao validar uma resposta
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// Caminho (JSON Pointer) do valor inválido; vazio = raiz
+    pub path: String,
+    /// Descrição da violação
+    pub message: String,
+}
+
+impl SchemaViolation {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// JSON Schema validado, pronto para checar respostas
+///
+/// # Exemplo
+/// ```rust,ignore
+/// let schema = JsonSchema::new(serde_json::json!({
+///     "type": "object",
+///     "properties": { "name": { "type": "string" } },
+///     "required": ["name"]
+/// }))?;
+///
+/// let value = schema.parse_answer(r#"{"name": "Rust"}"#)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    schema: Value,
+    /// Regexes de `pattern`, compiladas uma única vez
+    patterns: HashMap<String, Regex>,
+}
+
+impl PartialEq for JsonSchema {
+    fn eq(&self, other: &Self) -> bool {
+        self.schema == other.schema
+    }
+}
+
+impl JsonSchema {
+    /// Valida o próprio schema (tipos e padrões conhecidos)
+    pub fn new(schema: Value) -> Result<Self, SchemaError> {
+        if !schema.is_object() {
+            return Err(SchemaError::NotAnObject);
+        }
+        let mut patterns = HashMap::new();
+        check_schema(&schema, "", &mut patterns)?;
+        check_recursive_refs(&schema)?;
+        Ok(Self { schema, patterns })
+    }
+
+    /// Schema original
+    pub fn as_value(&self) -> &Value {
+        &self.schema
+    }
+
+    /// Valida um valor JSON, retornando todas as violações
+    pub fn validate(&self, instance: &Value) -> Result<(), Vec<SchemaViolation>> {
+        let mut violations = Vec::new();
+        let visited = Cell::new(0);
+        self.validate_node(&self.schema, instance, "", 0, &visited, &mut violations);
+        if visited.get() > MAX_VALIDATION_NODES {
+            return Err(vec![SchemaViolation::new(
+                "",
+                format!(
+                    "schema is too complex to validate (more than {} steps)",
+                    MAX_VALIDATION_NODES
+                ),
+            )]);
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Extrai o JSON de uma resposta do LLM e valida contra o schema
+    ///
+    /// Aceita blocos ```json``` e texto ao redor do objeto.
+    pub fn parse_answer(&self, answer: &str) -> Result<Value, Vec<SchemaViolation>> {
+        let value = extract_json(answer).map_err(|e| vec![SchemaViolation::new("", e)])?;
+        self.validate(&value)?;
+        Ok(value)
+    }
+
+    fn validate_node(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        path: &str,
+        depth: usize,
+        visited: &Cell<usize>,
+        out: &mut Vec<SchemaViolation>,
+    ) {
+        visited.set(visited.get() + 1);
+        if visited.get() > MAX_VALIDATION_NODES {
+            // `validate` troca as violações por um erro único
+            return;
+        }
+
+        let Some(schema) = schema.as_object() else {
+            // `true` aceita tudo, `false` rejeita tudo
+            if schema == &Value::Bool(false) {
+                out.push(SchemaViolation::new(path, "value is not allowed"));
+            }
+            return;
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match self.resolve_ref(reference) {
+                Some(_) if depth >= MAX_REF_DEPTH => {
+                    out.push(SchemaViolation::new(path, "$ref nesting too deep"))
+                }
+                Some(target) => self.validate_node(target, instance, path, depth + 1, visited, out),
+                None => out.push(SchemaViolation::new(
+                    path,
+                    format!("unresolvable $ref {}", reference),
+                )),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let allowed: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(instance, t)) {
+                out.push(SchemaViolation::new(
+                    path,
+                    format!(
+                        "expected {}, got {}",
+                        allowed.join(" | "),
+                        type_name(instance)
+                    ),
+                ));
+                // Demais palavras-chave dependem do tipo
+                return;
+            }
+        }
+
+        if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+            if !options.contains(instance) {
+                out.push(SchemaViolation::new(
+                    path,
+                    format!("must be one of {}", Value::Array(options.clone())),
+                ));
+            }
+        }
+
+        if let Some(constant) = schema.get("const") {
+            if constant != instance {
+                out.push(SchemaViolation::new(path, format!("must be {}", constant)));
+            }
+        }
+
+        match instance {
+            Value::Object(map) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for key in required.iter().filter_map(Value::as_str) {
+                        if !map.contains_key(key) {
+                            out.push(SchemaViolation::new(
+                                path,
+                                format!("missing required property \"{}\"", key),
+                            ));
+                        }
+                    }
+                }
+
+                for (key, value) in map {
+                    let child_path = format!("{}/{}", path, escape_pointer(key));
+                    match properties.and_then(|p| p.get(key)) {
+                        Some(child) => self.validate_node(child, value, &child_path, depth, visited, out),
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => out.push(SchemaViolation::new(
+                                &child_path,
+                                "additional property is not allowed",
+                            )),
+                            Some(extra @ Value::Object(_)) => {
+                                self.validate_node(extra, value, &child_path, depth, visited, out)
+                            }
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) < min {
+                        out.push(SchemaViolation::new(
+                            path,
+                            format!("expected at least {} items, got {}", min, items.len()),
+                        ));
+                    }
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) > max {
+                        out.push(SchemaViolation::new(
+                            path,
+                            format!("expected at most {} items, got {}", max, items.len()),
+                        ));
+                    }
+                }
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        let child_path = format!("{}/{}", path, i);
+                        self.validate_node(item_schema, item, &child_path, depth, visited, out);
+                    }
+                }
+            }
+            Value::String(s) => {
+                let len = s.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                    if len < min {
+                        out.push(SchemaViolation::new(
+                            path,
+                            format!("expected at least {} characters", min),
+                        ));
+                    }
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                    if len > max {
+                        out.push(SchemaViolation::new(
+                            path,
+                            format!("expected at most {} characters", max),
+                        ));
+                    }
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    // Padrões alcançáveis só via `$ref` para fora de $defs não
+                    // foram pré-compilados em `check_schema`
+                    let compiled = match self.patterns.get(pattern) {
+                        Some(re) => Some(std::borrow::Cow::Borrowed(re)),
+                        None => Regex::new(pattern).ok().map(std::borrow::Cow::Owned),
+                    };
+                    if let Some(re) = compiled {
+                        if !re.is_match(s) {
+                            out.push(SchemaViolation::new(
+                                path,
+                                format!("does not match pattern {}", pattern),
+                            ));
+                        }
+                    }
+                }
+            }
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or(f64::NAN);
+                let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+                if let Some(min) = bound("minimum").filter(|min| n < *min) {
+                    out.push(SchemaViolation::new(path, format!("must be >= {}", min)));
+                }
+                if let Some(max) = bound("maximum").filter(|max| n > *max) {
+                    out.push(SchemaViolation::new(path, format!("must be <= {}", max)));
+                }
+                if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+                    out.push(SchemaViolation::new(path, format!("must be > {}", min)));
+                }
+                if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+                    out.push(SchemaViolation::new(path, format!("must be < {}", max)));
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for sub in all {
+                self.validate_node(sub, instance, path, depth, visited, out);
+            }
+        }
+
+        if let Some(any) = schema.get("anyOf").and_then(Value::as_array) {
+            let matches = self.count_matches(any, instance, path, depth, visited);
+            if matches == 0 {
+                out.push(SchemaViolation::new(
+                    path,
+                    "does not match any schema in anyOf",
+                ));
+            }
+        }
+
+        if let Some(one) = schema.get("oneOf").and_then(Value::as_array) {
+            let matches = self.count_matches(one, instance, path, depth, visited);
+            if matches != 1 {
+                out.push(SchemaViolation::new(
+                    path,
+                    format!(
+                        "must match exactly one schema in oneOf (matched {})",
+                        matches
+                    ),
+                ));
+            }
+        }
+
+        if let Some(not) = schema.get("not") {
+            if self.count_matches(std::slice::from_ref(not), instance, path, depth, visited) == 1 {
+                out.push(SchemaViolation::new(path, "must not match schema in not"));
+            }
+        }
+    }
+
+    fn count_matches(
+        &self,
+        schemas: &[Value],
+        instance: &Value,
+        path: &str,
+        depth: usize,
+        visited: &Cell<usize>,
+    ) -> usize {
+        schemas
+            .iter()
+            .filter(|sub| {
+                let mut errors = Vec::new();
+                self.validate_node(sub, instance, path, depth, visited, &mut errors);
+                errors.is_empty()
+            })
+            .count()
+    }
+
+    fn resolve_ref(&self, reference: &str) -> Option<&Value> {
+        match reference {
+            "#" => Some(&self.schema),
+            _ => reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.schema.pointer(pointer)),
+        }
+    }
+}
+
+/// Extrai um valor JSON de uma resposta (texto puro, bloco ``` ou texto ao redor)
+pub fn extract_json(text: &str) -> Result<Value, String> {
+    let trimmed = text.trim();
+    let error = match serde_json::from_str(trimmed) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+
+    // Bloco de código markdown
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .map(str::trim);
+    if let Some(Ok(value)) = unfenced.map(serde_json::from_str) {
+        return Ok(value);
+    }
+
+    // Primeiro objeto/array até o último fechamento correspondente
+    let start = trimmed.find(['{', '[']);
+    let end = trimmed.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end) {
+        if start < end {
+            if let Ok(value) = serde_json::from_str(&trimmed[start..=end]) {
+                return Ok(value);
+            }
+        }
+    }
+
+    Err(format!("answer is not valid JSON ({})", error))
+}
+
+/// Caminhos (JSON Pointer) e valores das folhas de um documento
+///
+/// Objetos e arrays vazios contam como folhas.
+pub fn json_leaves(value: &Value) -> Vec<(String, &Value)> {
+    fn walk<'a>(value: &'a Value, path: String, out: &mut Vec<(String, &'a Value)>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, child) in map {
+                    walk(child, format!("{}/{}", path, escape_pointer(key)), out);
+                }
+            }
+            Value::Array(items) if !items.is_empty() => {
+                for (i, child) in items.iter().enumerate() {
+                    walk(child, format!("{}/{}", path, i), out);
+                }
+            }
+            _ => out.push((path, value)),
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(value, String::new(), &mut out);
+    out
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn matches_type(instance: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "boolean" => instance.is_boolean(),
+        "null" => instance.is_null(),
+        _ => false,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+/// Verifica palavras-chave do schema que quebrariam a validação
+///
+/// Os `pattern` encontrados são compilados e guardados em `patterns`.
+fn check_schema(
+    schema: &Value,
+    path: &str,
+    patterns: &mut HashMap<String, Regex>,
+) -> Result<(), SchemaError> {
+    let Some(map) = schema.as_object() else {
+        return match schema {
+            Value::Bool(_) => Ok(()),
+            _ => Err(SchemaError::Invalid {
+                path: path.to_string(),
+                message: "subschema must be an object or boolean".into(),
+            }),
+        };
+    };
+
+    let invalid = |message: String| SchemaError::Invalid {
+        path: path.to_string(),
+        message,
+    };
+
+    if let Some(expected) = map.get("type") {
+        let types: Vec<&Value> = match expected {
+            Value::Array(types) => types.iter().collect(),
+            other => vec![other],
+        };
+        for t in types {
+            match t.as_str() {
+                Some(name) if KNOWN_TYPES.contains(&name) => {}
+                _ => return Err(invalid(format!("unknown type {}", t))),
+            }
+        }
+    }
+
+    if let Some(pattern) = map.get("pattern") {
+        let compiled = pattern.as_str().and_then(|p| Regex::new(p).ok());
+        match compiled {
+            Some(re) => {
+                patterns.insert(re.as_str().to_string(), re);
+            }
+            None => return Err(invalid(format!("invalid pattern {}", pattern))),
+        }
+    }
+
+    for key in ["properties", "$defs", "definitions"] {
+        if let Some(children) = map.get(key).and_then(Value::as_object) {
+            for (name, child) in children {
+                check_schema(
+                    child,
+                    &format!("{}/{}/{}", path, key, escape_pointer(name)),
+                    patterns,
+                )?;
+            }
+        }
+    }
+    for key in ["items", "additionalProperties", "not"] {
+        if let Some(child) = map.get(key) {
+            check_schema(child, &format!("{}/{}", path, key), patterns)?;
+        }
+    }
+    for key in ["anyOf", "oneOf", "allOf"] {
+        if let Some(children) = map.get(key).and_then(Value::as_array) {
+            for (i, child) in children.iter().enumerate() {
+                check_schema(child, &format!("{}/{}/{}", path, key, i), patterns)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejeita `$ref` que voltam ao mesmo subschema sem consumir o valor
+///
+/// Recursão via `properties`/`items` desce no documento e termina; já um
+/// ciclo formado só por `$ref`, `anyOf`, `oneOf`, `allOf` e `not` revalida o
+/// mesmo valor indefinidamente (ex.: `{"anyOf": [{"$ref": "#"}]}`).
+fn check_recursive_refs(root: &Value) -> Result<(), SchemaError> {
+    fn visit(
+        root: &Value,
+        path: String,
+        active: &mut HashSet<String>,
+        done: &mut HashSet<String>,
+    ) -> Result<(), SchemaError> {
+        if done.contains(&path) {
+            return Ok(());
+        }
+        if !active.insert(path.clone()) {
+            return Err(SchemaError::Invalid {
+                path,
+                message: "recursive $ref without consuming input".into(),
+            });
+        }
+
+        if let Some(map) = root.pointer(&path).and_then(Value::as_object) {
+            if let Some(target) = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix('#'))
+                .filter(|pointer| root.pointer(pointer).is_some())
+            {
+                visit(root, target.to_string(), active, done)?;
+            }
+            for key in ["anyOf", "oneOf", "allOf"] {
+                if let Some(children) = map.get(key).and_then(Value::as_array) {
+                    for i in 0..children.len() {
+                        visit(root, format!("{}/{}/{}", path, key, i), active, done)?;
+                    }
+                }
+            }
+            if map.contains_key("not") {
+                visit(root, format!("{}/not", path), active, done)?;
+            }
+        }
+
+        active.remove(&path);
+        done.insert(path);
+        Ok(())
+    }
+
+    fn subschemas(schema: &Value, path: String, out: &mut Vec<String>) {
+        let Some(map) = schema.as_object() else {
+            return;
+        };
+        for key in ["properties", "$defs", "definitions"] {
+            if let Some(children) = map.get(key).and_then(Value::as_object) {
+                for (name, child) in children {
+                    let child_path = format!("{}/{}/{}", path, key, escape_pointer(name));
+                    subschemas(child, child_path, out);
+                }
+            }
+        }
+        for key in ["items", "additionalProperties", "not"] {
+            if let Some(child) = map.get(key) {
+                subschemas(child, format!("{}/{}", path, key), out);
+            }
+        }
+        for key in ["anyOf", "oneOf", "allOf"] {
+            if let Some(children) = map.get(key).and_then(Value::as_array) {
+                for (i, child) in children.iter().enumerate() {
+                    subschemas(child, format!("{}/{}/{}", path, key, i), out);
+                }
+            }
+        }
+        out.push(path);
+    }
+
+    let mut paths = Vec::new();
+    subschemas(root, String::new(), &mut paths);
+
+    let mut done = HashSet::new();
+    for path in paths {
+        visit(root, path, &mut HashSet::new(), &mut done)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn company_schema() -> JsonSchema {
+        JsonSchema::new(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "founded": { "type": "integer", "minimum": 1800 },
+                "status": { "enum": ["public", "private"] },
+                "products": { "type": "array", "items": { "$ref": "#/$defs/product" } }
+            },
+            "required": ["name", "founded"],
+            "additionalProperties": false,
+            "$defs": {
+                "product": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } },
+                    "required": ["name"]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_instance() {
+        let schema = company_schema();
+        let value = json!({
+            "name": "Mozilla",
+            "founded": 1998,
+            "status": "private",
+            "products": [{ "name": "Firefox" }]
+        });
+        assert!(schema.validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_collects_violations_with_paths() {
+        let schema = company_schema();
+        let value = json!({
+            "founded": 1700,
+            "status": "bankrupt",
+            "products": [{ "title": "Firefox" }],
+            "ceo": "someone"
+        });
+        let errors = schema.validate(&value).unwrap_err();
+        let rendered: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+
+        assert!(rendered.contains(&"/: missing required property \"name\"".to_string()));
+        assert!(rendered.contains(&"/founded: must be >= 1800".to_string()));
+        assert!(rendered
+            .iter()
+            .any(|e| e.starts_with("/status: must be one of")));
+        assert!(rendered.contains(&"/products/0: missing required property \"name\"".to_string()));
+        assert!(rendered.contains(&"/ceo: additional property is not allowed".to_string()));
+    }
+
+    #[test]
+    fn test_parse_answer_with_fence() {
+        let schema = company_schema();
+        let answer = "Here it is:\n```json\n{\"name\": \"Mozilla\", \"founded\": 1998}\n```";
+        let value = schema.parse_answer(answer).unwrap();
+        assert_eq!(value["name"], "Mozilla");
+
+        let errors = schema
+            .parse_answer("Mozilla was founded in 1998")
+            .unwrap_err();
+        assert!(errors[0].message.contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(matches!(
+            JsonSchema::new(json!("string")),
+            Err(SchemaError::NotAnObject)
+        ));
+        assert!(JsonSchema::new(json!({ "type": "text" })).is_err());
+        assert!(JsonSchema::new(json!({ "properties": { "a": { "pattern": "(" } } })).is_err());
+    }
+
+    #[test]
+    fn test_rejects_recursive_combinators() {
+        for schema in [
+            json!({ "anyOf": [{ "$ref": "#" }, { "$ref": "#" }] }),
+            json!({ "$ref": "#/$defs/a", "$defs": { "a": { "not": { "$ref": "#/$defs/a" } } } }),
+            json!({ "$defs": { "a": { "$ref": "#/$defs/b" }, "b": { "allOf": [{ "$ref": "#/$defs/a" }] } } }),
+        ] {
+            assert!(JsonSchema::new(schema).is_err());
+        }
+
+        // Recursão que desce no documento continua aceita
+        let tree = JsonSchema::new(json!({
+            "type": "object",
+            "properties": {
+                "children": { "type": "array", "items": { "$ref": "#" } }
+            }
+        }))
+        .unwrap();
+        assert!(tree
+            .validate(&json!({ "children": [{ "children": [] }] }))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validation_step_limit() {
+        // Cadeia de $defs sem ciclo: passa em `new`, mas cada nível dobra o
+        // trabalho (2^30 validações sem o limite)
+        let mut defs = serde_json::Map::new();
+        defs.insert("d0".into(), json!({ "type": "string" }));
+        for i in 1..=30 {
+            let previous = format!("#/$defs/d{}", i - 1);
+            defs.insert(
+                format!("d{}", i),
+                json!({ "anyOf": [{ "$ref": previous }, { "$ref": previous }] }),
+            );
+        }
+        let schema =
+            JsonSchema::new(json!({ "$ref": "#/$defs/d30", "$defs": defs })).unwrap();
+
+        let errors = schema.validate(&json!(1)).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("too complex"));
+    }
+
+    #[test]
+    fn test_pattern_compiled_once() {
+        let schema = JsonSchema::new(json!({
+            "type": "array",
+            "items": { "type": "string", "pattern": "^[a-z]+$" }
+        }))
+        .unwrap();
+        assert_eq!(schema.patterns.len(), 1);
+        assert!(schema.validate(&json!(["abc", "def"])).is_ok());
+
+        let errors = schema.validate(&json!(["abc", "D1"])).unwrap_err();
+        assert_eq!(errors[0].path, "/1");
+    }
+
+    #[test]
+    fn test_json_leaves() {
+        let value = json!({ "a": { "b": 1 }, "c": ["x", "y"], "d": [] });
+        let leaves: Vec<String> = json_leaves(&value).into_iter().map(|(p, _)| p).collect();
+        assert_eq!(leaves, vec!["/a/b", "/c/0", "/c/1", "/d"]);
+    }
+}
@@ -4,6 +4,8 @@
 //
 // Utilitários compartilhados por todo o sistema:
 // - Token tracking e budget management
+// - Preços por modelo e custo monetário
+// - Validação de respostas estruturadas (JSON Schema)
 // - Text processing
 // - Timing e performance
 // - File reading (PDFs, documents)
@@ -14,6 +16,8 @@
 /// Sistema de referências semânticas usando embeddings e cosine similarity.
 pub mod build_ref;
 mod file_reader;
+mod json_schema;
+mod pricing;
 /// Chunking de texto para processamento de referências.
 pub mod segment;
 mod text;
@@ -22,7 +26,11 @@ mod token_tracker;
 
 pub use build_ref::{ReferenceBuilder, ReferenceBuilderConfig, ReferenceError, ReferenceResult};
 pub use file_reader::{FileContent, FileReader, FileReaderError, FileType};
+pub use json_schema::{extract_json, json_leaves, JsonSchema, SchemaError, SchemaViolation};
+pub use pricing::{
+    CostBreakdown, CostRates, ModelPricing, PricingError, PricingTable, DEFAULT_SEARCH_CREDIT_PRICE,
+};
 pub use segment::{chunk_text, ChunkOptions, ChunkResult, ChunkType};
 pub use text::*;
 pub use timing::{ActionTimer, TimingStats};
-pub use token_tracker::{TokenTracker, TrackerStats};
+pub use token_tracker::{TokenTracker, TrackerStats, DEFAULT_TOKEN_BUDGET};
@@ -0,0 +1,335 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// PRICING
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// Tabela de preços por modelo para calcular o custo monetário de uma pesquisa.
+// Suporta:
+// - Preços separados para prompt, completion e embedding (USD por 1M tokens)
+// - Créditos de busca/leitura (Jina) com preço próprio
+// - Sobrescrita via arquivo JSON (AGENT_PRICING_FILE)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Preço padrão de 1M créditos Jina (busca + leitura), em USD
+pub const DEFAULT_SEARCH_CREDIT_PRICE: f64 = 0.05;
+
+/// Erros ao carregar a tabela de preços
+#[derive(Debug, Error)]
+pub enum PricingError {
+    /// Falha ao ler o arquivo
+    #[error("Failed to read pricing file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON inválido
+    #[error("Invalid pricing file: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Preço de um modelo, em USD por 1M tokens
+///
+/// Modelos de chat usam `prompt`/`completion`; modelos de embedding usam `embedding`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Tokens de entrada
+    #[serde(default)]
+    pub prompt_per_million: f64,
+    /// Tokens de saída
+    #[serde(default)]
+    pub completion_per_million: f64,
+    /// Tokens de embedding
+    #[serde(default)]
+    pub embedding_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Preço de um modelo de chat
+    pub const fn chat(prompt_per_million: f64, completion_per_million: f64) -> Self {
+        Self {
+            prompt_per_million,
+            completion_per_million,
+            embedding_per_million: 0.0,
+        }
+    }
+
+    /// Preço de um modelo de embedding
+    pub const fn embedding(embedding_per_million: f64) -> Self {
+        Self {
+            prompt_per_million: 0.0,
+            completion_per_million: 0.0,
+            embedding_per_million,
+        }
+    }
+}
+
+/// Preços resolvidos para uma execução (modelo de chat + embedding + busca)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostRates {
+    /// USD por 1M tokens de prompt
+    pub prompt_per_million: f64,
+    /// USD por 1M tokens de completion
+    pub completion_per_million: f64,
+    /// USD por 1M tokens de embedding
+    pub embedding_per_million: f64,
+    /// USD por 1M créditos de busca/leitura
+    pub search_credit_per_million: f64,
+}
+
+impl CostRates {
+    /// Verifica se nenhum preço foi definido (ex: checkpoint antigo)
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Calcula o custo de um conjunto de contadores
+    pub fn cost(
+        &self,
+        prompt: u64,
+        completion: u64,
+        embedding: u64,
+        credits: u64,
+    ) -> CostBreakdown {
+        CostBreakdown::new(
+            prompt as f64 * self.prompt_per_million / 1_000_000.0,
+            completion as f64 * self.completion_per_million / 1_000_000.0,
+            embedding as f64 * self.embedding_per_million / 1_000_000.0,
+            credits as f64 * self.search_credit_per_million / 1_000_000.0,
+        )
+    }
+}
+
+/// Custo de uma pesquisa, em USD
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    /// Custo dos tokens de prompt
+    pub prompt_usd: f64,
+    /// Custo dos tokens de completion
+    pub completion_usd: f64,
+    /// Custo dos tokens de embedding
+    pub embedding_usd: f64,
+    /// Custo dos créditos de busca/leitura
+    pub search_usd: f64,
+    /// Soma de todas as parcelas
+    pub total_usd: f64,
+}
+
+impl CostBreakdown {
+    /// Cria um custo a partir das parcelas
+    pub fn new(prompt_usd: f64, completion_usd: f64, embedding_usd: f64, search_usd: f64) -> Self {
+        Self {
+            prompt_usd,
+            completion_usd,
+            embedding_usd,
+            search_usd,
+            total_usd: prompt_usd + completion_usd + embedding_usd + search_usd,
+        }
+    }
+
+    /// Mesmo custo sem a parcela de busca
+    ///
+    /// Usado no modo equipe, onde os créditos de busca vêm de um cliente compartilhado.
+    pub fn without_search(&self) -> Self {
+        Self::new(
+            self.prompt_usd,
+            self.completion_usd,
+            self.embedding_usd,
+            0.0,
+        )
+    }
+}
+
+impl std::ops::AddAssign for CostBreakdown {
+    fn add_assign(&mut self, other: Self) {
+        *self = Self::new(
+            self.prompt_usd + other.prompt_usd,
+            self.completion_usd + other.completion_usd,
+            self.embedding_usd + other.embedding_usd,
+            self.search_usd + other.search_usd,
+        );
+    }
+}
+
+impl std::fmt::Display for CostBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "${:.4} (prompt ${:.4} + completion ${:.4} + embedding ${:.4} + busca ${:.4})",
+            self.total_usd,
+            self.prompt_usd,
+            self.completion_usd,
+            self.embedding_usd,
+            self.search_usd
+        )
+    }
+}
+
+/// Tabela de preços por modelo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingTable {
+    /// Preço por nome de modelo
+    #[serde(default)]
+    pub models: HashMap<String, ModelPricing>,
+    /// USD por 1M créditos de busca/leitura (Jina)
+    #[serde(default = "default_search_credit_price")]
+    pub search_credit_per_million: f64,
+}
+
+fn default_search_credit_price() -> f64 {
+    DEFAULT_SEARCH_CREDIT_PRICE
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PricingTable {
+    /// Tabela embutida com os modelos mais usados
+    pub fn builtin() -> Self {
+        let models = [
+            ("gpt-4.1", ModelPricing::chat(2.00, 8.00)),
+            ("gpt-4.1-mini", ModelPricing::chat(0.40, 1.60)),
+            ("gpt-4.1-nano", ModelPricing::chat(0.10, 0.40)),
+            ("gpt-4o", ModelPricing::chat(2.50, 10.00)),
+            ("gpt-4o-mini", ModelPricing::chat(0.15, 0.60)),
+            ("o4-mini", ModelPricing::chat(1.10, 4.40)),
+            ("claude-opus-4", ModelPricing::chat(15.00, 75.00)),
+            ("claude-opus-4-5", ModelPricing::chat(5.00, 25.00)),
+            ("claude-sonnet-4", ModelPricing::chat(3.00, 15.00)),
+            ("claude-haiku-4-5", ModelPricing::chat(1.00, 5.00)),
+            ("claude-3-5-haiku", ModelPricing::chat(0.80, 4.00)),
+            ("text-embedding-3-small", ModelPricing::embedding(0.02)),
+            ("text-embedding-3-large", ModelPricing::embedding(0.13)),
+            ("text-embedding-ada-002", ModelPricing::embedding(0.10)),
+        ];
+
+        Self {
+            models: models
+                .into_iter()
+                .map(|(name, pricing)| (name.to_string(), pricing))
+                .collect(),
+            search_credit_per_million: DEFAULT_SEARCH_CREDIT_PRICE,
+        }
+    }
+
+    /// Carrega um arquivo JSON e sobrescreve a tabela embutida
+    ///
+    /// ```json
+    /// {
+    ///   "models": { "gpt-4.1-mini": { "prompt_per_million": 0.4, "completion_per_million": 1.6 } },
+    ///   "search_credit_per_million": 0.05
+    /// }
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PricingError> {
+        let overrides: PricingTable = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let mut table = Self::builtin();
+        table.models.extend(overrides.models);
+        table.search_credit_per_million = overrides.search_credit_per_million;
+        Ok(table)
+    }
+
+    /// Carrega o arquivo configurado ou usa a tabela embutida em caso de erro
+    pub fn load(path: Option<&str>) -> Self {
+        match path {
+            Some(path) => Self::from_file(path).unwrap_or_else(|e| {
+                log::warn!("⚠️ {} ({}), usando preços embutidos", e, path);
+                Self::builtin()
+            }),
+            None => Self::builtin(),
+        }
+    }
+
+    /// Busca o preço de um modelo
+    ///
+    /// Aceita nomes com sufixo de versão (ex: `gpt-4.1-mini-2025-04-14`)
+    /// usando o prefixo conhecido mais longo.
+    pub fn get(&self, model: &str) -> Option<&ModelPricing> {
+        self.models.get(model).or_else(|| {
+            self.models
+                .iter()
+                .filter(|(name, _)| model.starts_with(name.as_str()))
+                .max_by_key(|(name, _)| name.len())
+                .map(|(_, pricing)| pricing)
+        })
+    }
+
+    /// Resolve os preços de uma execução a partir dos modelos em uso
+    pub fn rates_for(&self, model: Option<&str>, embedding_model: Option<&str>) -> CostRates {
+        let chat = model.and_then(|m| self.lookup(m)).unwrap_or_default();
+        let embedding = embedding_model
+            .and_then(|m| self.lookup(m))
+            .unwrap_or_default();
+
+        CostRates {
+            prompt_per_million: chat.prompt_per_million,
+            completion_per_million: chat.completion_per_million,
+            embedding_per_million: embedding.embedding_per_million,
+            search_credit_per_million: self.search_credit_per_million,
+        }
+    }
+
+    fn lookup(&self, model: &str) -> Option<ModelPricing> {
+        let pricing = self.get(model).copied();
+        if pricing.is_none() {
+            log::warn!(
+                "⚠️ Modelo sem preço cadastrado: {} (custo contado como $0)",
+                model
+            );
+        }
+        pricing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_model_uses_longest_prefix() {
+        let table = PricingTable::builtin();
+        assert_eq!(
+            table.get("gpt-4.1-mini-2025-04-14"),
+            Some(&ModelPricing::chat(0.40, 1.60))
+        );
+        assert_eq!(
+            table.get("gpt-4.1-2025-04-14"),
+            Some(&ModelPricing::chat(2.00, 8.00))
+        );
+        assert!(table.get("unknown-model").is_none());
+    }
+
+    #[test]
+    fn test_rates_cost() {
+        let rates =
+            PricingTable::builtin().rates_for(Some("gpt-4.1-mini"), Some("text-embedding-3-small"));
+        let cost = rates.cost(1_000_000, 500_000, 1_000_000, 2_000_000);
+
+        assert!((cost.prompt_usd - 0.40).abs() < 1e-9);
+        assert!((cost.completion_usd - 0.80).abs() < 1e-9);
+        assert!((cost.embedding_usd - 0.02).abs() < 1e-9);
+        assert!((cost.search_usd - 0.10).abs() < 1e-9);
+        assert!((cost.total_usd - 1.32).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_file_overrides_builtin() {
+        let path = std::env::temp_dir().join(format!("pricing-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"models": {"my-model": {"prompt_per_million": 1.0, "completion_per_million": 2.0}},
+                "search_credit_per_million": 0.0}"#,
+        )
+        .unwrap();
+
+        let table = PricingTable::from_file(&path).unwrap();
+        assert_eq!(table.get("my-model"), Some(&ModelPricing::chat(1.0, 2.0)));
+        assert!(table.get("gpt-4o").is_some());
+        assert_eq!(table.search_credit_per_million, 0.0);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -7,9 +7,13 @@
 // - Tracking de tokens por operação
 // - Budget limits
 // - Alertas de threshold
+// - Custo monetário (prompt, completion, embedding, créditos de busca)
+// - Budget opcional em moeda
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
+use super::pricing::{CostBreakdown, CostRates};
 use crate::agent::TokenUsage;
+use serde::{Deserialize, Serialize};
 
 /// Budget padrão: 1 milhão de tokens
 pub const DEFAULT_TOKEN_BUDGET: u64 = 1_000_000;
@@ -18,7 +22,7 @@ pub const DEFAULT_TOKEN_BUDGET: u64 = 1_000_000;
 pub const BEAST_MODE_THRESHOLD: f64 = 0.85;
 
 /// Tracker de uso de tokens
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenTracker {
     /// Budget total disponível
     budget: u64,
@@ -31,10 +35,26 @@ pub struct TokenTracker {
 
     /// Histórico de uso por step
     history: Vec<StepUsage>,
+
+    /// Tokens de embedding utilizados (cobrados à parte)
+    #[serde(default)]
+    embedding_tokens: u64,
+
+    /// Créditos de busca/leitura consumidos (Jina)
+    #[serde(default)]
+    search_credits: u64,
+
+    /// Preços aplicados a esta execução
+    #[serde(default)]
+    rates: CostRates,
+
+    /// Budget opcional em USD
+    #[serde(default)]
+    cost_budget: Option<f64>,
 }
 
 /// Uso de tokens em um step específico
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StepUsage {
     /// Número do step (não utilizado em implementação atual)
     #[allow(dead_code)]
@@ -58,6 +78,10 @@ impl TokenTracker {
             prompt_tokens: 0,
             completion_tokens: 0,
             history: Vec::new(),
+            embedding_tokens: 0,
+            search_credits: 0,
+            rates: CostRates::default(),
+            cost_budget: None,
         }
     }
 
@@ -93,6 +117,31 @@ impl TokenTracker {
         self.track(step, operation, prompt, completion);
     }
 
+    /// Registra tokens de embedding
+    pub fn track_embedding(&mut self, tokens: u64) {
+        self.embedding_tokens += tokens;
+    }
+
+    /// Registra créditos de busca/leitura
+    pub fn track_search_credits(&mut self, credits: u64) {
+        self.search_credits += credits;
+    }
+
+    /// Registra os créditos de busca e os tokens de embedding de clientes
+    /// compartilhados consumidos desde `before` (créditos, tokens de embedding)
+    ///
+    /// Usado por equipe e relatório, cujo planner/reducer usa os mesmos
+    /// clientes dos subagentes.
+    pub fn track_shared_usage(
+        &mut self,
+        search_client: &dyn crate::search::SearchClient,
+        llm_client: &dyn crate::llm::LlmClient,
+        before: (u64, u64),
+    ) {
+        self.track_search_credits(search_client.get_credits().saturating_sub(before.0));
+        self.track_embedding(llm_client.get_embedding_tokens().saturating_sub(before.1));
+    }
+
     /// Retorna tokens totais utilizados
     pub fn total_tokens(&self) -> u64 {
         self.prompt_tokens + self.completion_tokens
@@ -103,14 +152,34 @@ impl TokenTracker {
         self.total_tokens() as f64 / self.budget as f64
     }
 
-    /// Verifica se está em território de beast mode (>= 85%)
+    /// Retorna o custo acumulado
+    pub fn cost(&self) -> CostBreakdown {
+        self.rates.cost(
+            self.prompt_tokens,
+            self.completion_tokens,
+            self.embedding_tokens,
+            self.search_credits,
+        )
+    }
+
+    /// Retorna porcentagem do budget de custo utilizado (0.0 sem budget)
+    pub fn cost_budget_used_percentage(&self) -> f64 {
+        match self.cost_budget {
+            Some(budget) if budget > 0.0 => self.cost().total_usd / budget,
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Verifica se está em território de beast mode (>= 85% de tokens ou custo)
     pub fn should_enter_beast_mode(&self) -> bool {
         self.budget_used_percentage() >= BEAST_MODE_THRESHOLD
+            || self.cost_budget_used_percentage() >= BEAST_MODE_THRESHOLD
     }
 
     /// Verifica se ainda há budget disponível
     pub fn has_budget(&self) -> bool {
-        self.total_tokens() < self.budget
+        self.total_tokens() < self.budget && self.cost_budget_used_percentage() < 1.0
     }
 
     /// Retorna tokens restantes
@@ -145,6 +214,11 @@ impl TokenTracker {
             total_steps,
             avg_tokens_per_step: avg_per_step,
             remaining_tokens: self.remaining_tokens(),
+            embedding_tokens: self.embedding_tokens,
+            search_credits: self.search_credits,
+            cost: self.cost(),
+            cost_budget: self.cost_budget,
+            cost_budget_used_percentage: self.cost_budget_used_percentage(),
         }
     }
 
@@ -152,6 +226,8 @@ impl TokenTracker {
     pub fn reset(&mut self) {
         self.prompt_tokens = 0;
         self.completion_tokens = 0;
+        self.embedding_tokens = 0;
+        self.search_credits = 0;
         self.history.clear();
     }
 
@@ -159,6 +235,26 @@ impl TokenTracker {
     pub fn set_budget(&mut self, budget: u64) {
         self.budget = budget;
     }
+
+    /// Retorna os preços aplicados
+    pub fn rates(&self) -> &CostRates {
+        &self.rates
+    }
+
+    /// Define os preços usados no cálculo de custo
+    pub fn set_rates(&mut self, rates: CostRates) {
+        self.rates = rates;
+    }
+
+    /// Retorna o budget de custo, se houver
+    pub fn cost_budget(&self) -> Option<f64> {
+        self.cost_budget
+    }
+
+    /// Define (ou remove) o budget de custo em USD
+    pub fn set_cost_budget(&mut self, budget: Option<f64>) {
+        self.cost_budget = budget;
+    }
 }
 
 /// Estatísticas detalhadas do tracker de tokens.
@@ -180,6 +276,16 @@ pub struct TrackerStats {
     pub avg_tokens_per_step: u64,
     /// Tokens restantes no budget
     pub remaining_tokens: u64,
+    /// Tokens de embedding consumidos
+    pub embedding_tokens: u64,
+    /// Créditos de busca/leitura consumidos
+    pub search_credits: u64,
+    /// Custo acumulado em USD
+    pub cost: CostBreakdown,
+    /// Budget de custo configurado (USD)
+    pub cost_budget: Option<f64>,
+    /// Percentual do budget de custo já utilizado (0.0 sem budget)
+    pub cost_budget_used_percentage: f64,
 }
 
 impl Default for TokenTracker {
@@ -249,6 +355,32 @@ mod tests {
         assert_eq!(stats.avg_tokens_per_step, 150);
     }
 
+    #[test]
+    fn test_cost_budget_beast_mode() {
+        let mut tracker = TokenTracker::new(Some(1_000_000));
+        tracker.set_rates(CostRates {
+            prompt_per_million: 1.0,
+            completion_per_million: 4.0,
+            embedding_per_million: 0.1,
+            search_credit_per_million: 0.05,
+        });
+        tracker.set_cost_budget(Some(0.50));
+
+        tracker.track(1, "op", 100_000, 50_000); // $0.10 + $0.20
+        tracker.track_embedding(1_000_000); // $0.10
+        assert!((tracker.cost().total_usd - 0.40).abs() < 1e-9);
+        assert!(!tracker.should_enter_beast_mode()); // 80% do custo, 15% dos tokens
+
+        tracker.track_search_credits(1_000_000); // $0.05
+        assert!(tracker.should_enter_beast_mode()); // 90% do custo
+        assert!(tracker.has_budget());
+
+        let stats = tracker.stats();
+        assert_eq!(stats.embedding_tokens, 1_000_000);
+        assert_eq!(stats.search_credits, 1_000_000);
+        assert!((stats.cost.search_usd - 0.05).abs() < 1e-9);
+    }
+
     #[test]
     fn test_reset() {
         let mut tracker = TokenTracker::new(Some(1000));